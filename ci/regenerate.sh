@@ -36,6 +36,11 @@ generate_p2 crates/wasip2/src/imports.rs \
   --generate-all \
   --world wasi:cli/imports
 
+# With the `mock-host` feature the imports are also declared on non-wasm
+# targets, where they link against the functions defined in
+# `crates/wasip2/src/mock_host`, see `crates/mock-host-bindgen`.
+cargo run -q -p mock-host-bindgen -- --wit crates/wasip2/wit crates/wasip2/src/imports.rs
+
 p2=0.2.12
 p3=0.3.0
 
@@ -159,3 +164,12 @@ generate_p3 crates/wasip3/src/service.rs \
   --default-bindings-module '$crate' \
   --pub-export-macro \
   --export-macro-name _export_service
+
+# As for WASIp2, with the `mock-host` feature the imports are also declared on
+# non-wasm targets, including the intrinsics of the `wit_stream` and
+# `wit_future` modules, whose payload layouts are then computed for the size of
+# pointers of the target.
+cargo run -q -p mock-host-bindgen -- --wit crates/wasip3/wit \
+  crates/wasip3/src/imports.rs \
+  crates/wasip3/src/service.rs
+rustfmt --edition 2021 crates/wasip3/src/imports.rs crates/wasip3/src/service.rs
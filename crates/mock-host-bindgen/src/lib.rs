@@ -0,0 +1,388 @@
+//! Adapts bindings generated by `wit-bindgen` so that, with the `mock-host`
+//! feature of the crate they're part of, their imports link against the
+//! in-memory host of that crate on targets other than wasm.
+//!
+//! `wit-bindgen` only declares imports on wasm32, and elsewhere defines stubs
+//! which panic in their place. [`rewrite`] changes the imports of `wasi:`
+//! interfaces to be declared with the `mock-host` feature too, keeping their
+//! stubs for builds without it. On native targets they link against symbols
+//! named after both the interface and the function, such as
+//! `wasi:cli/exit@0.2.12#exit`, since function names alone such as `exit` or
+//! `now` aren't unique. Imports from `[export]` modules, such as the
+//! `[task-return]` intrinsics of exported async functions, keep their stubs.
+//!
+//! The payloads of streams and futures are lowered by the bindings at
+//! offsets which depend on the size of pointers, but the layouts of the
+//! vtables in `wit_stream` and `wit_future` are computed by `wit-bindgen` for
+//! wasm32 only. With the `mock-host` feature on other targets, [`rewrite`]
+//! replaces them with layouts computed from the WIT for the size of pointers
+//! of the target, keeping the layouts of `wit-bindgen` otherwise.
+//!
+//! The result of [`rewrite`] can be rewritten again without changes, so it
+//! can be checked against the source it was written to.
+
+use anyhow::{anyhow, bail, Result};
+use wit_parser::{Alignment, ArchitectureSize, Resolve, SizeAlign, TypeDefKind};
+
+const WASM32: &str = "#[cfg(target_arch = \"wasm32\")]";
+const WASM32_OR_MOCK: &str = "#[cfg(any(target_arch = \"wasm32\", feature = \"mock-host\"))]";
+const NOT_WASM32: &str = "#[cfg(not(target_arch = \"wasm32\"))]";
+const NOT_WASM32_OR_MOCK: &str =
+    "#[cfg(not(any(target_arch = \"wasm32\", feature = \"mock-host\")))]";
+const MOCK_NOT_WASM32: &str = "#[cfg(all(feature = \"mock-host\", not(target_arch = \"wasm32\")))]";
+const WASM32_OR_NOT_MOCK: &str =
+    "#[cfg(any(target_arch = \"wasm32\", not(feature = \"mock-host\")))]";
+/// The expression `wit-bindgen` uses for the size of pointers.
+const POINTER_SIZE: &str = "::core::mem::size_of::<*const u8>()";
+/// The width `rustfmt` formats to.
+const MAX_WIDTH: usize = 100;
+
+/// An item of generated source, as a range of its lines.
+enum Item {
+    /// A line outside of the items below.
+    Line(usize),
+    /// An `extern` block importing from `module`, spanning `lines`.
+    Imports {
+        lines: std::ops::Range<usize>,
+        module: String,
+    },
+    /// A stub defined in place of an import, spanning `lines`.
+    Stub { lines: std::ops::Range<usize> },
+    /// The `layout` field of a stream or future vtable, spanning `lines`,
+    /// with the layout generated by `wit-bindgen` on `original`.
+    Layout {
+        lines: std::ops::Range<usize>,
+        original: std::ops::Range<usize>,
+    },
+}
+
+/// The payload of the `index`th stream or future in the signature of the
+/// function `func` of the interface `module`, which is how `wit-bindgen`
+/// names the intrinsics of its vtable.
+struct Payload {
+    module: String,
+    func: String,
+    index: usize,
+}
+
+/// Rewrites the bindings in `src`, generated from WIT in `resolve`, to link
+/// against a mock host, see the [crate documentation](crate).
+pub fn rewrite(src: &str, resolve: &Resolve) -> Result<String> {
+    let mut sizes = SizeAlign::default();
+    sizes.fill(resolve);
+    let lines = src.lines().collect::<Vec<_>>();
+    let items = parse(&lines)?;
+    let mut out = String::with_capacity(src.len());
+    // The payload of the vtable whose intrinsics were imported last, which
+    // the vtable is defined after.
+    let mut payload = None;
+    let mut i = 0;
+    while i < items.len() {
+        match &items[i] {
+            Item::Line(line) => {
+                push(&mut out, lines[*line]);
+                i += 1;
+                continue;
+            }
+            Item::Layout {
+                lines: range,
+                original,
+            } => {
+                let Some(payload) = payload.take() else {
+                    bail!(
+                        "no intrinsics imported for layout on line {}",
+                        range.start + 1
+                    );
+                };
+                let indent = &lines[range.start][..indentation(lines[range.start])];
+                let (size, align) = payload_layout(resolve, &sizes, &payload)?;
+                // Layouts without pointers are the same on every target.
+                let native = format!("{size}{align}").contains(POINTER_SIZE);
+                if native {
+                    push(&mut out, &format!("{indent}{WASM32_OR_NOT_MOCK}"));
+                }
+                for line in &lines[original.clone()] {
+                    push(&mut out, line);
+                }
+                if native {
+                    push(&mut out, &format!("{indent}{MOCK_NOT_WASM32}"));
+                    push_layout(&mut out, indent, &size, &align);
+                }
+                i += 1;
+                continue;
+            }
+            Item::Imports { .. } | Item::Stub { .. } => {}
+        }
+        // Imports and their stubs are generated next to each other, either
+        // one stub after an `extern` block or a stub per intrinsic of a
+        // stream or future before it.
+        let start = i;
+        while i < items.len() && matches!(items[i], Item::Imports { .. } | Item::Stub { .. }) {
+            if let Item::Imports {
+                lines: range,
+                module,
+            } = &items[i]
+            {
+                payload = lines[range.clone()]
+                    .iter()
+                    .find_map(|line| vtable_payload(line, module))
+                    .or(payload);
+            }
+            i += 1;
+        }
+        let group = &items[start..i];
+        let mock = group.iter().all(|item| match item {
+            Item::Imports { module, .. } => module.starts_with("wasi:"),
+            _ => true,
+        });
+        for item in group {
+            match item {
+                Item::Imports {
+                    lines: range,
+                    module,
+                } if mock => rewrite_imports(&mut out, &lines[range.clone()], module),
+                Item::Stub { lines: range } if mock => {
+                    push(
+                        &mut out,
+                        &replace_cfg(lines[range.start], NOT_WASM32_OR_MOCK),
+                    );
+                    for line in &lines[range.start + 1..range.end] {
+                        push(&mut out, line);
+                    }
+                }
+                Item::Imports { lines: range, .. } | Item::Stub { lines: range } => {
+                    for line in &lines[range.clone()] {
+                        push(&mut out, line);
+                    }
+                }
+                Item::Line(_) | Item::Layout { .. } => unreachable!(),
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Splits `lines` into [`Item`]s.
+fn parse(lines: &[&str]) -> Result<Vec<Item>> {
+    let mut items = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let cfg = lines[i].trim();
+        let next = lines.get(i + 1).map_or("", |line| line.trim());
+        if (cfg == WASM32 || cfg == WASM32_OR_MOCK) && next.starts_with("#[link(") {
+            let end = block_end(lines, i)?;
+            let attr = lines[i + 1..end]
+                .iter()
+                .map(|line| line.trim())
+                .take_while(|line| !line.starts_with("unsafe extern"))
+                .collect::<String>();
+            let module = match attr.split_once("wasm_import_module = \"") {
+                Some((_, rest)) => rest.split('"').next().unwrap().to_string(),
+                None => bail!("no import module on line {}", i + 2),
+            };
+            items.push(Item::Imports {
+                lines: i..end,
+                module,
+            });
+            i = end;
+        } else if (cfg == NOT_WASM32 || cfg == NOT_WASM32_OR_MOCK)
+            && next.starts_with("unsafe extern \"C\" fn ")
+        {
+            let end = if next.ends_with('}') {
+                i + 2
+            } else {
+                block_end(lines, i)?
+            };
+            items.push(Item::Stub { lines: i..end });
+            i = end;
+        } else if cfg == WASM32_OR_NOT_MOCK && next.starts_with("layout: unsafe {") {
+            // A layout rewritten before, followed by the layout for the mock
+            // host.
+            let original = i + 1..layout_end(lines, i + 1)?;
+            let end = match lines.get(original.end) {
+                Some(line) if line.trim() == MOCK_NOT_WASM32 => {
+                    layout_end(lines, original.end + 1)?
+                }
+                _ => bail!("no mock host layout on line {}", original.end + 1),
+            };
+            items.push(Item::Layout {
+                lines: i..end,
+                original,
+            });
+            i = end;
+        } else if cfg.starts_with("layout: unsafe {") {
+            let end = layout_end(lines, i)?;
+            items.push(Item::Layout {
+                lines: i..end,
+                original: i..end,
+            });
+            i = end;
+        } else {
+            items.push(Item::Line(i));
+            i += 1;
+        }
+    }
+    Ok(items)
+}
+
+/// Returns the index after the line closing the item whose attributes start
+/// on line `start`, which is the first line after it with the same
+/// indentation.
+fn block_end(lines: &[&str], start: usize) -> Result<usize> {
+    let indent = indentation(lines[start]);
+    match lines[start + 1..]
+        .iter()
+        .position(|line| indentation(line) == indent && line.trim() == "}")
+    {
+        Some(offset) => Ok(start + 1 + offset + 1),
+        None => bail!("unterminated item on line {}", start + 1),
+    }
+}
+
+/// Returns the index after the last line of the `layout` field starting on
+/// line `start`.
+fn layout_end(lines: &[&str], start: usize) -> Result<usize> {
+    let line = lines.get(start).map_or("", |line| line.trim());
+    if !line.starts_with("layout: unsafe {") {
+        bail!("no layout on line {}", start + 1);
+    }
+    if line.ends_with("},") {
+        return Ok(start + 1);
+    }
+    let indent = indentation(lines[start]);
+    match lines[start + 1..]
+        .iter()
+        .position(|line| indentation(line) == indent && line.trim() == "},")
+    {
+        Some(offset) => Ok(start + 1 + offset + 1),
+        None => bail!("unterminated layout on line {}", start + 1),
+    }
+}
+
+/// Writes the `extern` block of `lines`, importing from `module`, declared
+/// on wasm32 and with the `mock-host` feature.
+fn rewrite_imports(out: &mut String, lines: &[&str], module: &str) {
+    push(out, &replace_cfg(lines[0], WASM32_OR_MOCK));
+    for line in &lines[1..] {
+        let name = line
+            .trim()
+            .strip_prefix("#[link_name = \"")
+            .and_then(|rest| rest.strip_suffix("\"]"));
+        match name {
+            Some(name) => {
+                let indent = &line[..indentation(line)];
+                push(
+                    out,
+                    &format!(
+                        "{indent}#[cfg_attr(target_arch = \"wasm32\", link_name = \"{name}\")]"
+                    ),
+                );
+                push(
+                    out,
+                    &format!(
+                        "{indent}#[cfg_attr(not(target_arch = \"wasm32\"), link_name = \"{module}#{name}\")]"
+                    ),
+                );
+            }
+            None => push(out, line),
+        }
+    }
+}
+
+/// Returns the payload of the vtable of a stream or future if `line` names
+/// its `new` intrinsic imported from `module`.
+fn vtable_payload(line: &str, module: &str) -> Option<Payload> {
+    let (_, name) = line.split_once("link_name = \"[")?;
+    let (intrinsic, rest) = name.split_once(']')?;
+    let index = intrinsic
+        .strip_prefix("future-new-")
+        .or_else(|| intrinsic.strip_prefix("stream-new-"))?;
+    Some(Payload {
+        module: module.to_string(),
+        func: rest.split('"').next()?.to_string(),
+        index: index.parse().ok()?,
+    })
+}
+
+/// Returns the size and alignment of `payload`, as expressions in terms of
+/// the size of pointers.
+fn payload_layout(
+    resolve: &Resolve,
+    sizes: &SizeAlign,
+    payload: &Payload,
+) -> Result<(String, String)> {
+    let Payload {
+        module,
+        func,
+        index,
+    } = payload;
+    let name = module.strip_prefix("[export]").unwrap_or(module);
+    let interface = resolve
+        .interfaces
+        .iter()
+        .find(|(id, _)| resolve.id_of(*id).as_deref() == Some(name))
+        .map(|(_, interface)| interface)
+        .ok_or_else(|| anyhow!("no interface `{name}` in the WIT"))?;
+    let function = interface
+        .functions
+        .get(func)
+        .ok_or_else(|| anyhow!("no function `{func}` in `{name}`"))?;
+    let ty = *function
+        .find_futures_and_streams(resolve)
+        .get(*index)
+        .ok_or_else(|| anyhow!("no stream or future {index} in `{func}` of `{name}`"))?;
+    let (size, align) = match &resolve.types[ty].kind {
+        TypeDefKind::Future(Some(ty)) | TypeDefKind::Stream(Some(ty)) => {
+            (sizes.size(ty), sizes.align(ty))
+        }
+        _ => (ArchitectureSize::default(), Alignment::default()),
+    };
+    let size = match (size.bytes, size.pointers) {
+        (bytes, 0) => bytes.to_string(),
+        (0, 1) => POINTER_SIZE.to_string(),
+        (0, pointers) => format!("{pointers} * {POINTER_SIZE}"),
+        (bytes, pointers) => format!("{bytes} + {pointers} * {POINTER_SIZE}"),
+    };
+    let align = match align {
+        Alignment::Pointer => POINTER_SIZE.to_string(),
+        Alignment::Bytes(bytes) => bytes.to_string(),
+    };
+    Ok((size, align))
+}
+
+/// Writes the `layout` field of a vtable, formatted as `rustfmt` would.
+fn push_layout(out: &mut String, indent: &str, size: &str, align: &str) {
+    let call = format!("::core::alloc::Layout::from_size_align_unchecked({size}, {align})");
+    let line = format!("{indent}layout: unsafe {{ {call} }},");
+    if line.len() <= MAX_WIDTH {
+        return push(out, &line);
+    }
+    push(out, &format!("{indent}layout: unsafe {{"));
+    if indent.len() + 4 + call.len() <= MAX_WIDTH {
+        push(out, &format!("{indent}    {call}"));
+    } else {
+        push(
+            out,
+            &format!("{indent}    ::core::alloc::Layout::from_size_align_unchecked("),
+        );
+        push(out, &format!("{indent}        {size},"));
+        push(out, &format!("{indent}        {align},"));
+        push(out, &format!("{indent}    )"));
+    }
+    push(out, &format!("{indent}}},"));
+}
+
+/// Returns `line`, a `#[cfg]` attribute, with the attribute replaced by
+/// `cfg`.
+fn replace_cfg(line: &str, cfg: &str) -> String {
+    format!("{}{}", &line[..indentation(line)], cfg)
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn push(out: &mut String, line: &str) {
+    out.push_str(line);
+    out.push('\n');
+}
@@ -0,0 +1,41 @@
+use std::env;
+use std::fs;
+use std::process;
+use wit_parser::Resolve;
+
+const USAGE: &str = "usage: mock-host-bindgen --wit <dir> <path>...";
+
+fn main() {
+    let mut args = env::args_os().skip(1);
+    if args.next().is_none_or(|arg| arg != "--wit") {
+        fail(USAGE);
+    }
+    let wit = args.next().unwrap_or_else(|| fail(USAGE));
+    let paths = args.collect::<Vec<_>>();
+    if paths.is_empty() {
+        fail(USAGE);
+    }
+    let mut resolve = Resolve::default();
+    if let Err(e) = resolve.push_dir(&wit) {
+        fail(&format!(
+            "failed to load {}: {:#}",
+            wit.to_string_lossy(),
+            e
+        ));
+    }
+    for path in paths {
+        let display = path.to_string_lossy();
+        let src = fs::read_to_string(&path)
+            .unwrap_or_else(|e| fail(&format!("failed to read {}: {}", display, e)));
+        let src = mock_host_bindgen::rewrite(&src, &resolve)
+            .unwrap_or_else(|e| fail(&format!("failed to rewrite {}: {}", display, e)));
+        if let Err(e) = fs::write(&path, src) {
+            fail(&format!("failed to write {}: {}", display, e));
+        }
+    }
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("error: {}", msg);
+    process::exit(1);
+}
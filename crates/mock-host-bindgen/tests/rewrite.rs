@@ -0,0 +1,184 @@
+use wit_parser::Resolve;
+
+const IMPORT: &str = r#"
+pub fn exit(status: i32) {
+    unsafe {
+        #[cfg(target_arch = "wasm32")]
+        #[link(wasm_import_module = "wasi:cli/exit@0.2.12")]
+        unsafe extern "C" {
+            #[link_name = "exit"]
+            fn wit_import0(_: i32);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        unsafe extern "C" fn wit_import0(_: i32) {
+            unreachable!()
+        }
+        wit_import0(status);
+    }
+}
+"#;
+
+const IMPORT_REWRITTEN: &str = r#"
+pub fn exit(status: i32) {
+    unsafe {
+        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
+        #[link(wasm_import_module = "wasi:cli/exit@0.2.12")]
+        unsafe extern "C" {
+            #[cfg_attr(target_arch = "wasm32", link_name = "exit")]
+            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/exit@0.2.12#exit")]
+            fn wit_import0(_: i32);
+        }
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
+        unsafe extern "C" fn wit_import0(_: i32) {
+            unreachable!()
+        }
+        wit_import0(status);
+    }
+}
+"#;
+
+const TASK_RETURN: &str = r#"
+unsafe {
+    #[cfg(target_arch = "wasm32")]
+    #[link(
+        wasm_import_module = "[export]wasi:http/handler@0.3.0"
+    )]
+    unsafe extern "C" {
+        #[link_name = "[task-return]handle"]
+        fn wit_import41(_: i32);
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe extern "C" fn wit_import41(_: i32) { unreachable!() }
+    wit_import41(0);
+}
+"#;
+
+const WIT: &str = "
+package wasi:example@0.1.0;
+
+interface lines {
+    read: func(prefix: stream<u8>) -> tuple<stream<string>, future<result<u32>>>;
+}
+";
+
+const VTABLE: &str = r#"
+pub mod vtable0 {
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe extern "C" fn new() -> u64 {
+        unreachable!()
+    }
+    #[cfg(target_arch = "wasm32")]
+    #[link(wasm_import_module = "wasi:example/lines@0.1.0")]
+    unsafe extern "C" {
+        #[link_name = "[stream-new-1]read"]
+        fn new() -> u64;
+    }
+    pub static VTABLE: StreamVtable<String> = StreamVtable::<String> {
+        layout: unsafe { ::core::alloc::Layout::from_size_align_unchecked(8, 4) },
+        new,
+    };
+}
+pub mod vtable1 {
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe extern "C" fn new() -> u64 {
+        unreachable!()
+    }
+    #[cfg(target_arch = "wasm32")]
+    #[link(wasm_import_module = "wasi:example/lines@0.1.0")]
+    unsafe extern "C" {
+        #[link_name = "[future-new-2]read"]
+        fn new() -> u64;
+    }
+    pub static VTABLE: FutureVtable<Result<u32, ()>> = FutureVtable::<Result<u32, ()>> {
+        layout: unsafe { ::core::alloc::Layout::from_size_align_unchecked(8, 4) },
+        new,
+    };
+}
+"#;
+
+const VTABLE_REWRITTEN: &str = r#"
+pub mod vtable0 {
+    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
+    unsafe extern "C" fn new() -> u64 {
+        unreachable!()
+    }
+    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
+    #[link(wasm_import_module = "wasi:example/lines@0.1.0")]
+    unsafe extern "C" {
+        #[cfg_attr(target_arch = "wasm32", link_name = "[stream-new-1]read")]
+        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:example/lines@0.1.0#[stream-new-1]read")]
+        fn new() -> u64;
+    }
+    pub static VTABLE: StreamVtable<String> = StreamVtable::<String> {
+        #[cfg(any(target_arch = "wasm32", not(feature = "mock-host")))]
+        layout: unsafe { ::core::alloc::Layout::from_size_align_unchecked(8, 4) },
+        #[cfg(all(feature = "mock-host", not(target_arch = "wasm32")))]
+        layout: unsafe {
+            ::core::alloc::Layout::from_size_align_unchecked(
+                2 * ::core::mem::size_of::<*const u8>(),
+                ::core::mem::size_of::<*const u8>(),
+            )
+        },
+        new,
+    };
+}
+pub mod vtable1 {
+    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
+    unsafe extern "C" fn new() -> u64 {
+        unreachable!()
+    }
+    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
+    #[link(wasm_import_module = "wasi:example/lines@0.1.0")]
+    unsafe extern "C" {
+        #[cfg_attr(target_arch = "wasm32", link_name = "[future-new-2]read")]
+        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:example/lines@0.1.0#[future-new-2]read")]
+        fn new() -> u64;
+    }
+    pub static VTABLE: FutureVtable<Result<u32, ()>> = FutureVtable::<Result<u32, ()>> {
+        layout: unsafe { ::core::alloc::Layout::from_size_align_unchecked(8, 4) },
+        new,
+    };
+}
+"#;
+
+fn rewrite(src: &str) -> anyhow::Result<String> {
+    let mut resolve = Resolve::default();
+    resolve.push_str("lines.wit", WIT).unwrap();
+    mock_host_bindgen::rewrite(src, &resolve)
+}
+
+#[test]
+fn imports_link_against_mock_host() {
+    let rewritten = rewrite(IMPORT).unwrap();
+    assert_eq!(rewritten.trim(), IMPORT_REWRITTEN.trim());
+    assert_eq!(rewrite(&rewritten).unwrap(), rewritten);
+}
+
+#[test]
+fn export_intrinsics_keep_stubs() {
+    let rewritten = rewrite(TASK_RETURN).unwrap();
+    assert_eq!(rewritten.trim(), TASK_RETURN.trim());
+}
+
+#[test]
+fn mock_host_vtable_layouts_depend_on_pointer_size() {
+    let rewritten = rewrite(VTABLE).unwrap();
+    assert_eq!(rewritten.trim(), VTABLE_REWRITTEN.trim());
+    assert_eq!(rewrite(&rewritten).unwrap(), rewritten);
+}
+
+#[test]
+fn unknown_payloads_fail() {
+    let src = VTABLE.replace("[future-new-2]read", "[future-new-3]read");
+    let err = rewrite(&src).unwrap_err().to_string();
+    assert_eq!(
+        err,
+        "no stream or future 3 in `read` of `wasi:example/lines@0.1.0`"
+    );
+}
+
+#[test]
+fn unterminated_items_fail() {
+    let truncated = IMPORT.lines().take(8).collect::<Vec<_>>().join("\n");
+    assert!(rewrite(&truncated).is_err());
+}
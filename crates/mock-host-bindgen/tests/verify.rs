@@ -0,0 +1,50 @@
+use std::path::Path;
+use wit_parser::Resolve;
+
+fn assert_rewritten(wit: &str, path: &str, actual: &str) {
+    let mut resolve = Resolve::default();
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    resolve.push_dir(root.join(wit)).unwrap();
+    let expected = mock_host_bindgen::rewrite(actual, &resolve).unwrap();
+    if actual == expected {
+        return;
+    }
+    panic!(
+        "
+
+the bindings in `{path}` aren't linked against the mock host, it's
+recommended to run `ci/regenerate.sh` from the root of the repository,
+or this command for just this file:
+
+    cargo run -p mock-host-bindgen -- --wit {wit} {path}
+
+"
+    );
+}
+
+#[test]
+fn wasip2_imports() {
+    assert_rewritten(
+        "crates/wasip2/wit",
+        "crates/wasip2/src/imports.rs",
+        include_str!("../../wasip2/src/imports.rs"),
+    );
+}
+
+#[test]
+fn wasip3_imports() {
+    assert_rewritten(
+        "crates/wasip3/wit",
+        "crates/wasip3/src/imports.rs",
+        include_str!("../../wasip3/src/imports.rs"),
+    );
+}
+
+#[test]
+fn wasip3_service() {
+    assert_rewritten(
+        "crates/wasip3/wit",
+        "crates/wasip3/src/service.rs",
+        include_str!("../../wasip3/src/service.rs"),
+    );
+}
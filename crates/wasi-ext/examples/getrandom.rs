@@ -0,0 +1,13 @@
+use std::io::Write as _;
+
+// Link the custom backend.
+use wasi_ext as _;
+
+fn main() {
+    let mut stdout = wasi::cli::stdout::get_stdout();
+
+    let r = getrandom03::u64().unwrap();
+    writeln!(stdout, "Random u64 from `getrandom` is {r}").unwrap();
+
+    stdout.flush().unwrap();
+}
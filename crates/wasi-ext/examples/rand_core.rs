@@ -0,0 +1,22 @@
+use std::io::Write as _;
+
+use rand09::rngs::SmallRng;
+use rand09::Rng as _;
+use wasi_ext::rand_core::rand_core::TryRngCore as _;
+use wasi_ext::rand_core::{from_insecure_seed, HostInsecureRng, HostRng};
+
+fn main() {
+    let mut stdout = wasi::cli::stdout::get_stdout();
+
+    let r = HostRng.try_next_u64().unwrap();
+    writeln!(stdout, "Cryptographically-secure random u64 is {r}").unwrap();
+
+    let r: u64 = HostInsecureRng.random();
+    writeln!(stdout, "Pseudo-random u64 is {r}").unwrap();
+
+    let mut rng: SmallRng = from_insecure_seed();
+    let r: u64 = rng.random();
+    writeln!(stdout, "Pseudo-random u64 from the insecure seed is {r}").unwrap();
+
+    stdout.flush().unwrap();
+}
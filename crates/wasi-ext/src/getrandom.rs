@@ -0,0 +1,55 @@
+//! A custom [`getrandom`](https://docs.rs/getrandom) backend, for `getrandom`
+//! 0.2 and 0.3, getting its entropy from `wasi:random/random`.
+//!
+//! This lets crates like `uuid` and `ring` call `getrandom` on component
+//! targets it has no backend of its own for, such as `wasm32-unknown-unknown`.
+//! `wasi:random/insecure` is never used, since `getrandom` promises
+//! cryptographically-secure random bytes.
+//!
+//! The backend is registered as soon as this crate is linked, which a binary
+//! which doesn't otherwise use it can make sure of with:
+//!
+//! ```
+//! use wasi_ext as _;
+//! ```
+//!
+//! `getrandom` 0.2 picks the backend up on targets it has no other backend
+//! for. `getrandom` 0.3 only uses it when built with
+//! `RUSTFLAGS='--cfg getrandom_backend="custom"'`. Either way, no other crate
+//! in the dependency graph may register a custom backend of its own.
+
+/// Fills the `len` bytes at `dest` with cryptographically-secure random
+/// bytes.
+///
+/// Fails, leaving `dest` untouched, if the host returns fewer or more bytes
+/// than requested.
+///
+/// # Safety
+///
+/// `dest` must be valid for writes of `len` bytes. They don't need to be
+/// initialized.
+unsafe fn fill(dest: *mut u8, len: usize) -> Result<(), ()> {
+    let bytes = wasi::random::random::get_random_bytes(len as _);
+    if bytes.len() != len {
+        return Err(());
+    }
+    unsafe { core::ptr::copy_nonoverlapping(bytes.as_ptr(), dest, len) };
+    Ok(())
+}
+
+fn getrandom_v02(dest: &mut [u8]) -> Result<(), getrandom02::Error> {
+    // SAFETY: `dest` is a valid slice.
+    unsafe { fill(dest.as_mut_ptr(), dest.len()) }.map_err(|()| getrandom02::Error::UNEXPECTED)
+}
+
+getrandom02::register_custom_getrandom!(getrandom_v02);
+
+// The function `getrandom` 0.3 calls with `getrandom_backend = "custom"`.
+#[no_mangle]
+unsafe extern "Rust" fn __getrandom_v03_custom(
+    dest: *mut u8,
+    len: usize,
+) -> Result<(), getrandom03::Error> {
+    // SAFETY: `getrandom` passes a buffer valid for writes of `len` bytes.
+    unsafe { fill(dest, len) }.map_err(|()| getrandom03::Error::UNEXPECTED)
+}
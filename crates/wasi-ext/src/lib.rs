@@ -1,2 +1,6 @@
+#[cfg(feature = "getrandom")]
+pub mod getrandom;
 #[cfg(feature = "rand")]
 pub mod rand;
+#[cfg(feature = "rand_core")]
+pub mod rand_core;
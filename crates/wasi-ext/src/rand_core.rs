@@ -0,0 +1,87 @@
+pub use rand_core;
+
+use rand_core::{CryptoRng, RngCore, SeedableRng};
+
+/// The secure interface for cryptographically-secure random numbers
+///
+/// It never fails, so it's a [`TryCryptoRng`](rand_core::TryCryptoRng) with
+/// an [`Infallible`](core::convert::Infallible) error.
+pub struct HostRng;
+
+impl CryptoRng for HostRng {}
+
+impl RngCore for HostRng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        wasi::random::random::get_random_u64() as _
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        wasi::random::random::get_random_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let n = dest.len();
+        if usize::BITS <= u64::BITS || n <= u64::MAX as _ {
+            dest.copy_from_slice(&wasi::random::random::get_random_bytes(n as _));
+        } else {
+            let (head, tail) = dest.split_at_mut(u64::MAX as _);
+            head.copy_from_slice(&wasi::random::random::get_random_bytes(u64::MAX));
+            self.fill_bytes(tail);
+        }
+    }
+}
+
+/// The insecure interface for insecure pseudo-random numbers
+///
+/// It never fails, so it's a [`TryRngCore`](rand_core::TryRngCore) with an
+/// [`Infallible`](core::convert::Infallible) error.
+pub struct HostInsecureRng;
+
+impl RngCore for HostInsecureRng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        wasi::random::insecure::get_insecure_random_u64() as _
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        wasi::random::insecure::get_insecure_random_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let n = dest.len();
+        if usize::BITS <= u64::BITS || n <= u64::MAX as _ {
+            dest.copy_from_slice(&wasi::random::insecure::get_insecure_random_bytes(n as _));
+        } else {
+            let (head, tail) = dest.split_at_mut(u64::MAX as _);
+            head.copy_from_slice(&wasi::random::insecure::get_insecure_random_bytes(u64::MAX));
+            self.fill_bytes(tail);
+        }
+    }
+}
+
+/// Seeds a generator, such as `rand`'s `SmallRng` or a `ChaCha8Rng`, with the
+/// 128 bits of the insecure seed interface.
+///
+/// The host may return the same seed on every call, so this is meant to seed
+/// one generator per instance, e.g. for hash-map randomisation, rather than
+/// to get independent generators.
+pub fn from_insecure_seed<R: SeedableRng>() -> R {
+    let (lo, hi) = wasi::random::insecure_seed::insecure_seed();
+    let mut states = [lo, hi];
+    let mut seed = R::Seed::default();
+    // Spread both halves over seeds of any size with SplitMix64, taking the
+    // next output of each half in turn for every 8 bytes of the seed.
+    for (i, chunk) in seed.as_mut().chunks_mut(8).enumerate() {
+        let state = &mut states[i % 2];
+        *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        chunk.copy_from_slice(&z.to_le_bytes()[..chunk.len()]);
+    }
+    R::from_seed(seed)
+}
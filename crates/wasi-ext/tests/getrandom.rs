@@ -0,0 +1,20 @@
+//! `getrandom` 0.3 only calls the backend of this crate when built with
+//! `RUSTFLAGS='--cfg getrandom_backend="custom"'`, as CI does.
+#![cfg(getrandom_backend = "custom")]
+
+use wasi_ext as _;
+use wasip2::mock_host::MockHost;
+
+#[test]
+fn getrandom03_gets_bytes_from_wasi_random() {
+    let host = MockHost::new();
+    let _guard = host.install();
+    let mut buf = [0; 33];
+    getrandom03::fill(&mut buf).unwrap();
+    getrandom03::fill(&mut []).unwrap();
+
+    // Restarting the host from the same seed gives the same bytes again.
+    host.set_random_seed(0);
+    assert_eq!(buf[..], wasi::random::random::get_random_bytes(33));
+    assert_ne!(buf, [0; 33]);
+}
@@ -0,0 +1,44 @@
+use wasi_ext::rand_core::from_insecure_seed;
+use wasi_ext::rand_core::rand_core::SeedableRng;
+use wasip2::mock_host::MockHost;
+
+/// A generator which only records the seed it was created from.
+struct Seed<S>(S);
+
+impl<S: Default + AsRef<[u8]> + AsMut<[u8]> + Clone> SeedableRng for Seed<S> {
+    type Seed = S;
+
+    fn from_seed(seed: S) -> Self {
+        Seed(seed)
+    }
+}
+
+#[test]
+fn insecure_seeds_are_deterministic() {
+    let host = MockHost::new();
+    let _guard = host.install();
+    let Seed(seed) = from_insecure_seed::<Seed<[u8; 32]>>();
+    let Seed(again) = from_insecure_seed::<Seed<[u8; 32]>>();
+    assert_eq!(seed, again);
+
+    // Every 8 bytes of the seed are filled, each differently.
+    let chunks = seed.chunks(8).collect::<Vec<_>>();
+    for (i, chunk) in chunks.iter().enumerate() {
+        assert_ne!(*chunk, [0; 8]);
+        assert!(!chunks[..i].contains(chunk), "{seed:?}");
+    }
+
+    // The host returns another `(lo, hi)` from another seed.
+    host.set_random_seed(1);
+    let Seed(other) = from_insecure_seed::<Seed<[u8; 32]>>();
+    assert_ne!(seed, other);
+}
+
+#[test]
+fn insecure_seeds_fill_seeds_of_any_size() {
+    let host = MockHost::new();
+    let _guard = host.install();
+    let Seed(long) = from_insecure_seed::<Seed<[u8; 32]>>();
+    let Seed(short) = from_insecure_seed::<Seed<[u8; 12]>>();
+    assert_eq!(short[..], long[..12]);
+}
@@ -0,0 +1,314 @@
+use crate::Errno;
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec};
+use core::ffi::CStr;
+#[cfg(feature = "alloc")]
+use core::fmt;
+use core::ptr;
+
+/// The maximum number of strings [`with_args`] and [`with_vars`] can decode,
+/// see [`with_args_sized`] for other limits.
+pub const MAX_STACK_STRINGS: usize = 128;
+
+/// The maximum total size in bytes, including NUL terminators, of the strings
+/// [`with_args`] and [`with_vars`] can decode, see [`with_args_sized`] for
+/// other limits.
+pub const MAX_STACK_BYTES: usize = 4096;
+
+/// Returns an iterator over the command-line arguments.
+///
+/// Fails with [`ERRNO_ILSEQ`](crate::ERRNO_ILSEQ) if an argument isn't valid
+/// UTF-8.
+#[cfg(feature = "alloc")]
+pub fn args() -> Result<Args, Errno> {
+    Ok(Args {
+        inner: crate::safe::args_get()?.into_iter(),
+    })
+}
+
+/// Returns an iterator over the `(key, value)` pairs of the environment
+/// variables.
+///
+/// Variables are split at the first `=` after their first character, and
+/// those without an `=` are skipped. Fails with
+/// [`ERRNO_ILSEQ`](crate::ERRNO_ILSEQ) if a variable isn't valid UTF-8.
+#[cfg(feature = "alloc")]
+pub fn vars() -> Result<Vars, Errno> {
+    Ok(Vars {
+        inner: crate::safe::environ_get()?.into_iter(),
+    })
+}
+
+/// An iterator over the command-line arguments, created by [`args`].
+#[cfg(feature = "alloc")]
+pub struct Args {
+    inner: vec::IntoIter<String>,
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for Args {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl DoubleEndedIterator for Args {
+    fn next_back(&mut self) -> Option<String> {
+        self.inner.next_back()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ExactSizeIterator for Args {}
+
+#[cfg(feature = "alloc")]
+impl fmt::Debug for Args {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.inner.as_slice()).finish()
+    }
+}
+
+/// An iterator over the environment variables, created by [`vars`].
+#[cfg(feature = "alloc")]
+pub struct Vars {
+    inner: vec::IntoIter<String>,
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for Vars {
+    type Item = (String, String);
+
+    fn next(&mut self) -> Option<(String, String)> {
+        self.inner.by_ref().find_map(|mut var| {
+            let start = var.char_indices().nth(1)?.0;
+            let pos = start + var[start..].find('=')?;
+            let value = var[pos + 1..].into();
+            var.truncate(pos);
+            Some((var, value))
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Debug for Vars {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.inner.as_slice()).finish()
+    }
+}
+
+/// Calls `f` with the command-line arguments, decoded into buffers on the
+/// stack.
+///
+/// This doesn't allocate, so it's available without the `alloc` feature. Fails
+/// with [`ERRNO_2BIG`](crate::ERRNO_2BIG) if there are more than
+/// [`MAX_STACK_STRINGS`] arguments or they take up more than
+/// [`MAX_STACK_BYTES`] bytes.
+pub fn with_args<R>(f: impl FnOnce(&[&CStr]) -> R) -> Result<R, Errno> {
+    with_args_sized::<MAX_STACK_STRINGS, MAX_STACK_BYTES, R>(f)
+}
+
+/// Calls `f` with the environment variables as `KEY=VALUE` strings, decoded
+/// into buffers on the stack.
+///
+/// This has the same limits as [`with_args`].
+pub fn with_vars<R>(f: impl FnOnce(&[&CStr]) -> R) -> Result<R, Errno> {
+    with_vars_sized::<MAX_STACK_STRINGS, MAX_STACK_BYTES, R>(f)
+}
+
+/// Like [`with_args`], with room on the stack for `STRINGS` arguments taking
+/// up `BYTES` bytes, including NUL terminators.
+///
+/// ```no_run
+/// // A single argument of up to 255 bytes.
+/// let program = wasip1::with_args_sized::<1, 256, _>(|args| args[0].to_bytes().len());
+/// ```
+pub fn with_args_sized<const STRINGS: usize, const BYTES: usize, R>(
+    f: impl FnOnce(&[&CStr]) -> R,
+) -> Result<R, Errno> {
+    unsafe {
+        with_strings::<STRINGS, BYTES, R>(
+            crate::args_sizes_get()?,
+            |ptrs, buf| crate::args_get(ptrs, buf),
+            f,
+        )
+    }
+}
+
+/// Like [`with_vars`], with room on the stack for `STRINGS` variables taking
+/// up `BYTES` bytes, including NUL terminators.
+pub fn with_vars_sized<const STRINGS: usize, const BYTES: usize, R>(
+    f: impl FnOnce(&[&CStr]) -> R,
+) -> Result<R, Errno> {
+    unsafe {
+        with_strings::<STRINGS, BYTES, R>(
+            crate::environ_sizes_get()?,
+            |ptrs, buf| crate::environ_get(ptrs, buf),
+            f,
+        )
+    }
+}
+
+/// Fills stack buffers for `STRINGS` strings of `BYTES` bytes using `get`,
+/// which is `args_get` or `environ_get`, and calls `f` with the decoded
+/// strings.
+unsafe fn with_strings<const STRINGS: usize, const BYTES: usize, R>(
+    (count, buf_len): (usize, usize),
+    get: impl FnOnce(*mut *mut u8, *mut u8) -> Result<(), Errno>,
+    f: impl FnOnce(&[&CStr]) -> R,
+) -> Result<R, Errno> {
+    if count > STRINGS || buf_len > BYTES {
+        return Err(crate::ERRNO_2BIG);
+    }
+    let mut ptrs = [ptr::null_mut::<u8>(); STRINGS];
+    let mut buf = [0; BYTES];
+    get(ptrs.as_mut_ptr(), buf.as_mut_ptr())?;
+    let buf = &buf[..buf_len];
+    let mut strs = [<&CStr>::default(); STRINGS];
+    for (s, ptr) in strs.iter_mut().zip(&ptrs[..count]) {
+        *s = cstr_at(buf, *ptr)?;
+    }
+    Ok(f(&strs[..count]))
+}
+
+/// Returns the NUL-terminated string at `ptr` within `buf`, as filled in by
+/// `args_get` or `environ_get`.
+pub(crate) fn cstr_at(buf: &[u8], ptr: *const u8) -> Result<&CStr, Errno> {
+    let start = (ptr as usize).wrapping_sub(buf.as_ptr() as usize);
+    let s = buf.get(start..).ok_or(crate::ERRNO_INVAL)?;
+    CStr::from_bytes_until_nul(s).map_err(|_| crate::ERRNO_INVAL)
+}
+
+#[cfg(all(test, feature = "native-host"))]
+mod tests {
+    use super::*;
+    use crate::host::{set_host, Preview1Host};
+    use crate::Size;
+    use std::rc::Rc;
+    use std::vec::Vec;
+    use std::{format, vec};
+
+    /// A host passing `args` and `vars` to the program.
+    struct Strings {
+        args: Vec<String>,
+        vars: Vec<String>,
+    }
+
+    /// Returns the count and total size of `strings`, with NUL terminators.
+    fn sizes(strings: &[String]) -> (Size, Size) {
+        let len = strings.iter().map(|s| s.len() + 1).sum();
+        (strings.len(), len)
+    }
+
+    /// Fills `ptrs` and `buf` with `strings` as `args_get` would.
+    unsafe fn get(strings: &[String], ptrs: *mut *mut u8, buf: *mut u8) {
+        let mut offset = 0;
+        for (i, s) in strings.iter().enumerate() {
+            *ptrs.add(i) = buf.add(offset);
+            ptr::copy_nonoverlapping(s.as_ptr(), buf.add(offset), s.len());
+            *buf.add(offset + s.len()) = 0;
+            offset += s.len() + 1;
+        }
+    }
+
+    impl Preview1Host for Strings {
+        unsafe fn args_get(&self, argv: *mut *mut u8, argv_buf: *mut u8) -> Result<(), Errno> {
+            get(&self.args, argv, argv_buf);
+            Ok(())
+        }
+
+        unsafe fn args_sizes_get(&self) -> Result<(Size, Size), Errno> {
+            Ok(sizes(&self.args))
+        }
+
+        unsafe fn environ_get(&self, environ: *mut *mut u8, buf: *mut u8) -> Result<(), Errno> {
+            get(&self.vars, environ, buf);
+            Ok(())
+        }
+
+        unsafe fn environ_sizes_get(&self) -> Result<(Size, Size), Errno> {
+            Ok(sizes(&self.vars))
+        }
+    }
+
+    fn install(args: &[&str], vars: &[&str]) -> crate::host::HostGuard {
+        set_host(Rc::new(Strings {
+            args: args.iter().map(|s| String::from(*s)).collect(),
+            vars: vars.iter().map(|s| String::from(*s)).collect(),
+        }))
+    }
+
+    #[test]
+    fn vars_split_at_first_equals_after_first_char() {
+        let _guard = install(&[], &["A=1", "B==2", "=C=3", "é=4", "ü", "D", "E="]);
+        let vars = vars().unwrap().collect::<Vec<_>>();
+        let expected = [("A", "1"), ("B", "=2"), ("=C", "3"), ("é", "4"), ("E", "")];
+        assert_eq!(vars.len(), expected.len());
+        for ((key, value), (k, v)) in vars.iter().zip(expected) {
+            assert_eq!((key.as_str(), value.as_str()), (k, v));
+        }
+    }
+
+    #[test]
+    fn args_are_decoded_in_order() {
+        let _guard = install(&["prog", "", "ärg"], &[]);
+        assert_eq!(args().unwrap().collect::<Vec<_>>(), ["prog", "", "ärg"]);
+        assert_eq!(args().unwrap().next_back().as_deref(), Some("ärg"));
+    }
+
+    #[test]
+    fn with_args_and_vars_decode_on_stack() {
+        let _guard = install(&["prog", "x"], &["A=1"]);
+        let args = with_args(|args| {
+            args.iter()
+                .map(|s| String::from(s.to_str().unwrap()))
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(args.unwrap(), ["prog", "x"]);
+        let vars = with_vars(|vars| {
+            vars.iter()
+                .map(|s| s.to_bytes().to_vec())
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(vars.unwrap(), [b"A=1".to_vec()]);
+    }
+
+    #[test]
+    fn with_args_fails_past_stack_limits() {
+        let many = vec!["a"; MAX_STACK_STRINGS + 1];
+        let _guard = install(&many, &[]);
+        assert_eq!(with_args(|_| ()), Err(crate::ERRNO_2BIG));
+        assert_eq!(args().unwrap().len(), MAX_STACK_STRINGS + 1);
+
+        // One string of exactly the byte limit fits, with its NUL.
+        let fits = "a".repeat(MAX_STACK_BYTES - 1);
+        let _guard = install(&[], &[fits.as_str()]);
+        assert_eq!(with_vars(|vars| vars[0].to_bytes().len()), Ok(fits.len()));
+        let long = format!("{fits}a");
+        let _guard = install(&[], &[long.as_str()]);
+        assert_eq!(with_vars(|_| ()), Err(crate::ERRNO_2BIG));
+    }
+
+    #[test]
+    fn sized_buffers_set_the_limits() {
+        let _guard = install(&["prog", "x"], &["A=1"]);
+        assert_eq!(with_args_sized::<1, 64, _>(|_| ()), Err(crate::ERRNO_2BIG));
+        assert_eq!(with_args_sized::<2, 6, _>(|_| ()), Err(crate::ERRNO_2BIG));
+        assert_eq!(with_args_sized::<2, 7, _>(|args| args.len()), Ok(2));
+        assert_eq!(
+            with_vars_sized::<1, 4, _>(|vars| vars[0].to_bytes().len()),
+            Ok(3)
+        );
+        assert_eq!(with_vars_sized::<0, 0, _>(|_| ()), Err(crate::ERRNO_2BIG));
+    }
+}
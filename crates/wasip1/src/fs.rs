@@ -0,0 +1,401 @@
+//! `std::io` integration for file descriptors.
+//!
+//! This is useful on targets where the standard library's own filesystem
+//! support is stubbed out but the WASIp1 functions are available.
+
+use crate::safe::Fd;
+use crate::Filestat;
+use core::convert::TryInto;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+/// An open file, implementing [`Read`], [`Write`] and [`Seek`] on top of an
+/// owned [`Fd`].
+///
+/// Like [`std::fs::File`] this does no buffering of its own, and the traits
+/// are also implemented for `&File`. Errors are converted from
+/// [`Errno`](crate::Errno) with [`Errno::kind`](crate::Errno::kind).
+///
+/// ```
+/// use std::io::Read;
+/// use wasip1::fs::File;
+///
+/// fn read_all(fd: wasip1::safe::Fd) -> std::io::Result<String> {
+///     let mut contents = String::new();
+///     File::from(fd).read_to_string(&mut contents)?;
+///     Ok(contents)
+/// }
+/// ```
+#[derive(Debug)]
+pub struct File {
+    fd: Fd,
+}
+
+impl File {
+    /// Returns the underlying file descriptor.
+    pub fn as_fd(&self) -> &Fd {
+        &self.fd
+    }
+
+    /// Returns the underlying file descriptor, giving up the [`File`].
+    pub fn into_fd(self) -> Fd {
+        self.fd
+    }
+
+    /// Reads into `buf` at `offset` without using or updating the file
+    /// offset, returning the number of bytes read.
+    pub fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+        Ok(self.fd.pread(buf, offset)?)
+    }
+
+    /// Writes `buf` at `offset` without using or updating the file offset,
+    /// returning the number of bytes written.
+    pub fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
+        Ok(self.fd.pwrite(buf, offset)?)
+    }
+
+    /// Returns the attributes of this file.
+    pub fn metadata(&self) -> io::Result<Filestat> {
+        Ok(self.fd.filestat()?)
+    }
+
+    /// Truncates or extends this file to `size` bytes.
+    pub fn set_len(&self, size: u64) -> io::Result<()> {
+        Ok(self.fd.set_size(size)?)
+    }
+
+    /// Synchronizes the data and metadata of this file to disk.
+    pub fn sync_all(&self) -> io::Result<()> {
+        Ok(self.fd.sync()?)
+    }
+
+    /// Synchronizes the data of this file to disk.
+    pub fn sync_data(&self) -> io::Result<()> {
+        Ok(self.fd.datasync()?)
+    }
+}
+
+impl From<Fd> for File {
+    fn from(fd: Fd) -> File {
+        File { fd }
+    }
+}
+
+impl From<File> for Fd {
+    fn from(file: File) -> Fd {
+        file.fd
+    }
+}
+
+impl Read for &File {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        Ok(self.fd.read(buf)?)
+    }
+}
+
+impl Write for &File {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(self.fd.write(buf)?)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for &File {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (offset, whence) = match pos {
+            SeekFrom::Start(offset) => {
+                let offset = offset.try_into().map_err(|_| {
+                    io::Error::new(io::ErrorKind::InvalidInput, "seek offset overflowed")
+                })?;
+                (offset, crate::WHENCE_SET)
+            }
+            SeekFrom::Current(offset) => (offset, crate::WHENCE_CUR),
+            SeekFrom::End(offset) => (offset, crate::WHENCE_END),
+        };
+        Ok(self.fd.seek(offset, whence)?)
+    }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        Ok(self.fd.tell()?)
+    }
+}
+
+impl Read for File {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&*self).read(buf)
+    }
+}
+
+impl Write for File {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&*self).write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        (&*self).flush()
+    }
+}
+
+impl Seek for File {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        (&*self).seek(pos)
+    }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        (&*self).stream_position()
+    }
+}
+
+#[cfg(all(test, feature = "native-host"))]
+mod tests {
+    use super::*;
+    use crate::host::{set_host, Preview1Host};
+    use crate::safe::BorrowedFd;
+    use crate::{Ciovec, Errno, Filedelta, Filesize, Iovec, Size, Whence};
+    use core::cell::{Cell, RefCell};
+    use std::rc::Rc;
+    use std::string::String;
+    use std::vec::Vec;
+
+    /// The descriptor `file.txt` is opened as.
+    const FILE: crate::Fd = 4;
+
+    /// A directory on descriptor 3 holding a single file, `file.txt`.
+    #[derive(Default)]
+    struct Host {
+        data: RefCell<Vec<u8>>,
+        pos: Cell<Filesize>,
+        closed: RefCell<Vec<crate::Fd>>,
+    }
+
+    impl Host {
+        fn check(&self, fd: crate::Fd) -> Result<(), Errno> {
+            if fd == FILE && !self.closed.borrow().contains(&fd) {
+                Ok(())
+            } else {
+                Err(crate::ERRNO_BADF)
+            }
+        }
+
+        unsafe fn read_at(&self, iovs: &[Iovec], offset: Filesize) -> Size {
+            let data = self.data.borrow();
+            let mut n = 0;
+            for iov in iovs {
+                let start = (offset as usize + n).min(data.len());
+                let len = iov.buf_len.min(data.len() - start);
+                core::ptr::copy_nonoverlapping(data[start..].as_ptr(), iov.buf, len);
+                n += len;
+            }
+            n
+        }
+
+        unsafe fn write_at(&self, iovs: &[Ciovec], offset: Filesize) -> Size {
+            let mut data = self.data.borrow_mut();
+            let mut n = 0;
+            for iov in iovs {
+                let buf = core::slice::from_raw_parts(iov.buf, iov.buf_len);
+                let start = offset as usize + n;
+                if data.len() < start + buf.len() {
+                    data.resize(start + buf.len(), 0);
+                }
+                data[start..start + buf.len()].copy_from_slice(buf);
+                n += buf.len();
+            }
+            n
+        }
+    }
+
+    impl Preview1Host for Host {
+        unsafe fn path_open(
+            &self,
+            fd: crate::Fd,
+            _dirflags: crate::Lookupflags,
+            path: &str,
+            _oflags: crate::Oflags,
+            _fs_rights_base: crate::Rights,
+            _fs_rights_inheriting: crate::Rights,
+            _fdflags: crate::Fdflags,
+        ) -> Result<crate::Fd, Errno> {
+            match (fd, path) {
+                (3, "file.txt") => Ok(FILE),
+                (3, _) => Err(crate::ERRNO_NOENT),
+                _ => Err(crate::ERRNO_BADF),
+            }
+        }
+
+        unsafe fn fd_close(&self, fd: crate::Fd) -> Result<(), Errno> {
+            self.check(fd)?;
+            self.closed.borrow_mut().push(fd);
+            Ok(())
+        }
+
+        unsafe fn fd_read(&self, fd: crate::Fd, iovs: &[Iovec]) -> Result<Size, Errno> {
+            self.check(fd)?;
+            let n = self.read_at(iovs, self.pos.get());
+            self.pos.set(self.pos.get() + n as Filesize);
+            Ok(n)
+        }
+
+        unsafe fn fd_write(&self, fd: crate::Fd, iovs: &[Ciovec]) -> Result<Size, Errno> {
+            self.check(fd)?;
+            let n = self.write_at(iovs, self.pos.get());
+            self.pos.set(self.pos.get() + n as Filesize);
+            Ok(n)
+        }
+
+        unsafe fn fd_pread(
+            &self,
+            fd: crate::Fd,
+            iovs: &[Iovec],
+            offset: Filesize,
+        ) -> Result<Size, Errno> {
+            self.check(fd)?;
+            Ok(self.read_at(iovs, offset))
+        }
+
+        unsafe fn fd_pwrite(
+            &self,
+            fd: crate::Fd,
+            iovs: &[Ciovec],
+            offset: Filesize,
+        ) -> Result<Size, Errno> {
+            self.check(fd)?;
+            Ok(self.write_at(iovs, offset))
+        }
+
+        unsafe fn fd_seek(
+            &self,
+            fd: crate::Fd,
+            offset: Filedelta,
+            whence: Whence,
+        ) -> Result<Filesize, Errno> {
+            self.check(fd)?;
+            let base = match whence {
+                crate::WHENCE_SET => 0,
+                crate::WHENCE_CUR => self.pos.get() as Filedelta,
+                _ => self.data.borrow().len() as Filedelta,
+            };
+            let pos = base
+                .checked_add(offset)
+                .filter(|pos| *pos >= 0)
+                .ok_or(crate::ERRNO_INVAL)?;
+            self.pos.set(pos as Filesize);
+            Ok(pos as Filesize)
+        }
+
+        unsafe fn fd_tell(&self, fd: crate::Fd) -> Result<Filesize, Errno> {
+            self.check(fd)?;
+            Ok(self.pos.get())
+        }
+
+        unsafe fn fd_filestat_get(&self, fd: crate::Fd) -> Result<Filestat, Errno> {
+            self.check(fd)?;
+            Ok(Filestat {
+                dev: 1,
+                ino: 2,
+                filetype: crate::FILETYPE_REGULAR_FILE,
+                nlink: 1,
+                size: self.data.borrow().len() as Filesize,
+                atim: 0,
+                mtim: 0,
+                ctim: 0,
+            })
+        }
+
+        unsafe fn fd_filestat_set_size(&self, fd: crate::Fd, size: Filesize) -> Result<(), Errno> {
+            self.check(fd)?;
+            self.data.borrow_mut().resize(size as usize, 0);
+            Ok(())
+        }
+    }
+
+    fn open(path: &str) -> Result<File, Errno> {
+        let dir = unsafe { BorrowedFd::borrow_raw(3) };
+        let rights = crate::RIGHTS_FD_READ | crate::RIGHTS_FD_WRITE;
+        let fd = dir.open(
+            Default::default(),
+            path,
+            Default::default(),
+            rights,
+            Default::default(),
+            Default::default(),
+        )?;
+        Ok(File::from(fd))
+    }
+
+    /// Returns the [`Errno`] `err` was converted from.
+    fn errno(err: &io::Error) -> Option<Errno> {
+        err.get_ref()?.downcast_ref::<Errno>().copied()
+    }
+
+    #[test]
+    fn read_write_and_seek() {
+        let host = Rc::new(Host::default());
+        let _guard = set_host(host.clone());
+        let mut file = open("file.txt").unwrap();
+        file.write_all(b"hello world").unwrap();
+        assert_eq!(file.stream_position().unwrap(), 11);
+
+        let mut contents = String::new();
+        assert_eq!(file.seek(SeekFrom::Start(0)).unwrap(), 0);
+        file.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "hello world");
+
+        let mut word = [0; 5];
+        assert_eq!(file.seek(SeekFrom::End(-5)).unwrap(), 6);
+        file.read_exact(&mut word).unwrap();
+        assert_eq!(&word, b"world");
+        assert_eq!(file.seek(SeekFrom::Current(-5)).unwrap(), 6);
+
+        // Positioned reads and writes leave the offset alone.
+        assert_eq!(file.read_at(&mut word, 0).unwrap(), 5);
+        assert_eq!(&word, b"hello");
+        assert_eq!(file.write_at(b"J", 0).unwrap(), 1);
+        assert_eq!(file.stream_position().unwrap(), 6);
+        assert_eq!(*host.data.borrow(), b"Jello world");
+
+        drop(file);
+        assert_eq!(*host.closed.borrow(), [FILE]);
+    }
+
+    #[test]
+    fn metadata_and_set_len() {
+        let _guard = set_host(Rc::new(Host::default()));
+        let file = open("file.txt").unwrap();
+        (&file).write_all(b"abc").unwrap();
+        let metadata = file.metadata().unwrap();
+        assert_eq!(metadata.size, 3);
+        assert!(metadata.filetype == crate::FILETYPE_REGULAR_FILE);
+        file.set_len(10).unwrap();
+        assert_eq!(file.metadata().unwrap().size, 10);
+    }
+
+    #[test]
+    fn errors_map_to_io_kinds() {
+        let _guard = set_host(Rc::new(Host::default()));
+        assert_eq!(open("missing.txt").unwrap_err(), crate::ERRNO_NOENT);
+
+        let mut file = open("file.txt").unwrap();
+        let err = file.seek(SeekFrom::Current(-1)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(errno(&err), Some(crate::ERRNO_INVAL));
+
+        // Offsets past `i64::MAX` are rejected before calling the host.
+        let err = file.seek(SeekFrom::Start(u64::MAX)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(errno(&err), None);
+
+        let err = file.sync_all().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+        assert_eq!(errno(&err), Some(crate::ERRNO_NOSYS));
+
+        // A closed descriptor is reported as such.
+        drop(file);
+        let stale = File::from(unsafe { crate::safe::Fd::from_raw(FILE) });
+        let err = (&stale).read(&mut [0; 1]).unwrap_err();
+        assert_eq!(errno(&err), Some(crate::ERRNO_BADF));
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+    }
+}
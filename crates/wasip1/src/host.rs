@@ -0,0 +1,150 @@
+//! Running on non-wasm targets against a fake host.
+//!
+//! With the `native-host` feature, on targets other than wasm every function
+//! of this crate calls the [`Preview1Host`] installed on the current thread
+//! with [`set_host`] instead of importing it from the wasm host. Code built on
+//! this crate can then be unit tested natively by implementing just the
+//! functions it uses:
+//!
+//! ```
+//! use std::cell::RefCell;
+//! use wasip1::host::{set_host, Preview1Host};
+//! use wasip1::{Ciovec, Errno, Fd, Size};
+//!
+//! #[derive(Default)]
+//! struct Capture(RefCell<Vec<u8>>);
+//!
+//! impl Preview1Host for Capture {
+//!     unsafe fn fd_write(&self, fd: Fd, iovs: &[Ciovec]) -> Result<Size, Errno> {
+//!         assert_eq!(fd, 1);
+//!         let mut n = 0;
+//!         for iov in iovs {
+//!             let buf = std::slice::from_raw_parts(iov.buf, iov.buf_len);
+//!             self.0.borrow_mut().extend_from_slice(buf);
+//!             n += buf.len();
+//!         }
+//!         Ok(n)
+//!     }
+//! }
+//!
+//! let host = std::rc::Rc::new(Capture::default());
+//! let _guard = set_host(host.clone());
+//! wasip1::safe::stdout().write(b"hello").unwrap();
+//! assert_eq!(*host.0.borrow(), b"hello");
+//! ```
+//!
+//! Methods which aren't implemented fail with [`ERRNO_NOSYS`](crate::ERRNO_NOSYS),
+//! except [`Preview1Host::proc_exit`], which exits the process.
+
+pub use crate::Preview1Host;
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+std::thread_local! {
+    static HOST: RefCell<Option<Rc<dyn Preview1Host>>> = RefCell::new(None);
+}
+
+/// Installs `host` as the implementation of the functions of this crate on
+/// the current thread, until the returned guard is dropped.
+///
+/// Dropping the guard reinstalls the host which was installed before, if
+/// any, so hosts can be nested.
+pub fn set_host(host: Rc<dyn Preview1Host>) -> HostGuard {
+    let prev = HOST.with(|h| h.replace(Some(host)));
+    HostGuard {
+        prev,
+        _not_send: PhantomData,
+    }
+}
+
+/// Uninstalls a host installed with [`set_host`] when dropped.
+#[must_use = "the host is uninstalled when the guard is dropped"]
+pub struct HostGuard {
+    prev: Option<Rc<dyn Preview1Host>>,
+    // The host is installed on the thread which created the guard.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for HostGuard {
+    fn drop(&mut self) {
+        let prev = self.prev.take();
+        HOST.with(|h| *h.borrow_mut() = prev);
+    }
+}
+
+impl core::fmt::Debug for HostGuard {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HostGuard").finish_non_exhaustive()
+    }
+}
+
+/// Returns the host installed on the current thread, which the generated
+/// wrappers call.
+pub(crate) fn current() -> Rc<dyn Preview1Host> {
+    HOST.with(|h| h.borrow().clone())
+        .expect("no `Preview1Host` is installed on this thread, see `wasip1::host::set_host`")
+}
+
+#[cfg(all(test, feature = "native-host"))]
+mod tests {
+    use super::*;
+    use crate::{Ciovec, Errno, Fd, Size, ERRNO_BADF, ERRNO_NOSYS};
+    use std::vec::Vec;
+
+    #[derive(Default)]
+    struct Capture(RefCell<Vec<(Fd, Vec<u8>)>>);
+
+    impl Preview1Host for Capture {
+        unsafe fn fd_write(&self, fd: Fd, iovs: &[Ciovec]) -> Result<Size, Errno> {
+            if fd > 2 {
+                return Err(ERRNO_BADF);
+            }
+            let mut data = Vec::new();
+            for iov in iovs {
+                data.extend_from_slice(core::slice::from_raw_parts(iov.buf, iov.buf_len));
+            }
+            let n = data.len();
+            self.0.borrow_mut().push((fd, data));
+            Ok(n)
+        }
+    }
+
+    fn write(fd: Fd, data: &[u8]) -> Result<Size, Errno> {
+        let iov = Ciovec {
+            buf: data.as_ptr(),
+            buf_len: data.len(),
+        };
+        unsafe { crate::fd_write(fd, &[iov]) }
+    }
+
+    #[test]
+    fn wrappers_call_installed_host() {
+        let host = Rc::new(Capture::default());
+        let _guard = set_host(host.clone());
+        assert_eq!(write(1, b"hello"), Ok(5));
+        assert_eq!(write(7, b"hello"), Err(ERRNO_BADF));
+        assert_eq!(*host.0.borrow(), [(1, b"hello".to_vec())]);
+    }
+
+    #[test]
+    fn unimplemented_methods_fail_with_nosys() {
+        let _guard = set_host(Rc::new(Capture::default()));
+        assert_eq!(unsafe { crate::sched_yield() }, Err(ERRNO_NOSYS));
+        assert_eq!(unsafe { crate::fd_close(3) }, Err(ERRNO_NOSYS));
+    }
+
+    #[test]
+    fn guards_restore_previous_host() {
+        let outer = Rc::new(Capture::default());
+        let inner = Rc::new(Capture::default());
+        let _outer_guard = set_host(outer.clone());
+        {
+            let _inner_guard = set_host(inner.clone());
+            write(1, b"inner").unwrap();
+        }
+        write(2, b"outer").unwrap();
+        assert_eq!(*inner.0.borrow(), [(1, b"inner".to_vec())]);
+        assert_eq!(*outer.0.borrow(), [(2, b"outer".to_vec())]);
+    }
+}
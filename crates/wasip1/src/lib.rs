@@ -22,18 +22,73 @@
 //! bumping the "0.9.0") is expected whenever the generated code changes
 //! or a new WASI snapshot is used.
 //!
+//! # Safe Wrappers
+//!
+//! The functions at the root of this crate are all `unsafe` as they take raw
+//! pointers and file descriptor numbers. The [`safe`] module provides safe
+//! wrappers over them built around an owned [`safe::Fd`] type, and the
+//! [`poll`] module wraps `poll_oneoff` without exposing its unions.
+//! [`resolve`] maps absolute paths to the preopened directory serving them,
+//! and with the `alloc` feature [`preopens`] lists the preopened directories.
+//! [`args`] and [`vars`] return the command-line arguments and environment,
+//! and [`with_args`] and [`with_vars`] do the same without allocating.
+//! With the `std` feature, [`fs::File`] implements the `std::io` traits over
+//! a descriptor. The [`net`] module wraps listening sockets passed in by the
+//! host and the connections accepted on them.
+//!
 //! # Crate Features
 //!
-//! This crate supports one feature, `std`, which implements the standard
-//! `Error` trait for the exported [`Error`] type in this crate. This is
-//! enabled by default but can be disabled to make the library `no_std`
-//! compatible.
+//! This crate supports the following features:
+//!
+//! * `std` - implements the standard `Error` trait for the exported [`Errno`]
+//!   type in this crate, enables the APIs which return owned `String`s and
+//!   `Vec`s and the `std::io` integration. Implies `alloc`. This is enabled by
+//!   default but can be disabled to make the library `no_std` compatible.
+//! * `alloc` - enables [`args`], [`vars`] and the other APIs of [`safe`] which
+//!   return owned `String`s and `Vec`s without requiring `std`.
+//! * `bitflags` - turns flag types such as [`Rights`], [`Oflags`] and
+//!   [`Fdflags`] from plain integer aliases into [`bitflags`] newtypes, so the
+//!   flags of one type can't be passed where another is expected. The
+//!   `RIGHTS_*`-style constants are available either way. This is disabled by
+//!   default.
+//!
+//! * `native-host` - on non-wasm targets, makes the functions of this crate
+//!   call an implementation of `Preview1Host` installed with
+//!   `host::set_host` instead of the wasm imports, so code using this crate
+//!   can be unit tested natively against a fake host. Implies `std`. This is
+//!   disabled by default.
+//!
+//! [`bitflags`]: https://docs.rs/bitflags
 
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 mod lib_generated;
 pub use lib_generated::*;
 
+mod env;
+#[cfg(feature = "std")]
+pub mod fs;
+#[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+pub mod host;
+pub mod net;
+pub mod poll;
+mod preopen;
+pub mod safe;
+
+#[cfg(feature = "alloc")]
+pub use env::{args, vars, Args, Vars};
+pub use env::{
+    with_args, with_args_sized, with_vars, with_vars_sized, MAX_STACK_BYTES, MAX_STACK_STRINGS,
+};
+pub use preopen::resolve;
+#[cfg(feature = "alloc")]
+pub use preopen::{preopens, Preopens};
+
 /// Special `Dircookie` value indicating the start of a directory.
 pub const DIRCOOKIE_START: Dircookie = 0;
 
@@ -55,3 +110,59 @@ impl core::fmt::Debug for Prestat {
         }
     }
 }
+
+#[cfg(feature = "std")]
+impl Errno {
+    /// Returns the [`std::io::ErrorKind`] corresponding to this error.
+    pub fn kind(&self) -> std::io::ErrorKind {
+        use std::io::ErrorKind;
+
+        match *self {
+            ERRNO_2BIG => ErrorKind::ArgumentListTooLong,
+            ERRNO_ACCES | ERRNO_PERM | ERRNO_NOTCAPABLE => ErrorKind::PermissionDenied,
+            ERRNO_ADDRINUSE => ErrorKind::AddrInUse,
+            ERRNO_ADDRNOTAVAIL => ErrorKind::AddrNotAvailable,
+            ERRNO_AGAIN => ErrorKind::WouldBlock,
+            ERRNO_BUSY => ErrorKind::ResourceBusy,
+            ERRNO_CONNABORTED => ErrorKind::ConnectionAborted,
+            ERRNO_CONNREFUSED => ErrorKind::ConnectionRefused,
+            ERRNO_CONNRESET => ErrorKind::ConnectionReset,
+            ERRNO_DEADLK => ErrorKind::Deadlock,
+            ERRNO_EXIST => ErrorKind::AlreadyExists,
+            ERRNO_FBIG => ErrorKind::FileTooLarge,
+            ERRNO_HOSTUNREACH => ErrorKind::HostUnreachable,
+            ERRNO_ILSEQ => ErrorKind::InvalidData,
+            ERRNO_INTR => ErrorKind::Interrupted,
+            ERRNO_INVAL => ErrorKind::InvalidInput,
+            ERRNO_ISDIR => ErrorKind::IsADirectory,
+            ERRNO_MLINK => ErrorKind::TooManyLinks,
+            ERRNO_NAMETOOLONG => ErrorKind::InvalidFilename,
+            ERRNO_NETDOWN => ErrorKind::NetworkDown,
+            ERRNO_NETUNREACH => ErrorKind::NetworkUnreachable,
+            ERRNO_NOENT => ErrorKind::NotFound,
+            ERRNO_NOMEM => ErrorKind::OutOfMemory,
+            ERRNO_NOSPC => ErrorKind::StorageFull,
+            ERRNO_NOSYS | ERRNO_NOTSUP => ErrorKind::Unsupported,
+            ERRNO_NOTCONN => ErrorKind::NotConnected,
+            ERRNO_NOTDIR => ErrorKind::NotADirectory,
+            ERRNO_NOTEMPTY => ErrorKind::DirectoryNotEmpty,
+            ERRNO_PIPE => ErrorKind::BrokenPipe,
+            ERRNO_ROFS => ErrorKind::ReadOnlyFilesystem,
+            ERRNO_SPIPE => ErrorKind::NotSeekable,
+            ERRNO_STALE => ErrorKind::StaleNetworkFileHandle,
+            ERRNO_TIMEDOUT => ErrorKind::TimedOut,
+            ERRNO_TXTBSY => ErrorKind::ExecutableFileBusy,
+            ERRNO_XDEV => ErrorKind::CrossesDevices,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+/// Converts an [`Errno`] into an [`std::io::Error`] of the corresponding
+/// [`kind`](Errno::kind), keeping the [`Errno`] as the inner error.
+#[cfg(feature = "std")]
+impl From<Errno> for std::io::Error {
+    fn from(errno: Errno) -> std::io::Error {
+        std::io::Error::new(errno.kind(), errno)
+    }
+}
@@ -2,8 +2,9 @@
 //
 // To regenerate this file run the `crates/witx-bindgen` command
 
-use core::fmt;
+#[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
 use core::mem::MaybeUninit;
+use core::fmt;
 pub type Size = usize;
 pub type Filesize = u64;
 pub type Timestamp = u64;
@@ -26,14 +27,13 @@ impl Clockid {
     pub const fn raw(&self) -> u32 {
         self.0
     }
-
     pub fn name(&self) -> &'static str {
         match self.0 {
             0 => "REALTIME",
             1 => "MONOTONIC",
             2 => "PROCESS_CPUTIME_ID",
             3 => "THREAD_CPUTIME_ID",
-            _ => unsafe { core::hint::unreachable_unchecked() },
+            _ => unsafe { core::hint::unreachable_unchecked() }
         }
     }
     pub fn message(&self) -> &'static str {
@@ -50,7 +50,7 @@ value of this clock therefore has no meaning."
             }
             2 => "The CPU-time clock associated with the current process.",
             3 => "The CPU-time clock associated with the current thread.",
-            _ => unsafe { core::hint::unreachable_unchecked() },
+            _ => unsafe { core::hint::unreachable_unchecked() }
         }
     }
 }
@@ -63,7 +63,6 @@ impl fmt::Debug for Clockid {
             .finish()
     }
 }
-
 #[repr(transparent)]
 #[derive(Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Errno(u16);
@@ -225,7 +224,6 @@ impl Errno {
     pub const fn raw(&self) -> u16 {
         self.0
     }
-
     pub fn name(&self) -> &'static str {
         match self.0 {
             0 => "SUCCESS",
@@ -305,7 +303,7 @@ impl Errno {
             74 => "TXTBSY",
             75 => "XDEV",
             76 => "NOTCAPABLE",
-            _ => unsafe { core::hint::unreachable_unchecked() },
+            _ => unsafe { core::hint::unreachable_unchecked() }
         }
     }
     pub fn message(&self) -> &'static str {
@@ -387,7 +385,7 @@ impl Errno {
             74 => "Text file busy.",
             75 => "Cross-device link.",
             76 => "Extension: Capabilities insufficient.",
-            _ => unsafe { core::hint::unreachable_unchecked() },
+            _ => unsafe { core::hint::unreachable_unchecked() }
         }
     }
 }
@@ -405,86 +403,295 @@ impl fmt::Display for Errno {
         write!(f, "{} (error {})", self.name(), self.0)
     }
 }
-
 #[cfg(feature = "std")]
 extern crate std;
 #[cfg(feature = "std")]
 impl std::error::Error for Errno {}
-
+#[cfg(not(feature = "bitflags"))]
 pub type Rights = u64;
+#[cfg(feature = "bitflags")]
+bitflags::bitflags! {
+    #[repr(transparent)]
+    #[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+    pub struct Rights: u64 {
+        /// The right to invoke `fd_datasync`.
+        /// If `path_open` is set, includes the right to invoke
+        /// `path_open` with `fdflags::dsync`.
+        const FD_DATASYNC = 1 << 0;
+        /// The right to invoke `fd_read` and `sock_recv`.
+        /// If `rights::fd_seek` is set, includes the right to invoke `fd_pread`.
+        const FD_READ = 1 << 1;
+        /// The right to invoke `fd_seek`. This flag implies `rights::fd_tell`.
+        const FD_SEEK = 1 << 2;
+        /// The right to invoke `fd_fdstat_set_flags`.
+        const FD_FDSTAT_SET_FLAGS = 1 << 3;
+        /// The right to invoke `fd_sync`.
+        /// If `path_open` is set, includes the right to invoke
+        /// `path_open` with `fdflags::rsync` and `fdflags::dsync`.
+        const FD_SYNC = 1 << 4;
+        /// The right to invoke `fd_seek` in such a way that the file offset
+        /// remains unaltered (i.e., `whence::cur` with offset zero), or to
+        /// invoke `fd_tell`.
+        const FD_TELL = 1 << 5;
+        /// The right to invoke `fd_write` and `sock_send`.
+        /// If `rights::fd_seek` is set, includes the right to invoke `fd_pwrite`.
+        const FD_WRITE = 1 << 6;
+        /// The right to invoke `fd_advise`.
+        const FD_ADVISE = 1 << 7;
+        /// The right to invoke `fd_allocate`.
+        const FD_ALLOCATE = 1 << 8;
+        /// The right to invoke `path_create_directory`.
+        const PATH_CREATE_DIRECTORY = 1 << 9;
+        /// If `path_open` is set, the right to invoke `path_open` with `oflags::creat`.
+        const PATH_CREATE_FILE = 1 << 10;
+        /// The right to invoke `path_link` with the file descriptor as the
+        /// source directory.
+        const PATH_LINK_SOURCE = 1 << 11;
+        /// The right to invoke `path_link` with the file descriptor as the
+        /// target directory.
+        const PATH_LINK_TARGET = 1 << 12;
+        /// The right to invoke `path_open`.
+        const PATH_OPEN = 1 << 13;
+        /// The right to invoke `fd_readdir`.
+        const FD_READDIR = 1 << 14;
+        /// The right to invoke `path_readlink`.
+        const PATH_READLINK = 1 << 15;
+        /// The right to invoke `path_rename` with the file descriptor as the source directory.
+        const PATH_RENAME_SOURCE = 1 << 16;
+        /// The right to invoke `path_rename` with the file descriptor as the target directory.
+        const PATH_RENAME_TARGET = 1 << 17;
+        /// The right to invoke `path_filestat_get`.
+        const PATH_FILESTAT_GET = 1 << 18;
+        /// The right to change a file's size (there is no `path_filestat_set_size`).
+        /// If `path_open` is set, includes the right to invoke `path_open` with `oflags::trunc`.
+        const PATH_FILESTAT_SET_SIZE = 1 << 19;
+        /// The right to invoke `path_filestat_set_times`.
+        const PATH_FILESTAT_SET_TIMES = 1 << 20;
+        /// The right to invoke `fd_filestat_get`.
+        const FD_FILESTAT_GET = 1 << 21;
+        /// The right to invoke `fd_filestat_set_size`.
+        const FD_FILESTAT_SET_SIZE = 1 << 22;
+        /// The right to invoke `fd_filestat_set_times`.
+        const FD_FILESTAT_SET_TIMES = 1 << 23;
+        /// The right to invoke `path_symlink`.
+        const PATH_SYMLINK = 1 << 24;
+        /// The right to invoke `path_remove_directory`.
+        const PATH_REMOVE_DIRECTORY = 1 << 25;
+        /// The right to invoke `path_unlink_file`.
+        const PATH_UNLINK_FILE = 1 << 26;
+        /// If `rights::fd_read` is set, includes the right to invoke `poll_oneoff` to subscribe to `eventtype::fd_read`.
+        /// If `rights::fd_write` is set, includes the right to invoke `poll_oneoff` to subscribe to `eventtype::fd_write`.
+        const POLL_FD_READWRITE = 1 << 27;
+        /// The right to invoke `sock_shutdown`.
+        const SOCK_SHUTDOWN = 1 << 28;
+        /// The right to invoke `sock_accept`.
+        const SOCK_ACCEPT = 1 << 29;
+    }
+}
 /// The right to invoke `fd_datasync`.
 /// If `path_open` is set, includes the right to invoke
 /// `path_open` with `fdflags::dsync`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_FD_DATASYNC: Rights = 1 << 0;
+/// The right to invoke `fd_datasync`.
+/// If `path_open` is set, includes the right to invoke
+/// `path_open` with `fdflags::dsync`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_FD_DATASYNC: Rights = Rights::FD_DATASYNC;
 /// The right to invoke `fd_read` and `sock_recv`.
 /// If `rights::fd_seek` is set, includes the right to invoke `fd_pread`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_FD_READ: Rights = 1 << 1;
+/// The right to invoke `fd_read` and `sock_recv`.
+/// If `rights::fd_seek` is set, includes the right to invoke `fd_pread`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_FD_READ: Rights = Rights::FD_READ;
 /// The right to invoke `fd_seek`. This flag implies `rights::fd_tell`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_FD_SEEK: Rights = 1 << 2;
+/// The right to invoke `fd_seek`. This flag implies `rights::fd_tell`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_FD_SEEK: Rights = Rights::FD_SEEK;
 /// The right to invoke `fd_fdstat_set_flags`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_FD_FDSTAT_SET_FLAGS: Rights = 1 << 3;
+/// The right to invoke `fd_fdstat_set_flags`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_FD_FDSTAT_SET_FLAGS: Rights = Rights::FD_FDSTAT_SET_FLAGS;
 /// The right to invoke `fd_sync`.
 /// If `path_open` is set, includes the right to invoke
 /// `path_open` with `fdflags::rsync` and `fdflags::dsync`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_FD_SYNC: Rights = 1 << 4;
+/// The right to invoke `fd_sync`.
+/// If `path_open` is set, includes the right to invoke
+/// `path_open` with `fdflags::rsync` and `fdflags::dsync`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_FD_SYNC: Rights = Rights::FD_SYNC;
 /// The right to invoke `fd_seek` in such a way that the file offset
 /// remains unaltered (i.e., `whence::cur` with offset zero), or to
 /// invoke `fd_tell`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_FD_TELL: Rights = 1 << 5;
+/// The right to invoke `fd_seek` in such a way that the file offset
+/// remains unaltered (i.e., `whence::cur` with offset zero), or to
+/// invoke `fd_tell`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_FD_TELL: Rights = Rights::FD_TELL;
 /// The right to invoke `fd_write` and `sock_send`.
 /// If `rights::fd_seek` is set, includes the right to invoke `fd_pwrite`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_FD_WRITE: Rights = 1 << 6;
+/// The right to invoke `fd_write` and `sock_send`.
+/// If `rights::fd_seek` is set, includes the right to invoke `fd_pwrite`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_FD_WRITE: Rights = Rights::FD_WRITE;
 /// The right to invoke `fd_advise`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_FD_ADVISE: Rights = 1 << 7;
+/// The right to invoke `fd_advise`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_FD_ADVISE: Rights = Rights::FD_ADVISE;
 /// The right to invoke `fd_allocate`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_FD_ALLOCATE: Rights = 1 << 8;
+/// The right to invoke `fd_allocate`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_FD_ALLOCATE: Rights = Rights::FD_ALLOCATE;
 /// The right to invoke `path_create_directory`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_PATH_CREATE_DIRECTORY: Rights = 1 << 9;
+/// The right to invoke `path_create_directory`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_PATH_CREATE_DIRECTORY: Rights = Rights::PATH_CREATE_DIRECTORY;
 /// If `path_open` is set, the right to invoke `path_open` with `oflags::creat`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_PATH_CREATE_FILE: Rights = 1 << 10;
+/// If `path_open` is set, the right to invoke `path_open` with `oflags::creat`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_PATH_CREATE_FILE: Rights = Rights::PATH_CREATE_FILE;
 /// The right to invoke `path_link` with the file descriptor as the
 /// source directory.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_PATH_LINK_SOURCE: Rights = 1 << 11;
 /// The right to invoke `path_link` with the file descriptor as the
+/// source directory.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_PATH_LINK_SOURCE: Rights = Rights::PATH_LINK_SOURCE;
+/// The right to invoke `path_link` with the file descriptor as the
 /// target directory.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_PATH_LINK_TARGET: Rights = 1 << 12;
+/// The right to invoke `path_link` with the file descriptor as the
+/// target directory.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_PATH_LINK_TARGET: Rights = Rights::PATH_LINK_TARGET;
 /// The right to invoke `path_open`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_PATH_OPEN: Rights = 1 << 13;
+/// The right to invoke `path_open`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_PATH_OPEN: Rights = Rights::PATH_OPEN;
 /// The right to invoke `fd_readdir`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_FD_READDIR: Rights = 1 << 14;
+/// The right to invoke `fd_readdir`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_FD_READDIR: Rights = Rights::FD_READDIR;
 /// The right to invoke `path_readlink`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_PATH_READLINK: Rights = 1 << 15;
+/// The right to invoke `path_readlink`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_PATH_READLINK: Rights = Rights::PATH_READLINK;
 /// The right to invoke `path_rename` with the file descriptor as the source directory.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_PATH_RENAME_SOURCE: Rights = 1 << 16;
+/// The right to invoke `path_rename` with the file descriptor as the source directory.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_PATH_RENAME_SOURCE: Rights = Rights::PATH_RENAME_SOURCE;
 /// The right to invoke `path_rename` with the file descriptor as the target directory.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_PATH_RENAME_TARGET: Rights = 1 << 17;
+/// The right to invoke `path_rename` with the file descriptor as the target directory.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_PATH_RENAME_TARGET: Rights = Rights::PATH_RENAME_TARGET;
 /// The right to invoke `path_filestat_get`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_PATH_FILESTAT_GET: Rights = 1 << 18;
+/// The right to invoke `path_filestat_get`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_PATH_FILESTAT_GET: Rights = Rights::PATH_FILESTAT_GET;
 /// The right to change a file's size (there is no `path_filestat_set_size`).
 /// If `path_open` is set, includes the right to invoke `path_open` with `oflags::trunc`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_PATH_FILESTAT_SET_SIZE: Rights = 1 << 19;
+/// The right to change a file's size (there is no `path_filestat_set_size`).
+/// If `path_open` is set, includes the right to invoke `path_open` with `oflags::trunc`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_PATH_FILESTAT_SET_SIZE: Rights = Rights::PATH_FILESTAT_SET_SIZE;
 /// The right to invoke `path_filestat_set_times`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_PATH_FILESTAT_SET_TIMES: Rights = 1 << 20;
+/// The right to invoke `path_filestat_set_times`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_PATH_FILESTAT_SET_TIMES: Rights = Rights::PATH_FILESTAT_SET_TIMES;
 /// The right to invoke `fd_filestat_get`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_FD_FILESTAT_GET: Rights = 1 << 21;
+/// The right to invoke `fd_filestat_get`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_FD_FILESTAT_GET: Rights = Rights::FD_FILESTAT_GET;
 /// The right to invoke `fd_filestat_set_size`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_FD_FILESTAT_SET_SIZE: Rights = 1 << 22;
+/// The right to invoke `fd_filestat_set_size`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_FD_FILESTAT_SET_SIZE: Rights = Rights::FD_FILESTAT_SET_SIZE;
 /// The right to invoke `fd_filestat_set_times`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_FD_FILESTAT_SET_TIMES: Rights = 1 << 23;
+/// The right to invoke `fd_filestat_set_times`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_FD_FILESTAT_SET_TIMES: Rights = Rights::FD_FILESTAT_SET_TIMES;
 /// The right to invoke `path_symlink`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_PATH_SYMLINK: Rights = 1 << 24;
+/// The right to invoke `path_symlink`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_PATH_SYMLINK: Rights = Rights::PATH_SYMLINK;
 /// The right to invoke `path_remove_directory`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_PATH_REMOVE_DIRECTORY: Rights = 1 << 25;
+/// The right to invoke `path_remove_directory`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_PATH_REMOVE_DIRECTORY: Rights = Rights::PATH_REMOVE_DIRECTORY;
 /// The right to invoke `path_unlink_file`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_PATH_UNLINK_FILE: Rights = 1 << 26;
+/// The right to invoke `path_unlink_file`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_PATH_UNLINK_FILE: Rights = Rights::PATH_UNLINK_FILE;
 /// If `rights::fd_read` is set, includes the right to invoke `poll_oneoff` to subscribe to `eventtype::fd_read`.
 /// If `rights::fd_write` is set, includes the right to invoke `poll_oneoff` to subscribe to `eventtype::fd_write`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_POLL_FD_READWRITE: Rights = 1 << 27;
+/// If `rights::fd_read` is set, includes the right to invoke `poll_oneoff` to subscribe to `eventtype::fd_read`.
+/// If `rights::fd_write` is set, includes the right to invoke `poll_oneoff` to subscribe to `eventtype::fd_write`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_POLL_FD_READWRITE: Rights = Rights::POLL_FD_READWRITE;
 /// The right to invoke `sock_shutdown`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_SOCK_SHUTDOWN: Rights = 1 << 28;
+/// The right to invoke `sock_shutdown`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_SOCK_SHUTDOWN: Rights = Rights::SOCK_SHUTDOWN;
 /// The right to invoke `sock_accept`.
+#[cfg(not(feature = "bitflags"))]
 pub const RIGHTS_SOCK_ACCEPT: Rights = 1 << 29;
-
+/// The right to invoke `sock_accept`.
+#[cfg(feature = "bitflags")]
+pub const RIGHTS_SOCK_ACCEPT: Rights = Rights::SOCK_ACCEPT;
 pub type Fd = u32;
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
@@ -494,6 +701,13 @@ pub struct Iovec {
     /// The length of the buffer to be filled.
     pub buf_len: Size,
 }
+#[cfg(target_pointer_width = "32")]
+const _: () = {
+    assert!(core::mem::size_of::<Iovec>() == 8);
+    assert!(core::mem::align_of::<Iovec>() == 4);
+    assert!(core::mem::offset_of!(Iovec, buf) == 0);
+    assert!(core::mem::offset_of!(Iovec, buf_len) == 4);
+};
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
 pub struct Ciovec {
@@ -502,6 +716,13 @@ pub struct Ciovec {
     /// The length of the buffer to be written.
     pub buf_len: Size,
 }
+#[cfg(target_pointer_width = "32")]
+const _: () = {
+    assert!(core::mem::size_of::<Ciovec>() == 8);
+    assert!(core::mem::align_of::<Ciovec>() == 4);
+    assert!(core::mem::offset_of!(Ciovec, buf) == 0);
+    assert!(core::mem::offset_of!(Ciovec, buf_len) == 4);
+};
 pub type IovecArray<'a> = &'a [Iovec];
 pub type CiovecArray<'a> = &'a [Ciovec];
 pub type Filedelta = i64;
@@ -518,13 +739,12 @@ impl Whence {
     pub const fn raw(&self) -> u8 {
         self.0
     }
-
     pub fn name(&self) -> &'static str {
         match self.0 {
             0 => "SET",
             1 => "CUR",
             2 => "END",
-            _ => unsafe { core::hint::unreachable_unchecked() },
+            _ => unsafe { core::hint::unreachable_unchecked() }
         }
     }
     pub fn message(&self) -> &'static str {
@@ -532,7 +752,7 @@ impl Whence {
             0 => "Seek relative to start-of-file.",
             1 => "Seek relative to current position.",
             2 => "Seek relative to end-of-file.",
-            _ => unsafe { core::hint::unreachable_unchecked() },
+            _ => unsafe { core::hint::unreachable_unchecked() }
         }
     }
 }
@@ -545,7 +765,6 @@ impl fmt::Debug for Whence {
             .finish()
     }
 }
-
 pub type Dircookie = u64;
 pub type Dirnamlen = u32;
 pub type Inode = u64;
@@ -572,7 +791,6 @@ impl Filetype {
     pub const fn raw(&self) -> u8 {
         self.0
     }
-
     pub fn name(&self) -> &'static str {
         match self.0 {
             0 => "UNKNOWN",
@@ -583,11 +801,23 @@ impl Filetype {
             5 => "SOCKET_DGRAM",
             6 => "SOCKET_STREAM",
             7 => "SYMBOLIC_LINK",
-            _ => unsafe { core::hint::unreachable_unchecked() },
+            _ => unsafe { core::hint::unreachable_unchecked() }
         }
     }
     pub fn message(&self) -> &'static str {
-        match self.0 {0 => "The type of the file descriptor or file is unknown or is different from any of the other types specified.",1 => "The file descriptor or file refers to a block device inode.",2 => "The file descriptor or file refers to a character device inode.",3 => "The file descriptor or file refers to a directory inode.",4 => "The file descriptor or file refers to a regular file inode.",5 => "The file descriptor or file refers to a datagram socket.",6 => "The file descriptor or file refers to a byte-stream socket.",7 => "The file refers to a symbolic link inode.",_ => unsafe { core::hint::unreachable_unchecked() },}
+        match self.0 {
+            0 => {
+                "The type of the file descriptor or file is unknown or is different from any of the other types specified."
+            }
+            1 => "The file descriptor or file refers to a block device inode.",
+            2 => "The file descriptor or file refers to a character device inode.",
+            3 => "The file descriptor or file refers to a directory inode.",
+            4 => "The file descriptor or file refers to a regular file inode.",
+            5 => "The file descriptor or file refers to a datagram socket.",
+            6 => "The file descriptor or file refers to a byte-stream socket.",
+            7 => "The file refers to a symbolic link inode.",
+            _ => unsafe { core::hint::unreachable_unchecked() }
+        }
     }
 }
 impl fmt::Debug for Filetype {
@@ -599,7 +829,6 @@ impl fmt::Debug for Filetype {
             .finish()
     }
 }
-
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
 pub struct Dirent {
@@ -612,6 +841,14 @@ pub struct Dirent {
     /// The type of the file referred to by this directory entry.
     pub d_type: Filetype,
 }
+const _: () = {
+    assert!(core::mem::size_of::<Dirent>() == 24);
+    assert!(core::mem::align_of::<Dirent>() == 8);
+    assert!(core::mem::offset_of!(Dirent, d_next) == 0);
+    assert!(core::mem::offset_of!(Dirent, d_ino) == 8);
+    assert!(core::mem::offset_of!(Dirent, d_namlen) == 16);
+    assert!(core::mem::offset_of!(Dirent, d_type) == 20);
+};
 #[repr(transparent)]
 #[derive(Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Advice(u8);
@@ -631,7 +868,6 @@ impl Advice {
     pub const fn raw(&self) -> u8 {
         self.0
     }
-
     pub fn name(&self) -> &'static str {
         match self.0 {
             0 => "NORMAL",
@@ -640,11 +876,31 @@ impl Advice {
             3 => "WILLNEED",
             4 => "DONTNEED",
             5 => "NOREUSE",
-            _ => unsafe { core::hint::unreachable_unchecked() },
+            _ => unsafe { core::hint::unreachable_unchecked() }
         }
     }
     pub fn message(&self) -> &'static str {
-        match self.0 {0 => "The application has no advice to give on its behavior with respect to the specified data.",1 => "The application expects to access the specified data sequentially from lower offsets to higher offsets.",2 => "The application expects to access the specified data in a random order.",3 => "The application expects to access the specified data in the near future.",4 => "The application expects that it will not access the specified data in the near future.",5 => "The application expects to access the specified data once and then not reuse it thereafter.",_ => unsafe { core::hint::unreachable_unchecked() },}
+        match self.0 {
+            0 => {
+                "The application has no advice to give on its behavior with respect to the specified data."
+            }
+            1 => {
+                "The application expects to access the specified data sequentially from lower offsets to higher offsets."
+            }
+            2 => {
+                "The application expects to access the specified data in a random order."
+            }
+            3 => {
+                "The application expects to access the specified data in the near future."
+            }
+            4 => {
+                "The application expects that it will not access the specified data in the near future."
+            }
+            5 => {
+                "The application expects to access the specified data once and then not reuse it thereafter."
+            }
+            _ => unsafe { core::hint::unreachable_unchecked() }
+        }
     }
 }
 impl fmt::Debug for Advice {
@@ -656,21 +912,61 @@ impl fmt::Debug for Advice {
             .finish()
     }
 }
-
+#[cfg(not(feature = "bitflags"))]
 pub type Fdflags = u16;
+#[cfg(feature = "bitflags")]
+bitflags::bitflags! {
+    #[repr(transparent)]
+    #[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+    pub struct Fdflags: u16 {
+        /// Append mode: Data written to the file is always appended to the file's end.
+        const APPEND = 1 << 0;
+        /// Write according to synchronized I/O data integrity completion. Only the data stored in the file is synchronized.
+        const DSYNC = 1 << 1;
+        /// Non-blocking mode.
+        const NONBLOCK = 1 << 2;
+        /// Synchronized read I/O operations.
+        const RSYNC = 1 << 3;
+        /// Write according to synchronized I/O file integrity completion. In
+        /// addition to synchronizing the data stored in the file, the implementation
+        /// may also synchronously update the file's metadata.
+        const SYNC = 1 << 4;
+    }
+}
 /// Append mode: Data written to the file is always appended to the file's end.
+#[cfg(not(feature = "bitflags"))]
 pub const FDFLAGS_APPEND: Fdflags = 1 << 0;
+/// Append mode: Data written to the file is always appended to the file's end.
+#[cfg(feature = "bitflags")]
+pub const FDFLAGS_APPEND: Fdflags = Fdflags::APPEND;
 /// Write according to synchronized I/O data integrity completion. Only the data stored in the file is synchronized.
+#[cfg(not(feature = "bitflags"))]
 pub const FDFLAGS_DSYNC: Fdflags = 1 << 1;
+/// Write according to synchronized I/O data integrity completion. Only the data stored in the file is synchronized.
+#[cfg(feature = "bitflags")]
+pub const FDFLAGS_DSYNC: Fdflags = Fdflags::DSYNC;
 /// Non-blocking mode.
+#[cfg(not(feature = "bitflags"))]
 pub const FDFLAGS_NONBLOCK: Fdflags = 1 << 2;
+/// Non-blocking mode.
+#[cfg(feature = "bitflags")]
+pub const FDFLAGS_NONBLOCK: Fdflags = Fdflags::NONBLOCK;
 /// Synchronized read I/O operations.
+#[cfg(not(feature = "bitflags"))]
 pub const FDFLAGS_RSYNC: Fdflags = 1 << 3;
+/// Synchronized read I/O operations.
+#[cfg(feature = "bitflags")]
+pub const FDFLAGS_RSYNC: Fdflags = Fdflags::RSYNC;
 /// Write according to synchronized I/O file integrity completion. In
 /// addition to synchronizing the data stored in the file, the implementation
 /// may also synchronously update the file's metadata.
+#[cfg(not(feature = "bitflags"))]
 pub const FDFLAGS_SYNC: Fdflags = 1 << 4;
-
+/// Write according to synchronized I/O file integrity completion. In
+/// addition to synchronizing the data stored in the file, the implementation
+/// may also synchronously update the file's metadata.
+#[cfg(feature = "bitflags")]
+pub const FDFLAGS_SYNC: Fdflags = Fdflags::SYNC;
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
 pub struct Fdstat {
@@ -684,31 +980,114 @@ pub struct Fdstat {
     /// are created through this file descriptor, e.g., through `path_open`.
     pub fs_rights_inheriting: Rights,
 }
+const _: () = {
+    assert!(core::mem::size_of::<Fdstat>() == 24);
+    assert!(core::mem::align_of::<Fdstat>() == 8);
+    assert!(core::mem::offset_of!(Fdstat, fs_filetype) == 0);
+    assert!(core::mem::offset_of!(Fdstat, fs_flags) == 2);
+    assert!(core::mem::offset_of!(Fdstat, fs_rights_base) == 8);
+    assert!(core::mem::offset_of!(Fdstat, fs_rights_inheriting) == 16);
+};
 pub type Device = u64;
+#[cfg(not(feature = "bitflags"))]
 pub type Fstflags = u16;
+#[cfg(feature = "bitflags")]
+bitflags::bitflags! {
+    #[repr(transparent)]
+    #[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+    pub struct Fstflags: u16 {
+        /// Adjust the last data access timestamp to the value stored in `filestat::atim`.
+        const ATIM = 1 << 0;
+        /// Adjust the last data access timestamp to the time of clock `clockid::realtime`.
+        const ATIM_NOW = 1 << 1;
+        /// Adjust the last data modification timestamp to the value stored in `filestat::mtim`.
+        const MTIM = 1 << 2;
+        /// Adjust the last data modification timestamp to the time of clock `clockid::realtime`.
+        const MTIM_NOW = 1 << 3;
+    }
+}
 /// Adjust the last data access timestamp to the value stored in `filestat::atim`.
+#[cfg(not(feature = "bitflags"))]
 pub const FSTFLAGS_ATIM: Fstflags = 1 << 0;
+/// Adjust the last data access timestamp to the value stored in `filestat::atim`.
+#[cfg(feature = "bitflags")]
+pub const FSTFLAGS_ATIM: Fstflags = Fstflags::ATIM;
 /// Adjust the last data access timestamp to the time of clock `clockid::realtime`.
+#[cfg(not(feature = "bitflags"))]
 pub const FSTFLAGS_ATIM_NOW: Fstflags = 1 << 1;
+/// Adjust the last data access timestamp to the time of clock `clockid::realtime`.
+#[cfg(feature = "bitflags")]
+pub const FSTFLAGS_ATIM_NOW: Fstflags = Fstflags::ATIM_NOW;
 /// Adjust the last data modification timestamp to the value stored in `filestat::mtim`.
+#[cfg(not(feature = "bitflags"))]
 pub const FSTFLAGS_MTIM: Fstflags = 1 << 2;
+/// Adjust the last data modification timestamp to the value stored in `filestat::mtim`.
+#[cfg(feature = "bitflags")]
+pub const FSTFLAGS_MTIM: Fstflags = Fstflags::MTIM;
 /// Adjust the last data modification timestamp to the time of clock `clockid::realtime`.
+#[cfg(not(feature = "bitflags"))]
 pub const FSTFLAGS_MTIM_NOW: Fstflags = 1 << 3;
-
+/// Adjust the last data modification timestamp to the time of clock `clockid::realtime`.
+#[cfg(feature = "bitflags")]
+pub const FSTFLAGS_MTIM_NOW: Fstflags = Fstflags::MTIM_NOW;
+#[cfg(not(feature = "bitflags"))]
 pub type Lookupflags = u32;
+#[cfg(feature = "bitflags")]
+bitflags::bitflags! {
+    #[repr(transparent)]
+    #[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+    pub struct Lookupflags: u32 {
+        /// As long as the resolved path corresponds to a symbolic link, it is expanded.
+        const SYMLINK_FOLLOW = 1 << 0;
+    }
+}
 /// As long as the resolved path corresponds to a symbolic link, it is expanded.
+#[cfg(not(feature = "bitflags"))]
 pub const LOOKUPFLAGS_SYMLINK_FOLLOW: Lookupflags = 1 << 0;
-
+/// As long as the resolved path corresponds to a symbolic link, it is expanded.
+#[cfg(feature = "bitflags")]
+pub const LOOKUPFLAGS_SYMLINK_FOLLOW: Lookupflags = Lookupflags::SYMLINK_FOLLOW;
+#[cfg(not(feature = "bitflags"))]
 pub type Oflags = u16;
+#[cfg(feature = "bitflags")]
+bitflags::bitflags! {
+    #[repr(transparent)]
+    #[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+    pub struct Oflags: u16 {
+        /// Create file if it does not exist.
+        const CREAT = 1 << 0;
+        /// Fail if not a directory.
+        const DIRECTORY = 1 << 1;
+        /// Fail if file already exists.
+        const EXCL = 1 << 2;
+        /// Truncate file to size 0.
+        const TRUNC = 1 << 3;
+    }
+}
 /// Create file if it does not exist.
+#[cfg(not(feature = "bitflags"))]
 pub const OFLAGS_CREAT: Oflags = 1 << 0;
+/// Create file if it does not exist.
+#[cfg(feature = "bitflags")]
+pub const OFLAGS_CREAT: Oflags = Oflags::CREAT;
 /// Fail if not a directory.
+#[cfg(not(feature = "bitflags"))]
 pub const OFLAGS_DIRECTORY: Oflags = 1 << 1;
+/// Fail if not a directory.
+#[cfg(feature = "bitflags")]
+pub const OFLAGS_DIRECTORY: Oflags = Oflags::DIRECTORY;
 /// Fail if file already exists.
+#[cfg(not(feature = "bitflags"))]
 pub const OFLAGS_EXCL: Oflags = 1 << 2;
+/// Fail if file already exists.
+#[cfg(feature = "bitflags")]
+pub const OFLAGS_EXCL: Oflags = Oflags::EXCL;
 /// Truncate file to size 0.
+#[cfg(not(feature = "bitflags"))]
 pub const OFLAGS_TRUNC: Oflags = 1 << 3;
-
+/// Truncate file to size 0.
+#[cfg(feature = "bitflags")]
+pub const OFLAGS_TRUNC: Oflags = Oflags::TRUNC;
 pub type Linkcount = u64;
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
@@ -730,6 +1109,18 @@ pub struct Filestat {
     /// Last file status change timestamp.
     pub ctim: Timestamp,
 }
+const _: () = {
+    assert!(core::mem::size_of::<Filestat>() == 64);
+    assert!(core::mem::align_of::<Filestat>() == 8);
+    assert!(core::mem::offset_of!(Filestat, dev) == 0);
+    assert!(core::mem::offset_of!(Filestat, ino) == 8);
+    assert!(core::mem::offset_of!(Filestat, filetype) == 16);
+    assert!(core::mem::offset_of!(Filestat, nlink) == 24);
+    assert!(core::mem::offset_of!(Filestat, size) == 32);
+    assert!(core::mem::offset_of!(Filestat, atim) == 40);
+    assert!(core::mem::offset_of!(Filestat, mtim) == 48);
+    assert!(core::mem::offset_of!(Filestat, ctim) == 56);
+};
 pub type Userdata = u64;
 #[repr(transparent)]
 #[derive(Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
@@ -747,13 +1138,12 @@ impl Eventtype {
     pub const fn raw(&self) -> u8 {
         self.0
     }
-
     pub fn name(&self) -> &'static str {
         match self.0 {
             0 => "CLOCK",
             1 => "FD_READ",
             2 => "FD_WRITE",
-            _ => unsafe { core::hint::unreachable_unchecked() },
+            _ => unsafe { core::hint::unreachable_unchecked() }
         }
     }
     pub fn message(&self) -> &'static str {
@@ -770,7 +1160,7 @@ available for reading. This event always triggers for regular files."
                 "File descriptor `subscription_fd_readwrite::file_descriptor` has capacity
 available for writing. This event always triggers for regular files."
             }
-            _ => unsafe { core::hint::unreachable_unchecked() },
+            _ => unsafe { core::hint::unreachable_unchecked() }
         }
     }
 }
@@ -783,11 +1173,23 @@ impl fmt::Debug for Eventtype {
             .finish()
     }
 }
-
+#[cfg(not(feature = "bitflags"))]
 pub type Eventrwflags = u16;
+#[cfg(feature = "bitflags")]
+bitflags::bitflags! {
+    #[repr(transparent)]
+    #[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+    pub struct Eventrwflags: u16 {
+        /// The peer of this socket has closed or disconnected.
+        const FD_READWRITE_HANGUP = 1 << 0;
+    }
+}
 /// The peer of this socket has closed or disconnected.
+#[cfg(not(feature = "bitflags"))]
 pub const EVENTRWFLAGS_FD_READWRITE_HANGUP: Eventrwflags = 1 << 0;
-
+/// The peer of this socket has closed or disconnected.
+#[cfg(feature = "bitflags")]
+pub const EVENTRWFLAGS_FD_READWRITE_HANGUP: Eventrwflags = Eventrwflags::FD_READWRITE_HANGUP;
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
 pub struct EventFdReadwrite {
@@ -796,6 +1198,12 @@ pub struct EventFdReadwrite {
     /// The state of the file descriptor.
     pub flags: Eventrwflags,
 }
+const _: () = {
+    assert!(core::mem::size_of::<EventFdReadwrite>() == 16);
+    assert!(core::mem::align_of::<EventFdReadwrite>() == 8);
+    assert!(core::mem::offset_of!(EventFdReadwrite, nbytes) == 0);
+    assert!(core::mem::offset_of!(EventFdReadwrite, flags) == 8);
+};
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
 pub struct Event {
@@ -809,14 +1217,43 @@ pub struct Event {
     /// `eventtype::fd_write`. `eventtype::clock` events ignore this field.
     pub fd_readwrite: EventFdReadwrite,
 }
+const _: () = {
+    assert!(core::mem::size_of::<Event>() == 32);
+    assert!(core::mem::align_of::<Event>() == 8);
+    assert!(core::mem::offset_of!(Event, userdata) == 0);
+    assert!(core::mem::offset_of!(Event, error) == 8);
+    assert!(core::mem::offset_of!(Event, type_) == 10);
+    assert!(core::mem::offset_of!(Event, fd_readwrite) == 16);
+};
+#[cfg(not(feature = "bitflags"))]
 pub type Subclockflags = u16;
+#[cfg(feature = "bitflags")]
+bitflags::bitflags! {
+    #[repr(transparent)]
+    #[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+    pub struct Subclockflags: u16 {
+        /// If set, treat the timestamp provided in
+        /// `subscription_clock::timeout` as an absolute timestamp of clock
+        /// `subscription_clock::id`. If clear, treat the timestamp
+        /// provided in `subscription_clock::timeout` relative to the
+        /// current time value of clock `subscription_clock::id`.
+        const SUBSCRIPTION_CLOCK_ABSTIME = 1 << 0;
+    }
+}
 /// If set, treat the timestamp provided in
 /// `subscription_clock::timeout` as an absolute timestamp of clock
 /// `subscription_clock::id`. If clear, treat the timestamp
 /// provided in `subscription_clock::timeout` relative to the
 /// current time value of clock `subscription_clock::id`.
+#[cfg(not(feature = "bitflags"))]
 pub const SUBCLOCKFLAGS_SUBSCRIPTION_CLOCK_ABSTIME: Subclockflags = 1 << 0;
-
+/// If set, treat the timestamp provided in
+/// `subscription_clock::timeout` as an absolute timestamp of clock
+/// `subscription_clock::id`. If clear, treat the timestamp
+/// provided in `subscription_clock::timeout` relative to the
+/// current time value of clock `subscription_clock::id`.
+#[cfg(feature = "bitflags")]
+pub const SUBCLOCKFLAGS_SUBSCRIPTION_CLOCK_ABSTIME: Subclockflags = Subclockflags::SUBSCRIPTION_CLOCK_ABSTIME;
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
 pub struct SubscriptionClock {
@@ -830,12 +1267,25 @@ pub struct SubscriptionClock {
     /// Flags specifying whether the timeout is absolute or relative
     pub flags: Subclockflags,
 }
+const _: () = {
+    assert!(core::mem::size_of::<SubscriptionClock>() == 32);
+    assert!(core::mem::align_of::<SubscriptionClock>() == 8);
+    assert!(core::mem::offset_of!(SubscriptionClock, id) == 0);
+    assert!(core::mem::offset_of!(SubscriptionClock, timeout) == 8);
+    assert!(core::mem::offset_of!(SubscriptionClock, precision) == 16);
+    assert!(core::mem::offset_of!(SubscriptionClock, flags) == 24);
+};
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
 pub struct SubscriptionFdReadwrite {
     /// The file descriptor on which to wait for it to become ready for reading or writing.
     pub file_descriptor: Fd,
 }
+const _: () = {
+    assert!(core::mem::size_of::<SubscriptionFdReadwrite>() == 4);
+    assert!(core::mem::align_of::<SubscriptionFdReadwrite>() == 4);
+    assert!(core::mem::offset_of!(SubscriptionFdReadwrite, file_descriptor) == 0);
+};
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub union SubscriptionUU {
@@ -849,7 +1299,19 @@ pub struct SubscriptionU {
     pub tag: u8,
     pub u: SubscriptionUU,
 }
-
+const _: () = {
+    assert!(core::mem::size_of::<SubscriptionUU>() == 32);
+    assert!(core::mem::align_of::<SubscriptionUU>() == 8);
+    assert!(core::mem::offset_of!(SubscriptionUU, clock) == 0);
+    assert!(core::mem::offset_of!(SubscriptionUU, fd_read) == 0);
+    assert!(core::mem::offset_of!(SubscriptionUU, fd_write) == 0);
+};
+const _: () = {
+    assert!(core::mem::size_of::<SubscriptionU>() == 40);
+    assert!(core::mem::align_of::<SubscriptionU>() == 8);
+    assert!(core::mem::offset_of!(SubscriptionU, tag) == 0);
+    assert!(core::mem::offset_of!(SubscriptionU, u) == 8);
+};
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct Subscription {
@@ -859,6 +1321,12 @@ pub struct Subscription {
     /// The type of the event to which to subscribe, and its contents
     pub u: SubscriptionU,
 }
+const _: () = {
+    assert!(core::mem::size_of::<Subscription>() == 48);
+    assert!(core::mem::align_of::<Subscription>() == 8);
+    assert!(core::mem::offset_of!(Subscription, userdata) == 0);
+    assert!(core::mem::offset_of!(Subscription, u) == 8);
+};
 pub type Exitcode = u32;
 #[repr(transparent)]
 #[derive(Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
@@ -960,7 +1428,6 @@ impl Signal {
     pub const fn raw(&self) -> u8 {
         self.0
     }
-
     pub fn name(&self) -> &'static str {
         match self.0 {
             0 => "NONE",
@@ -994,7 +1461,7 @@ impl Signal {
             28 => "POLL",
             29 => "PWR",
             30 => "SYS",
-            _ => unsafe { core::hint::unreachable_unchecked() },
+            _ => unsafe { core::hint::unreachable_unchecked() }
         }
     }
     pub fn message(&self) -> &'static str {
@@ -1003,127 +1470,71 @@ impl Signal {
                 "No signal. Note that POSIX has special semantics for `kill(pid, 0)`,
 so this value is reserved."
             }
-            1 => {
-                "Hangup.
-Action: Terminates the process."
-            }
-            2 => {
-                "Terminate interrupt signal.
-Action: Terminates the process."
-            }
-            3 => {
-                "Terminal quit signal.
-Action: Terminates the process."
-            }
-            4 => {
-                "Illegal instruction.
-Action: Terminates the process."
-            }
-            5 => {
-                "Trace/breakpoint trap.
-Action: Terminates the process."
-            }
-            6 => {
-                "Process abort signal.
-Action: Terminates the process."
-            }
+            1 => "Hangup.
+Action: Terminates the process.",
+            2 => "Terminate interrupt signal.
+Action: Terminates the process.",
+            3 => "Terminal quit signal.
+Action: Terminates the process.",
+            4 => "Illegal instruction.
+Action: Terminates the process.",
+            5 => "Trace/breakpoint trap.
+Action: Terminates the process.",
+            6 => "Process abort signal.
+Action: Terminates the process.",
             7 => {
                 "Access to an undefined portion of a memory object.
 Action: Terminates the process."
             }
-            8 => {
-                "Erroneous arithmetic operation.
-Action: Terminates the process."
-            }
-            9 => {
-                "Kill.
-Action: Terminates the process."
-            }
-            10 => {
-                "User-defined signal 1.
-Action: Terminates the process."
-            }
-            11 => {
-                "Invalid memory reference.
-Action: Terminates the process."
-            }
-            12 => {
-                "User-defined signal 2.
-Action: Terminates the process."
-            }
-            13 => {
-                "Write on a pipe with no one to read it.
-Action: Ignored."
-            }
-            14 => {
-                "Alarm clock.
-Action: Terminates the process."
-            }
-            15 => {
-                "Termination signal.
-Action: Terminates the process."
-            }
-            16 => {
-                "Child process terminated, stopped, or continued.
-Action: Ignored."
-            }
+            8 => "Erroneous arithmetic operation.
+Action: Terminates the process.",
+            9 => "Kill.
+Action: Terminates the process.",
+            10 => "User-defined signal 1.
+Action: Terminates the process.",
+            11 => "Invalid memory reference.
+Action: Terminates the process.",
+            12 => "User-defined signal 2.
+Action: Terminates the process.",
+            13 => "Write on a pipe with no one to read it.
+Action: Ignored.",
+            14 => "Alarm clock.
+Action: Terminates the process.",
+            15 => "Termination signal.
+Action: Terminates the process.",
+            16 => "Child process terminated, stopped, or continued.
+Action: Ignored.",
             17 => {
                 "Continue executing, if stopped.
 Action: Continues executing, if stopped."
             }
-            18 => {
-                "Stop executing.
-Action: Stops executing."
-            }
-            19 => {
-                "Terminal stop signal.
-Action: Stops executing."
-            }
-            20 => {
-                "Background process attempting read.
-Action: Stops executing."
-            }
-            21 => {
-                "Background process attempting write.
-Action: Stops executing."
-            }
-            22 => {
-                "High bandwidth data is available at a socket.
-Action: Ignored."
-            }
-            23 => {
-                "CPU time limit exceeded.
-Action: Terminates the process."
-            }
-            24 => {
-                "File size limit exceeded.
-Action: Terminates the process."
-            }
-            25 => {
-                "Virtual timer expired.
-Action: Terminates the process."
-            }
-            26 => {
-                "Profiling timer expired.
-Action: Terminates the process."
-            }
-            27 => {
-                "Window changed.
-Action: Ignored."
-            }
-            28 => {
-                "I/O possible.
-Action: Terminates the process."
-            }
-            29 => {
-                "Power failure.
-Action: Terminates the process."
-            }
-            30 => {
-                "Bad system call.
-Action: Terminates the process."
-            }
-            _ => unsafe { core::hint::unreachable_unchecked() },
+            18 => "Stop executing.
+Action: Stops executing.",
+            19 => "Terminal stop signal.
+Action: Stops executing.",
+            20 => "Background process attempting read.
+Action: Stops executing.",
+            21 => "Background process attempting write.
+Action: Stops executing.",
+            22 => "High bandwidth data is available at a socket.
+Action: Ignored.",
+            23 => "CPU time limit exceeded.
+Action: Terminates the process.",
+            24 => "File size limit exceeded.
+Action: Terminates the process.",
+            25 => "Virtual timer expired.
+Action: Terminates the process.",
+            26 => "Profiling timer expired.
+Action: Terminates the process.",
+            27 => "Window changed.
+Action: Ignored.",
+            28 => "I/O possible.
+Action: Terminates the process.",
+            29 => "Power failure.
+Action: Terminates the process.",
+            30 => "Bad system call.
+Action: Terminates the process.",
+            _ => unsafe { core::hint::unreachable_unchecked() }
         }
     }
 }
@@ -1136,24 +1547,74 @@ impl fmt::Debug for Signal {
             .finish()
     }
 }
-
+#[cfg(not(feature = "bitflags"))]
 pub type Riflags = u16;
+#[cfg(feature = "bitflags")]
+bitflags::bitflags! {
+    #[repr(transparent)]
+    #[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+    pub struct Riflags: u16 {
+        /// Returns the message without removing it from the socket's receive queue.
+        const RECV_PEEK = 1 << 0;
+        /// On byte-stream sockets, block until the full amount of data can be returned.
+        const RECV_WAITALL = 1 << 1;
+    }
+}
 /// Returns the message without removing it from the socket's receive queue.
+#[cfg(not(feature = "bitflags"))]
 pub const RIFLAGS_RECV_PEEK: Riflags = 1 << 0;
+/// Returns the message without removing it from the socket's receive queue.
+#[cfg(feature = "bitflags")]
+pub const RIFLAGS_RECV_PEEK: Riflags = Riflags::RECV_PEEK;
 /// On byte-stream sockets, block until the full amount of data can be returned.
+#[cfg(not(feature = "bitflags"))]
 pub const RIFLAGS_RECV_WAITALL: Riflags = 1 << 1;
-
+/// On byte-stream sockets, block until the full amount of data can be returned.
+#[cfg(feature = "bitflags")]
+pub const RIFLAGS_RECV_WAITALL: Riflags = Riflags::RECV_WAITALL;
+#[cfg(not(feature = "bitflags"))]
 pub type Roflags = u16;
+#[cfg(feature = "bitflags")]
+bitflags::bitflags! {
+    #[repr(transparent)]
+    #[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+    pub struct Roflags: u16 {
+        /// Returned by `sock_recv`: Message data has been truncated.
+        const RECV_DATA_TRUNCATED = 1 << 0;
+    }
+}
 /// Returned by `sock_recv`: Message data has been truncated.
+#[cfg(not(feature = "bitflags"))]
 pub const ROFLAGS_RECV_DATA_TRUNCATED: Roflags = 1 << 0;
-
+/// Returned by `sock_recv`: Message data has been truncated.
+#[cfg(feature = "bitflags")]
+pub const ROFLAGS_RECV_DATA_TRUNCATED: Roflags = Roflags::RECV_DATA_TRUNCATED;
 pub type Siflags = u16;
+#[cfg(not(feature = "bitflags"))]
 pub type Sdflags = u8;
+#[cfg(feature = "bitflags")]
+bitflags::bitflags! {
+    #[repr(transparent)]
+    #[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+    pub struct Sdflags: u8 {
+        /// Disables further receive operations.
+        const RD = 1 << 0;
+        /// Disables further send operations.
+        const WR = 1 << 1;
+    }
+}
 /// Disables further receive operations.
+#[cfg(not(feature = "bitflags"))]
 pub const SDFLAGS_RD: Sdflags = 1 << 0;
+/// Disables further receive operations.
+#[cfg(feature = "bitflags")]
+pub const SDFLAGS_RD: Sdflags = Sdflags::RD;
 /// Disables further send operations.
+#[cfg(not(feature = "bitflags"))]
 pub const SDFLAGS_WR: Sdflags = 1 << 1;
-
+/// Disables further send operations.
+#[cfg(feature = "bitflags")]
+pub const SDFLAGS_WR: Sdflags = Sdflags::WR;
 #[repr(transparent)]
 #[derive(Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Preopentype(u8);
@@ -1163,17 +1624,16 @@ impl Preopentype {
     pub const fn raw(&self) -> u8 {
         self.0
     }
-
     pub fn name(&self) -> &'static str {
         match self.0 {
             0 => "DIR",
-            _ => unsafe { core::hint::unreachable_unchecked() },
+            _ => unsafe { core::hint::unreachable_unchecked() }
         }
     }
     pub fn message(&self) -> &'static str {
         match self.0 {
             0 => "A pre-opened directory.",
-            _ => unsafe { core::hint::unreachable_unchecked() },
+            _ => unsafe { core::hint::unreachable_unchecked() }
         }
     }
 }
@@ -1186,13 +1646,18 @@ impl fmt::Debug for Preopentype {
             .finish()
     }
 }
-
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
 pub struct PrestatDir {
     /// The length of the directory name for use with `fd_prestat_dir_name`.
     pub pr_name_len: Size,
 }
+#[cfg(target_pointer_width = "32")]
+const _: () = {
+    assert!(core::mem::size_of::<PrestatDir>() == 4);
+    assert!(core::mem::align_of::<PrestatDir>() == 4);
+    assert!(core::mem::offset_of!(PrestatDir, pr_name_len) == 0);
+};
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub union PrestatU {
@@ -1204,18 +1669,34 @@ pub struct Prestat {
     pub tag: u8,
     pub u: PrestatU,
 }
-
+#[cfg(target_pointer_width = "32")]
+const _: () = {
+    assert!(core::mem::size_of::<PrestatU>() == 4);
+    assert!(core::mem::align_of::<PrestatU>() == 4);
+    assert!(core::mem::offset_of!(PrestatU, dir) == 0);
+};
+#[cfg(target_pointer_width = "32")]
+const _: () = {
+    assert!(core::mem::size_of::<Prestat>() == 8);
+    assert!(core::mem::align_of::<Prestat>() == 4);
+    assert!(core::mem::offset_of!(Prestat, tag) == 0);
+    assert!(core::mem::offset_of!(Prestat, u) == 4);
+};
 /// Read command-line argument data.
 /// The size of the array should match that returned by `args_sizes_get`.
 /// Each argument is expected to be `\0` terminated.
 pub unsafe fn args_get(argv: *mut *mut u8, argv_buf: *mut u8) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::args_get(argv as i32, argv_buf as i32);
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().args_get(argv, argv_buf) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let ret = wasi_snapshot_preview1::args_get(argv as i32, argv_buf as i32);
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Return command-line argument data sizes.
 ///
 /// ## Return
@@ -1223,30 +1704,48 @@ pub unsafe fn args_get(argv: *mut *mut u8, argv_buf: *mut u8) -> Result<(), Errn
 /// Returns the number of arguments and the size of the argument string
 /// data, or an error.
 pub unsafe fn args_sizes_get() -> Result<(Size, Size), Errno> {
-    let mut rp0 = MaybeUninit::<Size>::uninit();
-    let mut rp1 = MaybeUninit::<Size>::uninit();
-    let ret =
-        wasi_snapshot_preview1::args_sizes_get(rp0.as_mut_ptr() as i32, rp1.as_mut_ptr() as i32);
-    match ret {
-        0 => Ok((
-            core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size),
-            core::ptr::read(rp1.as_mut_ptr() as i32 as *const Size),
-        )),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().args_sizes_get() }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let mut rp0 = MaybeUninit::<Size>::uninit();
+        let mut rp1 = MaybeUninit::<Size>::uninit();
+        let ret = wasi_snapshot_preview1::args_sizes_get(
+            rp0.as_mut_ptr() as i32,
+            rp1.as_mut_ptr() as i32,
+        );
+        match ret {
+            0 => {
+                Ok((
+                    core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size),
+                    core::ptr::read(rp1.as_mut_ptr() as i32 as *const Size),
+                ))
+            }
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Read environment variable data.
 /// The sizes of the buffers should match that returned by `environ_sizes_get`.
 /// Key/value pairs are expected to be joined with `=`s, and terminated with `\0`s.
-pub unsafe fn environ_get(environ: *mut *mut u8, environ_buf: *mut u8) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::environ_get(environ as i32, environ_buf as i32);
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+pub unsafe fn environ_get(
+    environ: *mut *mut u8,
+    environ_buf: *mut u8,
+) -> Result<(), Errno> {
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().environ_get(environ, environ_buf) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let ret = wasi_snapshot_preview1::environ_get(
+            environ as i32,
+            environ_buf as i32,
+        );
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Return environment variable data sizes.
 ///
 /// ## Return
@@ -1254,19 +1753,27 @@ pub unsafe fn environ_get(environ: *mut *mut u8, environ_buf: *mut u8) -> Result
 /// Returns the number of environment variable arguments and the size of the
 /// environment variable data.
 pub unsafe fn environ_sizes_get() -> Result<(Size, Size), Errno> {
-    let mut rp0 = MaybeUninit::<Size>::uninit();
-    let mut rp1 = MaybeUninit::<Size>::uninit();
-    let ret =
-        wasi_snapshot_preview1::environ_sizes_get(rp0.as_mut_ptr() as i32, rp1.as_mut_ptr() as i32);
-    match ret {
-        0 => Ok((
-            core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size),
-            core::ptr::read(rp1.as_mut_ptr() as i32 as *const Size),
-        )),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().environ_sizes_get() }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let mut rp0 = MaybeUninit::<Size>::uninit();
+        let mut rp1 = MaybeUninit::<Size>::uninit();
+        let ret = wasi_snapshot_preview1::environ_sizes_get(
+            rp0.as_mut_ptr() as i32,
+            rp1.as_mut_ptr() as i32,
+        );
+        match ret {
+            0 => {
+                Ok((
+                    core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size),
+                    core::ptr::read(rp1.as_mut_ptr() as i32 as *const Size),
+                ))
+            }
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Return the resolution of a clock.
 /// Implementations are required to provide a non-zero value for supported clocks. For unsupported clocks,
 /// return `errno::inval`.
@@ -1280,14 +1787,21 @@ pub unsafe fn environ_sizes_get() -> Result<(Size, Size), Errno> {
 ///
 /// The resolution of the clock, or an error if one happened.
 pub unsafe fn clock_res_get(id: Clockid) -> Result<Timestamp, Errno> {
-    let mut rp0 = MaybeUninit::<Timestamp>::uninit();
-    let ret = wasi_snapshot_preview1::clock_res_get(id.0 as i32, rp0.as_mut_ptr() as i32);
-    match ret {
-        0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Timestamp)),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().clock_res_get(id) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let mut rp0 = MaybeUninit::<Timestamp>::uninit();
+        let ret = wasi_snapshot_preview1::clock_res_get(
+            id.0 as i32,
+            rp0.as_mut_ptr() as i32,
+        );
+        match ret {
+            0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Timestamp)),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Return the time value of a clock.
 /// Note: This is similar to `clock_gettime` in POSIX.
 ///
@@ -1299,19 +1813,26 @@ pub unsafe fn clock_res_get(id: Clockid) -> Result<Timestamp, Errno> {
 /// ## Return
 ///
 /// The time value of the clock.
-pub unsafe fn clock_time_get(id: Clockid, precision: Timestamp) -> Result<Timestamp, Errno> {
-    let mut rp0 = MaybeUninit::<Timestamp>::uninit();
-    let ret = wasi_snapshot_preview1::clock_time_get(
-        id.0 as i32,
-        precision as i64,
-        rp0.as_mut_ptr() as i32,
-    );
-    match ret {
-        0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Timestamp)),
-        _ => Err(Errno(ret as u16)),
+pub unsafe fn clock_time_get(
+    id: Clockid,
+    precision: Timestamp,
+) -> Result<Timestamp, Errno> {
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().clock_time_get(id, precision) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let mut rp0 = MaybeUninit::<Timestamp>::uninit();
+        let ret = wasi_snapshot_preview1::clock_time_get(
+            id.0 as i32,
+            precision as i64,
+            rp0.as_mut_ptr() as i32,
+        );
+        match ret {
+            0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Timestamp)),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Provide file advisory information on a file descriptor.
 /// Note: This is similar to `posix_fadvise` in POSIX.
 ///
@@ -1326,14 +1847,22 @@ pub unsafe fn fd_advise(
     len: Filesize,
     advice: Advice,
 ) -> Result<(), Errno> {
-    let ret =
-        wasi_snapshot_preview1::fd_advise(fd as i32, offset as i64, len as i64, advice.0 as i32);
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().fd_advise(fd, offset, len, advice) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let ret = wasi_snapshot_preview1::fd_advise(
+            fd as i32,
+            offset as i64,
+            len as i64,
+            advice.0 as i32,
+        );
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Force the allocation of space in a file.
 /// Note: This is similar to `posix_fallocate` in POSIX.
 ///
@@ -1342,33 +1871,49 @@ pub unsafe fn fd_advise(
 /// * `offset` - The offset at which to start the allocation.
 /// * `len` - The length of the area that is allocated.
 pub unsafe fn fd_allocate(fd: Fd, offset: Filesize, len: Filesize) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::fd_allocate(fd as i32, offset as i64, len as i64);
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().fd_allocate(fd, offset, len) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let ret = wasi_snapshot_preview1::fd_allocate(
+            fd as i32,
+            offset as i64,
+            len as i64,
+        );
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Close a file descriptor.
 /// Note: This is similar to `close` in POSIX.
 pub unsafe fn fd_close(fd: Fd) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::fd_close(fd as i32);
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().fd_close(fd) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let ret = wasi_snapshot_preview1::fd_close(fd as i32);
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Synchronize the data of a file to disk.
 /// Note: This is similar to `fdatasync` in POSIX.
 pub unsafe fn fd_datasync(fd: Fd) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::fd_datasync(fd as i32);
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().fd_datasync(fd) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let ret = wasi_snapshot_preview1::fd_datasync(fd as i32);
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Get the attributes of a file descriptor.
 /// Note: This returns similar flags to `fsync(fd, F_GETFL)` in POSIX, as well as additional fields.
 ///
@@ -1376,14 +1921,21 @@ pub unsafe fn fd_datasync(fd: Fd) -> Result<(), Errno> {
 ///
 /// The buffer where the file descriptor's attributes are stored.
 pub unsafe fn fd_fdstat_get(fd: Fd) -> Result<Fdstat, Errno> {
-    let mut rp0 = MaybeUninit::<Fdstat>::uninit();
-    let ret = wasi_snapshot_preview1::fd_fdstat_get(fd as i32, rp0.as_mut_ptr() as i32);
-    match ret {
-        0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Fdstat)),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().fd_fdstat_get(fd) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let mut rp0 = MaybeUninit::<Fdstat>::uninit();
+        let ret = wasi_snapshot_preview1::fd_fdstat_get(
+            fd as i32,
+            rp0.as_mut_ptr() as i32,
+        );
+        match ret {
+            0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Fdstat)),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Adjust the flags associated with a file descriptor.
 /// Note: This is similar to `fcntl(fd, F_SETFL, flags)` in POSIX.
 ///
@@ -1391,13 +1943,19 @@ pub unsafe fn fd_fdstat_get(fd: Fd) -> Result<Fdstat, Errno> {
 ///
 /// * `flags` - The desired values of the file descriptor flags.
 pub unsafe fn fd_fdstat_set_flags(fd: Fd, flags: Fdflags) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::fd_fdstat_set_flags(fd as i32, flags as i32);
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().fd_fdstat_set_flags(fd, flags) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        #[cfg(feature = "bitflags")]
+        let flags = flags.bits();
+        let ret = wasi_snapshot_preview1::fd_fdstat_set_flags(fd as i32, flags as i32);
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Adjust the rights associated with a file descriptor.
 /// This can only be used to remove rights, and returns `errno::notcapable` if called in a way that would attempt to add rights
 ///
@@ -1409,31 +1967,49 @@ pub unsafe fn fd_fdstat_set_rights(
     fs_rights_base: Rights,
     fs_rights_inheriting: Rights,
 ) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::fd_fdstat_set_rights(
-        fd as i32,
-        fs_rights_base as i64,
-        fs_rights_inheriting as i64,
-    );
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    {
+        crate::host::current()
+            .fd_fdstat_set_rights(fd, fs_rights_base, fs_rights_inheriting)
+    }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        #[cfg(feature = "bitflags")]
+        let fs_rights_base = fs_rights_base.bits();
+        #[cfg(feature = "bitflags")]
+        let fs_rights_inheriting = fs_rights_inheriting.bits();
+        let ret = wasi_snapshot_preview1::fd_fdstat_set_rights(
+            fd as i32,
+            fs_rights_base as i64,
+            fs_rights_inheriting as i64,
+        );
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Return the attributes of an open file.
 ///
 /// ## Return
 ///
 /// The buffer where the file's attributes are stored.
 pub unsafe fn fd_filestat_get(fd: Fd) -> Result<Filestat, Errno> {
-    let mut rp0 = MaybeUninit::<Filestat>::uninit();
-    let ret = wasi_snapshot_preview1::fd_filestat_get(fd as i32, rp0.as_mut_ptr() as i32);
-    match ret {
-        0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Filestat)),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().fd_filestat_get(fd) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let mut rp0 = MaybeUninit::<Filestat>::uninit();
+        let ret = wasi_snapshot_preview1::fd_filestat_get(
+            fd as i32,
+            rp0.as_mut_ptr() as i32,
+        );
+        match ret {
+            0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Filestat)),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Adjust the size of an open file. If this increases the file's size, the extra bytes are filled with zeros.
 /// Note: This is similar to `ftruncate` in POSIX.
 ///
@@ -1441,13 +2017,17 @@ pub unsafe fn fd_filestat_get(fd: Fd) -> Result<Filestat, Errno> {
 ///
 /// * `size` - The desired file size.
 pub unsafe fn fd_filestat_set_size(fd: Fd, size: Filesize) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::fd_filestat_set_size(fd as i32, size as i64);
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().fd_filestat_set_size(fd, size) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let ret = wasi_snapshot_preview1::fd_filestat_set_size(fd as i32, size as i64);
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Adjust the timestamps of an open file or directory.
 /// Note: This is similar to `futimens` in POSIX.
 ///
@@ -1462,18 +2042,24 @@ pub unsafe fn fd_filestat_set_times(
     mtim: Timestamp,
     fst_flags: Fstflags,
 ) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::fd_filestat_set_times(
-        fd as i32,
-        atim as i64,
-        mtim as i64,
-        fst_flags as i32,
-    );
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().fd_filestat_set_times(fd, atim, mtim, fst_flags) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        #[cfg(feature = "bitflags")]
+        let fst_flags = fst_flags.bits();
+        let ret = wasi_snapshot_preview1::fd_filestat_set_times(
+            fd as i32,
+            atim as i64,
+            mtim as i64,
+            fst_flags as i32,
+        );
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Read from a file descriptor, without using and updating the file descriptor's offset.
 /// Note: This is similar to `preadv` in POSIX.
 ///
@@ -1485,48 +2071,75 @@ pub unsafe fn fd_filestat_set_times(
 /// ## Return
 ///
 /// The number of bytes read.
-pub unsafe fn fd_pread(fd: Fd, iovs: IovecArray<'_>, offset: Filesize) -> Result<Size, Errno> {
-    let mut rp0 = MaybeUninit::<Size>::uninit();
-    let ret = wasi_snapshot_preview1::fd_pread(
-        fd as i32,
-        iovs.as_ptr() as i32,
-        iovs.len() as i32,
-        offset as i64,
-        rp0.as_mut_ptr() as i32,
-    );
-    match ret {
-        0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size)),
-        _ => Err(Errno(ret as u16)),
+pub unsafe fn fd_pread(
+    fd: Fd,
+    iovs: IovecArray<'_>,
+    offset: Filesize,
+) -> Result<Size, Errno> {
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().fd_pread(fd, iovs, offset) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let mut rp0 = MaybeUninit::<Size>::uninit();
+        let ret = wasi_snapshot_preview1::fd_pread(
+            fd as i32,
+            iovs.as_ptr() as i32,
+            iovs.len() as i32,
+            offset as i64,
+            rp0.as_mut_ptr() as i32,
+        );
+        match ret {
+            0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size)),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Return a description of the given preopened file descriptor.
 ///
 /// ## Return
 ///
 /// The buffer where the description is stored.
 pub unsafe fn fd_prestat_get(fd: Fd) -> Result<Prestat, Errno> {
-    let mut rp0 = MaybeUninit::<Prestat>::uninit();
-    let ret = wasi_snapshot_preview1::fd_prestat_get(fd as i32, rp0.as_mut_ptr() as i32);
-    match ret {
-        0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Prestat)),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().fd_prestat_get(fd) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let mut rp0 = MaybeUninit::<Prestat>::uninit();
+        let ret = wasi_snapshot_preview1::fd_prestat_get(
+            fd as i32,
+            rp0.as_mut_ptr() as i32,
+        );
+        match ret {
+            0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Prestat)),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Return a description of the given preopened file descriptor.
 ///
 /// ## Parameters
 ///
 /// * `path` - A buffer into which to write the preopened directory name.
-pub unsafe fn fd_prestat_dir_name(fd: Fd, path: *mut u8, path_len: Size) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::fd_prestat_dir_name(fd as i32, path as i32, path_len as i32);
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+pub unsafe fn fd_prestat_dir_name(
+    fd: Fd,
+    path: *mut u8,
+    path_len: Size,
+) -> Result<(), Errno> {
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().fd_prestat_dir_name(fd, path, path_len) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let ret = wasi_snapshot_preview1::fd_prestat_dir_name(
+            fd as i32,
+            path as i32,
+            path_len as i32,
+        );
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Write to a file descriptor, without using and updating the file descriptor's offset.
 /// Note: This is similar to `pwritev` in POSIX.
 ///
@@ -1538,21 +2151,29 @@ pub unsafe fn fd_prestat_dir_name(fd: Fd, path: *mut u8, path_len: Size) -> Resu
 /// ## Return
 ///
 /// The number of bytes written.
-pub unsafe fn fd_pwrite(fd: Fd, iovs: CiovecArray<'_>, offset: Filesize) -> Result<Size, Errno> {
-    let mut rp0 = MaybeUninit::<Size>::uninit();
-    let ret = wasi_snapshot_preview1::fd_pwrite(
-        fd as i32,
-        iovs.as_ptr() as i32,
-        iovs.len() as i32,
-        offset as i64,
-        rp0.as_mut_ptr() as i32,
-    );
-    match ret {
-        0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size)),
-        _ => Err(Errno(ret as u16)),
+pub unsafe fn fd_pwrite(
+    fd: Fd,
+    iovs: CiovecArray<'_>,
+    offset: Filesize,
+) -> Result<Size, Errno> {
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().fd_pwrite(fd, iovs, offset) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let mut rp0 = MaybeUninit::<Size>::uninit();
+        let ret = wasi_snapshot_preview1::fd_pwrite(
+            fd as i32,
+            iovs.as_ptr() as i32,
+            iovs.len() as i32,
+            offset as i64,
+            rp0.as_mut_ptr() as i32,
+        );
+        match ret {
+            0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size)),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Read from a file descriptor.
 /// Note: This is similar to `readv` in POSIX.
 ///
@@ -1564,19 +2185,23 @@ pub unsafe fn fd_pwrite(fd: Fd, iovs: CiovecArray<'_>, offset: Filesize) -> Resu
 ///
 /// The number of bytes read.
 pub unsafe fn fd_read(fd: Fd, iovs: IovecArray<'_>) -> Result<Size, Errno> {
-    let mut rp0 = MaybeUninit::<Size>::uninit();
-    let ret = wasi_snapshot_preview1::fd_read(
-        fd as i32,
-        iovs.as_ptr() as i32,
-        iovs.len() as i32,
-        rp0.as_mut_ptr() as i32,
-    );
-    match ret {
-        0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size)),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().fd_read(fd, iovs) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let mut rp0 = MaybeUninit::<Size>::uninit();
+        let ret = wasi_snapshot_preview1::fd_read(
+            fd as i32,
+            iovs.as_ptr() as i32,
+            iovs.len() as i32,
+            rp0.as_mut_ptr() as i32,
+        );
+        match ret {
+            0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size)),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Read directory entries from a directory.
 /// When successful, the contents of the output buffer consist of a sequence of
 /// directory entries. Each directory entry consists of a `dirent` object,
@@ -1601,20 +2226,24 @@ pub unsafe fn fd_readdir(
     buf_len: Size,
     cookie: Dircookie,
 ) -> Result<Size, Errno> {
-    let mut rp0 = MaybeUninit::<Size>::uninit();
-    let ret = wasi_snapshot_preview1::fd_readdir(
-        fd as i32,
-        buf as i32,
-        buf_len as i32,
-        cookie as i64,
-        rp0.as_mut_ptr() as i32,
-    );
-    match ret {
-        0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size)),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().fd_readdir(fd, buf, buf_len, cookie) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let mut rp0 = MaybeUninit::<Size>::uninit();
+        let ret = wasi_snapshot_preview1::fd_readdir(
+            fd as i32,
+            buf as i32,
+            buf_len as i32,
+            cookie as i64,
+            rp0.as_mut_ptr() as i32,
+        );
+        match ret {
+            0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size)),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Atomically replace a file descriptor by renumbering another file descriptor.
 /// Due to the strong focus on thread safety, this environment does not provide
 /// a mechanism to duplicate or renumber a file descriptor to an arbitrary
@@ -1628,13 +2257,17 @@ pub unsafe fn fd_readdir(
 ///
 /// * `to` - The file descriptor to overwrite.
 pub unsafe fn fd_renumber(fd: Fd, to: Fd) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::fd_renumber(fd as i32, to as i32);
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().fd_renumber(fd, to) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let ret = wasi_snapshot_preview1::fd_renumber(fd as i32, to as i32);
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Move the offset of a file descriptor.
 /// Note: This is similar to `lseek` in POSIX.
 ///
@@ -1646,30 +2279,42 @@ pub unsafe fn fd_renumber(fd: Fd, to: Fd) -> Result<(), Errno> {
 /// ## Return
 ///
 /// The new offset of the file descriptor, relative to the start of the file.
-pub unsafe fn fd_seek(fd: Fd, offset: Filedelta, whence: Whence) -> Result<Filesize, Errno> {
-    let mut rp0 = MaybeUninit::<Filesize>::uninit();
-    let ret = wasi_snapshot_preview1::fd_seek(
-        fd as i32,
-        offset,
-        whence.0 as i32,
-        rp0.as_mut_ptr() as i32,
-    );
-    match ret {
-        0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Filesize)),
-        _ => Err(Errno(ret as u16)),
+pub unsafe fn fd_seek(
+    fd: Fd,
+    offset: Filedelta,
+    whence: Whence,
+) -> Result<Filesize, Errno> {
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().fd_seek(fd, offset, whence) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let mut rp0 = MaybeUninit::<Filesize>::uninit();
+        let ret = wasi_snapshot_preview1::fd_seek(
+            fd as i32,
+            offset,
+            whence.0 as i32,
+            rp0.as_mut_ptr() as i32,
+        );
+        match ret {
+            0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Filesize)),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Synchronize the data and metadata of a file to disk.
 /// Note: This is similar to `fsync` in POSIX.
 pub unsafe fn fd_sync(fd: Fd) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::fd_sync(fd as i32);
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().fd_sync(fd) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let ret = wasi_snapshot_preview1::fd_sync(fd as i32);
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Return the current offset of a file descriptor.
 /// Note: This is similar to `lseek(fd, 0, SEEK_CUR)` in POSIX.
 ///
@@ -1677,14 +2322,18 @@ pub unsafe fn fd_sync(fd: Fd) -> Result<(), Errno> {
 ///
 /// The current offset of the file descriptor, relative to the start of the file.
 pub unsafe fn fd_tell(fd: Fd) -> Result<Filesize, Errno> {
-    let mut rp0 = MaybeUninit::<Filesize>::uninit();
-    let ret = wasi_snapshot_preview1::fd_tell(fd as i32, rp0.as_mut_ptr() as i32);
-    match ret {
-        0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Filesize)),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().fd_tell(fd) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let mut rp0 = MaybeUninit::<Filesize>::uninit();
+        let ret = wasi_snapshot_preview1::fd_tell(fd as i32, rp0.as_mut_ptr() as i32);
+        match ret {
+            0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Filesize)),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Write to a file descriptor.
 /// Note: This is similar to `writev` in POSIX.
 ///
@@ -1692,19 +2341,23 @@ pub unsafe fn fd_tell(fd: Fd) -> Result<Filesize, Errno> {
 ///
 /// * `iovs` - List of scatter/gather vectors from which to retrieve data.
 pub unsafe fn fd_write(fd: Fd, iovs: CiovecArray<'_>) -> Result<Size, Errno> {
-    let mut rp0 = MaybeUninit::<Size>::uninit();
-    let ret = wasi_snapshot_preview1::fd_write(
-        fd as i32,
-        iovs.as_ptr() as i32,
-        iovs.len() as i32,
-        rp0.as_mut_ptr() as i32,
-    );
-    match ret {
-        0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size)),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().fd_write(fd, iovs) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let mut rp0 = MaybeUninit::<Size>::uninit();
+        let ret = wasi_snapshot_preview1::fd_write(
+            fd as i32,
+            iovs.as_ptr() as i32,
+            iovs.len() as i32,
+            rp0.as_mut_ptr() as i32,
+        );
+        match ret {
+            0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size)),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Create a directory.
 /// Note: This is similar to `mkdirat` in POSIX.
 ///
@@ -1712,17 +2365,21 @@ pub unsafe fn fd_write(fd: Fd, iovs: CiovecArray<'_>) -> Result<Size, Errno> {
 ///
 /// * `path` - The path at which to create the directory.
 pub unsafe fn path_create_directory(fd: Fd, path: &str) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::path_create_directory(
-        fd as i32,
-        path.as_ptr() as i32,
-        path.len() as i32,
-    );
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().path_create_directory(fd, path) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let ret = wasi_snapshot_preview1::path_create_directory(
+            fd as i32,
+            path.as_ptr() as i32,
+            path.len() as i32,
+        );
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Return the attributes of a file or directory.
 /// Note: This is similar to `stat` in POSIX.
 ///
@@ -1734,21 +2391,31 @@ pub unsafe fn path_create_directory(fd: Fd, path: &str) -> Result<(), Errno> {
 /// ## Return
 ///
 /// The buffer where the file's attributes are stored.
-pub unsafe fn path_filestat_get(fd: Fd, flags: Lookupflags, path: &str) -> Result<Filestat, Errno> {
-    let mut rp0 = MaybeUninit::<Filestat>::uninit();
-    let ret = wasi_snapshot_preview1::path_filestat_get(
-        fd as i32,
-        flags as i32,
-        path.as_ptr() as i32,
-        path.len() as i32,
-        rp0.as_mut_ptr() as i32,
-    );
-    match ret {
-        0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Filestat)),
-        _ => Err(Errno(ret as u16)),
+pub unsafe fn path_filestat_get(
+    fd: Fd,
+    flags: Lookupflags,
+    path: &str,
+) -> Result<Filestat, Errno> {
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().path_filestat_get(fd, flags, path) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        #[cfg(feature = "bitflags")]
+        let flags = flags.bits();
+        let mut rp0 = MaybeUninit::<Filestat>::uninit();
+        let ret = wasi_snapshot_preview1::path_filestat_get(
+            fd as i32,
+            flags as i32,
+            path.as_ptr() as i32,
+            path.len() as i32,
+            rp0.as_mut_ptr() as i32,
+        );
+        match ret {
+            0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Filestat)),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Adjust the timestamps of a file or directory.
 /// Note: This is similar to `utimensat` in POSIX.
 ///
@@ -1767,21 +2434,32 @@ pub unsafe fn path_filestat_set_times(
     mtim: Timestamp,
     fst_flags: Fstflags,
 ) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::path_filestat_set_times(
-        fd as i32,
-        flags as i32,
-        path.as_ptr() as i32,
-        path.len() as i32,
-        atim as i64,
-        mtim as i64,
-        fst_flags as i32,
-    );
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    {
+        crate::host::current()
+            .path_filestat_set_times(fd, flags, path, atim, mtim, fst_flags)
+    }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        #[cfg(feature = "bitflags")]
+        let flags = flags.bits();
+        #[cfg(feature = "bitflags")]
+        let fst_flags = fst_flags.bits();
+        let ret = wasi_snapshot_preview1::path_filestat_set_times(
+            fd as i32,
+            flags as i32,
+            path.as_ptr() as i32,
+            path.len() as i32,
+            atim as i64,
+            mtim as i64,
+            fst_flags as i32,
+        );
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Create a hard link.
 /// Note: This is similar to `linkat` in POSIX.
 ///
@@ -1798,21 +2476,27 @@ pub unsafe fn path_link(
     new_fd: Fd,
     new_path: &str,
 ) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::path_link(
-        old_fd as i32,
-        old_flags as i32,
-        old_path.as_ptr() as i32,
-        old_path.len() as i32,
-        new_fd as i32,
-        new_path.as_ptr() as i32,
-        new_path.len() as i32,
-    );
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().path_link(old_fd, old_flags, old_path, new_fd, new_path) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        #[cfg(feature = "bitflags")]
+        let old_flags = old_flags.bits();
+        let ret = wasi_snapshot_preview1::path_link(
+            old_fd as i32,
+            old_flags as i32,
+            old_path.as_ptr() as i32,
+            old_path.len() as i32,
+            new_fd as i32,
+            new_path.as_ptr() as i32,
+            new_path.len() as i32,
+        );
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Open a file or directory.
 /// The returned file descriptor is not guaranteed to be the lowest-numbered
 /// file descriptor not currently open; it is randomized to prevent
@@ -1847,24 +2531,49 @@ pub unsafe fn path_open(
     fs_rights_inheriting: Rights,
     fdflags: Fdflags,
 ) -> Result<Fd, Errno> {
-    let mut rp0 = MaybeUninit::<Fd>::uninit();
-    let ret = wasi_snapshot_preview1::path_open(
-        fd as i32,
-        dirflags as i32,
-        path.as_ptr() as i32,
-        path.len() as i32,
-        oflags as i32,
-        fs_rights_base as i64,
-        fs_rights_inheriting as i64,
-        fdflags as i32,
-        rp0.as_mut_ptr() as i32,
-    );
-    match ret {
-        0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Fd)),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    {
+        crate::host::current()
+            .path_open(
+                fd,
+                dirflags,
+                path,
+                oflags,
+                fs_rights_base,
+                fs_rights_inheriting,
+                fdflags,
+            )
+    }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        #[cfg(feature = "bitflags")]
+        let dirflags = dirflags.bits();
+        #[cfg(feature = "bitflags")]
+        let oflags = oflags.bits();
+        #[cfg(feature = "bitflags")]
+        let fs_rights_base = fs_rights_base.bits();
+        #[cfg(feature = "bitflags")]
+        let fs_rights_inheriting = fs_rights_inheriting.bits();
+        #[cfg(feature = "bitflags")]
+        let fdflags = fdflags.bits();
+        let mut rp0 = MaybeUninit::<Fd>::uninit();
+        let ret = wasi_snapshot_preview1::path_open(
+            fd as i32,
+            dirflags as i32,
+            path.as_ptr() as i32,
+            path.len() as i32,
+            oflags as i32,
+            fs_rights_base as i64,
+            fs_rights_inheriting as i64,
+            fdflags as i32,
+            rp0.as_mut_ptr() as i32,
+        );
+        match ret {
+            0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Fd)),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Read the contents of a symbolic link.
 /// Note: This is similar to `readlinkat` in POSIX.
 ///
@@ -1882,21 +2591,25 @@ pub unsafe fn path_readlink(
     buf: *mut u8,
     buf_len: Size,
 ) -> Result<Size, Errno> {
-    let mut rp0 = MaybeUninit::<Size>::uninit();
-    let ret = wasi_snapshot_preview1::path_readlink(
-        fd as i32,
-        path.as_ptr() as i32,
-        path.len() as i32,
-        buf as i32,
-        buf_len as i32,
-        rp0.as_mut_ptr() as i32,
-    );
-    match ret {
-        0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size)),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().path_readlink(fd, path, buf, buf_len) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let mut rp0 = MaybeUninit::<Size>::uninit();
+        let ret = wasi_snapshot_preview1::path_readlink(
+            fd as i32,
+            path.as_ptr() as i32,
+            path.len() as i32,
+            buf as i32,
+            buf_len as i32,
+            rp0.as_mut_ptr() as i32,
+        );
+        match ret {
+            0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size)),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Remove a directory.
 /// Return `errno::notempty` if the directory is not empty.
 /// Note: This is similar to `unlinkat(fd, path, AT_REMOVEDIR)` in POSIX.
@@ -1905,17 +2618,21 @@ pub unsafe fn path_readlink(
 ///
 /// * `path` - The path to a directory to remove.
 pub unsafe fn path_remove_directory(fd: Fd, path: &str) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::path_remove_directory(
-        fd as i32,
-        path.as_ptr() as i32,
-        path.len() as i32,
-    );
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().path_remove_directory(fd, path) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let ret = wasi_snapshot_preview1::path_remove_directory(
+            fd as i32,
+            path.as_ptr() as i32,
+            path.len() as i32,
+        );
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Rename a file or directory.
 /// Note: This is similar to `renameat` in POSIX.
 ///
@@ -1924,21 +2641,30 @@ pub unsafe fn path_remove_directory(fd: Fd, path: &str) -> Result<(), Errno> {
 /// * `old_path` - The source path of the file or directory to rename.
 /// * `new_fd` - The working directory at which the resolution of the new path starts.
 /// * `new_path` - The destination path to which to rename the file or directory.
-pub unsafe fn path_rename(fd: Fd, old_path: &str, new_fd: Fd, new_path: &str) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::path_rename(
-        fd as i32,
-        old_path.as_ptr() as i32,
-        old_path.len() as i32,
-        new_fd as i32,
-        new_path.as_ptr() as i32,
-        new_path.len() as i32,
-    );
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+pub unsafe fn path_rename(
+    fd: Fd,
+    old_path: &str,
+    new_fd: Fd,
+    new_path: &str,
+) -> Result<(), Errno> {
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().path_rename(fd, old_path, new_fd, new_path) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let ret = wasi_snapshot_preview1::path_rename(
+            fd as i32,
+            old_path.as_ptr() as i32,
+            old_path.len() as i32,
+            new_fd as i32,
+            new_path.as_ptr() as i32,
+            new_path.len() as i32,
+        );
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Create a symbolic link.
 /// Note: This is similar to `symlinkat` in POSIX.
 ///
@@ -1947,19 +2673,23 @@ pub unsafe fn path_rename(fd: Fd, old_path: &str, new_fd: Fd, new_path: &str) ->
 /// * `old_path` - The contents of the symbolic link.
 /// * `new_path` - The destination path at which to create the symbolic link.
 pub unsafe fn path_symlink(old_path: &str, fd: Fd, new_path: &str) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::path_symlink(
-        old_path.as_ptr() as i32,
-        old_path.len() as i32,
-        fd as i32,
-        new_path.as_ptr() as i32,
-        new_path.len() as i32,
-    );
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().path_symlink(old_path, fd, new_path) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let ret = wasi_snapshot_preview1::path_symlink(
+            old_path.as_ptr() as i32,
+            old_path.len() as i32,
+            fd as i32,
+            new_path.as_ptr() as i32,
+            new_path.len() as i32,
+        );
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Unlink a file.
 /// Return `errno::isdir` if the path refers to a directory.
 /// Note: This is similar to `unlinkat(fd, path, 0)` in POSIX.
@@ -1968,17 +2698,21 @@ pub unsafe fn path_symlink(old_path: &str, fd: Fd, new_path: &str) -> Result<(),
 ///
 /// * `path` - The path to a file to unlink.
 pub unsafe fn path_unlink_file(fd: Fd, path: &str) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::path_unlink_file(
-        fd as i32,
-        path.as_ptr() as i32,
-        path.len() as i32,
-    );
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().path_unlink_file(fd, path) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let ret = wasi_snapshot_preview1::path_unlink_file(
+            fd as i32,
+            path.as_ptr() as i32,
+            path.len() as i32,
+        );
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Concurrently poll for the occurrence of a set of events.
 ///
 /// ## Parameters
@@ -1995,19 +2729,23 @@ pub unsafe fn poll_oneoff(
     out: *mut Event,
     nsubscriptions: Size,
 ) -> Result<Size, Errno> {
-    let mut rp0 = MaybeUninit::<Size>::uninit();
-    let ret = wasi_snapshot_preview1::poll_oneoff(
-        in_ as i32,
-        out as i32,
-        nsubscriptions as i32,
-        rp0.as_mut_ptr() as i32,
-    );
-    match ret {
-        0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size)),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().poll_oneoff(in_, out, nsubscriptions) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let mut rp0 = MaybeUninit::<Size>::uninit();
+        let ret = wasi_snapshot_preview1::poll_oneoff(
+            in_ as i32,
+            out as i32,
+            nsubscriptions as i32,
+            rp0.as_mut_ptr() as i32,
+        );
+        match ret {
+            0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size)),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Terminate the process normally. An exit code of 0 indicates successful
 /// termination of the program. The meanings of other values is dependent on
 /// the environment.
@@ -2015,10 +2753,14 @@ pub unsafe fn poll_oneoff(
 /// ## Parameters
 ///
 /// * `rval` - The exit code returned by the process.
-pub unsafe fn proc_exit(rval: Exitcode) {
-    wasi_snapshot_preview1::proc_exit(rval as i32);
+pub unsafe fn proc_exit(rval: Exitcode) -> ! {
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().proc_exit(rval) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        wasi_snapshot_preview1::proc_exit(rval as i32);
+    }
 }
-
 /// Send a signal to the process of the calling thread.
 /// Note: This is similar to `raise` in POSIX.
 ///
@@ -2026,23 +2768,31 @@ pub unsafe fn proc_exit(rval: Exitcode) {
 ///
 /// * `sig` - The signal condition to trigger.
 pub unsafe fn proc_raise(sig: Signal) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::proc_raise(sig.0 as i32);
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().proc_raise(sig) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let ret = wasi_snapshot_preview1::proc_raise(sig.0 as i32);
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Temporarily yield execution of the calling thread.
 /// Note: This is similar to `sched_yield` in POSIX.
 pub unsafe fn sched_yield() -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::sched_yield();
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().sched_yield() }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let ret = wasi_snapshot_preview1::sched_yield();
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Write high-quality random data into a buffer.
 /// This function blocks when the implementation is unable to immediately
 /// provide sufficient high-quality random data.
@@ -2054,13 +2804,17 @@ pub unsafe fn sched_yield() -> Result<(), Errno> {
 ///
 /// * `buf` - The buffer to fill with random data.
 pub unsafe fn random_get(buf: *mut u8, buf_len: Size) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::random_get(buf as i32, buf_len as i32);
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().random_get(buf, buf_len) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let ret = wasi_snapshot_preview1::random_get(buf as i32, buf_len as i32);
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Accept a new incoming connection.
 /// Note: This is similar to `accept` in POSIX.
 ///
@@ -2073,14 +2827,24 @@ pub unsafe fn random_get(buf: *mut u8, buf_len: Size) -> Result<(), Errno> {
 ///
 /// New socket connection
 pub unsafe fn sock_accept(fd: Fd, flags: Fdflags) -> Result<Fd, Errno> {
-    let mut rp0 = MaybeUninit::<Fd>::uninit();
-    let ret = wasi_snapshot_preview1::sock_accept(fd as i32, flags as i32, rp0.as_mut_ptr() as i32);
-    match ret {
-        0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Fd)),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().sock_accept(fd, flags) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        #[cfg(feature = "bitflags")]
+        let flags = flags.bits();
+        let mut rp0 = MaybeUninit::<Fd>::uninit();
+        let ret = wasi_snapshot_preview1::sock_accept(
+            fd as i32,
+            flags as i32,
+            rp0.as_mut_ptr() as i32,
+        );
+        match ret {
+            0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Fd)),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Receive a message from a socket.
 /// Note: This is similar to `recv` in POSIX, though it also supports reading
 /// the data into multiple buffers in the manner of `readv`.
@@ -2098,25 +2862,33 @@ pub unsafe fn sock_recv(
     ri_data: IovecArray<'_>,
     ri_flags: Riflags,
 ) -> Result<(Size, Roflags), Errno> {
-    let mut rp0 = MaybeUninit::<Size>::uninit();
-    let mut rp1 = MaybeUninit::<Roflags>::uninit();
-    let ret = wasi_snapshot_preview1::sock_recv(
-        fd as i32,
-        ri_data.as_ptr() as i32,
-        ri_data.len() as i32,
-        ri_flags as i32,
-        rp0.as_mut_ptr() as i32,
-        rp1.as_mut_ptr() as i32,
-    );
-    match ret {
-        0 => Ok((
-            core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size),
-            core::ptr::read(rp1.as_mut_ptr() as i32 as *const Roflags),
-        )),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().sock_recv(fd, ri_data, ri_flags) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        #[cfg(feature = "bitflags")]
+        let ri_flags = ri_flags.bits();
+        let mut rp0 = MaybeUninit::<Size>::uninit();
+        let mut rp1 = MaybeUninit::<Roflags>::uninit();
+        let ret = wasi_snapshot_preview1::sock_recv(
+            fd as i32,
+            ri_data.as_ptr() as i32,
+            ri_data.len() as i32,
+            ri_flags as i32,
+            rp0.as_mut_ptr() as i32,
+            rp1.as_mut_ptr() as i32,
+        );
+        match ret {
+            0 => {
+                Ok((
+                    core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size),
+                    core::ptr::read(rp1.as_mut_ptr() as i32 as *const Roflags),
+                ))
+            }
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Send a message on a socket.
 /// Note: This is similar to `send` in POSIX, though it also supports writing
 /// the data from multiple buffers in the manner of `writev`.
@@ -2134,20 +2906,24 @@ pub unsafe fn sock_send(
     si_data: CiovecArray<'_>,
     si_flags: Siflags,
 ) -> Result<Size, Errno> {
-    let mut rp0 = MaybeUninit::<Size>::uninit();
-    let ret = wasi_snapshot_preview1::sock_send(
-        fd as i32,
-        si_data.as_ptr() as i32,
-        si_data.len() as i32,
-        si_flags as i32,
-        rp0.as_mut_ptr() as i32,
-    );
-    match ret {
-        0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size)),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().sock_send(fd, si_data, si_flags) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        let mut rp0 = MaybeUninit::<Size>::uninit();
+        let ret = wasi_snapshot_preview1::sock_send(
+            fd as i32,
+            si_data.as_ptr() as i32,
+            si_data.len() as i32,
+            si_flags as i32,
+            rp0.as_mut_ptr() as i32,
+        );
+        match ret {
+            0 => Ok(core::ptr::read(rp0.as_mut_ptr() as i32 as *const Size)),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
 /// Shut down socket send and receive channels.
 /// Note: This is similar to `shutdown` in POSIX.
 ///
@@ -2155,13 +2931,20 @@ pub unsafe fn sock_send(
 ///
 /// * `how` - Which channels on the socket to shut down.
 pub unsafe fn sock_shutdown(fd: Fd, how: Sdflags) -> Result<(), Errno> {
-    let ret = wasi_snapshot_preview1::sock_shutdown(fd as i32, how as i32);
-    match ret {
-        0 => Ok(()),
-        _ => Err(Errno(ret as u16)),
+    #[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+    { crate::host::current().sock_shutdown(fd, how) }
+    #[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
+    {
+        #[cfg(feature = "bitflags")]
+        let how = how.bits();
+        let ret = wasi_snapshot_preview1::sock_shutdown(fd as i32, how as i32);
+        match ret {
+            0 => Ok(()),
+            _ => Err(Errno(ret as u16)),
+        }
     }
 }
-
+#[cfg(not(all(feature = "native-host", not(target_family = "wasm"))))]
 pub mod wasi_snapshot_preview1 {
     #[link(wasm_import_module = "wasi_snapshot_preview1")]
     extern "C" {
@@ -2263,7 +3046,13 @@ pub mod wasi_snapshot_preview1 {
         pub fn path_create_directory(arg0: i32, arg1: i32, arg2: i32) -> i32;
         /// Return the attributes of a file or directory.
         /// Note: This is similar to `stat` in POSIX.
-        pub fn path_filestat_get(arg0: i32, arg1: i32, arg2: i32, arg3: i32, arg4: i32) -> i32;
+        pub fn path_filestat_get(
+            arg0: i32,
+            arg1: i32,
+            arg2: i32,
+            arg3: i32,
+            arg4: i32,
+        ) -> i32;
         /// Adjust the timestamps of a file or directory.
         /// Note: This is similar to `utimensat` in POSIX.
         pub fn path_filestat_set_times(
@@ -2320,11 +3109,23 @@ pub mod wasi_snapshot_preview1 {
         pub fn path_remove_directory(arg0: i32, arg1: i32, arg2: i32) -> i32;
         /// Rename a file or directory.
         /// Note: This is similar to `renameat` in POSIX.
-        pub fn path_rename(arg0: i32, arg1: i32, arg2: i32, arg3: i32, arg4: i32, arg5: i32)
-            -> i32;
+        pub fn path_rename(
+            arg0: i32,
+            arg1: i32,
+            arg2: i32,
+            arg3: i32,
+            arg4: i32,
+            arg5: i32,
+        ) -> i32;
         /// Create a symbolic link.
         /// Note: This is similar to `symlinkat` in POSIX.
-        pub fn path_symlink(arg0: i32, arg1: i32, arg2: i32, arg3: i32, arg4: i32) -> i32;
+        pub fn path_symlink(
+            arg0: i32,
+            arg1: i32,
+            arg2: i32,
+            arg3: i32,
+            arg4: i32,
+        ) -> i32;
         /// Unlink a file.
         /// Return `errno::isdir` if the path refers to a directory.
         /// Note: This is similar to `unlinkat(fd, path, 0)` in POSIX.
@@ -2354,7 +3155,14 @@ pub mod wasi_snapshot_preview1 {
         /// Receive a message from a socket.
         /// Note: This is similar to `recv` in POSIX, though it also supports reading
         /// the data into multiple buffers in the manner of `readv`.
-        pub fn sock_recv(arg0: i32, arg1: i32, arg2: i32, arg3: i32, arg4: i32, arg5: i32) -> i32;
+        pub fn sock_recv(
+            arg0: i32,
+            arg1: i32,
+            arg2: i32,
+            arg3: i32,
+            arg4: i32,
+            arg5: i32,
+        ) -> i32;
         /// Send a message on a socket.
         /// Note: This is similar to `send` in POSIX, though it also supports writing
         /// the data from multiple buffers in the manner of `writev`.
@@ -2364,3 +3172,367 @@ pub mod wasi_snapshot_preview1 {
         pub fn sock_shutdown(arg0: i32, arg1: i32) -> i32;
     }
 }
+#[cfg(all(feature = "native-host", not(target_family = "wasm")))]
+/// The functions called by the wrappers in this crate when built with the
+/// `native-host` feature for a non-wasm target.
+///
+/// Methods which aren't implemented fail with `nosys`, except the ones of
+/// functions which don't return, which exit the process.
+#[allow(clippy::too_many_arguments, clippy::missing_safety_doc)]
+pub trait Preview1Host {
+    /// Implements [`args_get`].
+    unsafe fn args_get(
+        &self,
+        argv: *mut *mut u8,
+        argv_buf: *mut u8,
+    ) -> Result<(), Errno> {
+        let _ = (argv, argv_buf);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`args_sizes_get`].
+    unsafe fn args_sizes_get(&self) -> Result<(Size, Size), Errno> {
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`environ_get`].
+    unsafe fn environ_get(
+        &self,
+        environ: *mut *mut u8,
+        environ_buf: *mut u8,
+    ) -> Result<(), Errno> {
+        let _ = (environ, environ_buf);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`environ_sizes_get`].
+    unsafe fn environ_sizes_get(&self) -> Result<(Size, Size), Errno> {
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`clock_res_get`].
+    unsafe fn clock_res_get(&self, id: Clockid) -> Result<Timestamp, Errno> {
+        let _ = (id,);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`clock_time_get`].
+    unsafe fn clock_time_get(
+        &self,
+        id: Clockid,
+        precision: Timestamp,
+    ) -> Result<Timestamp, Errno> {
+        let _ = (id, precision);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_advise`].
+    unsafe fn fd_advise(
+        &self,
+        fd: Fd,
+        offset: Filesize,
+        len: Filesize,
+        advice: Advice,
+    ) -> Result<(), Errno> {
+        let _ = (fd, offset, len, advice);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_allocate`].
+    unsafe fn fd_allocate(
+        &self,
+        fd: Fd,
+        offset: Filesize,
+        len: Filesize,
+    ) -> Result<(), Errno> {
+        let _ = (fd, offset, len);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_close`].
+    unsafe fn fd_close(&self, fd: Fd) -> Result<(), Errno> {
+        let _ = (fd,);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_datasync`].
+    unsafe fn fd_datasync(&self, fd: Fd) -> Result<(), Errno> {
+        let _ = (fd,);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_fdstat_get`].
+    unsafe fn fd_fdstat_get(&self, fd: Fd) -> Result<Fdstat, Errno> {
+        let _ = (fd,);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_fdstat_set_flags`].
+    unsafe fn fd_fdstat_set_flags(&self, fd: Fd, flags: Fdflags) -> Result<(), Errno> {
+        let _ = (fd, flags);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_fdstat_set_rights`].
+    unsafe fn fd_fdstat_set_rights(
+        &self,
+        fd: Fd,
+        fs_rights_base: Rights,
+        fs_rights_inheriting: Rights,
+    ) -> Result<(), Errno> {
+        let _ = (fd, fs_rights_base, fs_rights_inheriting);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_filestat_get`].
+    unsafe fn fd_filestat_get(&self, fd: Fd) -> Result<Filestat, Errno> {
+        let _ = (fd,);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_filestat_set_size`].
+    unsafe fn fd_filestat_set_size(&self, fd: Fd, size: Filesize) -> Result<(), Errno> {
+        let _ = (fd, size);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_filestat_set_times`].
+    unsafe fn fd_filestat_set_times(
+        &self,
+        fd: Fd,
+        atim: Timestamp,
+        mtim: Timestamp,
+        fst_flags: Fstflags,
+    ) -> Result<(), Errno> {
+        let _ = (fd, atim, mtim, fst_flags);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_pread`].
+    unsafe fn fd_pread(
+        &self,
+        fd: Fd,
+        iovs: IovecArray<'_>,
+        offset: Filesize,
+    ) -> Result<Size, Errno> {
+        let _ = (fd, iovs, offset);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_prestat_get`].
+    unsafe fn fd_prestat_get(&self, fd: Fd) -> Result<Prestat, Errno> {
+        let _ = (fd,);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_prestat_dir_name`].
+    unsafe fn fd_prestat_dir_name(
+        &self,
+        fd: Fd,
+        path: *mut u8,
+        path_len: Size,
+    ) -> Result<(), Errno> {
+        let _ = (fd, path, path_len);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_pwrite`].
+    unsafe fn fd_pwrite(
+        &self,
+        fd: Fd,
+        iovs: CiovecArray<'_>,
+        offset: Filesize,
+    ) -> Result<Size, Errno> {
+        let _ = (fd, iovs, offset);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_read`].
+    unsafe fn fd_read(&self, fd: Fd, iovs: IovecArray<'_>) -> Result<Size, Errno> {
+        let _ = (fd, iovs);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_readdir`].
+    unsafe fn fd_readdir(
+        &self,
+        fd: Fd,
+        buf: *mut u8,
+        buf_len: Size,
+        cookie: Dircookie,
+    ) -> Result<Size, Errno> {
+        let _ = (fd, buf, buf_len, cookie);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_renumber`].
+    unsafe fn fd_renumber(&self, fd: Fd, to: Fd) -> Result<(), Errno> {
+        let _ = (fd, to);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_seek`].
+    unsafe fn fd_seek(
+        &self,
+        fd: Fd,
+        offset: Filedelta,
+        whence: Whence,
+    ) -> Result<Filesize, Errno> {
+        let _ = (fd, offset, whence);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_sync`].
+    unsafe fn fd_sync(&self, fd: Fd) -> Result<(), Errno> {
+        let _ = (fd,);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_tell`].
+    unsafe fn fd_tell(&self, fd: Fd) -> Result<Filesize, Errno> {
+        let _ = (fd,);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`fd_write`].
+    unsafe fn fd_write(&self, fd: Fd, iovs: CiovecArray<'_>) -> Result<Size, Errno> {
+        let _ = (fd, iovs);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`path_create_directory`].
+    unsafe fn path_create_directory(&self, fd: Fd, path: &str) -> Result<(), Errno> {
+        let _ = (fd, path);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`path_filestat_get`].
+    unsafe fn path_filestat_get(
+        &self,
+        fd: Fd,
+        flags: Lookupflags,
+        path: &str,
+    ) -> Result<Filestat, Errno> {
+        let _ = (fd, flags, path);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`path_filestat_set_times`].
+    unsafe fn path_filestat_set_times(
+        &self,
+        fd: Fd,
+        flags: Lookupflags,
+        path: &str,
+        atim: Timestamp,
+        mtim: Timestamp,
+        fst_flags: Fstflags,
+    ) -> Result<(), Errno> {
+        let _ = (fd, flags, path, atim, mtim, fst_flags);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`path_link`].
+    unsafe fn path_link(
+        &self,
+        old_fd: Fd,
+        old_flags: Lookupflags,
+        old_path: &str,
+        new_fd: Fd,
+        new_path: &str,
+    ) -> Result<(), Errno> {
+        let _ = (old_fd, old_flags, old_path, new_fd, new_path);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`path_open`].
+    unsafe fn path_open(
+        &self,
+        fd: Fd,
+        dirflags: Lookupflags,
+        path: &str,
+        oflags: Oflags,
+        fs_rights_base: Rights,
+        fs_rights_inheriting: Rights,
+        fdflags: Fdflags,
+    ) -> Result<Fd, Errno> {
+        let _ = (
+            fd,
+            dirflags,
+            path,
+            oflags,
+            fs_rights_base,
+            fs_rights_inheriting,
+            fdflags,
+        );
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`path_readlink`].
+    unsafe fn path_readlink(
+        &self,
+        fd: Fd,
+        path: &str,
+        buf: *mut u8,
+        buf_len: Size,
+    ) -> Result<Size, Errno> {
+        let _ = (fd, path, buf, buf_len);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`path_remove_directory`].
+    unsafe fn path_remove_directory(&self, fd: Fd, path: &str) -> Result<(), Errno> {
+        let _ = (fd, path);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`path_rename`].
+    unsafe fn path_rename(
+        &self,
+        fd: Fd,
+        old_path: &str,
+        new_fd: Fd,
+        new_path: &str,
+    ) -> Result<(), Errno> {
+        let _ = (fd, old_path, new_fd, new_path);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`path_symlink`].
+    unsafe fn path_symlink(
+        &self,
+        old_path: &str,
+        fd: Fd,
+        new_path: &str,
+    ) -> Result<(), Errno> {
+        let _ = (old_path, fd, new_path);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`path_unlink_file`].
+    unsafe fn path_unlink_file(&self, fd: Fd, path: &str) -> Result<(), Errno> {
+        let _ = (fd, path);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`poll_oneoff`].
+    unsafe fn poll_oneoff(
+        &self,
+        in_: *const Subscription,
+        out: *mut Event,
+        nsubscriptions: Size,
+    ) -> Result<Size, Errno> {
+        let _ = (in_, out, nsubscriptions);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`proc_exit`].
+    unsafe fn proc_exit(&self, rval: Exitcode) -> ! {
+        std::process::exit(rval as i32)
+    }
+    /// Implements [`proc_raise`].
+    unsafe fn proc_raise(&self, sig: Signal) -> Result<(), Errno> {
+        let _ = (sig,);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`sched_yield`].
+    unsafe fn sched_yield(&self) -> Result<(), Errno> {
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`random_get`].
+    unsafe fn random_get(&self, buf: *mut u8, buf_len: Size) -> Result<(), Errno> {
+        let _ = (buf, buf_len);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`sock_accept`].
+    unsafe fn sock_accept(&self, fd: Fd, flags: Fdflags) -> Result<Fd, Errno> {
+        let _ = (fd, flags);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`sock_recv`].
+    unsafe fn sock_recv(
+        &self,
+        fd: Fd,
+        ri_data: IovecArray<'_>,
+        ri_flags: Riflags,
+    ) -> Result<(Size, Roflags), Errno> {
+        let _ = (fd, ri_data, ri_flags);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`sock_send`].
+    unsafe fn sock_send(
+        &self,
+        fd: Fd,
+        si_data: CiovecArray<'_>,
+        si_flags: Siflags,
+    ) -> Result<Size, Errno> {
+        let _ = (fd, si_data, si_flags);
+        Err(ERRNO_NOSYS)
+    }
+    /// Implements [`sock_shutdown`].
+    unsafe fn sock_shutdown(&self, fd: Fd, how: Sdflags) -> Result<(), Errno> {
+        let _ = (fd, how);
+        Err(ERRNO_NOSYS)
+    }
+}
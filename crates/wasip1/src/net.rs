@@ -0,0 +1,402 @@
+//! Sockets passed in by the host.
+//!
+//! WASIp1 can't create sockets itself, but hosts may preopen listening
+//! sockets and pass them to the program as file descriptors. [`Listener`]
+//! wraps such a descriptor and [`Stream`] wraps the connections accepted on
+//! it. With the `std` feature, [`Stream`] implements [`std::io::Read`] and
+//! [`std::io::Write`].
+
+use crate::safe::Fd;
+use crate::{Errno, Riflags, Roflags, Sdflags};
+
+/// A listening socket.
+///
+/// ```no_run
+/// use std::io::Write;
+/// use wasip1::net::Listener;
+///
+/// # fn main() -> std::io::Result<()> {
+/// // Hosts such as `wasmtime run --tcplisten` pass the socket in as fd 3.
+/// let listener = Listener::from(unsafe { wasip1::safe::Fd::from_raw(3) });
+/// for stream in listener.incoming() {
+///     stream?.write_all(b"hello\n")?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Listener {
+    fd: Fd,
+}
+
+impl Listener {
+    /// Accepts a new connection, blocking until one is available unless this
+    /// listener is in non-blocking mode.
+    pub fn accept(&self) -> Result<Stream, Errno> {
+        self.fd.sock_accept(Default::default()).map(Stream::from)
+    }
+
+    /// Returns an iterator over the connections accepted on this listener.
+    ///
+    /// The iterator never returns `None`.
+    pub fn incoming(&self) -> Incoming<'_> {
+        Incoming { listener: self }
+    }
+
+    /// Moves this listener into or out of non-blocking mode.
+    ///
+    /// In non-blocking mode [`Listener::accept`] fails with
+    /// [`ERRNO_AGAIN`](crate::ERRNO_AGAIN) if no connection is pending.
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<(), Errno> {
+        set_nonblocking(&self.fd, nonblocking)
+    }
+
+    /// Returns the underlying file descriptor.
+    pub fn as_fd(&self) -> &Fd {
+        &self.fd
+    }
+
+    /// Returns the underlying file descriptor, giving up the [`Listener`].
+    pub fn into_fd(self) -> Fd {
+        self.fd
+    }
+}
+
+impl From<Fd> for Listener {
+    fn from(fd: Fd) -> Listener {
+        Listener { fd }
+    }
+}
+
+/// An iterator over the connections accepted on a [`Listener`], created by
+/// [`Listener::incoming`].
+#[derive(Debug)]
+pub struct Incoming<'a> {
+    listener: &'a Listener,
+}
+
+impl Iterator for Incoming<'_> {
+    type Item = Result<Stream, Errno>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.listener.accept())
+    }
+}
+
+/// A connected socket, usually accepted from a [`Listener`].
+#[derive(Debug)]
+pub struct Stream {
+    fd: Fd,
+}
+
+impl Stream {
+    /// Receives data into `buf`, returning the number of bytes received.
+    ///
+    /// Returns `0` once the peer has shut down its sending side.
+    pub fn recv(&self, buf: &mut [u8]) -> Result<usize, Errno> {
+        self.recv_with_flags(buf, Default::default())
+            .map(|(n, _)| n)
+    }
+
+    /// Receives data into `buf` without removing it from the socket's
+    /// receive queue, so it's returned again by the next receive.
+    pub fn peek(&self, buf: &mut [u8]) -> Result<usize, Errno> {
+        self.recv_with_flags(buf, crate::RIFLAGS_RECV_PEEK)
+            .map(|(n, _)| n)
+    }
+
+    /// Receives data into `buf`, blocking until all of `buf` is filled
+    /// unless the connection is shut down or an error occurs first.
+    pub fn recv_waitall(&self, buf: &mut [u8]) -> Result<usize, Errno> {
+        self.recv_with_flags(buf, crate::RIFLAGS_RECV_WAITALL)
+            .map(|(n, _)| n)
+    }
+
+    /// Receives data into `buf` with the given `flags`, returning the number
+    /// of bytes received and the flags reported by the host.
+    pub fn recv_with_flags(
+        &self,
+        buf: &mut [u8],
+        flags: Riflags,
+    ) -> Result<(usize, Roflags), Errno> {
+        self.fd.sock_recv(buf, flags)
+    }
+
+    /// Sends `buf`, returning the number of bytes sent.
+    pub fn send(&self, buf: &[u8]) -> Result<usize, Errno> {
+        self.fd.sock_send(buf, Default::default())
+    }
+
+    /// Shuts down the receive and/or send sides of this connection, as
+    /// selected by [`SDFLAGS_RD`](crate::SDFLAGS_RD) and
+    /// [`SDFLAGS_WR`](crate::SDFLAGS_WR).
+    pub fn shutdown(&self, how: Sdflags) -> Result<(), Errno> {
+        self.fd.sock_shutdown(how)
+    }
+
+    /// Moves this stream into or out of non-blocking mode.
+    ///
+    /// In non-blocking mode receiving and sending fail with
+    /// [`ERRNO_AGAIN`](crate::ERRNO_AGAIN) instead of blocking.
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<(), Errno> {
+        set_nonblocking(&self.fd, nonblocking)
+    }
+
+    /// Returns the underlying file descriptor.
+    pub fn as_fd(&self) -> &Fd {
+        &self.fd
+    }
+
+    /// Returns the underlying file descriptor, giving up the [`Stream`].
+    pub fn into_fd(self) -> Fd {
+        self.fd
+    }
+}
+
+impl From<Fd> for Stream {
+    fn from(fd: Fd) -> Stream {
+        Stream { fd }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Read for &Stream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        Ok(self.recv(buf)?)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for &Stream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        Ok(self.send(buf)?)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        (&*self).read(buf)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        (&*self).write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        (&*self).flush()
+    }
+}
+
+fn set_nonblocking(fd: &Fd, nonblocking: bool) -> Result<(), Errno> {
+    let flags = fd.fdstat()?.fs_flags;
+    let flags = if nonblocking {
+        flags | crate::FDFLAGS_NONBLOCK
+    } else {
+        flags & !crate::FDFLAGS_NONBLOCK
+    };
+    fd.set_flags(flags)
+}
+
+#[cfg(all(test, feature = "native-host"))]
+mod tests {
+    use super::*;
+    use crate::host::{set_host, Preview1Host};
+    use crate::{Ciovec, Fdflags, Fdstat, Iovec, Siflags, Size};
+    use core::cell::{Cell, RefCell};
+    use std::collections::VecDeque;
+    use std::io::{self, Read, Write};
+    use std::rc::Rc;
+    use std::string::String;
+    use std::vec::Vec;
+
+    /// The descriptor of the listening socket.
+    const LISTENER: crate::Fd = 3;
+
+    /// The descriptor of the first connection accepted.
+    const FIRST_STREAM: crate::Fd = 10;
+
+    /// A connection, whose peer has sent `input` and closed its side.
+    #[derive(Default)]
+    struct Conn {
+        input: Vec<u8>,
+        output: Vec<u8>,
+        shut: Sdflags,
+    }
+
+    /// A host with a listening socket on which connections sending
+    /// `pending` are waiting to be accepted.
+    #[derive(Default)]
+    struct Host {
+        pending: RefCell<VecDeque<Vec<u8>>>,
+        conns: RefCell<Vec<Conn>>,
+        nonblocking: Cell<bool>,
+    }
+
+    impl Host {
+        fn new(pending: &[&[u8]]) -> Rc<Host> {
+            Rc::new(Host {
+                pending: RefCell::new(pending.iter().map(|input| input.to_vec()).collect()),
+                ..Host::default()
+            })
+        }
+
+        fn conn<R>(&self, fd: crate::Fd, f: impl FnOnce(&mut Conn) -> R) -> Result<R, Errno> {
+            let mut conns = self.conns.borrow_mut();
+            let i = fd.checked_sub(FIRST_STREAM).ok_or(crate::ERRNO_BADF)?;
+            let conn = conns.get_mut(i as usize).ok_or(crate::ERRNO_BADF)?;
+            Ok(f(conn))
+        }
+    }
+
+    impl Preview1Host for Host {
+        unsafe fn fd_close(&self, _fd: crate::Fd) -> Result<(), Errno> {
+            Ok(())
+        }
+
+        unsafe fn fd_fdstat_get(&self, fd: crate::Fd) -> Result<Fdstat, Errno> {
+            assert_eq!(fd, LISTENER);
+            let mut fdstat: Fdstat = core::mem::zeroed();
+            fdstat.fs_filetype = crate::FILETYPE_SOCKET_STREAM;
+            if self.nonblocking.get() {
+                fdstat.fs_flags = crate::FDFLAGS_NONBLOCK;
+            }
+            Ok(fdstat)
+        }
+
+        unsafe fn fd_fdstat_set_flags(&self, fd: crate::Fd, flags: Fdflags) -> Result<(), Errno> {
+            assert_eq!(fd, LISTENER);
+            self.nonblocking
+                .set((flags & crate::FDFLAGS_NONBLOCK) == crate::FDFLAGS_NONBLOCK);
+            Ok(())
+        }
+
+        unsafe fn sock_accept(&self, fd: crate::Fd, _flags: Fdflags) -> Result<crate::Fd, Errno> {
+            assert_eq!(fd, LISTENER);
+            let Some(input) = self.pending.borrow_mut().pop_front() else {
+                assert!(self.nonblocking.get(), "accept would block");
+                return Err(crate::ERRNO_AGAIN);
+            };
+            let mut conns = self.conns.borrow_mut();
+            conns.push(Conn {
+                input,
+                ..Conn::default()
+            });
+            Ok(FIRST_STREAM + conns.len() as crate::Fd - 1)
+        }
+
+        unsafe fn sock_recv(
+            &self,
+            fd: crate::Fd,
+            ri_data: &[Iovec],
+            ri_flags: Riflags,
+        ) -> Result<(Size, Roflags), Errno> {
+            self.conn(fd, |conn| {
+                if (conn.shut & crate::SDFLAGS_RD) == crate::SDFLAGS_RD {
+                    return (0, Default::default());
+                }
+                let mut n = 0;
+                for iov in ri_data {
+                    let len = iov.buf_len.min(conn.input.len() - n);
+                    core::ptr::copy_nonoverlapping(conn.input[n..].as_ptr(), iov.buf, len);
+                    n += len;
+                }
+                if (ri_flags & crate::RIFLAGS_RECV_PEEK) != crate::RIFLAGS_RECV_PEEK {
+                    conn.input.drain(..n);
+                }
+                (n, Default::default())
+            })
+        }
+
+        unsafe fn sock_send(
+            &self,
+            fd: crate::Fd,
+            si_data: &[Ciovec],
+            _si_flags: Siflags,
+        ) -> Result<Size, Errno> {
+            self.conn(fd, |conn| {
+                if (conn.shut & crate::SDFLAGS_WR) == crate::SDFLAGS_WR {
+                    return Err(crate::ERRNO_PIPE);
+                }
+                let mut n = 0;
+                for iov in si_data {
+                    let buf = core::slice::from_raw_parts(iov.buf, iov.buf_len);
+                    conn.output.extend_from_slice(buf);
+                    n += buf.len();
+                }
+                Ok(n)
+            })?
+        }
+
+        unsafe fn sock_shutdown(&self, fd: crate::Fd, how: Sdflags) -> Result<(), Errno> {
+            self.conn(fd, |conn| conn.shut |= how)
+        }
+    }
+
+    fn listener() -> Listener {
+        Listener::from(unsafe { Fd::from_raw(LISTENER) })
+    }
+
+    #[test]
+    fn accept_yields_pending_connections() {
+        let host = Host::new(&[b"a", b"b"]);
+        let _guard = set_host(host.clone());
+        let listener = listener();
+        let stream = listener.accept().unwrap();
+        assert_eq!(stream.as_fd().as_raw(), FIRST_STREAM);
+        let stream = listener.incoming().next().unwrap().unwrap();
+        assert_eq!(stream.as_fd().as_raw(), FIRST_STREAM + 1);
+
+        listener.set_nonblocking(true).unwrap();
+        assert!(host.nonblocking.get());
+        assert_eq!(listener.accept().unwrap_err(), crate::ERRNO_AGAIN);
+        assert_eq!(
+            listener.incoming().next().unwrap().unwrap_err(),
+            crate::ERRNO_AGAIN
+        );
+        listener.set_nonblocking(false).unwrap();
+        assert!(!host.nonblocking.get());
+    }
+
+    #[test]
+    fn stream_reads_and_writes() {
+        let host = Host::new(&[b"ping"]);
+        let _guard = set_host(host.clone());
+        let mut stream = listener().accept().unwrap();
+
+        // Peeked data is received again.
+        let mut buf = [0; 2];
+        assert_eq!(stream.peek(&mut buf), Ok(2));
+        assert_eq!(&buf, b"pi");
+        let mut input = String::new();
+        stream.read_to_string(&mut input).unwrap();
+        assert_eq!(input, "ping");
+
+        stream.write_all(b"pong").unwrap();
+        assert_eq!(stream.send(b"!"), Ok(1));
+        assert_eq!(host.conns.borrow()[0].output, b"pong!");
+    }
+
+    #[test]
+    fn shutdown_closes_each_side() {
+        let host = Host::new(&[b"unread"]);
+        let _guard = set_host(host.clone());
+        let stream = listener().accept().unwrap();
+
+        stream.shutdown(crate::SDFLAGS_WR).unwrap();
+        assert_eq!(stream.send(b"late"), Err(crate::ERRNO_PIPE));
+        let err = (&stream).write(b"late").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+
+        stream.shutdown(crate::SDFLAGS_RD).unwrap();
+        assert_eq!(stream.recv(&mut [0; 6]), Ok(0));
+        assert!(host.conns.borrow()[0].output.is_empty());
+    }
+}
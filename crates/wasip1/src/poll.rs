@@ -0,0 +1,408 @@
+//! A safe interface to [`poll_oneoff`](crate::poll_oneoff).
+//!
+//! [`Poller`] builds the [`Subscription`] array without touching its unions
+//! and decodes the returned [`Event`]s into [`PollEvent`]s. [`sleep`] covers
+//! the common case of waiting on a single relative timeout and doesn't need
+//! the `alloc` feature.
+
+#[cfg(feature = "alloc")]
+use crate::safe::Fd;
+use crate::{
+    Clockid, Errno, Event, Filesize, Subscription, SubscriptionClock, Timestamp, Userdata,
+};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::convert::TryInto;
+use core::mem::{self, MaybeUninit};
+use core::time::Duration;
+
+/// A set of subscriptions to wait on with a single call to `poll_oneoff`.
+///
+/// Each `add_*` method returns the [`Userdata`] assigned to the subscription,
+/// which is its index in the order the subscriptions were added. The
+/// [`PollEvent`]s returned by [`Poller::poll`] carry the same value.
+///
+/// ```no_run
+/// use wasip1::poll::{PollEventKind, Poller};
+///
+/// # fn main() -> Result<(), wasip1::Errno> {
+/// let stdin = wasip1::safe::stdin();
+/// let mut poller = Poller::new();
+/// let input = poller.add_fd_read(&stdin);
+/// let timer = poller.add_clock(wasip1::CLOCKID_MONOTONIC, 1_000_000_000, 0, false);
+/// for event in poller.poll()? {
+///     match event.kind? {
+///         PollEventKind::FdRead(ready) if event.userdata == input => {
+///             println!("{} bytes ready on stdin", ready.nbytes);
+///         }
+///         PollEventKind::Clock if event.userdata == timer => println!("timed out"),
+///         _ => {}
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Default)]
+pub struct Poller {
+    subscriptions: Vec<Subscription>,
+}
+
+#[cfg(feature = "alloc")]
+impl Poller {
+    /// Creates a poller with no subscriptions.
+    pub fn new() -> Poller {
+        Poller::default()
+    }
+
+    /// Subscribes to a timeout on the clock `id`.
+    ///
+    /// `timeout` is in nanoseconds and is relative to the current time of the
+    /// clock unless `abstime` is set. The host may wait up to `precision`
+    /// additional nanoseconds to coalesce with other events.
+    pub fn add_clock(
+        &mut self,
+        id: Clockid,
+        timeout: Timestamp,
+        precision: Timestamp,
+        abstime: bool,
+    ) -> Userdata {
+        let userdata = self.next_userdata();
+        self.subscriptions.push(clock_subscription(
+            userdata, id, timeout, precision, abstime,
+        ));
+        userdata
+    }
+
+    /// Subscribes to `fd` becoming ready for reading.
+    pub fn add_fd_read(&mut self, fd: &Fd) -> Userdata {
+        let userdata = self.next_userdata();
+        let mut subscription = empty_subscription(userdata, crate::EVENTTYPE_FD_READ);
+        subscription.u.u.fd_read = crate::SubscriptionFdReadwrite {
+            file_descriptor: fd.as_raw(),
+        };
+        self.subscriptions.push(subscription);
+        userdata
+    }
+
+    /// Subscribes to `fd` becoming ready for writing.
+    pub fn add_fd_write(&mut self, fd: &Fd) -> Userdata {
+        let userdata = self.next_userdata();
+        let mut subscription = empty_subscription(userdata, crate::EVENTTYPE_FD_WRITE);
+        subscription.u.u.fd_write = crate::SubscriptionFdReadwrite {
+            file_descriptor: fd.as_raw(),
+        };
+        self.subscriptions.push(subscription);
+        userdata
+    }
+
+    /// Returns the number of subscriptions added so far.
+    pub fn len(&self) -> usize {
+        self.subscriptions.len()
+    }
+
+    /// Returns whether no subscriptions have been added.
+    pub fn is_empty(&self) -> bool {
+        self.subscriptions.is_empty()
+    }
+
+    /// Removes all subscriptions, restarting userdata numbering from zero.
+    pub fn clear(&mut self) {
+        self.subscriptions.clear();
+    }
+
+    /// Blocks until at least one of the subscriptions is ready, returning an
+    /// event for each one that is.
+    ///
+    /// Errors for individual subscriptions are reported through
+    /// [`PollEvent::kind`]; the outer error is for the call as a whole.
+    pub fn poll(&self) -> Result<Vec<PollEvent>, Errno> {
+        let mut events = Vec::<Event>::with_capacity(self.subscriptions.len());
+        let n = unsafe {
+            let n = crate::poll_oneoff(
+                self.subscriptions.as_ptr(),
+                events.as_mut_ptr(),
+                self.subscriptions.len(),
+            )?;
+            events.set_len(n);
+            n
+        };
+        debug_assert!(n <= self.subscriptions.len());
+        Ok(events.iter().map(PollEvent::from_raw).collect())
+    }
+
+    fn next_userdata(&self) -> Userdata {
+        self.subscriptions.len() as Userdata
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Debug for Poller {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Poller")
+            .field("subscriptions", &self.subscriptions.len())
+            .finish()
+    }
+}
+
+/// An event returned by [`Poller::poll`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PollEvent {
+    /// The userdata of the subscription this event is for.
+    pub userdata: Userdata,
+    /// What happened, or the error the host reported for the subscription.
+    pub kind: Result<PollEventKind, Errno>,
+}
+
+impl PollEvent {
+    /// Decodes a raw event filled in by `poll_oneoff`.
+    pub fn from_raw(event: &Event) -> PollEvent {
+        let readiness = FdReadiness {
+            nbytes: event.fd_readwrite.nbytes,
+            hangup: (event.fd_readwrite.flags & crate::EVENTRWFLAGS_FD_READWRITE_HANGUP)
+                == crate::EVENTRWFLAGS_FD_READWRITE_HANGUP,
+        };
+        let kind = if event.error != crate::ERRNO_SUCCESS {
+            Err(event.error)
+        } else {
+            match event.type_ {
+                crate::EVENTTYPE_CLOCK => Ok(PollEventKind::Clock),
+                crate::EVENTTYPE_FD_READ => Ok(PollEventKind::FdRead(readiness)),
+                crate::EVENTTYPE_FD_WRITE => Ok(PollEventKind::FdWrite(readiness)),
+                _ => Err(crate::ERRNO_NOTSUP),
+            }
+        };
+        PollEvent {
+            userdata: event.userdata,
+            kind,
+        }
+    }
+}
+
+/// The type of a [`PollEvent`] along with its contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollEventKind {
+    /// A clock subscription's timeout expired.
+    Clock,
+    /// A file descriptor is ready for reading.
+    FdRead(FdReadiness),
+    /// A file descriptor is ready for writing.
+    FdWrite(FdReadiness),
+}
+
+/// The state of a file descriptor reported by a [`PollEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FdReadiness {
+    /// The number of bytes available for reading or writing.
+    pub nbytes: Filesize,
+    /// Whether the peer of this socket has closed or disconnected.
+    pub hangup: bool,
+}
+
+/// Blocks the calling thread for `duration` on the monotonic clock.
+///
+/// Durations which don't fit in a [`Timestamp`] are saturated.
+pub fn sleep(duration: Duration) -> Result<(), Errno> {
+    let timeout = duration.as_nanos().try_into().unwrap_or(Timestamp::MAX);
+    let subscription = clock_subscription(0, crate::CLOCKID_MONOTONIC, timeout, 0, false);
+    let mut event = MaybeUninit::<Event>::uninit();
+    let event = unsafe {
+        let n = crate::poll_oneoff(&subscription, event.as_mut_ptr(), 1)?;
+        if n == 0 {
+            return Ok(());
+        }
+        event.assume_init()
+    };
+    if event.error != crate::ERRNO_SUCCESS {
+        return Err(event.error);
+    }
+    Ok(())
+}
+
+fn clock_subscription(
+    userdata: Userdata,
+    id: Clockid,
+    timeout: Timestamp,
+    precision: Timestamp,
+    abstime: bool,
+) -> Subscription {
+    let mut subscription = empty_subscription(userdata, crate::EVENTTYPE_CLOCK);
+    // The zeroed flags are an empty set regardless of whether the flags are
+    // an integer or a `bitflags` newtype.
+    let mut clock: SubscriptionClock = unsafe { subscription.u.u.clock };
+    clock.id = id;
+    clock.timeout = timeout;
+    clock.precision = precision;
+    if abstime {
+        clock.flags = crate::SUBCLOCKFLAGS_SUBSCRIPTION_CLOCK_ABSTIME;
+    }
+    subscription.u.u.clock = clock;
+    subscription
+}
+
+/// Returns a subscription of type `ty` with all contents zeroed.
+fn empty_subscription(userdata: Userdata, ty: crate::Eventtype) -> Subscription {
+    // All of the fields of `Subscription` are plain integers, so zero is a
+    // valid value for each of them.
+    let mut subscription: Subscription = unsafe { mem::zeroed() };
+    subscription.userdata = userdata;
+    subscription.u.tag = ty.raw();
+    subscription
+}
+
+#[cfg(all(test, feature = "native-host"))]
+mod tests {
+    use super::*;
+    use crate::host::{set_host, Preview1Host};
+    use crate::safe::BorrowedFd;
+    use crate::{Eventtype, Size};
+    use core::cell::RefCell;
+    use std::rc::Rc;
+    use std::vec;
+
+    /// A host recording the subscriptions passed to `poll_oneoff` and
+    /// returning `events`.
+    #[derive(Default)]
+    struct Events {
+        events: Vec<Event>,
+        subscriptions: RefCell<Vec<Subscription>>,
+    }
+
+    impl Preview1Host for Events {
+        unsafe fn poll_oneoff(
+            &self,
+            in_: *const Subscription,
+            out: *mut Event,
+            nsubscriptions: Size,
+        ) -> Result<Size, Errno> {
+            let subscriptions = core::slice::from_raw_parts(in_, nsubscriptions);
+            self.subscriptions
+                .borrow_mut()
+                .extend_from_slice(subscriptions);
+            let n = self.events.len().min(nsubscriptions);
+            core::ptr::copy_nonoverlapping(self.events.as_ptr(), out, n);
+            Ok(n)
+        }
+    }
+
+    fn event(userdata: Userdata, ty: Eventtype, nbytes: Filesize, hangup: bool) -> Event {
+        let mut event: Event = unsafe { mem::zeroed() };
+        event.userdata = userdata;
+        event.type_ = ty;
+        event.fd_readwrite.nbytes = nbytes;
+        if hangup {
+            event.fd_readwrite.flags = crate::EVENTRWFLAGS_FD_READWRITE_HANGUP;
+        }
+        event
+    }
+
+    #[test]
+    fn subscriptions_are_numbered_in_order() {
+        let host = Rc::new(Events::default());
+        let _guard = set_host(host.clone());
+        let stdin = unsafe { BorrowedFd::borrow_raw(0) };
+        let stdout = unsafe { BorrowedFd::borrow_raw(1) };
+
+        let mut poller = Poller::new();
+        assert!(poller.is_empty());
+        assert_eq!(poller.add_clock(crate::CLOCKID_REALTIME, 5, 1, true), 0);
+        assert_eq!(poller.add_fd_read(&stdin), 1);
+        assert_eq!(poller.add_fd_write(&stdout), 2);
+        assert_eq!(poller.len(), 3);
+        assert_eq!(poller.poll(), Ok(vec![]));
+
+        let subscriptions = host.subscriptions.take();
+        let userdata = subscriptions.iter().map(|s| s.userdata).collect::<Vec<_>>();
+        assert_eq!(userdata, [0, 1, 2]);
+        let tags = subscriptions.iter().map(|s| s.u.tag).collect::<Vec<_>>();
+        assert_eq!(
+            tags,
+            [
+                crate::EVENTTYPE_CLOCK.raw(),
+                crate::EVENTTYPE_FD_READ.raw(),
+                crate::EVENTTYPE_FD_WRITE.raw(),
+            ]
+        );
+        let clock = unsafe { subscriptions[0].u.u.clock };
+        assert_eq!(clock.id, crate::CLOCKID_REALTIME);
+        assert_eq!((clock.timeout, clock.precision), (5, 1));
+        assert!(clock.flags == crate::SUBCLOCKFLAGS_SUBSCRIPTION_CLOCK_ABSTIME);
+        assert_eq!(unsafe { subscriptions[1].u.u.fd_read.file_descriptor }, 0);
+        assert_eq!(unsafe { subscriptions[2].u.u.fd_write.file_descriptor }, 1);
+
+        // Clearing restarts the numbering.
+        poller.clear();
+        assert!(poller.is_empty());
+        assert_eq!(poller.add_clock(crate::CLOCKID_MONOTONIC, 7, 0, false), 0);
+        poller.poll().unwrap();
+        let clock = unsafe { host.subscriptions.take()[0].u.u.clock };
+        assert!(clock.flags != crate::SUBCLOCKFLAGS_SUBSCRIPTION_CLOCK_ABSTIME);
+    }
+
+    #[test]
+    fn events_are_decoded() {
+        let mut failed = event(1, crate::EVENTTYPE_FD_READ, 0, false);
+        failed.error = crate::ERRNO_BADF;
+        let mut unknown = event(3, crate::EVENTTYPE_CLOCK, 0, false);
+        unsafe { *(&mut unknown.type_ as *mut Eventtype).cast::<u8>() = 7 };
+        let host = Rc::new(Events {
+            events: vec![
+                event(2, crate::EVENTTYPE_FD_WRITE, 10, true),
+                event(0, crate::EVENTTYPE_CLOCK, 0, false),
+                failed,
+                unknown,
+            ],
+            ..Events::default()
+        });
+        let _guard = set_host(host);
+        let stdin = unsafe { BorrowedFd::borrow_raw(0) };
+        let mut poller = Poller::new();
+        poller.add_clock(crate::CLOCKID_MONOTONIC, 0, 0, false);
+        poller.add_fd_read(&stdin);
+        poller.add_fd_write(&stdin);
+        poller.add_fd_read(&stdin);
+        let ready = FdReadiness {
+            nbytes: 10,
+            hangup: true,
+        };
+        assert_eq!(
+            poller.poll(),
+            Ok(vec![
+                PollEvent {
+                    userdata: 2,
+                    kind: Ok(PollEventKind::FdWrite(ready)),
+                },
+                PollEvent {
+                    userdata: 0,
+                    kind: Ok(PollEventKind::Clock),
+                },
+                PollEvent {
+                    userdata: 1,
+                    kind: Err(crate::ERRNO_BADF),
+                },
+                PollEvent {
+                    userdata: 3,
+                    kind: Err(crate::ERRNO_NOTSUP),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn sleep_saturates_and_reports_errors() {
+        let host = Rc::new(Events::default());
+        let _guard = set_host(host.clone());
+        assert_eq!(sleep(Duration::MAX), Ok(()));
+        let clock = unsafe { host.subscriptions.take()[0].u.u.clock };
+        assert_eq!(clock.id, crate::CLOCKID_MONOTONIC);
+        assert_eq!(clock.timeout, Timestamp::MAX);
+
+        let mut failed = event(0, crate::EVENTTYPE_CLOCK, 0, false);
+        failed.error = crate::ERRNO_INVAL;
+        let _guard = set_host(Rc::new(Events {
+            events: vec![failed],
+            ..Events::default()
+        }));
+        assert_eq!(sleep(Duration::from_millis(1)), Err(crate::ERRNO_INVAL));
+    }
+}
@@ -0,0 +1,266 @@
+use crate::safe::BorrowedFd;
+use crate::{Errno, Prestat};
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// The first descriptor number which may be a preopen, after stdio.
+const FIRST_PREOPEN: crate::Fd = 3;
+
+/// The size of the stack buffer [`resolve`] reads preopen names into, matching
+/// `PATH_MAX` in wasi-libc.
+const PATH_MAX: usize = 4096;
+
+/// Returns an iterator over the preopened directories of this process along
+/// with their names.
+///
+/// Preopens are found by calling `fd_prestat_get` on each descriptor number
+/// from 3 up to the first one which fails with [`ERRNO_BADF`](crate::ERRNO_BADF).
+/// The returned descriptors are borrowed as preopens are owned by the process
+/// as a whole; the iterator doesn't cache anything, so a preopen closed by the
+/// program won't be found.
+///
+/// A name which isn't valid UTF-8 is reported as an
+/// [`ERRNO_ILSEQ`](crate::ERRNO_ILSEQ) error for that preopen only.
+#[cfg(feature = "alloc")]
+pub fn preopens() -> Preopens {
+    Preopens {
+        next: FIRST_PREOPEN,
+        done: false,
+    }
+}
+
+/// An iterator over the preopened directories, created by [`preopens`].
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct Preopens {
+    next: crate::Fd,
+    done: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for Preopens {
+    type Item = Result<(BorrowedFd<'static>, String), Errno>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let fd = self.next;
+            self.next += 1;
+            match prestat(fd) {
+                Ok(Some(prestat)) if prestat.tag == crate::PREOPENTYPE_DIR.raw() => {
+                    let fd = unsafe { BorrowedFd::borrow_raw(fd) };
+                    return Some(fd.prestat_dir_name().map(|name| (fd, name)));
+                }
+                Ok(Some(_)) => {}
+                Ok(None) => self.done = true,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Finds the preopened directory which serves the absolute `path`.
+///
+/// Returns the preopen along with the remainder of `path` relative to it,
+/// ready to be passed to functions such as [`Fd::open`](crate::safe::Fd::open).
+/// This matches the resolution done by wasi-libc:
+///
+/// * Leading `/`s in `path` are ignored, as are leading `/` and `./`
+///   components in preopen names, so a preopen named `/` or `.` matches any
+///   path.
+/// * A preopen only matches complete path components, so `/foo` doesn't
+///   serve `/foobar`.
+/// * The preopen with the longest matching name wins, with later preopens
+///   taking precedence over earlier ones of the same length.
+/// * An empty remainder is returned as `"."`.
+///
+/// Relative paths are resolved as if the current directory were `/`.
+/// Fails with [`ERRNO_NOENT`](crate::ERRNO_NOENT) if no preopen matches.
+/// Preopens with names longer than 4096 bytes are ignored so that this
+/// doesn't need to allocate.
+pub fn resolve(path: &str) -> Result<(BorrowedFd<'static>, &str), Errno> {
+    let path = path.trim_start_matches('/');
+    let mut buf = [0; PATH_MAX];
+    let mut best: Option<(crate::Fd, usize)> = None;
+    let mut fd = FIRST_PREOPEN;
+    while let Some(prestat) = prestat(fd)? {
+        if prestat.tag == crate::PREOPENTYPE_DIR.raw() {
+            let len = unsafe { prestat.u.dir.pr_name_len };
+            if let Some(name) = buf.get_mut(..len) {
+                unsafe { crate::fd_prestat_dir_name(fd, name.as_mut_ptr(), len)? };
+                let prefix = strip_prefixes(name);
+                if best.is_none_or(|(_, best_len)| prefix.len() >= best_len)
+                    && prefix_matches(prefix, path.as_bytes())
+                {
+                    best = Some((fd, prefix.len()));
+                }
+            }
+        }
+        fd += 1;
+    }
+    let (fd, len) = best.ok_or(crate::ERRNO_NOENT)?;
+    // `len` is the length of a byte-for-byte match of a prefix of `path`
+    // ending at a `/` or the end of `path`, so it's a char boundary.
+    let relative = path[len..].trim_start_matches('/');
+    let relative = if relative.is_empty() { "." } else { relative };
+    Ok((unsafe { BorrowedFd::borrow_raw(fd) }, relative))
+}
+
+/// Returns the preopen description of `fd`, or `None` if `fd` isn't open.
+fn prestat(fd: crate::Fd) -> Result<Option<Prestat>, Errno> {
+    match unsafe { crate::fd_prestat_get(fd) } {
+        Ok(prestat) => Ok(Some(prestat)),
+        Err(crate::ERRNO_BADF) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Strips leading `/`, `./` and a lone `.` from a preopen name, as wasi-libc
+/// does when registering preopens.
+fn strip_prefixes(mut name: &[u8]) -> &[u8] {
+    loop {
+        name = match name {
+            [b'/', rest @ ..] | [b'.', b'/', rest @ ..] => rest,
+            [b'.'] => &[],
+            _ => return name,
+        };
+    }
+}
+
+/// Returns whether `prefix` names a directory containing `path`, where
+/// neither has leading slashes.
+fn prefix_matches(prefix: &[u8], path: &[u8]) -> bool {
+    if !path.starts_with(prefix) {
+        return false;
+    }
+    // Ignore trailing slashes in the prefix, and then only match complete
+    // path components. An empty prefix matches everything.
+    let mut i = prefix.len();
+    while i > 0 && prefix[i - 1] == b'/' {
+        i -= 1;
+    }
+    i == 0 || matches!(path.get(i), None | Some(b'/'))
+}
+
+#[cfg(all(test, feature = "native-host"))]
+mod tests {
+    use super::*;
+    use crate::host::{set_host, Preview1Host};
+    use crate::{PrestatDir, PrestatU, Size};
+    use std::rc::Rc;
+    use std::vec::Vec;
+
+    /// A host whose preopens, from descriptor 3 on, are named `names`, where
+    /// `None` is a preopen of a type other than a directory.
+    struct Preopened(Vec<Option<&'static str>>);
+
+    impl Preopened {
+        fn name(&self, fd: crate::Fd) -> Result<Option<&'static str>, Errno> {
+            let index = fd.checked_sub(FIRST_PREOPEN).ok_or(crate::ERRNO_BADF)?;
+            self.0.get(index as usize).copied().ok_or(crate::ERRNO_BADF)
+        }
+    }
+
+    impl Preview1Host for Preopened {
+        unsafe fn fd_prestat_get(&self, fd: crate::Fd) -> Result<Prestat, Errno> {
+            let name = self.name(fd)?;
+            Ok(Prestat {
+                tag: if name.is_some() { 0 } else { 1 },
+                u: PrestatU {
+                    dir: PrestatDir {
+                        pr_name_len: name.map_or(0, str::len),
+                    },
+                },
+            })
+        }
+
+        unsafe fn fd_prestat_dir_name(
+            &self,
+            fd: crate::Fd,
+            path: *mut u8,
+            path_len: Size,
+        ) -> Result<(), Errno> {
+            let name = self.name(fd)?.ok_or(crate::ERRNO_BADF)?;
+            assert_eq!(path_len, name.len());
+            core::ptr::copy_nonoverlapping(name.as_ptr(), path, path_len);
+            Ok(())
+        }
+    }
+
+    /// Resolves `path` against preopens named `names`, returning the index
+    /// of the preopen in `names` and the relative path.
+    fn resolve_in(names: &[Option<&'static str>], path: &str) -> Result<(usize, String), Errno> {
+        let _guard = set_host(Rc::new(Preopened(names.to_vec())));
+        let (fd, relative) = resolve(path)?;
+        Ok((
+            (fd.as_raw() - FIRST_PREOPEN) as usize,
+            String::from(relative),
+        ))
+    }
+
+    fn ok(index: usize, relative: &str) -> Result<(usize, String), Errno> {
+        Ok((index, String::from(relative)))
+    }
+
+    #[test]
+    fn strips_leading_slashes_and_dots() {
+        assert_eq!(strip_prefixes(b"/"), b"");
+        assert_eq!(strip_prefixes(b"."), b"");
+        assert_eq!(strip_prefixes(b"./"), b"");
+        assert_eq!(strip_prefixes(b"//./foo"), b"foo");
+        assert_eq!(strip_prefixes(b"./.foo"), b".foo");
+        assert_eq!(strip_prefixes(b"foo/."), b"foo/.");
+    }
+
+    #[test]
+    fn matches_complete_components() {
+        assert!(prefix_matches(b"", b"anything"));
+        assert!(prefix_matches(b"foo", b"foo"));
+        assert!(prefix_matches(b"foo", b"foo/bar"));
+        assert!(prefix_matches(b"foo/", b"foo/bar"));
+        assert!(prefix_matches(b"foo//", b"foo//bar"));
+        assert!(!prefix_matches(b"foo", b"foobar"));
+        assert!(!prefix_matches(b"foo/", b"foo"));
+        assert!(!prefix_matches(b"foo/bar", b"foo"));
+    }
+
+    #[test]
+    fn resolves_against_longest_matching_preopen() {
+        let names = [Some("/"), Some("/foo"), Some("/foo/bar/"), Some("/foobar")];
+        assert_eq!(resolve_in(&names, "/foo/bar/baz"), ok(2, "baz"));
+        assert_eq!(resolve_in(&names, "/foo/baz"), ok(1, "baz"));
+        assert_eq!(resolve_in(&names, "/foobar/x"), ok(3, "x"));
+        assert_eq!(resolve_in(&names, "/foob"), ok(0, "foob"));
+        // Later preopens win over earlier ones of the same length.
+        assert_eq!(resolve_in(&[Some("/a"), Some("a")], "/a/b"), ok(1, "b"));
+    }
+
+    #[test]
+    fn resolves_dots_and_slashes() {
+        let names = [Some("."), Some("./tmp/")];
+        assert_eq!(resolve_in(&names, "/"), ok(0, "."));
+        assert_eq!(resolve_in(&names, "//tmp//x/"), ok(1, "x/"));
+        assert_eq!(resolve_in(&names, "/tmp/"), ok(1, "."));
+        // As in wasi-libc, a trailing `/` of a name has to be in the path too.
+        assert_eq!(resolve_in(&names, "/tmp"), ok(0, "tmp"));
+        // Relative paths are resolved from `/`.
+        assert_eq!(resolve_in(&names, "tmp/x"), ok(1, "x"));
+        // `..` isn't normalized, it's left for the host to reject.
+        assert_eq!(resolve_in(&names, "/tmp/../etc"), ok(1, "../etc"));
+        assert_eq!(resolve_in(&names, "/tmp/./x"), ok(1, "./x"));
+    }
+
+    #[test]
+    fn skips_non_matching_preopens() {
+        let names = [None, Some("/data"), Some("/tmp")];
+        assert_eq!(resolve_in(&names, "/data/x"), ok(1, "x"));
+        assert_eq!(resolve_in(&names, "/etc/x"), Err(crate::ERRNO_NOENT));
+        assert_eq!(resolve_in(&[], "/"), Err(crate::ERRNO_NOENT));
+        // Names longer than the stack buffer are ignored.
+        let long: &'static str = std::boxed::Box::leak("a".repeat(PATH_MAX + 1).into());
+        assert_eq!(resolve_in(&[Some(long)], long), Err(crate::ERRNO_NOENT));
+    }
+}
@@ -0,0 +1,796 @@
+//! Safe wrappers around the raw WASI functions.
+//!
+//! The functions at the root of this crate mirror the witx definitions
+//! exactly: they're all `unsafe`, take raw file descriptor numbers and are
+//! passed buffers as pointers or iovec arrays. This module provides an owned
+//! [`Fd`] type which closes its descriptor on drop, takes slices instead of
+//! iovec arrays and, with the `std` feature, returns owned `String`s and
+//! `Vec`s where the raw API fills caller-provided buffers.
+//!
+//! Errors are still reported as [`Errno`] values.
+
+use crate::{
+    Advice, Clockid, Dircookie, Errno, Event, Exitcode, Fdflags, Fdstat, Filedelta, Filesize,
+    Filestat, Fstflags, Lookupflags, Oflags, Prestat, Riflags, Rights, Roflags, Sdflags, Siflags,
+    Signal, Subscription, Timestamp, Whence, ERRNO_INVAL,
+};
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::{self, ManuallyDrop};
+use core::ops::Deref;
+
+/// An owned WASI file descriptor.
+///
+/// The descriptor is closed with [`fd_close`](crate::fd_close) when this value
+/// is dropped.
+pub struct Fd {
+    raw: crate::Fd,
+}
+
+impl Fd {
+    /// Takes ownership of the raw file descriptor `raw`.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be an open file descriptor which isn't owned by anything
+    /// else, as it will be closed when the returned value is dropped.
+    pub unsafe fn from_raw(raw: crate::Fd) -> Fd {
+        Fd { raw }
+    }
+
+    /// Returns the raw file descriptor number without giving up ownership.
+    pub fn as_raw(&self) -> crate::Fd {
+        self.raw
+    }
+
+    /// Returns the raw file descriptor number, giving up ownership.
+    ///
+    /// The descriptor will no longer be closed automatically.
+    pub fn into_raw(self) -> crate::Fd {
+        let raw = self.raw;
+        mem::forget(self);
+        raw
+    }
+
+    /// Closes this file descriptor, returning any error reported by the host.
+    ///
+    /// Dropping an [`Fd`] also closes it but ignores errors.
+    pub fn close(self) -> Result<(), Errno> {
+        unsafe { crate::fd_close(self.into_raw()) }
+    }
+
+    /// Reads from this file descriptor into `buf`, returning the number of
+    /// bytes read.
+    pub fn read(&self, buf: &mut [u8]) -> Result<usize, Errno> {
+        let iovs = [crate::Iovec {
+            buf: buf.as_mut_ptr(),
+            buf_len: buf.len(),
+        }];
+        unsafe { crate::fd_read(self.raw, &iovs) }
+    }
+
+    /// Writes `buf` to this file descriptor, returning the number of bytes
+    /// written.
+    pub fn write(&self, buf: &[u8]) -> Result<usize, Errno> {
+        let iovs = [crate::Ciovec {
+            buf: buf.as_ptr(),
+            buf_len: buf.len(),
+        }];
+        unsafe { crate::fd_write(self.raw, &iovs) }
+    }
+
+    /// Reads into `buf` at `offset` without using or updating the file
+    /// offset.
+    pub fn pread(&self, buf: &mut [u8], offset: Filesize) -> Result<usize, Errno> {
+        let iovs = [crate::Iovec {
+            buf: buf.as_mut_ptr(),
+            buf_len: buf.len(),
+        }];
+        unsafe { crate::fd_pread(self.raw, &iovs, offset) }
+    }
+
+    /// Writes `buf` at `offset` without using or updating the file offset.
+    pub fn pwrite(&self, buf: &[u8], offset: Filesize) -> Result<usize, Errno> {
+        let iovs = [crate::Ciovec {
+            buf: buf.as_ptr(),
+            buf_len: buf.len(),
+        }];
+        unsafe { crate::fd_pwrite(self.raw, &iovs, offset) }
+    }
+
+    /// Moves the file offset, returning the new offset from the start of the
+    /// file.
+    pub fn seek(&self, offset: Filedelta, whence: Whence) -> Result<Filesize, Errno> {
+        unsafe { crate::fd_seek(self.raw, offset, whence) }
+    }
+
+    /// Returns the current file offset.
+    pub fn tell(&self) -> Result<Filesize, Errno> {
+        unsafe { crate::fd_tell(self.raw) }
+    }
+
+    /// Provides file advisory information.
+    pub fn advise(&self, offset: Filesize, len: Filesize, advice: Advice) -> Result<(), Errno> {
+        unsafe { crate::fd_advise(self.raw, offset, len, advice) }
+    }
+
+    /// Forces the allocation of space in the file.
+    pub fn allocate(&self, offset: Filesize, len: Filesize) -> Result<(), Errno> {
+        unsafe { crate::fd_allocate(self.raw, offset, len) }
+    }
+
+    /// Synchronizes the data of the file to disk.
+    pub fn datasync(&self) -> Result<(), Errno> {
+        unsafe { crate::fd_datasync(self.raw) }
+    }
+
+    /// Synchronizes the data and metadata of the file to disk.
+    pub fn sync(&self) -> Result<(), Errno> {
+        unsafe { crate::fd_sync(self.raw) }
+    }
+
+    /// Returns the attributes of this file descriptor.
+    pub fn fdstat(&self) -> Result<Fdstat, Errno> {
+        unsafe { crate::fd_fdstat_get(self.raw) }
+    }
+
+    /// Sets the flags associated with this file descriptor.
+    pub fn set_flags(&self, flags: Fdflags) -> Result<(), Errno> {
+        unsafe { crate::fd_fdstat_set_flags(self.raw, flags) }
+    }
+
+    /// Drops rights associated with this file descriptor.
+    pub fn set_rights(&self, base: Rights, inheriting: Rights) -> Result<(), Errno> {
+        unsafe { crate::fd_fdstat_set_rights(self.raw, base, inheriting) }
+    }
+
+    /// Returns the attributes of the open file.
+    pub fn filestat(&self) -> Result<Filestat, Errno> {
+        unsafe { crate::fd_filestat_get(self.raw) }
+    }
+
+    /// Truncates or extends the open file to `size` bytes.
+    pub fn set_size(&self, size: Filesize) -> Result<(), Errno> {
+        unsafe { crate::fd_filestat_set_size(self.raw, size) }
+    }
+
+    /// Adjusts the timestamps of the open file or directory.
+    pub fn set_times(
+        &self,
+        atim: Timestamp,
+        mtim: Timestamp,
+        fst_flags: Fstflags,
+    ) -> Result<(), Errno> {
+        unsafe { crate::fd_filestat_set_times(self.raw, atim, mtim, fst_flags) }
+    }
+
+    /// Atomically replaces `to` with this file descriptor.
+    ///
+    /// On success `to` refers to the file previously referred to by `self`.
+    pub fn renumber(self, to: &Fd) -> Result<(), Errno> {
+        let raw = self.into_raw();
+        unsafe {
+            crate::fd_renumber(raw, to.raw).inspect_err(|_| {
+                drop(Fd::from_raw(raw));
+            })
+        }
+    }
+
+    /// Returns a description of this preopened file descriptor.
+    pub fn prestat(&self) -> Result<Prestat, Errno> {
+        unsafe { crate::fd_prestat_get(self.raw) }
+    }
+
+    /// Writes the name of this preopened directory into `buf`, returning the
+    /// length of the name.
+    ///
+    /// Fails with [`ERRNO_INVAL`] if `buf` is too small.
+    pub fn prestat_dir_name_into(&self, buf: &mut [u8]) -> Result<usize, Errno> {
+        let prestat = self.prestat()?;
+        let len = unsafe { prestat.u.dir.pr_name_len };
+        if len > buf.len() {
+            return Err(ERRNO_INVAL);
+        }
+        unsafe { crate::fd_prestat_dir_name(self.raw, buf.as_mut_ptr(), len)? };
+        Ok(len)
+    }
+
+    /// Returns the name of this preopened directory.
+    #[cfg(feature = "alloc")]
+    pub fn prestat_dir_name(&self) -> Result<String, Errno> {
+        let prestat = self.prestat()?;
+        let len = unsafe { prestat.u.dir.pr_name_len };
+        let mut buf = Vec::with_capacity(len);
+        unsafe {
+            crate::fd_prestat_dir_name(self.raw, buf.as_mut_ptr(), len)?;
+            buf.set_len(len);
+        }
+        into_string(buf)
+    }
+
+    /// Reads directory entries into `buf` starting at `cookie`, returning the
+    /// number of bytes stored.
+    ///
+    /// See [`fd_readdir`](crate::fd_readdir) for the format of the entries.
+    pub fn readdir(&self, buf: &mut [u8], cookie: Dircookie) -> Result<usize, Errno> {
+        unsafe { crate::fd_readdir(self.raw, buf.as_mut_ptr(), buf.len(), cookie) }
+    }
+
+    /// Returns an iterator over the entries of this directory, starting from
+    /// [`DIRCOOKIE_START`](crate::DIRCOOKIE_START).
+    #[cfg(feature = "alloc")]
+    pub fn read_dir(&self) -> ReadDir<'_> {
+        ReadDir {
+            fd: self,
+            buf: Vec::new(),
+            pos: 0,
+            cookie: crate::DIRCOOKIE_START,
+            eof: false,
+            done: false,
+        }
+    }
+
+    /// Opens the file or directory at `path` relative to this directory.
+    pub fn open(
+        &self,
+        dirflags: Lookupflags,
+        path: &str,
+        oflags: Oflags,
+        fs_rights_base: Rights,
+        fs_rights_inheriting: Rights,
+        fdflags: Fdflags,
+    ) -> Result<Fd, Errno> {
+        unsafe {
+            let raw = crate::path_open(
+                self.raw,
+                dirflags,
+                path,
+                oflags,
+                fs_rights_base,
+                fs_rights_inheriting,
+                fdflags,
+            )?;
+            Ok(Fd::from_raw(raw))
+        }
+    }
+
+    /// Creates a directory at `path` relative to this directory.
+    pub fn create_directory(&self, path: &str) -> Result<(), Errno> {
+        unsafe { crate::path_create_directory(self.raw, path) }
+    }
+
+    /// Removes the empty directory at `path` relative to this directory.
+    pub fn remove_directory(&self, path: &str) -> Result<(), Errno> {
+        unsafe { crate::path_remove_directory(self.raw, path) }
+    }
+
+    /// Unlinks the file at `path` relative to this directory.
+    pub fn unlink_file(&self, path: &str) -> Result<(), Errno> {
+        unsafe { crate::path_unlink_file(self.raw, path) }
+    }
+
+    /// Returns the attributes of the file or directory at `path` relative to
+    /// this directory.
+    pub fn path_filestat(&self, flags: Lookupflags, path: &str) -> Result<Filestat, Errno> {
+        unsafe { crate::path_filestat_get(self.raw, flags, path) }
+    }
+
+    /// Adjusts the timestamps of the file or directory at `path` relative to
+    /// this directory.
+    pub fn path_set_times(
+        &self,
+        flags: Lookupflags,
+        path: &str,
+        atim: Timestamp,
+        mtim: Timestamp,
+        fst_flags: Fstflags,
+    ) -> Result<(), Errno> {
+        unsafe { crate::path_filestat_set_times(self.raw, flags, path, atim, mtim, fst_flags) }
+    }
+
+    /// Creates a hard link at `new_path` relative to `new_dir` to the file at
+    /// `old_path` relative to this directory.
+    pub fn link(
+        &self,
+        old_flags: Lookupflags,
+        old_path: &str,
+        new_dir: &Fd,
+        new_path: &str,
+    ) -> Result<(), Errno> {
+        unsafe { crate::path_link(self.raw, old_flags, old_path, new_dir.raw, new_path) }
+    }
+
+    /// Renames the file or directory at `old_path` relative to this directory
+    /// to `new_path` relative to `new_dir`.
+    pub fn rename(&self, old_path: &str, new_dir: &Fd, new_path: &str) -> Result<(), Errno> {
+        unsafe { crate::path_rename(self.raw, old_path, new_dir.raw, new_path) }
+    }
+
+    /// Creates a symbolic link at `new_path` relative to this directory
+    /// pointing to `old_path`.
+    pub fn symlink(&self, old_path: &str, new_path: &str) -> Result<(), Errno> {
+        unsafe { crate::path_symlink(old_path, self.raw, new_path) }
+    }
+
+    /// Reads the contents of the symbolic link at `path` relative to this
+    /// directory into `buf`, returning the number of bytes stored.
+    pub fn readlink_into(&self, path: &str, buf: &mut [u8]) -> Result<usize, Errno> {
+        unsafe { crate::path_readlink(self.raw, path, buf.as_mut_ptr(), buf.len()) }
+    }
+
+    /// Returns the contents of the symbolic link at `path` relative to this
+    /// directory.
+    #[cfg(feature = "alloc")]
+    pub fn readlink(&self, path: &str) -> Result<String, Errno> {
+        let mut buf = Vec::with_capacity(256);
+        loop {
+            let len =
+                unsafe { crate::path_readlink(self.raw, path, buf.as_mut_ptr(), buf.capacity())? };
+            // A completely filled buffer may mean the contents were
+            // truncated, so retry with a larger one.
+            if len < buf.capacity() {
+                unsafe { buf.set_len(len) };
+                return into_string(buf);
+            }
+            buf.reserve(buf.capacity() * 2);
+        }
+    }
+
+    /// Accepts a new connection on this listening socket.
+    pub fn sock_accept(&self, flags: Fdflags) -> Result<Fd, Errno> {
+        unsafe { Ok(Fd::from_raw(crate::sock_accept(self.raw, flags)?)) }
+    }
+
+    /// Receives data from this socket into `buf`, returning the number of
+    /// bytes stored and the message flags.
+    pub fn sock_recv(&self, buf: &mut [u8], flags: Riflags) -> Result<(usize, Roflags), Errno> {
+        let iovs = [crate::Iovec {
+            buf: buf.as_mut_ptr(),
+            buf_len: buf.len(),
+        }];
+        unsafe { crate::sock_recv(self.raw, &iovs, flags) }
+    }
+
+    /// Sends `buf` on this socket, returning the number of bytes sent.
+    pub fn sock_send(&self, buf: &[u8], flags: Siflags) -> Result<usize, Errno> {
+        let iovs = [crate::Ciovec {
+            buf: buf.as_ptr(),
+            buf_len: buf.len(),
+        }];
+        unsafe { crate::sock_send(self.raw, &iovs, flags) }
+    }
+
+    /// Shuts down the receive and/or send channels of this socket.
+    pub fn sock_shutdown(&self, how: Sdflags) -> Result<(), Errno> {
+        unsafe { crate::sock_shutdown(self.raw, how) }
+    }
+}
+
+impl Drop for Fd {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = crate::fd_close(self.raw);
+        }
+    }
+}
+
+impl fmt::Debug for Fd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Fd").field(&self.raw).finish()
+    }
+}
+
+/// An iterator over the entries of a directory, created by [`Fd::read_dir`].
+///
+/// Each item is the [`Dirent`](crate::Dirent) header of an entry along with
+/// its name, as bytes since names aren't necessarily valid UTF-8. The `.` and
+/// `..` entries are yielded like any other if the host reports them.
+/// Iteration stops after the first error from the host.
+#[cfg(feature = "alloc")]
+pub struct ReadDir<'a> {
+    fd: &'a Fd,
+    buf: Vec<u8>,
+    pos: usize,
+    cookie: Dircookie,
+    eof: bool,
+    done: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl ReadDir<'_> {
+    const INITIAL_BUF_LEN: usize = 4096;
+
+    /// Returns the cookie at which the next call to `fd_readdir` would start,
+    /// which is the position just past the last entry yielded.
+    pub fn cookie(&self) -> Dircookie {
+        self.cookie
+    }
+
+    /// Refills `buf` with entries starting at `cookie`, growing it to at least
+    /// `min_len` bytes first.
+    fn fill(&mut self, min_len: usize) -> Result<(), Errno> {
+        if self.buf.len() < min_len {
+            self.buf.resize(min_len, 0);
+        }
+        let len = self.fd.readdir(&mut self.buf, self.cookie)?;
+        self.eof = len < self.buf.len();
+        self.buf.truncate(len);
+        self.pos = 0;
+        Ok(())
+    }
+
+    fn next_entry(&mut self) -> Result<Option<(crate::Dirent, Vec<u8>)>, Errno> {
+        const HEADER_LEN: usize = mem::size_of::<crate::Dirent>();
+        loop {
+            let rest = &self.buf[self.pos..];
+            if rest.len() >= HEADER_LEN {
+                let dirent =
+                    unsafe { core::ptr::read_unaligned(rest.as_ptr().cast::<crate::Dirent>()) };
+                let name_len = dirent.d_namlen as usize;
+                if let Some(name) = rest.get(HEADER_LEN..HEADER_LEN + name_len) {
+                    let name = name.to_vec();
+                    self.pos += HEADER_LEN + name_len;
+                    self.cookie = dirent.d_next;
+                    return Ok(Some((dirent, name)));
+                }
+                if self.eof {
+                    return Ok(None);
+                }
+                // The host truncated this entry. If it's the only thing in
+                // the buffer then the buffer is too small to hold it at all,
+                // so grow it before reading again from the same cookie.
+                let min_len = if self.pos == 0 {
+                    (self.buf.len() * 2).max(HEADER_LEN + name_len)
+                } else {
+                    self.buf.capacity()
+                };
+                self.fill(min_len)?;
+                continue;
+            }
+            if self.eof {
+                return Ok(None);
+            }
+            let min_len = self.buf.capacity().max(Self::INITIAL_BUF_LEN);
+            self.fill(min_len)?;
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for ReadDir<'_> {
+    type Item = Result<(crate::Dirent, Vec<u8>), Errno>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let entry = self.next_entry().transpose();
+        if !matches!(entry, Some(Ok(_))) {
+            self.done = true;
+        }
+        entry
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Debug for ReadDir<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadDir")
+            .field("fd", &self.fd.raw)
+            .field("cookie", &self.cookie)
+            .finish()
+    }
+}
+
+/// A borrowed WASI file descriptor.
+///
+/// This dereferences to an [`Fd`] but never closes the descriptor.
+pub struct BorrowedFd<'a> {
+    fd: ManuallyDrop<Fd>,
+    _marker: PhantomData<&'a Fd>,
+}
+
+impl BorrowedFd<'_> {
+    /// Borrows the raw file descriptor `raw`.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must remain open for the lifetime of the returned value.
+    pub unsafe fn borrow_raw(raw: crate::Fd) -> Self {
+        BorrowedFd {
+            fd: ManuallyDrop::new(Fd::from_raw(raw)),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Deref for BorrowedFd<'_> {
+    type Target = Fd;
+
+    fn deref(&self) -> &Fd {
+        &self.fd
+    }
+}
+
+impl fmt::Debug for BorrowedFd<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BorrowedFd").field(&self.fd.raw).finish()
+    }
+}
+
+/// Returns the standard input descriptor.
+pub fn stdin() -> BorrowedFd<'static> {
+    unsafe { BorrowedFd::borrow_raw(crate::FD_STDIN) }
+}
+
+/// Returns the standard output descriptor.
+pub fn stdout() -> BorrowedFd<'static> {
+    unsafe { BorrowedFd::borrow_raw(crate::FD_STDOUT) }
+}
+
+/// Returns the standard error descriptor.
+pub fn stderr() -> BorrowedFd<'static> {
+    unsafe { BorrowedFd::borrow_raw(crate::FD_STDERR) }
+}
+
+/// Returns the command-line arguments.
+///
+/// Fails with [`ERRNO_ILSEQ`](crate::ERRNO_ILSEQ) if an argument isn't valid
+/// UTF-8.
+#[cfg(feature = "alloc")]
+pub fn args_get() -> Result<Vec<String>, Errno> {
+    unsafe {
+        get_strings(crate::args_sizes_get()?, |ptrs, buf| {
+            crate::args_get(ptrs, buf)
+        })
+    }
+}
+
+/// Returns the environment variables as `KEY=VALUE` strings.
+///
+/// Fails with [`ERRNO_ILSEQ`](crate::ERRNO_ILSEQ) if a variable isn't valid
+/// UTF-8.
+#[cfg(feature = "alloc")]
+pub fn environ_get() -> Result<Vec<String>, Errno> {
+    unsafe {
+        get_strings(crate::environ_sizes_get()?, |ptrs, buf| {
+            crate::environ_get(ptrs, buf)
+        })
+    }
+}
+
+/// Decodes the NUL-terminated strings filled in by `args_get` or
+/// `environ_get`.
+#[cfg(feature = "alloc")]
+unsafe fn get_strings(
+    (count, buf_len): (usize, usize),
+    get: impl FnOnce(*mut *mut u8, *mut u8) -> Result<(), Errno>,
+) -> Result<Vec<String>, Errno> {
+    let mut ptrs = Vec::<*mut u8>::with_capacity(count);
+    let mut buf = Vec::<u8>::with_capacity(buf_len);
+    get(ptrs.as_mut_ptr(), buf.as_mut_ptr())?;
+    ptrs.set_len(count);
+    buf.set_len(buf_len);
+    ptrs.iter()
+        .map(|ptr| into_string(crate::env::cstr_at(&buf, *ptr)?.to_bytes().to_vec()))
+        .collect()
+}
+
+#[cfg(feature = "alloc")]
+fn into_string(buf: Vec<u8>) -> Result<String, Errno> {
+    String::from_utf8(buf).map_err(|_| crate::ERRNO_ILSEQ)
+}
+
+/// Returns the resolution of a clock.
+pub fn clock_res_get(id: Clockid) -> Result<Timestamp, Errno> {
+    unsafe { crate::clock_res_get(id) }
+}
+
+/// Returns the time value of a clock.
+pub fn clock_time_get(id: Clockid, precision: Timestamp) -> Result<Timestamp, Errno> {
+    unsafe { crate::clock_time_get(id, precision) }
+}
+
+/// Concurrently polls for the occurrence of the events in `subscriptions`.
+///
+/// Returns the number of events stored in `events`, which is filled from the
+/// start. Fails with [`ERRNO_INVAL`](crate::ERRNO_INVAL) if `events` is
+/// shorter than `subscriptions`, since the host may store an event for each
+/// of them.
+pub fn poll_oneoff(subscriptions: &[Subscription], events: &mut [Event]) -> Result<usize, Errno> {
+    if events.len() < subscriptions.len() {
+        return Err(crate::ERRNO_INVAL);
+    }
+    unsafe {
+        crate::poll_oneoff(
+            subscriptions.as_ptr(),
+            events.as_mut_ptr(),
+            subscriptions.len(),
+        )
+    }
+}
+
+/// Fills `buf` with high-quality random data.
+pub fn random_get(buf: &mut [u8]) -> Result<(), Errno> {
+    unsafe { crate::random_get(buf.as_mut_ptr(), buf.len()) }
+}
+
+/// Temporarily yields execution of the calling thread.
+pub fn sched_yield() -> Result<(), Errno> {
+    unsafe { crate::sched_yield() }
+}
+
+/// Sends a signal to the process of the calling thread.
+pub fn proc_raise(sig: Signal) -> Result<(), Errno> {
+    unsafe { crate::proc_raise(sig) }
+}
+
+/// Terminates the process with the exit code `rval`.
+pub fn proc_exit(rval: Exitcode) -> ! {
+    unsafe { crate::proc_exit(rval) }
+}
+
+#[cfg(all(test, feature = "native-host"))]
+mod tests {
+    use super::*;
+    use crate::host::{set_host, Preview1Host};
+    use crate::{Dirent, Size, FILETYPE_REGULAR_FILE};
+    use core::cell::RefCell;
+    use std::rc::Rc;
+    use std::vec;
+
+    /// A directory whose entries are named `names`, where the cookie after
+    /// the `i`th entry is `10 * (i + 1)`.
+    #[derive(Default)]
+    struct Dir {
+        names: Vec<Vec<u8>>,
+        /// Reads starting at this cookie or later fail with `ERRNO_IO`.
+        fail_from: Option<Dircookie>,
+        /// The `(buf_len, cookie)` of each call to `fd_readdir`.
+        reads: RefCell<Vec<(Size, Dircookie)>>,
+    }
+
+    impl Preview1Host for Dir {
+        unsafe fn fd_readdir(
+            &self,
+            _fd: crate::Fd,
+            buf: *mut u8,
+            buf_len: Size,
+            cookie: Dircookie,
+        ) -> Result<Size, Errno> {
+            self.reads.borrow_mut().push((buf_len, cookie));
+            if self.fail_from.is_some_and(|fail_from| cookie >= fail_from) {
+                return Err(crate::ERRNO_IO);
+            }
+            // Like real hosts, fill the buffer with as many entries as fit,
+            // truncating the last one.
+            let mut bytes = Vec::new();
+            for (i, name) in self.names.iter().enumerate().skip(cookie as usize / 10) {
+                let dirent = Dirent {
+                    d_next: 10 * (i as u64 + 1),
+                    d_ino: i as u64,
+                    d_namlen: name.len() as u32,
+                    d_type: FILETYPE_REGULAR_FILE,
+                };
+                let header = core::slice::from_raw_parts(
+                    (&dirent as *const Dirent).cast::<u8>(),
+                    mem::size_of::<Dirent>(),
+                );
+                bytes.extend_from_slice(header);
+                bytes.extend_from_slice(name);
+            }
+            let len = bytes.len().min(buf_len);
+            core::ptr::copy_nonoverlapping(bytes.as_ptr(), buf, len);
+            Ok(len)
+        }
+    }
+
+    /// The names of the entries read, along with the cookie after each.
+    type Entries = Vec<(Vec<u8>, Dircookie)>;
+
+    /// Reads the directory with entries named `names`, returning the entries
+    /// and the reads made.
+    fn read(names: Vec<Vec<u8>>) -> (Entries, Vec<(Size, Dircookie)>) {
+        let host = Rc::new(Dir {
+            names,
+            ..Dir::default()
+        });
+        let _guard = set_host(host.clone());
+        let fd = unsafe { BorrowedFd::borrow_raw(3) };
+        let mut dir = fd.read_dir();
+        assert_eq!(dir.cookie(), crate::DIRCOOKIE_START);
+        let mut entries = Vec::new();
+        while let Some(entry) = dir.next() {
+            let (dirent, name) = entry.unwrap();
+            assert_eq!(dirent.d_next, dir.cookie());
+            entries.push((name, dir.cookie()));
+        }
+        let reads = host.reads.take();
+        (entries, reads)
+    }
+
+    #[test]
+    fn read_dir_yields_entries_and_cookies() {
+        let (entries, reads) = read(vec![b"a".to_vec(), b"bb".to_vec()]);
+        assert_eq!(entries, [(b"a".to_vec(), 10), (b"bb".to_vec(), 20)]);
+        assert_eq!(reads, [(4096, 0)]);
+
+        let (entries, reads) = read(Vec::new());
+        assert_eq!(entries, []);
+        assert_eq!(reads, [(4096, 0)]);
+    }
+
+    #[test]
+    fn read_dir_refills_at_entry_straddling_buffer() {
+        // The name of the second entry is cut off by the end of the buffer.
+        let first = vec![b'a'; 4000];
+        let (entries, reads) = read(vec![first.clone(), vec![b'b'; 100], b"c".to_vec()]);
+        assert_eq!(
+            entries,
+            [(first, 10), (vec![b'b'; 100], 20), (b"c".to_vec(), 30)]
+        );
+        assert_eq!(reads, [(4096, 0), (4096, 10)]);
+
+        // Only part of the header of the second entry fits.
+        let first = vec![b'a'; 4060];
+        let (entries, reads) = read(vec![first.clone(), b"b".to_vec()]);
+        assert_eq!(entries, [(first, 10), (b"b".to_vec(), 20)]);
+        assert_eq!(reads, [(4096, 0), (4096, 10)]);
+    }
+
+    #[test]
+    fn read_dir_grows_buffer_for_large_entry() {
+        let large = vec![b'a'; 5000];
+        let (entries, reads) = read(vec![b"x".to_vec(), large.clone()]);
+        assert_eq!(entries, [(b"x".to_vec(), 10), (large.clone(), 20)]);
+        assert_eq!(reads, [(4096, 0), (4096, 10), (8192, 10)]);
+
+        let (entries, reads) = read(vec![large.clone()]);
+        assert_eq!(entries, [(large, 10)]);
+        assert_eq!(reads, [(4096, 0), (8192, 0)]);
+    }
+
+    #[test]
+    fn read_dir_yields_non_utf8_names() {
+        let (entries, _) = read(vec![b"a".to_vec(), vec![0xff], b"c".to_vec()]);
+        assert_eq!(
+            entries,
+            [(b"a".to_vec(), 10), (vec![0xff], 20), (b"c".to_vec(), 30)]
+        );
+    }
+
+    #[test]
+    fn read_dir_stops_after_error() {
+        // The large entry doesn't fit in the first read, so it's read again
+        // from its cookie.
+        let host = Rc::new(Dir {
+            names: vec![b"a".to_vec(), vec![b'b'; 5000]],
+            fail_from: Some(10),
+            ..Dir::default()
+        });
+        let _guard = set_host(host);
+        let fd = unsafe { BorrowedFd::borrow_raw(3) };
+        let mut dir = fd.read_dir();
+        assert_eq!(dir.next().unwrap().unwrap().1, b"a");
+        assert_eq!(dir.next().unwrap().unwrap_err(), crate::ERRNO_IO);
+        assert!(dir.next().is_none());
+        assert_eq!(dir.cookie(), 10);
+    }
+
+    #[test]
+    fn poll_oneoff_needs_an_event_per_subscription() {
+        let _guard = set_host(Rc::new(Dir::default()));
+        let subscriptions: [Subscription; 2] = unsafe { mem::zeroed() };
+        let mut events: [Event; 2] = unsafe { mem::zeroed() };
+        assert_eq!(
+            poll_oneoff(&subscriptions, &mut events[..1]),
+            Err(crate::ERRNO_INVAL)
+        );
+        assert_eq!(
+            poll_oneoff(&subscriptions, &mut events),
+            Err(crate::ERRNO_NOSYS)
+        );
+    }
+}
@@ -36,13 +36,14 @@ pub mod wasi {
                             * ::core::mem::size_of::<*const u8>()],
                     );
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/environment@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "get-environment"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-environment")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/environment@0.2.12#get-environment")]
                         fn wit_import1(_: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
@@ -102,13 +103,14 @@ pub mod wasi {
                             * ::core::mem::size_of::<*const u8>()],
                     );
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/environment@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "get-arguments"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-arguments")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/environment@0.2.12#get-arguments")]
                         fn wit_import1(_: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
@@ -161,13 +163,14 @@ pub mod wasi {
                             * ::core::mem::size_of::<*const u8>()],
                     );
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/environment@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "initial-cwd"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "initial-cwd")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/environment@0.2.12#initial-cwd")]
                         fn wit_import1(_: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
@@ -214,13 +217,14 @@ pub mod wasi {
                         Ok(_) => 0i32,
                         Err(_) => 1i32,
                     };
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/exit@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "exit"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "exit")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/exit@0.2.12#exit")]
                         fn wit_import1(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: i32) {
                         unreachable!()
                     }
@@ -239,13 +243,14 @@ pub mod wasi {
             #[allow(async_fn_in_trait)]
             pub fn exit_with_code(status_code: u8) -> () {
                 unsafe {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/exit@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "exit-with-code"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "exit-with-code")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/exit@0.2.12#exit-with-code")]
                         fn wit_import0(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import0(_: i32) {
                         unreachable!()
                     }
@@ -263,13 +268,14 @@ pub mod wasi {
             #[allow(async_fn_in_trait)]
             pub fn get_stdin() -> InputStream {
                 unsafe {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/stdin@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "get-stdin"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-stdin")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/stdin@0.2.12#get-stdin")]
                         fn wit_import0() -> i32;
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import0() -> i32 {
                         unreachable!()
                     }
@@ -290,13 +296,14 @@ pub mod wasi {
             #[allow(async_fn_in_trait)]
             pub fn get_stdout() -> OutputStream {
                 unsafe {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/stdout@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "get-stdout"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-stdout")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/stdout@0.2.12#get-stdout")]
                         fn wit_import0() -> i32;
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import0() -> i32 {
                         unreachable!()
                     }
@@ -317,13 +324,14 @@ pub mod wasi {
             #[allow(async_fn_in_trait)]
             pub fn get_stderr() -> OutputStream {
                 unsafe {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/stderr@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "get-stderr"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-stderr")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/stderr@0.2.12#get-stderr")]
                         fn wit_import0() -> i32;
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import0() -> i32 {
                         unreachable!()
                     }
@@ -370,13 +378,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for TerminalInput {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/terminal-input@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]terminal-input"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]terminal-input")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/terminal-input@0.2.12#[resource-drop]terminal-input")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -422,13 +431,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for TerminalOutput {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/terminal-output@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]terminal-output"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]terminal-output")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/terminal-output@0.2.12#[resource-drop]terminal-output")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -457,13 +467,14 @@ pub mod wasi {
                     struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/terminal-stdin@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "get-terminal-stdin"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-terminal-stdin")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/terminal-stdin@0.2.12#get-terminal-stdin")]
                         fn wit_import1(_: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
@@ -505,13 +516,14 @@ pub mod wasi {
                     struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/terminal-stdout@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "get-terminal-stdout"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-terminal-stdout")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/terminal-stdout@0.2.12#get-terminal-stdout")]
                         fn wit_import1(_: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
@@ -553,13 +565,14 @@ pub mod wasi {
                     struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/terminal-stderr@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "get-terminal-stderr"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-terminal-stderr")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/terminal-stderr@0.2.12#get-terminal-stderr")]
                         fn wit_import1(_: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
@@ -618,13 +631,14 @@ pub mod wasi {
             #[allow(async_fn_in_trait)]
             pub fn now() -> Instant {
                 unsafe {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:clocks/monotonic-clock@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "now"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "now")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:clocks/monotonic-clock@0.2.12#now")]
                         fn wit_import0() -> i64;
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import0() -> i64 {
                         unreachable!()
                     }
@@ -638,13 +652,14 @@ pub mod wasi {
             #[allow(async_fn_in_trait)]
             pub fn resolution() -> Duration {
                 unsafe {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:clocks/monotonic-clock@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "resolution"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "resolution")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:clocks/monotonic-clock@0.2.12#resolution")]
                         fn wit_import0() -> i64;
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import0() -> i64 {
                         unreachable!()
                     }
@@ -658,13 +673,14 @@ pub mod wasi {
             #[allow(async_fn_in_trait)]
             pub fn subscribe_instant(when: Instant) -> Pollable {
                 unsafe {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:clocks/monotonic-clock@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "subscribe-instant"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "subscribe-instant")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:clocks/monotonic-clock@0.2.12#subscribe-instant")]
                         fn wit_import0(_: i64) -> i32;
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import0(_: i64) -> i32 {
                         unreachable!()
                     }
@@ -680,13 +696,14 @@ pub mod wasi {
             #[allow(async_fn_in_trait)]
             pub fn subscribe_duration(when: Duration) -> Pollable {
                 unsafe {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:clocks/monotonic-clock@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "subscribe-duration"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "subscribe-duration")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:clocks/monotonic-clock@0.2.12#subscribe-duration")]
                         fn wit_import0(_: i64) -> i32;
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import0(_: i64) -> i32 {
                         unreachable!()
                     }
@@ -755,13 +772,14 @@ pub mod wasi {
                     struct RetArea([::core::mem::MaybeUninit<u8>; 16]);
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 16]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:clocks/wall-clock@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "now"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "now")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:clocks/wall-clock@0.2.12#now")]
                         fn wit_import1(_: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
@@ -786,13 +804,14 @@ pub mod wasi {
                     struct RetArea([::core::mem::MaybeUninit<u8>; 16]);
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 16]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:clocks/wall-clock@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "resolution"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "resolution")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:clocks/wall-clock@0.2.12#resolution")]
                         fn wit_import1(_: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
@@ -1489,13 +1508,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for Descriptor {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]descriptor"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]descriptor")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[resource-drop]descriptor")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -1529,13 +1549,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for DirectoryEntryStream {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]directory-entry-stream"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]directory-entry-stream")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[resource-drop]directory-entry-stream")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -1566,13 +1587,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 8],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.read-via-stream"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.read-via-stream")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.read-via-stream")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -1621,13 +1643,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 8],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.write-via-stream"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.write-via-stream")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.write-via-stream")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -1673,13 +1696,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 8],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.append-via-stream"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.append-via-stream")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.append-via-stream")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -1727,13 +1751,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.advise"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.advise")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.advise")]
                             fn wit_import1(_: i32, _: i64, _: i64, _: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(
                             _: i32,
                             _: i64,
@@ -1786,13 +1811,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.sync-data"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.sync-data")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.sync-data")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -1833,13 +1859,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.get-flags"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.get-flags")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.get-flags")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -1888,13 +1915,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.get-type"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.get-type")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.get-type")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -1936,13 +1964,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.set-size"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.set-size")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.set-size")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -2008,10 +2037,11 @@ pub mod wasi {
                             }
                         };
                         let ptr4 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.set-times"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.set-times")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.set-times")]
                             fn wit_import5(
                                 _: i32,
                                 _: i32,
@@ -2023,7 +2053,7 @@ pub mod wasi {
                                 _: *mut u8,
                             );
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import5(
                             _: i32,
                             _: i32,
@@ -2097,13 +2127,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.read"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.read")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.read")]
                             fn wit_import1(_: i32, _: i64, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(
                             _: i32,
                             _: i64,
@@ -2185,10 +2216,11 @@ pub mod wasi {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.write"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.write")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.write")]
                             fn wit_import2(
                                 _: i32,
                                 _: *mut u8,
@@ -2197,7 +2229,7 @@ pub mod wasi {
                                 _: *mut u8,
                             );
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import2(
                             _: i32,
                             _: *mut u8,
@@ -2256,13 +2288,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 8],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.read-directory"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.read-directory")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.read-directory")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -2306,13 +2339,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.sync"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.sync")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.sync")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -2353,13 +2387,14 @@ pub mod wasi {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.create-directory-at"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.create-directory-at")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.create-directory-at")]
                             fn wit_import2(_: i32, _: *mut u8, _: usize, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import2(
                             _: i32,
                             _: *mut u8,
@@ -2408,13 +2443,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 104],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.stat"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.stat")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.stat")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -2521,10 +2557,11 @@ pub mod wasi {
                         let ptr1 = vec1.as_ptr().cast::<u8>();
                         let len1 = vec1.len();
                         let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.stat-at"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.stat-at")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.stat-at")]
                             fn wit_import3(
                                 _: i32,
                                 _: i32,
@@ -2533,7 +2570,7 @@ pub mod wasi {
                                 _: *mut u8,
                             );
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import3(
                             _: i32,
                             _: i32,
@@ -2675,10 +2712,11 @@ pub mod wasi {
                             }
                         };
                         let ptr6 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.set-times-at"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.set-times-at")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.set-times-at")]
                             fn wit_import7(
                                 _: i32,
                                 _: i32,
@@ -2693,7 +2731,7 @@ pub mod wasi {
                                 _: *mut u8,
                             );
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import7(
                             _: i32,
                             _: i32,
@@ -2772,10 +2810,11 @@ pub mod wasi {
                         let ptr2 = vec2.as_ptr().cast::<u8>();
                         let len2 = vec2.len();
                         let ptr3 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.link-at"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.link-at")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.link-at")]
                             fn wit_import4(
                                 _: i32,
                                 _: i32,
@@ -2787,7 +2826,7 @@ pub mod wasi {
                                 _: *mut u8,
                             );
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import4(
                             _: i32,
                             _: i32,
@@ -2864,10 +2903,11 @@ pub mod wasi {
                         let flags2 = open_flags;
                         let flags3 = flags;
                         let ptr4 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.open-at"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.open-at")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.open-at")]
                             fn wit_import5(
                                 _: i32,
                                 _: i32,
@@ -2878,7 +2918,7 @@ pub mod wasi {
                                 _: *mut u8,
                             );
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import5(
                             _: i32,
                             _: i32,
@@ -2947,13 +2987,14 @@ pub mod wasi {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.readlink-at"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.readlink-at")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.readlink-at")]
                             fn wit_import2(_: i32, _: *mut u8, _: usize, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import2(
                             _: i32,
                             _: *mut u8,
@@ -3017,13 +3058,14 @@ pub mod wasi {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.remove-directory-at"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.remove-directory-at")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.remove-directory-at")]
                             fn wit_import2(_: i32, _: *mut u8, _: usize, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import2(
                             _: i32,
                             _: *mut u8,
@@ -3077,10 +3119,11 @@ pub mod wasi {
                         let ptr1 = vec1.as_ptr().cast::<u8>();
                         let len1 = vec1.len();
                         let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.rename-at"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.rename-at")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.rename-at")]
                             fn wit_import3(
                                 _: i32,
                                 _: *mut u8,
@@ -3091,7 +3134,7 @@ pub mod wasi {
                                 _: *mut u8,
                             );
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import3(
                             _: i32,
                             _: *mut u8,
@@ -3158,10 +3201,11 @@ pub mod wasi {
                         let ptr1 = vec1.as_ptr().cast::<u8>();
                         let len1 = vec1.len();
                         let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.symlink-at"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.symlink-at")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.symlink-at")]
                             fn wit_import3(
                                 _: i32,
                                 _: *mut u8,
@@ -3171,7 +3215,7 @@ pub mod wasi {
                                 _: *mut u8,
                             );
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import3(
                             _: i32,
                             _: *mut u8,
@@ -3227,13 +3271,14 @@ pub mod wasi {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.unlink-file-at"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.unlink-file-at")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.unlink-file-at")]
                             fn wit_import2(_: i32, _: *mut u8, _: usize, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import2(
                             _: i32,
                             _: *mut u8,
@@ -3273,13 +3318,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn is_same_object(&self, other: &Descriptor) -> bool {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.is-same-object"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.is-same-object")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.is-same-object")]
                             fn wit_import0(_: i32, _: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32, _: i32) -> i32 {
                             unreachable!()
                         }
@@ -3321,13 +3367,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 24],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.metadata-hash"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.metadata-hash")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.metadata-hash")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -3381,10 +3428,11 @@ pub mod wasi {
                         let ptr1 = vec1.as_ptr().cast::<u8>();
                         let len1 = vec1.len();
                         let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.metadata-hash-at"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.metadata-hash-at")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]descriptor.metadata-hash-at")]
                             fn wit_import3(
                                 _: i32,
                                 _: i32,
@@ -3393,7 +3441,7 @@ pub mod wasi {
                                 _: *mut u8,
                             );
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import3(
                             _: i32,
                             _: i32,
@@ -3456,13 +3504,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]directory-entry-stream.read-directory-entry"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]directory-entry-stream.read-directory-entry")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#[method]directory-entry-stream.read-directory-entry")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -3540,13 +3589,14 @@ pub mod wasi {
                     struct RetArea([::core::mem::MaybeUninit<u8>; 2]);
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 2]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:filesystem/types@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "filesystem-error-code"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "filesystem-error-code")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.2.12#filesystem-error-code")]
                         fn wit_import1(_: i32, _: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                         unreachable!()
                     }
@@ -3591,13 +3641,14 @@ pub mod wasi {
                             * ::core::mem::size_of::<*const u8>()],
                     );
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:filesystem/preopens@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "get-directories"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-directories")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/preopens@0.2.12#get-directories")]
                         fn wit_import1(_: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
@@ -3690,13 +3741,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for Error {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:io/error@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]error"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]error")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/error@0.2.12#[resource-drop]error")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -3729,13 +3781,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:io/error@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]error.to-debug-string"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]error.to-debug-string")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/error@0.2.12#[method]error.to-debug-string")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -3785,13 +3838,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for Pollable {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:io/poll@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]pollable"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]pollable")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/poll@0.2.12#[resource-drop]pollable")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -3808,13 +3862,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn ready(&self) -> bool {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:io/poll@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]pollable.ready"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]pollable.ready")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/poll@0.2.12#[method]pollable.ready")]
                             fn wit_import0(_: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
@@ -3833,13 +3888,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn block(&self) -> () {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:io/poll@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]pollable.block"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]pollable.block")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/poll@0.2.12#[method]pollable.block")]
                             fn wit_import0(_: i32);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32) {
                             unreachable!()
                         }
@@ -3893,13 +3949,14 @@ pub mod wasi {
                         }
                     }
                     let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:io/poll@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "poll"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "poll")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/poll@0.2.12#poll")]
                         fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
                         unreachable!()
                     }
@@ -4002,13 +4059,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for InputStream {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:io/streams@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]input-stream"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]input-stream")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/streams@0.2.12#[resource-drop]input-stream")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -4053,13 +4111,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for OutputStream {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:io/streams@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]output-stream"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]output-stream")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/streams@0.2.12#[resource-drop]output-stream")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -4111,13 +4170,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:io/streams@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]input-stream.read"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]input-stream.read")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/streams@0.2.12#[method]input-stream.read")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -4193,13 +4253,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:io/streams@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]input-stream.blocking-read"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]input-stream.blocking-read")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/streams@0.2.12#[method]input-stream.blocking-read")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -4268,13 +4329,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 16],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:io/streams@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]input-stream.skip"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]input-stream.skip")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/streams@0.2.12#[method]input-stream.skip")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -4329,13 +4391,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 16],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:io/streams@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]input-stream.blocking-skip"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]input-stream.blocking-skip")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/streams@0.2.12#[method]input-stream.blocking-skip")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -4388,13 +4451,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn subscribe(&self) -> Pollable {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:io/streams@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]input-stream.subscribe"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]input-stream.subscribe")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/streams@0.2.12#[method]input-stream.subscribe")]
                             fn wit_import0(_: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
@@ -4425,13 +4489,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 16],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:io/streams@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]output-stream.check-write"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]output-stream.check-write")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/streams@0.2.12#[method]output-stream.check-write")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -4500,13 +4565,14 @@ pub mod wasi {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:io/streams@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]output-stream.write"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]output-stream.write")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/streams@0.2.12#[method]output-stream.write")]
                             fn wit_import2(_: i32, _: *mut u8, _: usize, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import2(
                             _: i32,
                             _: *mut u8,
@@ -4577,13 +4643,14 @@ pub mod wasi {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:io/streams@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]output-stream.blocking-write-and-flush"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]output-stream.blocking-write-and-flush")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/streams@0.2.12#[method]output-stream.blocking-write-and-flush")]
                             fn wit_import2(_: i32, _: *mut u8, _: usize, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import2(
                             _: i32,
                             _: *mut u8,
@@ -4648,13 +4715,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 12],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:io/streams@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]output-stream.flush"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]output-stream.flush")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/streams@0.2.12#[method]output-stream.flush")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -4706,13 +4774,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 12],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:io/streams@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]output-stream.blocking-flush"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]output-stream.blocking-flush")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/streams@0.2.12#[method]output-stream.blocking-flush")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -4766,13 +4835,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn subscribe(&self) -> Pollable {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:io/streams@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]output-stream.subscribe"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]output-stream.subscribe")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/streams@0.2.12#[method]output-stream.subscribe")]
                             fn wit_import0(_: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
@@ -4800,13 +4870,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 12],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:io/streams@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]output-stream.write-zeroes"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]output-stream.write-zeroes")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/streams@0.2.12#[method]output-stream.write-zeroes")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -4865,13 +4936,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 12],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:io/streams@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]output-stream.blocking-write-zeroes-and-flush"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]output-stream.blocking-write-zeroes-and-flush")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/streams@0.2.12#[method]output-stream.blocking-write-zeroes-and-flush")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -4938,13 +5010,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 16],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:io/streams@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]output-stream.splice"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]output-stream.splice")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/streams@0.2.12#[method]output-stream.splice")]
                             fn wit_import1(_: i32, _: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(
                             _: i32,
                             _: i32,
@@ -5016,13 +5089,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 16],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:io/streams@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]output-stream.blocking-splice"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]output-stream.blocking-splice")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:io/streams@0.2.12#[method]output-stream.blocking-splice")]
                             fn wit_import1(_: i32, _: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(
                             _: i32,
                             _: i32,
@@ -5115,13 +5189,14 @@ pub mod wasi {
                             * ::core::mem::size_of::<*const u8>()],
                     );
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:random/random@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "get-random-bytes"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-random-bytes")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:random/random@0.2.12#get-random-bytes")]
                         fn wit_import1(_: i64, _: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: i64, _: *mut u8) {
                         unreachable!()
                     }
@@ -5145,13 +5220,14 @@ pub mod wasi {
             #[allow(async_fn_in_trait)]
             pub fn get_random_u64() -> u64 {
                 unsafe {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:random/random@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "get-random-u64"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-random-u64")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:random/random@0.2.12#get-random-u64")]
                         fn wit_import0() -> i64;
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import0() -> i64 {
                         unreachable!()
                     }
@@ -5194,13 +5270,14 @@ pub mod wasi {
                             * ::core::mem::size_of::<*const u8>()],
                     );
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:random/insecure@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "get-insecure-random-bytes"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-insecure-random-bytes")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:random/insecure@0.2.12#get-insecure-random-bytes")]
                         fn wit_import1(_: i64, _: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: i64, _: *mut u8) {
                         unreachable!()
                     }
@@ -5224,13 +5301,14 @@ pub mod wasi {
             #[allow(async_fn_in_trait)]
             pub fn get_insecure_random_u64() -> u64 {
                 unsafe {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:random/insecure@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "get-insecure-random-u64"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-insecure-random-u64")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:random/insecure@0.2.12#get-insecure-random-u64")]
                         fn wit_import0() -> i64;
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import0() -> i64 {
                         unreachable!()
                     }
@@ -5273,13 +5351,14 @@ pub mod wasi {
                     struct RetArea([::core::mem::MaybeUninit<u8>; 16]);
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 16]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:random/insecure-seed@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "insecure-seed"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "insecure-seed")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:random/insecure-seed@0.2.12#insecure-seed")]
                         fn wit_import1(_: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
@@ -5326,13 +5405,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for Network {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:sockets/network@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]network"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]network")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/network@0.2.12#[resource-drop]network")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -5714,13 +5794,14 @@ pub mod wasi {
             #[allow(async_fn_in_trait)]
             pub fn instance_network() -> Network {
                 unsafe {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:sockets/instance-network@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "instance-network"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "instance-network")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/instance-network@0.2.12#instance-network")]
                         fn wit_import0() -> i32;
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import0() -> i32 {
                         unreachable!()
                     }
@@ -5817,13 +5898,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for UdpSocket {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]udp-socket"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]udp-socket")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[resource-drop]udp-socket")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -5856,13 +5938,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for IncomingDatagramStream {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]incoming-datagram-stream"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]incoming-datagram-stream")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[resource-drop]incoming-datagram-stream")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -5895,13 +5978,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for OutgoingDatagramStream {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]outgoing-datagram-stream"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]outgoing-datagram-stream")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[resource-drop]outgoing-datagram-stream")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -6011,10 +6095,11 @@ pub mod wasi {
                             }
                         };
                         let ptr6 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.start-bind"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.start-bind")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[method]udp-socket.start-bind")]
                             fn wit_import7(
                                 _: i32,
                                 _: i32,
@@ -6033,7 +6118,7 @@ pub mod wasi {
                                 _: *mut u8,
                             );
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import7(
                             _: i32,
                             _: i32,
@@ -6102,13 +6187,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.finish-bind"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.finish-bind")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[method]udp-socket.finish-bind")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -6301,10 +6387,11 @@ pub mod wasi {
                             }
                         };
                         let ptr7 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.stream"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.stream")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[method]udp-socket.stream")]
                             fn wit_import8(
                                 _: i32,
                                 _: i32,
@@ -6323,7 +6410,7 @@ pub mod wasi {
                                 _: *mut u8,
                             );
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import8(
                             _: i32,
                             _: i32,
@@ -6415,13 +6502,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 36],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.local-address"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.local-address")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[method]udp-socket.local-address")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -6520,13 +6608,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 36],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.remote-address"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.remote-address")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[method]udp-socket.remote-address")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -6612,13 +6701,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn address_family(&self) -> IpAddressFamily {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.address-family"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.address-family")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[method]udp-socket.address-family")]
                             fn wit_import0(_: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
@@ -6646,13 +6736,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.unicast-hop-limit"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.unicast-hop-limit")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[method]udp-socket.unicast-hop-limit")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -6692,13 +6783,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.set-unicast-hop-limit"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.set-unicast-hop-limit")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[method]udp-socket.set-unicast-hop-limit")]
                             fn wit_import1(_: i32, _: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -6745,13 +6837,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 16],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.receive-buffer-size"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.receive-buffer-size")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[method]udp-socket.receive-buffer-size")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -6794,13 +6887,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.set-receive-buffer-size"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.set-receive-buffer-size")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[method]udp-socket.set-receive-buffer-size")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -6837,13 +6931,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 16],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.send-buffer-size"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.send-buffer-size")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[method]udp-socket.send-buffer-size")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -6883,13 +6978,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.set-send-buffer-size"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.set-send-buffer-size")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[method]udp-socket.set-send-buffer-size")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -6924,13 +7020,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn subscribe(&self) -> Pollable {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.subscribe"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.subscribe")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[method]udp-socket.subscribe")]
                             fn wit_import0(_: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
@@ -6984,13 +7081,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]incoming-datagram-stream.receive"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]incoming-datagram-stream.receive")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[method]incoming-datagram-stream.receive")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -7179,13 +7277,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn subscribe(&self) -> Pollable {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]incoming-datagram-stream.subscribe"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]incoming-datagram-stream.subscribe")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[method]incoming-datagram-stream.subscribe")]
                             fn wit_import0(_: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
@@ -7218,13 +7317,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 16],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]outgoing-datagram-stream.check-send"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]outgoing-datagram-stream.check-send")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[method]outgoing-datagram-stream.check-send")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -7413,13 +7513,14 @@ pub mod wasi {
                             }
                         }
                         let ptr8 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]outgoing-datagram-stream.send"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]outgoing-datagram-stream.send")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[method]outgoing-datagram-stream.send")]
                             fn wit_import9(_: i32, _: *mut u8, _: usize, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import9(
                             _: i32,
                             _: *mut u8,
@@ -7462,13 +7563,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn subscribe(&self) -> Pollable {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/udp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]outgoing-datagram-stream.subscribe"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]outgoing-datagram-stream.subscribe")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp@0.2.12#[method]outgoing-datagram-stream.subscribe")]
                             fn wit_import0(_: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
@@ -7520,13 +7622,14 @@ pub mod wasi {
                     struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:sockets/udp-create-socket@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "create-udp-socket"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "create-udp-socket")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/udp-create-socket@0.2.12#create-udp-socket")]
                         fn wit_import1(_: i32, _: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                         unreachable!()
                     }
@@ -7658,13 +7761,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for TcpSocket {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]tcp-socket"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]tcp-socket")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[resource-drop]tcp-socket")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -7786,10 +7890,11 @@ pub mod wasi {
                             }
                         };
                         let ptr6 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.start-bind"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.start-bind")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.start-bind")]
                             fn wit_import7(
                                 _: i32,
                                 _: i32,
@@ -7808,7 +7913,7 @@ pub mod wasi {
                                 _: *mut u8,
                             );
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import7(
                             _: i32,
                             _: i32,
@@ -7877,13 +7982,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.finish-bind"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.finish-bind")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.finish-bind")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -8026,10 +8132,11 @@ pub mod wasi {
                             }
                         };
                         let ptr6 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.start-connect"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.start-connect")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.start-connect")]
                             fn wit_import7(
                                 _: i32,
                                 _: i32,
@@ -8048,7 +8155,7 @@ pub mod wasi {
                                 _: *mut u8,
                             );
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import7(
                             _: i32,
                             _: i32,
@@ -8119,13 +8226,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 12],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.finish-connect"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.finish-connect")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.finish-connect")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -8198,13 +8306,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.start-listen"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.start-listen")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.start-listen")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -8241,13 +8350,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.finish-listen"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.finish-listen")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.finish-listen")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -8312,13 +8422,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 16],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.accept"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.accept")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.accept")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -8384,13 +8495,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 36],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.local-address"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.local-address")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.local-address")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -8489,13 +8601,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 36],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.remote-address"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.remote-address")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.remote-address")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -8581,13 +8694,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn is_listening(&self) -> bool {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.is-listening"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.is-listening")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.is-listening")]
                             fn wit_import0(_: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
@@ -8604,13 +8718,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn address_family(&self) -> IpAddressFamily {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.address-family"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.address-family")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.address-family")]
                             fn wit_import0(_: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
@@ -8644,13 +8759,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.set-listen-backlog-size"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.set-listen-backlog-size")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.set-listen-backlog-size")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -8696,13 +8812,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.keep-alive-enabled"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.keep-alive-enabled")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.keep-alive-enabled")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -8745,13 +8862,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.set-keep-alive-enabled"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.set-keep-alive-enabled")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.set-keep-alive-enabled")]
                             fn wit_import1(_: i32, _: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -8805,13 +8923,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 16],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.keep-alive-idle-time"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.keep-alive-idle-time")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.keep-alive-idle-time")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -8854,13 +8973,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.set-keep-alive-idle-time"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.set-keep-alive-idle-time")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.set-keep-alive-idle-time")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -8907,13 +9027,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 16],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.keep-alive-interval"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.keep-alive-interval")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.keep-alive-interval")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -8956,13 +9077,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.set-keep-alive-interval"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.set-keep-alive-interval")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.set-keep-alive-interval")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -9009,13 +9131,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 8],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.keep-alive-count"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.keep-alive-count")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.keep-alive-count")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -9055,13 +9178,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.set-keep-alive-count"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.set-keep-alive-count")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.set-keep-alive-count")]
                             fn wit_import1(_: i32, _: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -9104,13 +9228,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.hop-limit"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.hop-limit")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.hop-limit")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -9150,13 +9275,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.set-hop-limit"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.set-hop-limit")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.set-hop-limit")]
                             fn wit_import1(_: i32, _: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -9203,13 +9329,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 16],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.receive-buffer-size"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.receive-buffer-size")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.receive-buffer-size")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -9252,13 +9379,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.set-receive-buffer-size"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.set-receive-buffer-size")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.set-receive-buffer-size")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -9295,13 +9423,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 16],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.send-buffer-size"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.send-buffer-size")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.send-buffer-size")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -9341,13 +9470,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.set-send-buffer-size"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.set-send-buffer-size")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.set-send-buffer-size")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -9395,13 +9525,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn subscribe(&self) -> Pollable {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.subscribe"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.subscribe")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.subscribe")]
                             fn wit_import0(_: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
@@ -9449,13 +9580,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 2],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/tcp@0.2.12")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.shutdown"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.shutdown")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp@0.2.12#[method]tcp-socket.shutdown")]
                             fn wit_import1(_: i32, _: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -9526,13 +9658,14 @@ pub mod wasi {
                     struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:sockets/tcp-create-socket@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "create-tcp-socket"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "create-tcp-socket")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/tcp-create-socket@0.2.12#create-tcp-socket")]
                         fn wit_import1(_: i32, _: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                         unreachable!()
                     }
@@ -9597,13 +9730,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for ResolveAddressStream {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:sockets/ip-name-lookup@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]resolve-address-stream"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]resolve-address-stream")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/ip-name-lookup@0.2.12#[resource-drop]resolve-address-stream")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -9638,15 +9772,16 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 22],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(
                             wasm_import_module = "wasi:sockets/ip-name-lookup@0.2.12"
                         )]
                         unsafe extern "C" {
-                            #[link_name = "[method]resolve-address-stream.resolve-next-address"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]resolve-address-stream.resolve-next-address")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/ip-name-lookup@0.2.12#[method]resolve-address-stream.resolve-next-address")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -9731,15 +9866,16 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn subscribe(&self) -> Pollable {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(
                             wasm_import_module = "wasi:sockets/ip-name-lookup@0.2.12"
                         )]
                         unsafe extern "C" {
-                            #[link_name = "[method]resolve-address-stream.subscribe"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]resolve-address-stream.subscribe")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/ip-name-lookup@0.2.12#[method]resolve-address-stream.subscribe")]
                             fn wit_import0(_: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
@@ -9784,13 +9920,14 @@ pub mod wasi {
                     let ptr0 = vec0.as_ptr().cast::<u8>();
                     let len0 = vec0.len();
                     let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:sockets/ip-name-lookup@0.2.12")]
                     unsafe extern "C" {
-                        #[link_name = "resolve-addresses"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "resolve-addresses")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/ip-name-lookup@0.2.12#resolve-addresses")]
                         fn wit_import2(_: i32, _: *mut u8, _: usize, _: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import2(
                         _: i32,
                         _: *mut u8,
@@ -155,6 +155,13 @@
 //! - [`wasi::cli::command::export!`](crate::cli::command::export)
 //! - [`wasi::http::proxy::export!`](crate::http::proxy::export)
 //!
+//! ## Testing Natively
+//!
+//! Outside of wasm the imports of this crate panic when called. With the
+//! `mock-host` feature they instead call an in-memory host, `MockHost` in the
+//! `mock_host` module, so code built on this crate can be unit tested with a
+//! plain `cargo test`. The feature has no effect on wasm targets.
+//!
 //! [worlds]: https://component-model.bytecodealliance.org/design/worlds.html
 //! [`wasi:cli/command`]: https://github.com/WebAssembly/wasi-cli/
 //! [`wasi:http/proxy`]: https://github.com/WebAssembly/wasi-http
@@ -179,6 +186,8 @@
 extern crate std;
 
 pub mod ext;
+#[cfg(all(feature = "mock-host", not(target_arch = "wasm32")))]
+pub mod mock_host;
 
 // These modules are all auto-generated by `./ci/regenerate.sh`
 #[allow(unused_imports)]
@@ -0,0 +1,135 @@
+//! `wasi:cli`, apart from the `run` export.
+
+use super::io::{InputStream, OutputStream, Pipe};
+use super::{alloc_list, store, store_bytes, with, PTR};
+use std::cell::RefCell;
+use std::process;
+use std::rc::Rc;
+use std::string::String;
+use std::vec::Vec;
+
+pub(super) struct Cli {
+    pub(super) args: Vec<String>,
+    pub(super) env: Vec<(String, String)>,
+    pub(super) initial_cwd: Option<String>,
+    pub(super) stdin: Rc<RefCell<Pipe>>,
+    pub(super) stdout: Rc<RefCell<Pipe>>,
+    pub(super) stderr: Rc<RefCell<Pipe>>,
+}
+
+impl Default for Cli {
+    fn default() -> Cli {
+        // Stdin ends once everything pushed to it was read, rather than
+        // blocking forever.
+        let stdin = Pipe {
+            closed: true,
+            ..Pipe::default()
+        };
+        Cli {
+            args: Vec::new(),
+            env: Vec::new(),
+            initial_cwd: None,
+            stdin: Rc::new(RefCell::new(stdin)),
+            stdout: Rc::default(),
+            stderr: Rc::default(),
+        }
+    }
+}
+
+#[unsafe(export_name = symbol!("cli/environment", "get-environment"))]
+unsafe extern "C" fn get_environment(ret: *mut u8) {
+    with(|s| unsafe {
+        let env = &s.cli.env;
+        let list = alloc_list(env.len(), 4 * PTR, PTR);
+        for (i, (key, value)) in env.iter().enumerate() {
+            let elem = list.add(i * 4 * PTR);
+            store_bytes(elem, 0, key.as_bytes());
+            store_bytes(elem, 2 * PTR, value.as_bytes());
+        }
+        store(ret, 0, list);
+        store(ret, PTR, env.len());
+    })
+}
+
+#[unsafe(export_name = symbol!("cli/environment", "get-arguments"))]
+unsafe extern "C" fn get_arguments(ret: *mut u8) {
+    with(|s| unsafe {
+        let args = &s.cli.args;
+        let list = alloc_list(args.len(), 2 * PTR, PTR);
+        for (i, arg) in args.iter().enumerate() {
+            store_bytes(list.add(i * 2 * PTR), 0, arg.as_bytes());
+        }
+        store(ret, 0, list);
+        store(ret, PTR, args.len());
+    })
+}
+
+#[unsafe(export_name = symbol!("cli/environment", "initial-cwd"))]
+unsafe extern "C" fn initial_cwd(ret: *mut u8) {
+    with(|s| unsafe {
+        match &s.cli.initial_cwd {
+            Some(cwd) => {
+                store(ret, 0, 1u8);
+                store_bytes(ret, PTR, cwd.as_bytes());
+            }
+            None => store(ret, 0, 0u8),
+        }
+    })
+}
+
+#[unsafe(export_name = symbol!("cli/exit", "exit"))]
+unsafe extern "C" fn exit(status: i32) {
+    process::exit(status)
+}
+
+#[unsafe(export_name = symbol!("cli/exit", "exit-with-code"))]
+unsafe extern "C" fn exit_with_code(status_code: i32) {
+    process::exit(status_code & 0xff)
+}
+
+#[unsafe(export_name = symbol!("cli/stdin", "get-stdin"))]
+unsafe extern "C" fn get_stdin() -> i32 {
+    with(|s| {
+        let stream = InputStream::Pipe(s.cli.stdin.clone());
+        s.table.push(stream)
+    })
+}
+
+#[unsafe(export_name = symbol!("cli/stdout", "get-stdout"))]
+unsafe extern "C" fn get_stdout() -> i32 {
+    with(|s| {
+        let stream = OutputStream::Pipe(s.cli.stdout.clone());
+        s.table.push(stream)
+    })
+}
+
+#[unsafe(export_name = symbol!("cli/stderr", "get-stderr"))]
+unsafe extern "C" fn get_stderr() -> i32 {
+    with(|s| {
+        let stream = OutputStream::Pipe(s.cli.stderr.clone());
+        s.table.push(stream)
+    })
+}
+
+// There are no terminals, so no terminal resources are ever handed out.
+
+#[unsafe(export_name = symbol!("cli/terminal-input", "[resource-drop]terminal-input"))]
+unsafe extern "C" fn drop_terminal_input(_handle: i32) {}
+
+#[unsafe(export_name = symbol!("cli/terminal-output", "[resource-drop]terminal-output"))]
+unsafe extern "C" fn drop_terminal_output(_handle: i32) {}
+
+#[unsafe(export_name = symbol!("cli/terminal-stdin", "get-terminal-stdin"))]
+unsafe extern "C" fn get_terminal_stdin(ret: *mut u8) {
+    unsafe { store(ret, 0, 0u8) }
+}
+
+#[unsafe(export_name = symbol!("cli/terminal-stdout", "get-terminal-stdout"))]
+unsafe extern "C" fn get_terminal_stdout(ret: *mut u8) {
+    unsafe { store(ret, 0, 0u8) }
+}
+
+#[unsafe(export_name = symbol!("cli/terminal-stderr", "get-terminal-stderr"))]
+unsafe extern "C" fn get_terminal_stderr(ret: *mut u8) {
+    unsafe { store(ret, 0, 0u8) }
+}
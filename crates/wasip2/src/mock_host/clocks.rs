@@ -0,0 +1,67 @@
+//! `wasi:clocks`, with clocks which only move when told to.
+
+use super::io::Pollable;
+use super::{store, with};
+use std::time::Duration;
+
+#[derive(Default)]
+pub(super) struct Clocks {
+    /// The time of `monotonic-clock`, in nanoseconds.
+    pub(super) monotonic: u64,
+    /// The time of `wall-clock`, since the Unix epoch.
+    pub(super) wall: Duration,
+}
+
+impl Clocks {
+    pub(super) fn advance(&mut self, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        self.monotonic = self.monotonic.saturating_add(nanos);
+        self.wall = self.wall.saturating_add(duration);
+    }
+
+    /// Advances the clocks until `monotonic-clock` reaches `instant`.
+    pub(super) fn advance_to(&mut self, instant: u64) {
+        if let Some(nanos) = instant.checked_sub(self.monotonic) {
+            self.advance(Duration::from_nanos(nanos));
+        }
+    }
+}
+
+#[unsafe(export_name = symbol!("clocks/monotonic-clock", "now"))]
+unsafe extern "C" fn monotonic_now() -> i64 {
+    with(|s| s.clocks.monotonic as i64)
+}
+
+#[unsafe(export_name = symbol!("clocks/monotonic-clock", "resolution"))]
+unsafe extern "C" fn monotonic_resolution() -> i64 {
+    1
+}
+
+#[unsafe(export_name = symbol!("clocks/monotonic-clock", "subscribe-instant"))]
+unsafe extern "C" fn subscribe_instant(when: i64) -> i32 {
+    with(|s| s.table.push(Pollable::Clock(when as u64)))
+}
+
+#[unsafe(export_name = symbol!("clocks/monotonic-clock", "subscribe-duration"))]
+unsafe extern "C" fn subscribe_duration(when: i64) -> i32 {
+    with(|s| {
+        let deadline = s.clocks.monotonic.saturating_add(when as u64);
+        s.table.push(Pollable::Clock(deadline))
+    })
+}
+
+#[unsafe(export_name = symbol!("clocks/wall-clock", "now"))]
+unsafe extern "C" fn wall_now(ret: *mut u8) {
+    with(|s| unsafe {
+        store(ret, 0, s.clocks.wall.as_secs());
+        store(ret, 8, s.clocks.wall.subsec_nanos());
+    })
+}
+
+#[unsafe(export_name = symbol!("clocks/wall-clock", "resolution"))]
+unsafe extern "C" fn wall_resolution(ret: *mut u8) {
+    unsafe {
+        store(ret, 0, 0u64);
+        store(ret, 8, 1u32);
+    }
+}
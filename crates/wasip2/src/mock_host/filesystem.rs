@@ -0,0 +1,999 @@
+//! `wasi:filesystem`, backed by a tree of directories and files in memory.
+
+use super::io::{Error, InputStream, OutputStream};
+use super::{
+    alloc_list, drop_resource, store, store_bytes, store_result, string, with, Lower, PTR,
+};
+use crate::filesystem::types::{DescriptorFlags, DescriptorType, ErrorCode, OpenFlags};
+use std::borrow::ToOwned;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, VecDeque};
+use std::rc::Rc;
+use std::string::String;
+use std::time::Duration;
+use std::vec::Vec;
+
+pub(super) type NodeRef = Rc<RefCell<Node>>;
+
+pub(super) struct Node {
+    kind: Kind,
+    /// The number of directory entries referring to the node.
+    links: u64,
+    accessed: Duration,
+    modified: Duration,
+}
+
+enum Kind {
+    File(Vec<u8>),
+    Dir(BTreeMap<String, NodeRef>),
+}
+
+impl Node {
+    fn new(kind: Kind, now: Duration) -> NodeRef {
+        Rc::new(RefCell::new(Node {
+            kind,
+            links: 1,
+            accessed: now,
+            modified: now,
+        }))
+    }
+
+    fn is_dir(&self) -> bool {
+        matches!(self.kind, Kind::Dir(_))
+    }
+
+    fn entries(&self) -> Result<&BTreeMap<String, NodeRef>, ErrorCode> {
+        match &self.kind {
+            Kind::Dir(entries) => Ok(entries),
+            Kind::File(_) => Err(ErrorCode::NotDirectory),
+        }
+    }
+
+    fn entries_mut(&mut self) -> Result<&mut BTreeMap<String, NodeRef>, ErrorCode> {
+        match &mut self.kind {
+            Kind::Dir(entries) => Ok(entries),
+            Kind::File(_) => Err(ErrorCode::NotDirectory),
+        }
+    }
+
+    fn data_mut(&mut self) -> Result<&mut Vec<u8>, ErrorCode> {
+        match &mut self.kind {
+            Kind::File(data) => Ok(data),
+            Kind::Dir(_) => Err(ErrorCode::IsDirectory),
+        }
+    }
+
+    fn descriptor_type(&self) -> DescriptorType {
+        match self.kind {
+            Kind::File(_) => DescriptorType::RegularFile,
+            Kind::Dir(_) => DescriptorType::Directory,
+        }
+    }
+
+    fn size(&self) -> u64 {
+        match &self.kind {
+            Kind::File(data) => data.len() as u64,
+            Kind::Dir(_) => 0,
+        }
+    }
+
+    /// Returns up to `len` bytes at `offset` of a file, or `None` for a
+    /// directory.
+    pub(super) fn read_at(&self, offset: u64, len: usize) -> Option<Vec<u8>> {
+        match &self.kind {
+            Kind::File(data) => {
+                let start = usize::try_from(offset)
+                    .unwrap_or(usize::MAX)
+                    .min(data.len());
+                let end = start.saturating_add(len).min(data.len());
+                Some(data[start..end].to_vec())
+            }
+            Kind::Dir(_) => None,
+        }
+    }
+
+    pub(super) fn write_at(
+        &mut self,
+        offset: u64,
+        bytes: &[u8],
+        now: Duration,
+    ) -> Result<(), ErrorCode> {
+        let data = self.data_mut()?;
+        let start = usize::try_from(offset).map_err(|_| ErrorCode::FileTooLarge)?;
+        let end = start
+            .checked_add(bytes.len())
+            .ok_or(ErrorCode::FileTooLarge)?;
+        if data.len() < end {
+            data.resize(end, 0);
+        }
+        data[start..end].copy_from_slice(bytes);
+        self.modified = now;
+        Ok(())
+    }
+
+    pub(super) fn append(&mut self, bytes: &[u8], now: Duration) -> Result<(), ErrorCode> {
+        self.data_mut()?.extend_from_slice(bytes);
+        self.modified = now;
+        Ok(())
+    }
+
+    fn stat(&self) -> Stat {
+        Stat {
+            ty: self.descriptor_type(),
+            links: self.links,
+            size: self.size(),
+            accessed: self.accessed,
+            modified: self.modified,
+        }
+    }
+}
+
+/// A `descriptor-stat`.
+struct Stat {
+    ty: DescriptorType,
+    links: u64,
+    size: u64,
+    accessed: Duration,
+    modified: Duration,
+}
+
+impl Lower for Stat {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        unsafe {
+            store(ret, offset, self.ty as u8);
+            store(ret, offset + 8, self.links);
+            store(ret, offset + 16, self.size);
+            store_datetime(ret, offset + 24, Some(self.accessed));
+            store_datetime(ret, offset + 48, Some(self.modified));
+            store_datetime(ret, offset + 72, None);
+        }
+    }
+}
+
+/// Stores an `option<datetime>` at `offset` of `ret`.
+unsafe fn store_datetime(ret: *mut u8, offset: usize, time: Option<Duration>) {
+    unsafe {
+        match time {
+            Some(time) => {
+                store(ret, offset, 1u8);
+                store(ret, offset + 8, time.as_secs());
+                store(ret, offset + 16, time.subsec_nanos());
+            }
+            None => store(ret, offset, 0u8),
+        }
+    }
+}
+
+/// A `metadata-hash-value`.
+struct Hash(u64, u64);
+
+impl Lower for Hash {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        unsafe {
+            store(ret, offset, self.0);
+            store(ret, offset + 8, self.1);
+        }
+    }
+}
+
+impl Lower for Option<(DescriptorType, String)> {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        unsafe {
+            match self {
+                Some((ty, name)) => {
+                    store(ret, offset, 1u8);
+                    store(ret, offset + PTR, ty as u8);
+                    store_bytes(ret, offset + 2 * PTR, name.as_bytes());
+                }
+                None => store(ret, offset, 0u8),
+            }
+        }
+    }
+}
+
+pub(super) struct Fs {
+    root: NodeRef,
+    preopens: Vec<(NodeRef, String)>,
+}
+
+impl Default for Fs {
+    fn default() -> Fs {
+        Fs {
+            root: Node::new(Kind::Dir(BTreeMap::new()), Duration::ZERO),
+            preopens: Vec::new(),
+        }
+    }
+}
+
+/// Splits an absolute path given to [`MockHost`](super::MockHost).
+fn host_components(path: &str) -> impl Iterator<Item = &str> {
+    assert!(path.starts_with('/'), "`{}` isn't an absolute path", path);
+    path.split('/')
+        .filter(|c| !c.is_empty() && *c != ".")
+        .inspect(move |c| {
+            assert!(*c != "..", "`{}` contains `..`", path);
+        })
+}
+
+impl Fs {
+    pub(super) fn add_dir(&mut self, path: &str) -> NodeRef {
+        let mut dir = self.root.clone();
+        for name in host_components(path) {
+            let next = dir
+                .borrow_mut()
+                .entries_mut()
+                .unwrap_or_else(|_| panic!("a parent of `{}` is a file", path))
+                .entry(name.to_owned())
+                .or_insert_with(|| Node::new(Kind::Dir(BTreeMap::new()), Duration::ZERO))
+                .clone();
+            dir = next;
+        }
+        assert!(dir.borrow().is_dir(), "`{}` is a file", path);
+        dir
+    }
+
+    pub(super) fn add_file(&mut self, path: &str, contents: &[u8]) {
+        let (parent, name) = path.rsplit_once('/').expect("paths are absolute");
+        assert!(
+            !name.is_empty() && name != "." && name != "..",
+            "`{}` isn't a file path",
+            path
+        );
+        let parent = self.add_dir(if parent.is_empty() { "/" } else { parent });
+        let mut parent = parent.borrow_mut();
+        let entries = parent.entries_mut().unwrap();
+        match entries.get(name) {
+            Some(node) => match &mut node.borrow_mut().kind {
+                Kind::File(data) => *data = contents.to_vec(),
+                Kind::Dir(_) => panic!("`{}` is a directory", path),
+            },
+            None => {
+                let node = Node::new(Kind::File(contents.to_vec()), Duration::ZERO);
+                entries.insert(name.to_owned(), node);
+            }
+        }
+    }
+
+    pub(super) fn file(&self, path: &str) -> Option<Vec<u8>> {
+        let mut node = self.root.clone();
+        for name in host_components(path) {
+            let next = node.borrow().entries().ok()?.get(name)?.clone();
+            node = next;
+        }
+        let node = node.borrow();
+        match &node.kind {
+            Kind::File(data) => Some(data.clone()),
+            Kind::Dir(_) => None,
+        }
+    }
+
+    pub(super) fn preopen(&mut self, path: &str) {
+        let dir = self.add_dir(path);
+        self.preopens.push((dir, path.to_owned()));
+    }
+}
+
+/// Where a path leads, relative to a directory.
+enum Target {
+    /// A directory which the path names directly, such as `.`.
+    Dir(NodeRef),
+    /// The entry `name` of a directory, which may not exist.
+    Entry(NodeRef, String),
+}
+
+/// Resolves `path` relative to `base`, which it can't leave.
+fn resolve(base: &NodeRef, path: &str) -> Result<Target, ErrorCode> {
+    if path.is_empty() {
+        return Err(ErrorCode::NoEntry);
+    }
+    if path.starts_with('/') {
+        return Err(ErrorCode::NotPermitted);
+    }
+    let components = path
+        .split('/')
+        .filter(|c| !c.is_empty() && *c != ".")
+        .collect::<Vec<_>>();
+    let mut dirs = std::vec![base.clone()];
+    let (last, parents) = match components.split_last() {
+        Some(split) => split,
+        None => return Ok(Target::Dir(base.clone())),
+    };
+    for name in parents {
+        if *name == ".." {
+            if dirs.len() == 1 {
+                return Err(ErrorCode::NotPermitted);
+            }
+            dirs.pop();
+            continue;
+        }
+        let dir = dirs.last().unwrap().borrow().entries()?.get(*name).cloned();
+        let dir = dir.ok_or(ErrorCode::NoEntry)?;
+        if !dir.borrow().is_dir() {
+            return Err(ErrorCode::NotDirectory);
+        }
+        dirs.push(dir);
+    }
+    if *last == ".." {
+        if dirs.len() == 1 {
+            return Err(ErrorCode::NotPermitted);
+        }
+        dirs.pop();
+        return Ok(Target::Dir(dirs.pop().unwrap()));
+    }
+    Ok(Target::Entry(dirs.pop().unwrap(), (*last).to_owned()))
+}
+
+/// Returns the node `path` leads to relative to `base`.
+fn lookup(base: &NodeRef, path: &str) -> Result<NodeRef, ErrorCode> {
+    match resolve(base, path)? {
+        Target::Dir(dir) => Ok(dir),
+        Target::Entry(dir, name) => {
+            let node = dir.borrow().entries()?.get(&name).cloned();
+            node.ok_or(ErrorCode::NoEntry)
+        }
+    }
+}
+
+/// Returns whether `node` is `dir` or one of its ancestors.
+fn contains(node: &NodeRef, dir: &NodeRef) -> bool {
+    if Rc::ptr_eq(node, dir) {
+        return true;
+    }
+    match &node.borrow().kind {
+        Kind::Dir(entries) => entries.values().any(|entry| contains(entry, dir)),
+        Kind::File(_) => false,
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct Descriptor {
+    node: NodeRef,
+    flags: DescriptorFlags,
+}
+
+impl Descriptor {
+    fn has(&self, flag: DescriptorFlags) -> bool {
+        self.flags.bits() & flag.bits() != 0
+    }
+
+    fn readable(&self) -> Result<&NodeRef, ErrorCode> {
+        if !self.has(DescriptorFlags::READ) {
+            return Err(ErrorCode::BadDescriptor);
+        }
+        Ok(&self.node)
+    }
+
+    fn writable(&self) -> Result<&NodeRef, ErrorCode> {
+        if !self.has(DescriptorFlags::WRITE) {
+            return Err(ErrorCode::BadDescriptor);
+        }
+        Ok(&self.node)
+    }
+
+    /// Returns the directory, if its entries may be changed.
+    fn mutable(&self) -> Result<&NodeRef, ErrorCode> {
+        if !self.has(DescriptorFlags::MUTATE_DIRECTORY) {
+            return Err(ErrorCode::ReadOnly);
+        }
+        Ok(&self.node)
+    }
+
+    fn open_at(
+        &self,
+        path: &str,
+        open_flags: OpenFlags,
+        flags: DescriptorFlags,
+        now: Duration,
+    ) -> Result<Descriptor, ErrorCode> {
+        let open = |flag: OpenFlags| open_flags.bits() & flag.bits() != 0;
+        let wants = |flag: DescriptorFlags| flags.bits() & flag.bits() != 0;
+        if wants(DescriptorFlags::WRITE) || wants(DescriptorFlags::MUTATE_DIRECTORY) {
+            self.mutable()?;
+        }
+        let exclusive = open(OpenFlags::CREATE) && open(OpenFlags::EXCLUSIVE);
+        let node = match resolve(&self.node, path)? {
+            Target::Dir(_) if exclusive => return Err(ErrorCode::Exist),
+            Target::Dir(dir) => dir,
+            Target::Entry(dir, name) => {
+                let node = dir.borrow().entries()?.get(&name).cloned();
+                match node {
+                    Some(_) if exclusive => return Err(ErrorCode::Exist),
+                    Some(node) => node,
+                    None if open(OpenFlags::CREATE) => {
+                        if open(OpenFlags::DIRECTORY) {
+                            return Err(ErrorCode::Invalid);
+                        }
+                        self.mutable()?;
+                        let node = Node::new(Kind::File(Vec::new()), now);
+                        dir.borrow_mut().entries_mut()?.insert(name, node.clone());
+                        node
+                    }
+                    None => return Err(ErrorCode::NoEntry),
+                }
+            }
+        };
+        let is_dir = node.borrow().is_dir();
+        if open(OpenFlags::DIRECTORY) && !is_dir {
+            return Err(ErrorCode::NotDirectory);
+        }
+        if is_dir && wants(DescriptorFlags::WRITE) {
+            return Err(ErrorCode::IsDirectory);
+        }
+        if open(OpenFlags::TRUNCATE) {
+            if !wants(DescriptorFlags::WRITE) {
+                return Err(ErrorCode::Invalid);
+            }
+            let mut node = node.borrow_mut();
+            node.data_mut()?.clear();
+            node.modified = now;
+        }
+        let flags = if is_dir {
+            flags
+        } else {
+            // Only directories can be mutable.
+            DescriptorFlags::from_bits_retain(
+                flags.bits() & !DescriptorFlags::MUTATE_DIRECTORY.bits(),
+            )
+        };
+        Ok(Descriptor { node, flags })
+    }
+}
+
+pub(super) struct DirectoryEntryStream(VecDeque<(DescriptorType, String)>);
+
+/// Returns the time a `new-timestamp` passed as `tag`, `seconds` and
+/// `nanoseconds` sets, if any.
+fn new_timestamp(tag: i32, seconds: i64, nanoseconds: i32, now: Duration) -> Option<Duration> {
+    match tag {
+        0 => None,
+        1 => Some(now),
+        _ => Some(Duration::new(seconds as u64, nanoseconds as u32)),
+    }
+}
+
+fn set_times(node: &NodeRef, accessed: Option<Duration>, modified: Option<Duration>) {
+    let mut node = node.borrow_mut();
+    if let Some(accessed) = accessed {
+        node.accessed = accessed;
+    }
+    if let Some(modified) = modified {
+        node.modified = modified;
+    }
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[resource-drop]descriptor"))]
+unsafe extern "C" fn drop_descriptor(handle: i32) {
+    drop_resource::<Descriptor>(handle);
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[resource-drop]directory-entry-stream"))]
+unsafe extern "C" fn drop_directory_entry_stream(handle: i32) {
+    drop_resource::<DirectoryEntryStream>(handle);
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.read-via-stream"))]
+unsafe extern "C" fn descriptor_read_via_stream(handle: i32, offset: i64, ret: *mut u8) {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        let result = d.readable().and_then(|node| {
+            if node.borrow().is_dir() {
+                return Err(ErrorCode::IsDirectory);
+            }
+            Ok(InputStream::File {
+                node: node.clone(),
+                offset: offset as u64,
+            })
+        });
+        store_result(ret, 4, result.map(|stream| s.table.push(stream)));
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.write-via-stream"))]
+unsafe extern "C" fn descriptor_write_via_stream(handle: i32, offset: i64, ret: *mut u8) {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        let result = d.writable().map(|node| OutputStream::File {
+            node: node.clone(),
+            offset: Some(offset as u64),
+        });
+        store_result(ret, 4, result.map(|stream| s.table.push(stream)));
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.append-via-stream"))]
+unsafe extern "C" fn descriptor_append_via_stream(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        let result = d.writable().map(|node| OutputStream::File {
+            node: node.clone(),
+            offset: None,
+        });
+        store_result(ret, 4, result.map(|stream| s.table.push(stream)));
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.advise"))]
+unsafe extern "C" fn descriptor_advise(
+    handle: i32,
+    _offset: i64,
+    _len: i64,
+    _advice: i32,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        s.table.get::<Descriptor>(handle);
+        store_result(ret, 1, Ok::<(), ErrorCode>(()));
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.sync-data"))]
+unsafe extern "C" fn descriptor_sync_data(handle: i32, ret: *mut u8) {
+    unsafe { descriptor_sync(handle, ret) }
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.get-flags"))]
+unsafe extern "C" fn descriptor_get_flags(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        store_result(ret, 1, Ok::<u8, ErrorCode>(d.flags.bits()));
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.get-type"))]
+unsafe extern "C" fn descriptor_get_type(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        let ty = d.node.borrow().descriptor_type();
+        store_result(ret, 1, Ok::<u8, ErrorCode>(ty as u8));
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.set-size"))]
+unsafe extern "C" fn descriptor_set_size(handle: i32, size: i64, ret: *mut u8) {
+    with(|s| unsafe {
+        let now = s.clocks.wall;
+        let d = s.table.get::<Descriptor>(handle);
+        let result = d.writable().and_then(|node| {
+            let mut node = node.borrow_mut();
+            let size = usize::try_from(size as u64).map_err(|_| ErrorCode::FileTooLarge)?;
+            node.data_mut()?.resize(size, 0);
+            node.modified = now;
+            Ok(())
+        });
+        store_result(ret, 1, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.set-times"))]
+unsafe extern "C" fn descriptor_set_times(
+    handle: i32,
+    accessed_tag: i32,
+    accessed_seconds: i64,
+    accessed_nanoseconds: i32,
+    modified_tag: i32,
+    modified_seconds: i64,
+    modified_nanoseconds: i32,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let now = s.clocks.wall;
+        let d = s.table.get::<Descriptor>(handle);
+        set_times(
+            &d.node,
+            new_timestamp(accessed_tag, accessed_seconds, accessed_nanoseconds, now),
+            new_timestamp(modified_tag, modified_seconds, modified_nanoseconds, now),
+        );
+        store_result(ret, 1, Ok::<(), ErrorCode>(()));
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.read"))]
+unsafe extern "C" fn descriptor_read(handle: i32, len: i64, offset: i64, ret: *mut u8) {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        let result = d.readable().and_then(|node| {
+            let node = node.borrow();
+            let len = usize::try_from(len as u64).unwrap_or(usize::MAX);
+            let bytes = node
+                .read_at(offset as u64, len)
+                .ok_or(ErrorCode::IsDirectory)?;
+            let eof = (offset as u64).saturating_add(bytes.len() as u64) >= node.size();
+            Ok((bytes, eof))
+        });
+        store_result(ret, PTR, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.write"))]
+unsafe extern "C" fn descriptor_write(
+    handle: i32,
+    ptr: *const u8,
+    len: usize,
+    offset: i64,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let now = s.clocks.wall;
+        let d = s.table.get::<Descriptor>(handle);
+        let bytes = super::bytes(ptr, len);
+        let result = d.writable().and_then(|node| {
+            node.borrow_mut().write_at(offset as u64, bytes, now)?;
+            Ok(bytes.len() as u64)
+        });
+        store_result(ret, 8, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.read-directory"))]
+unsafe extern "C" fn descriptor_read_directory(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        let result = d.node.borrow().entries().map(|entries| {
+            let entries = entries
+                .iter()
+                .map(|(name, node)| (node.borrow().descriptor_type(), name.clone()))
+                .collect();
+            DirectoryEntryStream(entries)
+        });
+        store_result(ret, 4, result.map(|stream| s.table.push(stream)));
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.sync"))]
+unsafe extern "C" fn descriptor_sync(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        s.table.get::<Descriptor>(handle);
+        store_result(ret, 1, Ok::<(), ErrorCode>(()));
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.create-directory-at"))]
+unsafe extern "C" fn descriptor_create_directory_at(
+    handle: i32,
+    ptr: *const u8,
+    len: usize,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let now = s.clocks.wall;
+        let d = s.table.get::<Descriptor>(handle);
+        let result = d
+            .mutable()
+            .and_then(|base| match resolve(base, string(ptr, len))? {
+                Target::Dir(_) => Err(ErrorCode::Exist),
+                Target::Entry(dir, name) => {
+                    let mut dir = dir.borrow_mut();
+                    let entries = dir.entries_mut()?;
+                    if entries.contains_key(&name) {
+                        return Err(ErrorCode::Exist);
+                    }
+                    entries.insert(name, Node::new(Kind::Dir(BTreeMap::new()), now));
+                    Ok(())
+                }
+            });
+        store_result(ret, 1, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.stat"))]
+unsafe extern "C" fn descriptor_stat(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        let stat = d.node.borrow().stat();
+        store_result(ret, 8, Ok::<Stat, ErrorCode>(stat));
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.stat-at"))]
+unsafe extern "C" fn descriptor_stat_at(
+    handle: i32,
+    _path_flags: i32,
+    ptr: *const u8,
+    len: usize,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        let result = lookup(&d.node, string(ptr, len)).map(|node| node.borrow().stat());
+        store_result(ret, 8, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.set-times-at"))]
+unsafe extern "C" fn descriptor_set_times_at(
+    handle: i32,
+    _path_flags: i32,
+    ptr: *const u8,
+    len: usize,
+    accessed_tag: i32,
+    accessed_seconds: i64,
+    accessed_nanoseconds: i32,
+    modified_tag: i32,
+    modified_seconds: i64,
+    modified_nanoseconds: i32,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let now = s.clocks.wall;
+        let d = s.table.get::<Descriptor>(handle);
+        let result = lookup(&d.node, string(ptr, len)).map(|node| {
+            set_times(
+                &node,
+                new_timestamp(accessed_tag, accessed_seconds, accessed_nanoseconds, now),
+                new_timestamp(modified_tag, modified_seconds, modified_nanoseconds, now),
+            )
+        });
+        store_result(ret, 1, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.link-at"))]
+unsafe extern "C" fn descriptor_link_at(
+    handle: i32,
+    _old_path_flags: i32,
+    old_ptr: *const u8,
+    old_len: usize,
+    new_handle: i32,
+    new_ptr: *const u8,
+    new_len: usize,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let old = s.table.get::<Descriptor>(handle).clone();
+        let new = s.table.get::<Descriptor>(new_handle);
+        let result = new.mutable().and_then(|new_base| {
+            let node = lookup(&old.node, string(old_ptr, old_len))?;
+            if node.borrow().is_dir() {
+                return Err(ErrorCode::NotPermitted);
+            }
+            match resolve(new_base, string(new_ptr, new_len))? {
+                Target::Dir(_) => Err(ErrorCode::Exist),
+                Target::Entry(dir, name) => {
+                    let mut dir = dir.borrow_mut();
+                    let entries = dir.entries_mut()?;
+                    if entries.contains_key(&name) {
+                        return Err(ErrorCode::Exist);
+                    }
+                    node.borrow_mut().links += 1;
+                    entries.insert(name, node);
+                    Ok(())
+                }
+            }
+        });
+        store_result(ret, 1, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.open-at"))]
+unsafe extern "C" fn descriptor_open_at(
+    handle: i32,
+    _path_flags: i32,
+    ptr: *const u8,
+    len: usize,
+    open_flags: i32,
+    flags: i32,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let now = s.clocks.wall;
+        let d = s.table.get::<Descriptor>(handle);
+        let result = d.open_at(
+            string(ptr, len),
+            OpenFlags::from_bits_retain(open_flags as u8),
+            DescriptorFlags::from_bits_retain(flags as u8),
+            now,
+        );
+        store_result(ret, 4, result.map(|d| s.table.push(d)));
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.readlink-at"))]
+unsafe extern "C" fn descriptor_readlink_at(handle: i32, ptr: *const u8, len: usize, ret: *mut u8) {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        // There are no symbolic links to read.
+        let result = lookup(&d.node, string(ptr, len)).and(Err::<String, _>(ErrorCode::Invalid));
+        store_result(ret, PTR, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.remove-directory-at"))]
+unsafe extern "C" fn descriptor_remove_directory_at(
+    handle: i32,
+    ptr: *const u8,
+    len: usize,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        let result = d
+            .mutable()
+            .and_then(|base| match resolve(base, string(ptr, len))? {
+                Target::Dir(_) => Err(ErrorCode::Invalid),
+                Target::Entry(dir, name) => {
+                    let mut dir = dir.borrow_mut();
+                    let entries = dir.entries_mut()?;
+                    let node = entries.get(&name).ok_or(ErrorCode::NoEntry)?;
+                    if !node.borrow().entries()?.is_empty() {
+                        return Err(ErrorCode::NotEmpty);
+                    }
+                    let node = entries.remove(&name).unwrap();
+                    node.borrow_mut().links -= 1;
+                    Ok(())
+                }
+            });
+        store_result(ret, 1, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.rename-at"))]
+unsafe extern "C" fn descriptor_rename_at(
+    handle: i32,
+    old_ptr: *const u8,
+    old_len: usize,
+    new_handle: i32,
+    new_ptr: *const u8,
+    new_len: usize,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let old = s.table.get::<Descriptor>(handle).clone();
+        let new = s.table.get::<Descriptor>(new_handle);
+        let result = (|| {
+            let (old_dir, old_name) = match resolve(old.mutable()?, string(old_ptr, old_len))? {
+                Target::Entry(dir, name) => (dir, name),
+                Target::Dir(_) => return Err(ErrorCode::Invalid),
+            };
+            let (new_dir, new_name) = match resolve(new.mutable()?, string(new_ptr, new_len))? {
+                Target::Entry(dir, name) => (dir, name),
+                Target::Dir(_) => return Err(ErrorCode::Invalid),
+            };
+            let node = old_dir.borrow().entries()?.get(&old_name).cloned();
+            let node = node.ok_or(ErrorCode::NoEntry)?;
+            if Rc::ptr_eq(&old_dir, &new_dir) && old_name == new_name {
+                return Ok(());
+            }
+            let is_dir = node.borrow().is_dir();
+            if is_dir && contains(&node, &new_dir) {
+                return Err(ErrorCode::Invalid);
+            }
+            let replaced = new_dir.borrow().entries()?.get(&new_name).cloned();
+            if let Some(replaced) = &replaced {
+                let replaced = replaced.borrow();
+                match (is_dir, replaced.entries()) {
+                    (false, Ok(_)) => return Err(ErrorCode::IsDirectory),
+                    (true, Err(_)) => return Err(ErrorCode::NotDirectory),
+                    (true, Ok(entries)) if !entries.is_empty() => return Err(ErrorCode::NotEmpty),
+                    _ => {}
+                }
+            }
+            if let Some(replaced) = replaced {
+                replaced.borrow_mut().links -= 1;
+            }
+            old_dir.borrow_mut().entries_mut()?.remove(&old_name);
+            new_dir.borrow_mut().entries_mut()?.insert(new_name, node);
+            Ok(())
+        })();
+        store_result(ret, 1, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.symlink-at"))]
+unsafe extern "C" fn descriptor_symlink_at(
+    handle: i32,
+    _old_ptr: *const u8,
+    _old_len: usize,
+    _new_ptr: *const u8,
+    _new_len: usize,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        s.table.get::<Descriptor>(handle);
+        store_result(ret, 1, Err::<(), ErrorCode>(ErrorCode::Unsupported));
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.unlink-file-at"))]
+unsafe extern "C" fn descriptor_unlink_file_at(
+    handle: i32,
+    ptr: *const u8,
+    len: usize,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        let result = d
+            .mutable()
+            .and_then(|base| match resolve(base, string(ptr, len))? {
+                Target::Dir(_) => Err(ErrorCode::IsDirectory),
+                Target::Entry(dir, name) => {
+                    let mut dir = dir.borrow_mut();
+                    let entries = dir.entries_mut()?;
+                    let node = entries.get(&name).ok_or(ErrorCode::NoEntry)?;
+                    if node.borrow().is_dir() {
+                        return Err(ErrorCode::IsDirectory);
+                    }
+                    let node = entries.remove(&name).unwrap();
+                    node.borrow_mut().links -= 1;
+                    Ok(())
+                }
+            });
+        store_result(ret, 1, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.is-same-object"))]
+unsafe extern "C" fn descriptor_is_same_object(handle: i32, other: i32) -> i32 {
+    with(|s| {
+        let node = s.table.get::<Descriptor>(handle).node.clone();
+        let other = &s.table.get::<Descriptor>(other).node;
+        Rc::ptr_eq(&node, other) as i32
+    })
+}
+
+fn metadata_hash(node: &NodeRef) -> Hash {
+    let modified = node.borrow().modified;
+    Hash(Rc::as_ptr(node) as usize as u64, modified.as_nanos() as u64)
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.metadata-hash"))]
+unsafe extern "C" fn descriptor_metadata_hash(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        store_result(ret, 8, Ok::<Hash, ErrorCode>(metadata_hash(&d.node)));
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.metadata-hash-at"))]
+unsafe extern "C" fn descriptor_metadata_hash_at(
+    handle: i32,
+    _path_flags: i32,
+    ptr: *const u8,
+    len: usize,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        let result = lookup(&d.node, string(ptr, len)).map(|node| metadata_hash(&node));
+        store_result(ret, 8, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]directory-entry-stream.read-directory-entry"))]
+unsafe extern "C" fn directory_entry_stream_read_directory_entry(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let entry = s.table.get::<DirectoryEntryStream>(handle).0.pop_front();
+        store_result(ret, PTR, Ok::<_, ErrorCode>(entry));
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "filesystem-error-code"))]
+unsafe extern "C" fn filesystem_error_code(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        match s.table.get::<Error>(handle).code {
+            Some(code) => {
+                store(ret, 0, 1u8);
+                store(ret, 1, code as u8);
+            }
+            None => store(ret, 0, 0u8),
+        }
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/preopens", "get-directories"))]
+unsafe extern "C" fn get_directories(ret: *mut u8) {
+    with(|s| unsafe {
+        let list = alloc_list(s.fs.preopens.len(), 3 * PTR, PTR);
+        for (i, (dir, path)) in s.fs.preopens.iter().enumerate() {
+            let descriptor = Descriptor {
+                node: dir.clone(),
+                flags: DescriptorFlags::READ | DescriptorFlags::MUTATE_DIRECTORY,
+            };
+            let elem = list.add(i * 3 * PTR);
+            store(elem, 0, s.table.push(descriptor));
+            store_bytes(elem, PTR, path.as_bytes());
+        }
+        store(ret, 0, list);
+        store(ret, PTR, s.fs.preopens.len());
+    })
+}
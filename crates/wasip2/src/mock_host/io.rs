@@ -0,0 +1,441 @@
+//! `wasi:io`, for the streams and pollables of the other interfaces.
+
+use super::clocks::Clocks;
+use super::filesystem::NodeRef;
+use super::sockets::{Tcp, Udp};
+use super::{alloc_list, bytes, drop_resource, store, store_bytes, with, Lower, State, PTR};
+use crate::filesystem::types::ErrorCode;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::string::String;
+use std::vec::Vec;
+
+/// How many bytes `check-write` allows to be written at once.
+const WRITE_BUDGET: u64 = 64 * 1024;
+
+pub(super) struct Error {
+    pub(super) message: String,
+    /// The code returned by `filesystem-error-code`, for errors of file
+    /// streams.
+    pub(super) code: Option<ErrorCode>,
+}
+
+/// Bytes in flight from an output stream to an input stream.
+#[derive(Default)]
+pub(super) struct Pipe {
+    pub(super) buf: VecDeque<u8>,
+    /// Whether the writing side is done, after which reading reports the end
+    /// of the stream once `buf` is empty.
+    pub(super) closed: bool,
+    /// Whether the reading side is gone, after which writing fails.
+    pub(super) reader_gone: bool,
+}
+
+pub(super) enum Pollable {
+    Ready,
+    /// Ready once `monotonic-clock` reaches the instant.
+    Clock(u64),
+    /// Ready once the pipe has bytes to read or is closed.
+    Readable(Rc<RefCell<Pipe>>),
+    /// Ready once the listening socket has connections to accept.
+    Accept(Rc<RefCell<Tcp>>),
+    /// Ready once the socket has datagrams to receive.
+    Datagrams(Rc<RefCell<Udp>>),
+}
+
+impl Pollable {
+    fn ready(&self, clocks: &Clocks) -> bool {
+        match self {
+            Pollable::Ready => true,
+            Pollable::Clock(instant) => clocks.monotonic >= *instant,
+            Pollable::Readable(pipe) => {
+                let pipe = pipe.borrow();
+                !pipe.buf.is_empty() || pipe.closed
+            }
+            Pollable::Accept(socket) => socket.borrow().has_pending_connections(),
+            Pollable::Datagrams(socket) => socket.borrow().has_datagrams(),
+        }
+    }
+}
+
+pub(super) enum InputStream {
+    Pipe(Rc<RefCell<Pipe>>),
+    File { node: NodeRef, offset: u64 },
+}
+
+pub(super) enum OutputStream {
+    Pipe(Rc<RefCell<Pipe>>),
+    /// Writes at `offset`, or appends if it's `None`.
+    File {
+        node: NodeRef,
+        offset: Option<u64>,
+    },
+}
+
+pub(super) enum StreamError {
+    Failed(Error),
+    Closed,
+}
+
+impl StreamError {
+    fn failed(message: &str) -> StreamError {
+        StreamError::Failed(Error {
+            message: message.into(),
+            code: None,
+        })
+    }
+}
+
+/// A [`StreamError`] whose error was moved to the table.
+enum LoweredStreamError {
+    Failed(i32),
+    Closed,
+}
+
+impl Lower for LoweredStreamError {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        unsafe {
+            match self {
+                LoweredStreamError::Failed(error) => {
+                    store(ret, offset, 0u8);
+                    store(ret, offset + 4, error);
+                }
+                LoweredStreamError::Closed => store(ret, offset, 1u8),
+            }
+        }
+    }
+}
+
+/// Stores the result of a stream operation, with the payload at `offset`.
+unsafe fn store_stream_result<T: Lower>(
+    s: &mut State,
+    ret: *mut u8,
+    offset: usize,
+    result: Result<T, StreamError>,
+) {
+    let result = result.map_err(|e| match e {
+        StreamError::Failed(error) => LoweredStreamError::Failed(s.table.push(error)),
+        StreamError::Closed => LoweredStreamError::Closed,
+    });
+    unsafe { super::store_result(ret, offset, result) }
+}
+
+impl InputStream {
+    fn ready(&self) -> bool {
+        match self {
+            InputStream::Pipe(pipe) => {
+                let pipe = pipe.borrow();
+                !pipe.buf.is_empty() || pipe.closed
+            }
+            InputStream::File { .. } => true,
+        }
+    }
+
+    fn read(&mut self, len: u64) -> Result<Vec<u8>, StreamError> {
+        let len = usize::try_from(len).unwrap_or(usize::MAX);
+        match self {
+            InputStream::Pipe(pipe) => {
+                let mut pipe = pipe.borrow_mut();
+                if pipe.buf.is_empty() && pipe.closed {
+                    return Err(StreamError::Closed);
+                }
+                let n = len.min(pipe.buf.len());
+                Ok(pipe.buf.drain(..n).collect())
+            }
+            InputStream::File { node, offset } => {
+                let bytes = node.borrow().read_at(*offset, len);
+                match bytes {
+                    Some(bytes) if !bytes.is_empty() || len == 0 => {
+                        *offset += bytes.len() as u64;
+                        Ok(bytes)
+                    }
+                    _ => Err(StreamError::Closed),
+                }
+            }
+        }
+    }
+
+    fn blocking_read(&mut self, len: u64) -> Result<Vec<u8>, StreamError> {
+        if !self.ready() {
+            would_block_forever("input-stream");
+        }
+        self.read(len)
+    }
+}
+
+impl OutputStream {
+    fn check_write(&self) -> Result<u64, StreamError> {
+        match self {
+            OutputStream::Pipe(pipe) if pipe.borrow().reader_gone => Err(StreamError::failed(
+                "the reading side of the stream is gone",
+            )),
+            _ => Ok(WRITE_BUDGET),
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8], clocks: &Clocks) -> Result<(), StreamError> {
+        self.check_write()?;
+        match self {
+            OutputStream::Pipe(pipe) => pipe.borrow_mut().buf.extend(bytes),
+            OutputStream::File { node, offset } => {
+                let mut node = node.borrow_mut();
+                let result = match offset {
+                    Some(offset) => node.write_at(*offset, bytes, clocks.wall).map(|()| {
+                        *offset += bytes.len() as u64;
+                    }),
+                    None => node.append(bytes, clocks.wall),
+                };
+                result.map_err(|code| {
+                    StreamError::Failed(Error {
+                        message: std::format!("failed to write to file: {:?}", code),
+                        code: Some(code),
+                    })
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Panics because blocking on `what` would hang, since only the blocked
+/// thread could make it ready.
+fn would_block_forever(what: &str) -> ! {
+    panic!(
+        "blocking on this {} would never return, since nothing else can make it ready",
+        what
+    )
+}
+
+/// Returns the indices of the pollables in `handles` which are ready, first
+/// advancing the clocks to the earliest deadline if none are.
+fn poll(s: &mut State, handles: &[i32]) -> Vec<u32> {
+    for _ in 0..2 {
+        let mut ready = Vec::new();
+        let mut deadline = None::<u64>;
+        for (i, handle) in handles.iter().enumerate() {
+            let pollable = s.table.get::<Pollable>(*handle);
+            if pollable.ready(&s.clocks) {
+                ready.push(i as u32);
+            } else if let Pollable::Clock(instant) = pollable {
+                deadline = Some(deadline.map_or(*instant, |d| d.min(*instant)));
+            }
+        }
+        if !ready.is_empty() {
+            return ready;
+        }
+        match deadline {
+            Some(instant) => s.clocks.advance_to(instant),
+            None => break,
+        }
+    }
+    would_block_forever("pollable")
+}
+
+#[unsafe(export_name = symbol!("io/error", "[resource-drop]error"))]
+unsafe extern "C" fn drop_error(handle: i32) {
+    drop_resource::<Error>(handle);
+}
+
+#[unsafe(export_name = symbol!("io/error", "[method]error.to-debug-string"))]
+unsafe extern "C" fn error_to_debug_string(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let error = s.table.get::<Error>(handle);
+        store_bytes(ret, 0, error.message.as_bytes());
+    })
+}
+
+#[unsafe(export_name = symbol!("io/poll", "[resource-drop]pollable"))]
+unsafe extern "C" fn drop_pollable(handle: i32) {
+    drop_resource::<Pollable>(handle);
+}
+
+#[unsafe(export_name = symbol!("io/poll", "[method]pollable.ready"))]
+unsafe extern "C" fn pollable_ready(handle: i32) -> i32 {
+    with(|s| s.table.get::<Pollable>(handle).ready(&s.clocks) as i32)
+}
+
+#[unsafe(export_name = symbol!("io/poll", "[method]pollable.block"))]
+unsafe extern "C" fn pollable_block(handle: i32) {
+    with(|s| {
+        poll(s, &[handle]);
+    })
+}
+
+#[unsafe(export_name = symbol!("io/poll", "poll"))]
+unsafe extern "C" fn poll_list(ptr: *const u8, len: usize, ret: *mut u8) {
+    with(|s| unsafe {
+        let handles = (0..len)
+            .map(|i| super::load(ptr, i * 4))
+            .collect::<Vec<i32>>();
+        let ready = poll(s, &handles);
+        let list = alloc_list(ready.len(), 4, 4);
+        for (i, index) in ready.iter().enumerate() {
+            store(list, i * 4, *index);
+        }
+        store(ret, 0, list);
+        store(ret, PTR, ready.len());
+    })
+}
+
+#[unsafe(export_name = symbol!("io/streams", "[resource-drop]input-stream"))]
+unsafe extern "C" fn drop_input_stream(handle: i32) {
+    if let Some(InputStream::Pipe(pipe)) = drop_resource::<InputStream>(handle) {
+        pipe.borrow_mut().reader_gone = true;
+    }
+}
+
+#[unsafe(export_name = symbol!("io/streams", "[resource-drop]output-stream"))]
+unsafe extern "C" fn drop_output_stream(handle: i32) {
+    if let Some(OutputStream::Pipe(pipe)) = drop_resource::<OutputStream>(handle) {
+        pipe.borrow_mut().closed = true;
+    }
+}
+
+#[unsafe(export_name = symbol!("io/streams", "[method]input-stream.read"))]
+unsafe extern "C" fn input_stream_read(handle: i32, len: i64, ret: *mut u8) {
+    with(|s| unsafe {
+        let result = s.table.get::<InputStream>(handle).read(len as u64);
+        store_stream_result(s, ret, PTR, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("io/streams", "[method]input-stream.blocking-read"))]
+unsafe extern "C" fn input_stream_blocking_read(handle: i32, len: i64, ret: *mut u8) {
+    with(|s| unsafe {
+        let result = s.table.get::<InputStream>(handle).blocking_read(len as u64);
+        store_stream_result(s, ret, PTR, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("io/streams", "[method]input-stream.skip"))]
+unsafe extern "C" fn input_stream_skip(handle: i32, len: i64, ret: *mut u8) {
+    with(|s| unsafe {
+        let result = s.table.get::<InputStream>(handle).read(len as u64);
+        store_stream_result(s, ret, 8, result.map(|bytes| bytes.len() as u64));
+    })
+}
+
+#[unsafe(export_name = symbol!("io/streams", "[method]input-stream.blocking-skip"))]
+unsafe extern "C" fn input_stream_blocking_skip(handle: i32, len: i64, ret: *mut u8) {
+    with(|s| unsafe {
+        let result = s.table.get::<InputStream>(handle).blocking_read(len as u64);
+        store_stream_result(s, ret, 8, result.map(|bytes| bytes.len() as u64));
+    })
+}
+
+#[unsafe(export_name = symbol!("io/streams", "[method]input-stream.subscribe"))]
+unsafe extern "C" fn input_stream_subscribe(handle: i32) -> i32 {
+    with(|s| {
+        let pollable = match s.table.get::<InputStream>(handle) {
+            InputStream::Pipe(pipe) => Pollable::Readable(pipe.clone()),
+            InputStream::File { .. } => Pollable::Ready,
+        };
+        s.table.push(pollable)
+    })
+}
+
+#[unsafe(export_name = symbol!("io/streams", "[method]output-stream.check-write"))]
+unsafe extern "C" fn output_stream_check_write(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let result = s.table.get::<OutputStream>(handle).check_write();
+        store_stream_result(s, ret, 8, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("io/streams", "[method]output-stream.write"))]
+unsafe extern "C" fn output_stream_write(handle: i32, ptr: *const u8, len: usize, ret: *mut u8) {
+    with(|s| unsafe {
+        let stream = s.table.get::<OutputStream>(handle);
+        let result = stream.write(bytes(ptr, len), &s.clocks);
+        store_stream_result(s, ret, 4, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("io/streams", "[method]output-stream.blocking-write-and-flush"))]
+unsafe extern "C" fn output_stream_blocking_write_and_flush(
+    handle: i32,
+    ptr: *const u8,
+    len: usize,
+    ret: *mut u8,
+) {
+    // Writes never block, and there is nothing to flush.
+    unsafe { output_stream_write(handle, ptr, len, ret) }
+}
+
+#[unsafe(export_name = symbol!("io/streams", "[method]output-stream.flush"))]
+unsafe extern "C" fn output_stream_flush(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let result = s.table.get::<OutputStream>(handle).check_write();
+        store_stream_result(s, ret, 4, result.map(drop));
+    })
+}
+
+#[unsafe(export_name = symbol!("io/streams", "[method]output-stream.blocking-flush"))]
+unsafe extern "C" fn output_stream_blocking_flush(handle: i32, ret: *mut u8) {
+    unsafe { output_stream_flush(handle, ret) }
+}
+
+#[unsafe(export_name = symbol!("io/streams", "[method]output-stream.subscribe"))]
+unsafe extern "C" fn output_stream_subscribe(handle: i32) -> i32 {
+    with(|s| {
+        s.table.get::<OutputStream>(handle);
+        s.table.push(Pollable::Ready)
+    })
+}
+
+#[unsafe(export_name = symbol!("io/streams", "[method]output-stream.write-zeroes"))]
+unsafe extern "C" fn output_stream_write_zeroes(handle: i32, len: i64, ret: *mut u8) {
+    with(|s| unsafe {
+        let zeroes = std::vec![0; usize::try_from(len as u64).unwrap_or(usize::MAX)];
+        let stream = s.table.get::<OutputStream>(handle);
+        let result = stream.write(&zeroes, &s.clocks);
+        store_stream_result(s, ret, 4, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("io/streams", "[method]output-stream.blocking-write-zeroes-and-flush"))]
+unsafe extern "C" fn output_stream_blocking_write_zeroes_and_flush(
+    handle: i32,
+    len: i64,
+    ret: *mut u8,
+) {
+    unsafe { output_stream_write_zeroes(handle, len, ret) }
+}
+
+/// Moves up to `len` bytes from the input stream `src` to the output stream
+/// `handle`.
+fn splice(
+    s: &mut State,
+    handle: i32,
+    src: i32,
+    len: u64,
+    blocking: bool,
+) -> Result<u64, StreamError> {
+    let len = len.min(WRITE_BUDGET);
+    let input = s.table.get::<InputStream>(src);
+    let bytes = if blocking {
+        input.blocking_read(len)?
+    } else {
+        input.read(len)?
+    };
+    let output = s.table.get::<OutputStream>(handle);
+    output.write(&bytes, &s.clocks)?;
+    Ok(bytes.len() as u64)
+}
+
+#[unsafe(export_name = symbol!("io/streams", "[method]output-stream.splice"))]
+unsafe extern "C" fn output_stream_splice(handle: i32, src: i32, len: i64, ret: *mut u8) {
+    with(|s| unsafe {
+        let result = splice(s, handle, src, len as u64, false);
+        store_stream_result(s, ret, 8, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("io/streams", "[method]output-stream.blocking-splice"))]
+unsafe extern "C" fn output_stream_blocking_splice(handle: i32, src: i32, len: i64, ret: *mut u8) {
+    with(|s| unsafe {
+        let result = splice(s, handle, src, len as u64, true);
+        store_stream_result(s, ret, 8, result);
+    })
+}
@@ -0,0 +1,80 @@
+//! `wasi:random`, generating the same values for the same seed.
+
+use super::{store, store_bytes, with};
+use std::vec::Vec;
+
+pub(super) struct Random {
+    random: SplitMix64,
+    insecure: SplitMix64,
+    insecure_seed: (u64, u64),
+}
+
+impl Random {
+    pub(super) fn new(seed: u64) -> Random {
+        // Each interface gets its own stream, so that using one doesn't
+        // change the values of the others.
+        let mut seeds = SplitMix64(seed);
+        Random {
+            random: SplitMix64(seeds.next()),
+            insecure: SplitMix64(seeds.next()),
+            insecure_seed: (seeds.next(), seeds.next()),
+        }
+    }
+}
+
+impl Default for Random {
+    fn default() -> Random {
+        Random::new(0)
+    }
+}
+
+/// The SplitMix64 generator, which is tiny and good enough for tests.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn bytes(&mut self, len: u64) -> Vec<u8> {
+        let len = usize::try_from(len).expect("too many random bytes requested");
+        let mut bytes = Vec::with_capacity(len);
+        while bytes.len() < len {
+            let n = (len - bytes.len()).min(8);
+            bytes.extend_from_slice(&self.next().to_le_bytes()[..n]);
+        }
+        bytes
+    }
+}
+
+#[unsafe(export_name = symbol!("random/random", "get-random-bytes"))]
+unsafe extern "C" fn get_random_bytes(len: i64, ret: *mut u8) {
+    with(|s| unsafe { store_bytes(ret, 0, &s.random.random.bytes(len as u64)) })
+}
+
+#[unsafe(export_name = symbol!("random/random", "get-random-u64"))]
+unsafe extern "C" fn get_random_u64() -> i64 {
+    with(|s| s.random.random.next() as i64)
+}
+
+#[unsafe(export_name = symbol!("random/insecure", "get-insecure-random-bytes"))]
+unsafe extern "C" fn get_insecure_random_bytes(len: i64, ret: *mut u8) {
+    with(|s| unsafe { store_bytes(ret, 0, &s.random.insecure.bytes(len as u64)) })
+}
+
+#[unsafe(export_name = symbol!("random/insecure", "get-insecure-random-u64"))]
+unsafe extern "C" fn get_insecure_random_u64() -> i64 {
+    with(|s| s.random.insecure.next() as i64)
+}
+
+#[unsafe(export_name = symbol!("random/insecure-seed", "insecure-seed"))]
+unsafe extern "C" fn insecure_seed(ret: *mut u8) {
+    with(|s| unsafe {
+        store(ret, 0, s.random.insecure_seed.0);
+        store(ret, 8, s.random.insecure_seed.1);
+    })
+}
@@ -0,0 +1,1070 @@
+//! `wasi:sockets`, on a loopback network only reaching the sockets of the
+//! same host.
+
+use super::io::{InputStream, OutputStream, Pipe, Pollable};
+use super::{
+    alloc_list, bytes, drop_resource, load, store, store_bytes, store_result, string, with, Lower,
+    State, PTR,
+};
+use crate::sockets::network::{ErrorCode, IpAddressFamily};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV6};
+use std::rc::{Rc, Weak};
+use std::vec::Vec;
+
+/// The first port handed out when binding to port 0.
+const EPHEMERAL_PORTS: u16 = 49152;
+
+/// How many datagrams `check-send` allows to be sent at once.
+const SEND_BUDGET: u64 = 64;
+
+/// The sockets bound to an address, which is how sockets find each other.
+#[derive(Default)]
+pub(super) struct Net {
+    tcp: HashMap<SocketAddr, Weak<RefCell<Tcp>>>,
+    udp: HashMap<SocketAddr, Weak<RefCell<Udp>>>,
+    /// The number of ephemeral ports handed out so far.
+    ephemeral: u16,
+}
+
+/// Binds `socket` to `addr` in `bound`, picking an ephemeral port if its port
+/// is 0, and returns the address it's bound to.
+fn bind<T>(
+    bound: &mut HashMap<SocketAddr, Weak<RefCell<T>>>,
+    ephemeral: &mut u16,
+    socket: &Rc<RefCell<T>>,
+    mut addr: SocketAddr,
+) -> Result<SocketAddr, ErrorCode> {
+    if !addr.ip().is_loopback() && !addr.ip().is_unspecified() {
+        return Err(ErrorCode::AddressNotBindable);
+    }
+    bound.retain(|_, socket| socket.strong_count() > 0);
+    if addr.port() == 0 {
+        let port = EPHEMERAL_PORTS
+            .checked_add(*ephemeral)
+            .ok_or(ErrorCode::AddressInUse)?;
+        *ephemeral += 1;
+        addr.set_port(port);
+    }
+    let in_use = bound.keys().any(|other| {
+        other.port() == addr.port()
+            && other.is_ipv4() == addr.is_ipv4()
+            && (other.ip() == addr.ip()
+                || other.ip().is_unspecified()
+                || addr.ip().is_unspecified())
+    });
+    if in_use {
+        return Err(ErrorCode::AddressInUse);
+    }
+    bound.insert(addr, Rc::downgrade(socket));
+    Ok(addr)
+}
+
+/// Returns the socket in `bound` which `addr` reaches, if any.
+fn find<T>(
+    bound: &HashMap<SocketAddr, Weak<RefCell<T>>>,
+    addr: SocketAddr,
+) -> Option<Rc<RefCell<T>>> {
+    if !addr.ip().is_loopback() {
+        return None;
+    }
+    bound.iter().find_map(|(other, socket)| {
+        let reaches = other.port() == addr.port()
+            && other.is_ipv4() == addr.is_ipv4()
+            && (other.ip() == addr.ip() || other.ip().is_unspecified());
+        if reaches {
+            socket.upgrade()
+        } else {
+            None
+        }
+    })
+}
+
+fn family(family: i32) -> IpAddressFamily {
+    match family {
+        0 => IpAddressFamily::Ipv4,
+        _ => IpAddressFamily::Ipv6,
+    }
+}
+
+/// Checks that `addr` belongs to the address family of a socket.
+fn check_family(family: IpAddressFamily, addr: SocketAddr) -> Result<(), ErrorCode> {
+    match (family, addr) {
+        (IpAddressFamily::Ipv4, SocketAddr::V4(_)) | (IpAddressFamily::Ipv6, SocketAddr::V6(_)) => {
+            Ok(())
+        }
+        _ => Err(ErrorCode::InvalidArgument),
+    }
+}
+
+/// Checks that `addr` can be connected or sent to.
+fn check_remote(family: IpAddressFamily, addr: SocketAddr) -> Result<(), ErrorCode> {
+    check_family(family, addr)?;
+    if addr.port() == 0 || addr.ip().is_unspecified() {
+        return Err(ErrorCode::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// Reads an `ip-socket-address` passed as flattened parameters.
+fn lift_address(p: [i32; 12]) -> SocketAddr {
+    match p[0] {
+        0 => {
+            let ip = Ipv4Addr::new(p[2] as u8, p[3] as u8, p[4] as u8, p[5] as u8);
+            SocketAddr::from((ip, p[1] as u16))
+        }
+        _ => {
+            let segments = [3, 4, 5, 6, 7, 8, 9, 10].map(|i| p[i] as u16);
+            let ip = Ipv6Addr::from(segments);
+            SocketAddr::V6(SocketAddrV6::new(
+                ip,
+                p[1] as u16,
+                p[2] as u32,
+                p[11] as u32,
+            ))
+        }
+    }
+}
+
+/// Reads an `ip-socket-address` at `offset` of memory passed by the guest.
+unsafe fn load_address(ptr: *const u8, offset: usize) -> SocketAddr {
+    unsafe {
+        match load::<u8>(ptr, offset) {
+            0 => {
+                let octets = [6, 7, 8, 9].map(|i| load::<u8>(ptr, offset + i));
+                SocketAddr::from((octets, load::<u16>(ptr, offset + 4)))
+            }
+            _ => {
+                let segments =
+                    [0, 1, 2, 3, 4, 5, 6, 7].map(|i| load::<u16>(ptr, offset + 12 + 2 * i));
+                SocketAddr::V6(SocketAddrV6::new(
+                    Ipv6Addr::from(segments),
+                    load(ptr, offset + 4),
+                    load(ptr, offset + 8),
+                    load(ptr, offset + 28),
+                ))
+            }
+        }
+    }
+}
+
+impl Lower for SocketAddr {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        unsafe {
+            match self {
+                SocketAddr::V4(addr) => {
+                    store(ret, offset, 0u8);
+                    store(ret, offset + 4, addr.port());
+                    for (i, octet) in addr.ip().octets().into_iter().enumerate() {
+                        store(ret, offset + 6 + i, octet);
+                    }
+                }
+                SocketAddr::V6(addr) => {
+                    store(ret, offset, 1u8);
+                    store(ret, offset + 4, addr.port());
+                    store(ret, offset + 8, addr.flowinfo());
+                    for (i, segment) in addr.ip().segments().into_iter().enumerate() {
+                        store(ret, offset + 12 + 2 * i, segment);
+                    }
+                    store(ret, offset + 28, addr.scope_id());
+                }
+            }
+        }
+    }
+}
+
+/// The loopback address of `family`.
+fn loopback(family: IpAddressFamily) -> IpAddr {
+    match family {
+        IpAddressFamily::Ipv4 => Ipv4Addr::LOCALHOST.into(),
+        IpAddressFamily::Ipv6 => Ipv6Addr::LOCALHOST.into(),
+    }
+}
+
+/// Checks the value passed to the setter of a socket option, which can't be
+/// zero.
+fn nonzero<T: Default + PartialEq>(value: T) -> Result<T, ErrorCode> {
+    if value == T::default() {
+        return Err(ErrorCode::InvalidArgument);
+    }
+    Ok(value)
+}
+
+pub(super) struct Network;
+
+#[unsafe(export_name = symbol!("sockets/network", "[resource-drop]network"))]
+unsafe extern "C" fn drop_network(handle: i32) {
+    drop_resource::<Network>(handle);
+}
+
+#[unsafe(export_name = symbol!("sockets/instance-network", "instance-network"))]
+unsafe extern "C" fn instance_network() -> i32 {
+    with(|s| s.table.push(Network))
+}
+
+/// The streams of one end of a TCP connection.
+struct Connection {
+    remote: SocketAddr,
+    /// What the other end writes.
+    input: Rc<RefCell<Pipe>>,
+    /// What the other end reads.
+    output: Rc<RefCell<Pipe>>,
+}
+
+enum TcpState {
+    Unbound,
+    BindStarted,
+    Bound,
+    ListenStarted,
+    /// Listening, with the connections yet to be accepted.
+    Listening(VecDeque<Connection>),
+    ConnectStarted(Result<Connection, ErrorCode>),
+    Connected(Connection),
+    Closed,
+}
+
+pub(super) struct Tcp {
+    family: IpAddressFamily,
+    state: TcpState,
+    local: Option<SocketAddr>,
+    backlog: u64,
+    keep_alive: bool,
+    keep_alive_idle_time: u64,
+    keep_alive_interval: u64,
+    keep_alive_count: u32,
+    hop_limit: u8,
+    receive_buffer_size: u64,
+    send_buffer_size: u64,
+}
+
+impl Tcp {
+    fn new(family: IpAddressFamily) -> Tcp {
+        Tcp {
+            family,
+            state: TcpState::Unbound,
+            local: None,
+            backlog: 128,
+            keep_alive: false,
+            keep_alive_idle_time: 7_200_000_000_000,
+            keep_alive_interval: 75_000_000_000,
+            keep_alive_count: 9,
+            hop_limit: 64,
+            receive_buffer_size: 64 * 1024,
+            send_buffer_size: 64 * 1024,
+        }
+    }
+
+    pub(super) fn has_pending_connections(&self) -> bool {
+        matches!(&self.state, TcpState::Listening(pending) if !pending.is_empty())
+    }
+}
+
+pub(super) struct TcpSocket(Rc<RefCell<Tcp>>);
+
+impl TcpSocket {
+    fn start_bind(&self, net: &mut Net, addr: SocketAddr) -> Result<(), ErrorCode> {
+        let mut tcp = self.0.borrow_mut();
+        if !matches!(tcp.state, TcpState::Unbound) {
+            return Err(ErrorCode::InvalidState);
+        }
+        check_family(tcp.family, addr)?;
+        tcp.local = Some(bind(&mut net.tcp, &mut net.ephemeral, &self.0, addr)?);
+        tcp.state = TcpState::BindStarted;
+        Ok(())
+    }
+
+    fn start_connect(&self, net: &mut Net, remote: SocketAddr) -> Result<(), ErrorCode> {
+        let mut tcp = self.0.borrow_mut();
+        if !matches!(tcp.state, TcpState::Unbound | TcpState::Bound) {
+            return Err(ErrorCode::InvalidState);
+        }
+        check_remote(tcp.family, remote)?;
+        let local = match tcp.local {
+            Some(local) => local,
+            None => {
+                let addr = SocketAddr::new(loopback(tcp.family), 0);
+                let local = bind(&mut net.tcp, &mut net.ephemeral, &self.0, addr)?;
+                tcp.local = Some(local);
+                local
+            }
+        };
+        let listener = find(&net.tcp, remote).filter(|listener| !Rc::ptr_eq(listener, &self.0));
+        let result = match listener {
+            Some(listener) => {
+                let mut listener = listener.borrow_mut();
+                let backlog = listener.backlog;
+                match &mut listener.state {
+                    TcpState::Listening(pending) if (pending.len() as u64) < backlog => {
+                        let input = Rc::<RefCell<Pipe>>::default();
+                        let output = Rc::<RefCell<Pipe>>::default();
+                        pending.push_back(Connection {
+                            remote: local,
+                            input: output.clone(),
+                            output: input.clone(),
+                        });
+                        Ok(Connection {
+                            remote,
+                            input,
+                            output,
+                        })
+                    }
+                    _ => Err(ErrorCode::ConnectionRefused),
+                }
+            }
+            None => Err(ErrorCode::ConnectionRefused),
+        };
+        tcp.state = TcpState::ConnectStarted(result);
+        Ok(())
+    }
+}
+
+fn push_connection(s: &mut State, connection: Connection) -> (i32, i32) {
+    let input = s.table.push(InputStream::Pipe(connection.input));
+    let output = s.table.push(OutputStream::Pipe(connection.output));
+    (input, output)
+}
+
+#[unsafe(export_name = symbol!("sockets/tcp", "[resource-drop]tcp-socket"))]
+unsafe extern "C" fn drop_tcp_socket(handle: i32) {
+    drop_resource::<TcpSocket>(handle);
+}
+
+#[allow(clippy::too_many_arguments)]
+#[unsafe(export_name = symbol!("sockets/tcp", "[method]tcp-socket.start-bind"))]
+unsafe extern "C" fn tcp_socket_start_bind(
+    handle: i32,
+    _network: i32,
+    a0: i32,
+    a1: i32,
+    a2: i32,
+    a3: i32,
+    a4: i32,
+    a5: i32,
+    a6: i32,
+    a7: i32,
+    a8: i32,
+    a9: i32,
+    a10: i32,
+    a11: i32,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let addr = lift_address([a0, a1, a2, a3, a4, a5, a6, a7, a8, a9, a10, a11]);
+        let result = s
+            .table
+            .get::<TcpSocket>(handle)
+            .start_bind(&mut s.net, addr);
+        store_result(ret, 1, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/tcp", "[method]tcp-socket.finish-bind"))]
+unsafe extern "C" fn tcp_socket_finish_bind(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let mut tcp = s.table.get::<TcpSocket>(handle).0.borrow_mut();
+        let result = match tcp.state {
+            TcpState::BindStarted => {
+                tcp.state = TcpState::Bound;
+                Ok(())
+            }
+            _ => Err(ErrorCode::NotInProgress),
+        };
+        store_result(ret, 1, result);
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+#[unsafe(export_name = symbol!("sockets/tcp", "[method]tcp-socket.start-connect"))]
+unsafe extern "C" fn tcp_socket_start_connect(
+    handle: i32,
+    _network: i32,
+    a0: i32,
+    a1: i32,
+    a2: i32,
+    a3: i32,
+    a4: i32,
+    a5: i32,
+    a6: i32,
+    a7: i32,
+    a8: i32,
+    a9: i32,
+    a10: i32,
+    a11: i32,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let addr = lift_address([a0, a1, a2, a3, a4, a5, a6, a7, a8, a9, a10, a11]);
+        let result = s
+            .table
+            .get::<TcpSocket>(handle)
+            .start_connect(&mut s.net, addr);
+        store_result(ret, 1, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/tcp", "[method]tcp-socket.finish-connect"))]
+unsafe extern "C" fn tcp_socket_finish_connect(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let tcp = s.table.get::<TcpSocket>(handle).0.clone();
+        let mut tcp = tcp.borrow_mut();
+        let result = match core::mem::replace(&mut tcp.state, TcpState::Closed) {
+            TcpState::ConnectStarted(Ok(connection)) => {
+                let streams = push_connection(
+                    s,
+                    Connection {
+                        remote: connection.remote,
+                        input: connection.input.clone(),
+                        output: connection.output.clone(),
+                    },
+                );
+                tcp.state = TcpState::Connected(connection);
+                Ok(streams)
+            }
+            TcpState::ConnectStarted(Err(code)) => Err(code),
+            state => {
+                tcp.state = state;
+                Err(ErrorCode::NotInProgress)
+            }
+        };
+        store_result(ret, 4, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/tcp", "[method]tcp-socket.start-listen"))]
+unsafe extern "C" fn tcp_socket_start_listen(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let mut tcp = s.table.get::<TcpSocket>(handle).0.borrow_mut();
+        let result = match tcp.state {
+            TcpState::Bound => {
+                tcp.state = TcpState::ListenStarted;
+                Ok(())
+            }
+            _ => Err(ErrorCode::InvalidState),
+        };
+        store_result(ret, 1, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/tcp", "[method]tcp-socket.finish-listen"))]
+unsafe extern "C" fn tcp_socket_finish_listen(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let mut tcp = s.table.get::<TcpSocket>(handle).0.borrow_mut();
+        let result = match tcp.state {
+            TcpState::ListenStarted => {
+                tcp.state = TcpState::Listening(VecDeque::new());
+                Ok(())
+            }
+            _ => Err(ErrorCode::NotInProgress),
+        };
+        store_result(ret, 1, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/tcp", "[method]tcp-socket.accept"))]
+unsafe extern "C" fn tcp_socket_accept(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let listener = s.table.get::<TcpSocket>(handle).0.clone();
+        let mut listener = listener.borrow_mut();
+        let (family, local) = (listener.family, listener.local);
+        let result = match &mut listener.state {
+            TcpState::Listening(pending) => pending.pop_front().ok_or(ErrorCode::WouldBlock),
+            _ => Err(ErrorCode::InvalidState),
+        };
+        let result = result.map(|connection| {
+            let (input, output) = push_connection(
+                s,
+                Connection {
+                    remote: connection.remote,
+                    input: connection.input.clone(),
+                    output: connection.output.clone(),
+                },
+            );
+            let mut tcp = Tcp::new(family);
+            tcp.local = local;
+            tcp.keep_alive = listener.keep_alive;
+            tcp.keep_alive_idle_time = listener.keep_alive_idle_time;
+            tcp.keep_alive_interval = listener.keep_alive_interval;
+            tcp.keep_alive_count = listener.keep_alive_count;
+            tcp.hop_limit = listener.hop_limit;
+            tcp.receive_buffer_size = listener.receive_buffer_size;
+            tcp.send_buffer_size = listener.send_buffer_size;
+            tcp.state = TcpState::Connected(connection);
+            let socket = s.table.push(TcpSocket(Rc::new(RefCell::new(tcp))));
+            (socket, input, output)
+        });
+        store_result(ret, 4, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/tcp", "[method]tcp-socket.local-address"))]
+unsafe extern "C" fn tcp_socket_local_address(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let tcp = s.table.get::<TcpSocket>(handle).0.borrow();
+        store_result(ret, 4, tcp.local.ok_or(ErrorCode::InvalidState));
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/tcp", "[method]tcp-socket.remote-address"))]
+unsafe extern "C" fn tcp_socket_remote_address(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let tcp = s.table.get::<TcpSocket>(handle).0.borrow();
+        let result = match &tcp.state {
+            TcpState::Connected(connection) => Ok(connection.remote),
+            _ => Err(ErrorCode::InvalidState),
+        };
+        store_result(ret, 4, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/tcp", "[method]tcp-socket.is-listening"))]
+unsafe extern "C" fn tcp_socket_is_listening(handle: i32) -> i32 {
+    with(|s| {
+        let tcp = s.table.get::<TcpSocket>(handle).0.borrow();
+        matches!(tcp.state, TcpState::Listening(_)) as i32
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/tcp", "[method]tcp-socket.address-family"))]
+unsafe extern "C" fn tcp_socket_address_family(handle: i32) -> i32 {
+    with(|s| s.table.get::<TcpSocket>(handle).0.borrow().family as i32)
+}
+
+#[unsafe(export_name = symbol!("sockets/tcp", "[method]tcp-socket.set-listen-backlog-size"))]
+unsafe extern "C" fn tcp_socket_set_listen_backlog_size(handle: i32, value: i64, ret: *mut u8) {
+    with(|s| unsafe {
+        let mut tcp = s.table.get::<TcpSocket>(handle).0.borrow_mut();
+        let result = match tcp.state {
+            TcpState::ConnectStarted(_) | TcpState::Connected(_) => Err(ErrorCode::InvalidState),
+            _ => nonzero(value as u64).map(|value| tcp.backlog = value),
+        };
+        store_result(ret, 1, result);
+    })
+}
+
+/// Defines the getter and setter imports of a socket option.
+macro_rules! socket_option {
+    (
+        $interface:literal, $socket:ident . $field:ident : $ty:ty as $abi:ty,
+        $get:literal => $get_fn:ident, $set:literal => $set_fn:ident,
+        $offset:expr, $check:expr $(,)?
+    ) => {
+        #[unsafe(export_name = symbol!($interface, $get))]
+        unsafe extern "C" fn $get_fn(handle: i32, ret: *mut u8) {
+            with(|s| unsafe {
+                let value = s.table.get::<$socket>(handle).0.borrow().$field;
+                store_result(ret, $offset, Ok::<_, ErrorCode>(value));
+            })
+        }
+
+        #[unsafe(export_name = symbol!($interface, $set))]
+        unsafe extern "C" fn $set_fn(handle: i32, value: $abi, ret: *mut u8) {
+            with(|s| unsafe {
+                let mut socket = s.table.get::<$socket>(handle).0.borrow_mut();
+                let check: fn($abi) -> Result<$ty, ErrorCode> = $check;
+                let result = check(value).map(|value| socket.$field = value);
+                store_result(ret, 1, result);
+            })
+        }
+    };
+}
+
+socket_option!(
+    "sockets/tcp", TcpSocket.keep_alive: bool as i32,
+    "[method]tcp-socket.keep-alive-enabled" => tcp_socket_keep_alive_enabled,
+    "[method]tcp-socket.set-keep-alive-enabled" => tcp_socket_set_keep_alive_enabled,
+    1, |value| Ok(value != 0),
+);
+
+socket_option!(
+    "sockets/tcp", TcpSocket.keep_alive_idle_time: u64 as i64,
+    "[method]tcp-socket.keep-alive-idle-time" => tcp_socket_keep_alive_idle_time,
+    "[method]tcp-socket.set-keep-alive-idle-time" => tcp_socket_set_keep_alive_idle_time,
+    8, |value| nonzero(value as u64),
+);
+
+socket_option!(
+    "sockets/tcp", TcpSocket.keep_alive_interval: u64 as i64,
+    "[method]tcp-socket.keep-alive-interval" => tcp_socket_keep_alive_interval,
+    "[method]tcp-socket.set-keep-alive-interval" => tcp_socket_set_keep_alive_interval,
+    8, |value| nonzero(value as u64),
+);
+
+socket_option!(
+    "sockets/tcp", TcpSocket.keep_alive_count: u32 as i32,
+    "[method]tcp-socket.keep-alive-count" => tcp_socket_keep_alive_count,
+    "[method]tcp-socket.set-keep-alive-count" => tcp_socket_set_keep_alive_count,
+    4, |value| nonzero(value as u32),
+);
+
+socket_option!(
+    "sockets/tcp", TcpSocket.hop_limit: u8 as i32,
+    "[method]tcp-socket.hop-limit" => tcp_socket_hop_limit,
+    "[method]tcp-socket.set-hop-limit" => tcp_socket_set_hop_limit,
+    1, |value| nonzero(value as u8),
+);
+
+socket_option!(
+    "sockets/tcp", TcpSocket.receive_buffer_size: u64 as i64,
+    "[method]tcp-socket.receive-buffer-size" => tcp_socket_receive_buffer_size,
+    "[method]tcp-socket.set-receive-buffer-size" => tcp_socket_set_receive_buffer_size,
+    8, |value| nonzero(value as u64),
+);
+
+socket_option!(
+    "sockets/tcp", TcpSocket.send_buffer_size: u64 as i64,
+    "[method]tcp-socket.send-buffer-size" => tcp_socket_send_buffer_size,
+    "[method]tcp-socket.set-send-buffer-size" => tcp_socket_set_send_buffer_size,
+    8, |value| nonzero(value as u64),
+);
+
+#[unsafe(export_name = symbol!("sockets/tcp", "[method]tcp-socket.subscribe"))]
+unsafe extern "C" fn tcp_socket_subscribe(handle: i32) -> i32 {
+    with(|s| {
+        let tcp = s.table.get::<TcpSocket>(handle).0.clone();
+        let pollable = match tcp.borrow().state {
+            TcpState::Listening(_) => Pollable::Accept(tcp.clone()),
+            _ => Pollable::Ready,
+        };
+        s.table.push(pollable)
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/tcp", "[method]tcp-socket.shutdown"))]
+unsafe extern "C" fn tcp_socket_shutdown(handle: i32, shutdown_type: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let tcp = s.table.get::<TcpSocket>(handle).0.borrow();
+        let result = match &tcp.state {
+            TcpState::Connected(connection) => {
+                // 0 is `receive`, 1 is `send` and 2 is `both`.
+                if shutdown_type != 1 {
+                    let mut input = connection.input.borrow_mut();
+                    input.reader_gone = true;
+                    input.buf.clear();
+                }
+                if shutdown_type != 0 {
+                    connection.output.borrow_mut().closed = true;
+                }
+                Ok(())
+            }
+            _ => Err(ErrorCode::InvalidState),
+        };
+        store_result(ret, 1, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/tcp-create-socket", "create-tcp-socket"))]
+unsafe extern "C" fn create_tcp_socket(address_family: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let tcp = Tcp::new(family(address_family));
+        let socket = s.table.push(TcpSocket(Rc::new(RefCell::new(tcp))));
+        store_result(ret, 4, Ok::<i32, ErrorCode>(socket));
+    })
+}
+
+pub(super) struct Udp {
+    family: IpAddressFamily,
+    /// Whether `finish-bind` was called after `start-bind`.
+    bound: bool,
+    local: Option<SocketAddr>,
+    /// The address set by `stream`, the only one datagrams are sent to and
+    /// received from.
+    remote: Option<SocketAddr>,
+    incoming: VecDeque<(Vec<u8>, SocketAddr)>,
+    unicast_hop_limit: u8,
+    receive_buffer_size: u64,
+    send_buffer_size: u64,
+}
+
+impl Udp {
+    fn accepts(&self, from: SocketAddr) -> bool {
+        self.remote.is_none_or(|remote| remote == from)
+    }
+
+    pub(super) fn has_datagrams(&self) -> bool {
+        self.incoming.iter().any(|(_, from)| self.accepts(*from))
+    }
+}
+
+pub(super) struct UdpSocket(Rc<RefCell<Udp>>);
+
+pub(super) struct IncomingDatagramStream(Rc<RefCell<Udp>>);
+
+pub(super) struct OutgoingDatagramStream(Rc<RefCell<Udp>>);
+
+impl OutgoingDatagramStream {
+    fn send(
+        &self,
+        net: &Net,
+        datagrams: Vec<(&[u8], Option<SocketAddr>)>,
+    ) -> Result<u64, ErrorCode> {
+        let (family, local, remote) = {
+            let udp = self.0.borrow();
+            (udp.family, udp.local, udp.remote)
+        };
+        let local = local.ok_or(ErrorCode::InvalidState)?;
+        let mut sent = 0;
+        for (data, to) in datagrams {
+            let to = match (to, remote) {
+                (Some(to), Some(remote)) if to != remote => return Err(ErrorCode::InvalidArgument),
+                (Some(to), _) | (None, Some(to)) => to,
+                (None, None) => return Err(ErrorCode::InvalidArgument),
+            };
+            check_remote(family, to)?;
+            // Datagrams which reach no socket are lost, as they would be on a
+            // real network.
+            if let Some(socket) = find(&net.udp, to) {
+                let from = match local.ip().is_unspecified() {
+                    true => SocketAddr::new(loopback(family), local.port()),
+                    false => local,
+                };
+                socket
+                    .borrow_mut()
+                    .incoming
+                    .push_back((data.to_vec(), from));
+            }
+            sent += 1;
+        }
+        Ok(sent)
+    }
+}
+
+/// The result of `receive`.
+struct Datagrams(Vec<(Vec<u8>, SocketAddr)>);
+
+impl Lower for Datagrams {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        let size = 2 * PTR + 32;
+        let list = alloc_list(self.0.len(), size, PTR);
+        unsafe {
+            for (i, (data, from)) in self.0.iter().enumerate() {
+                let elem = list.add(i * size);
+                store_bytes(elem, 0, data);
+                from.lower(elem, 2 * PTR);
+            }
+            store(ret, offset, list);
+            store(ret, offset + PTR, self.0.len());
+        }
+    }
+}
+
+#[unsafe(export_name = symbol!("sockets/udp", "[resource-drop]udp-socket"))]
+unsafe extern "C" fn drop_udp_socket(handle: i32) {
+    drop_resource::<UdpSocket>(handle);
+}
+
+#[unsafe(export_name = symbol!("sockets/udp", "[resource-drop]incoming-datagram-stream"))]
+unsafe extern "C" fn drop_incoming_datagram_stream(handle: i32) {
+    drop_resource::<IncomingDatagramStream>(handle);
+}
+
+#[unsafe(export_name = symbol!("sockets/udp", "[resource-drop]outgoing-datagram-stream"))]
+unsafe extern "C" fn drop_outgoing_datagram_stream(handle: i32) {
+    drop_resource::<OutgoingDatagramStream>(handle);
+}
+
+#[allow(clippy::too_many_arguments)]
+#[unsafe(export_name = symbol!("sockets/udp", "[method]udp-socket.start-bind"))]
+unsafe extern "C" fn udp_socket_start_bind(
+    handle: i32,
+    _network: i32,
+    a0: i32,
+    a1: i32,
+    a2: i32,
+    a3: i32,
+    a4: i32,
+    a5: i32,
+    a6: i32,
+    a7: i32,
+    a8: i32,
+    a9: i32,
+    a10: i32,
+    a11: i32,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let addr = lift_address([a0, a1, a2, a3, a4, a5, a6, a7, a8, a9, a10, a11]);
+        let socket = s.table.get::<UdpSocket>(handle).0.clone();
+        let mut udp = socket.borrow_mut();
+        let result = match udp.local {
+            Some(_) => Err(ErrorCode::InvalidState),
+            None => check_family(udp.family, addr)
+                .and_then(|()| bind(&mut s.net.udp, &mut s.net.ephemeral, &socket, addr))
+                .map(|local| udp.local = Some(local)),
+        };
+        store_result(ret, 1, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/udp", "[method]udp-socket.finish-bind"))]
+unsafe extern "C" fn udp_socket_finish_bind(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let mut udp = s.table.get::<UdpSocket>(handle).0.borrow_mut();
+        let result = match (udp.local, udp.bound) {
+            (Some(_), false) => {
+                udp.bound = true;
+                Ok(())
+            }
+            _ => Err(ErrorCode::NotInProgress),
+        };
+        store_result(ret, 1, result);
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+#[unsafe(export_name = symbol!("sockets/udp", "[method]udp-socket.stream"))]
+unsafe extern "C" fn udp_socket_stream(
+    handle: i32,
+    has_remote: i32,
+    a0: i32,
+    a1: i32,
+    a2: i32,
+    a3: i32,
+    a4: i32,
+    a5: i32,
+    a6: i32,
+    a7: i32,
+    a8: i32,
+    a9: i32,
+    a10: i32,
+    a11: i32,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let socket = s.table.get::<UdpSocket>(handle).0.clone();
+        let result = {
+            let mut udp = socket.borrow_mut();
+            let remote = match has_remote {
+                0 => None,
+                _ => Some(lift_address([
+                    a0, a1, a2, a3, a4, a5, a6, a7, a8, a9, a10, a11,
+                ])),
+            };
+            if !udp.bound {
+                Err(ErrorCode::InvalidState)
+            } else if let Some(Err(code)) = remote.map(|remote| check_remote(udp.family, remote)) {
+                Err(code)
+            } else {
+                udp.remote = remote;
+                Ok(())
+            }
+        };
+        let result = result.map(|()| {
+            let incoming = s.table.push(IncomingDatagramStream(socket.clone()));
+            let outgoing = s.table.push(OutgoingDatagramStream(socket.clone()));
+            (incoming, outgoing)
+        });
+        store_result(ret, 4, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/udp", "[method]udp-socket.local-address"))]
+unsafe extern "C" fn udp_socket_local_address(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let udp = s.table.get::<UdpSocket>(handle).0.borrow();
+        let local = udp.local.filter(|_| udp.bound);
+        store_result(ret, 4, local.ok_or(ErrorCode::InvalidState));
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/udp", "[method]udp-socket.remote-address"))]
+unsafe extern "C" fn udp_socket_remote_address(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let udp = s.table.get::<UdpSocket>(handle).0.borrow();
+        store_result(ret, 4, udp.remote.ok_or(ErrorCode::InvalidState));
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/udp", "[method]udp-socket.address-family"))]
+unsafe extern "C" fn udp_socket_address_family(handle: i32) -> i32 {
+    with(|s| s.table.get::<UdpSocket>(handle).0.borrow().family as i32)
+}
+
+socket_option!(
+    "sockets/udp", UdpSocket.unicast_hop_limit: u8 as i32,
+    "[method]udp-socket.unicast-hop-limit" => udp_socket_unicast_hop_limit,
+    "[method]udp-socket.set-unicast-hop-limit" => udp_socket_set_unicast_hop_limit,
+    1, |value| nonzero(value as u8),
+);
+
+socket_option!(
+    "sockets/udp", UdpSocket.receive_buffer_size: u64 as i64,
+    "[method]udp-socket.receive-buffer-size" => udp_socket_receive_buffer_size,
+    "[method]udp-socket.set-receive-buffer-size" => udp_socket_set_receive_buffer_size,
+    8, |value| nonzero(value as u64),
+);
+
+socket_option!(
+    "sockets/udp", UdpSocket.send_buffer_size: u64 as i64,
+    "[method]udp-socket.send-buffer-size" => udp_socket_send_buffer_size,
+    "[method]udp-socket.set-send-buffer-size" => udp_socket_set_send_buffer_size,
+    8, |value| nonzero(value as u64),
+);
+
+#[unsafe(export_name = symbol!("sockets/udp", "[method]udp-socket.subscribe"))]
+unsafe extern "C" fn udp_socket_subscribe(handle: i32) -> i32 {
+    with(|s| {
+        s.table.get::<UdpSocket>(handle);
+        s.table.push(Pollable::Ready)
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/udp", "[method]incoming-datagram-stream.receive"))]
+unsafe extern "C" fn incoming_datagram_stream_receive(handle: i32, max_results: i64, ret: *mut u8) {
+    with(|s| unsafe {
+        let mut udp = s.table.get::<IncomingDatagramStream>(handle).0.borrow_mut();
+        let mut datagrams = Vec::new();
+        while (datagrams.len() as u64) < max_results as u64 {
+            let Some((data, from)) = udp.incoming.pop_front() else {
+                break;
+            };
+            // Datagrams from other addresses than the one passed to `stream`
+            // are discarded.
+            if udp.accepts(from) {
+                datagrams.push((data, from));
+            }
+        }
+        store_result(ret, PTR, Ok::<_, ErrorCode>(Datagrams(datagrams)));
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/udp", "[method]incoming-datagram-stream.subscribe"))]
+unsafe extern "C" fn incoming_datagram_stream_subscribe(handle: i32) -> i32 {
+    with(|s| {
+        let udp = s.table.get::<IncomingDatagramStream>(handle).0.clone();
+        s.table.push(Pollable::Datagrams(udp))
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/udp", "[method]outgoing-datagram-stream.check-send"))]
+unsafe extern "C" fn outgoing_datagram_stream_check_send(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        s.table.get::<OutgoingDatagramStream>(handle);
+        store_result(ret, 8, Ok::<u64, ErrorCode>(SEND_BUDGET));
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/udp", "[method]outgoing-datagram-stream.send"))]
+unsafe extern "C" fn outgoing_datagram_stream_send(
+    handle: i32,
+    ptr: *const u8,
+    len: usize,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let size = 2 * PTR + 40;
+        let datagrams = (0..len)
+            .map(|i| {
+                let elem = ptr.add(i * size);
+                let data = bytes(load(elem, 0), load(elem, PTR));
+                let to = match load::<u8>(elem, 2 * PTR) {
+                    0 => None,
+                    _ => Some(load_address(elem, 2 * PTR + 4)),
+                };
+                (data, to)
+            })
+            .collect();
+        let result = s
+            .table
+            .get::<OutgoingDatagramStream>(handle)
+            .send(&s.net, datagrams);
+        store_result(ret, 8, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/udp", "[method]outgoing-datagram-stream.subscribe"))]
+unsafe extern "C" fn outgoing_datagram_stream_subscribe(handle: i32) -> i32 {
+    with(|s| {
+        s.table.get::<OutgoingDatagramStream>(handle);
+        s.table.push(Pollable::Ready)
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/udp-create-socket", "create-udp-socket"))]
+unsafe extern "C" fn create_udp_socket(address_family: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let udp = Udp {
+            family: family(address_family),
+            bound: false,
+            local: None,
+            remote: None,
+            incoming: VecDeque::new(),
+            unicast_hop_limit: 64,
+            receive_buffer_size: 64 * 1024,
+            send_buffer_size: 64 * 1024,
+        };
+        let socket = s.table.push(UdpSocket(Rc::new(RefCell::new(udp))));
+        store_result(ret, 4, Ok::<i32, ErrorCode>(socket));
+    })
+}
+
+/// The addresses a name resolved to, or why it didn't resolve.
+pub(super) struct ResolveAddressStream(Result<VecDeque<IpAddr>, ErrorCode>);
+
+impl Lower for Option<IpAddr> {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        unsafe {
+            match self {
+                Some(IpAddr::V4(ip)) => {
+                    store(ret, offset, 1u8);
+                    store(ret, offset + 2, 0u8);
+                    for (i, octet) in ip.octets().into_iter().enumerate() {
+                        store(ret, offset + 4 + i, octet);
+                    }
+                }
+                Some(IpAddr::V6(ip)) => {
+                    store(ret, offset, 1u8);
+                    store(ret, offset + 2, 1u8);
+                    for (i, segment) in ip.segments().into_iter().enumerate() {
+                        store(ret, offset + 4 + 2 * i, segment);
+                    }
+                }
+                None => store(ret, offset, 0u8),
+            }
+        }
+    }
+}
+
+#[unsafe(export_name = symbol!("sockets/ip-name-lookup", "[resource-drop]resolve-address-stream"))]
+unsafe extern "C" fn drop_resolve_address_stream(handle: i32) {
+    drop_resource::<ResolveAddressStream>(handle);
+}
+
+#[unsafe(export_name = symbol!("sockets/ip-name-lookup", "[method]resolve-address-stream.resolve-next-address"))]
+unsafe extern "C" fn resolve_address_stream_resolve_next_address(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let result = match &mut s.table.get::<ResolveAddressStream>(handle).0 {
+            Ok(addresses) => Ok(addresses.pop_front()),
+            Err(code) => Err(*code),
+        };
+        store_result(ret, 2, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/ip-name-lookup", "[method]resolve-address-stream.subscribe"))]
+unsafe extern "C" fn resolve_address_stream_subscribe(handle: i32) -> i32 {
+    with(|s| {
+        s.table.get::<ResolveAddressStream>(handle);
+        s.table.push(Pollable::Ready)
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/ip-name-lookup", "resolve-addresses"))]
+unsafe extern "C" fn resolve_addresses(_network: i32, ptr: *const u8, len: usize, ret: *mut u8) {
+    with(|s| unsafe {
+        let name = string(ptr, len);
+        let valid = |c: char| c.is_ascii_alphanumeric() || "-.:_".contains(c);
+        let result = if name.is_empty() || !name.chars().all(valid) {
+            Err(ErrorCode::InvalidArgument)
+        } else if name.eq_ignore_ascii_case("localhost") {
+            let addresses = [Ipv4Addr::LOCALHOST.into(), Ipv6Addr::LOCALHOST.into()];
+            Ok(ResolveAddressStream(Ok(addresses.into())))
+        } else {
+            let addresses = name.parse::<IpAddr>().map(|ip| [ip].into());
+            Ok(ResolveAddressStream(
+                addresses.map_err(|_| ErrorCode::NameUnresolvable),
+            ))
+        };
+        store_result(ret, 4, result.map(|stream| s.table.push(stream)));
+    })
+}
@@ -0,0 +1,357 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+use wasip2::clocks::{monotonic_clock, wall_clock};
+use wasip2::filesystem::types::{
+    Descriptor, DescriptorFlags, DescriptorType, ErrorCode, OpenFlags, PathFlags,
+};
+use wasip2::io::streams::StreamError;
+use wasip2::mock_host::MockHost;
+use wasip2::random::{insecure, insecure_seed, random};
+use wasip2::sockets::instance_network::instance_network;
+use wasip2::sockets::network::{
+    self, IpAddress, IpAddressFamily, IpSocketAddress, Ipv4SocketAddress,
+};
+use wasip2::sockets::tcp::ShutdownType;
+use wasip2::sockets::udp::OutgoingDatagram;
+use wasip2::sockets::{ip_name_lookup, tcp_create_socket, udp_create_socket};
+
+fn root() -> Descriptor {
+    let (dir, path) = wasip2::filesystem::preopens::get_directories().remove(0);
+    assert_eq!(path, "/root");
+    dir
+}
+
+fn loopback(port: u16) -> IpSocketAddress {
+    IpSocketAddress::Ipv4(Ipv4SocketAddress {
+        port,
+        address: (127, 0, 0, 1),
+    })
+}
+
+fn ip(address: IpAddress) -> IpAddr {
+    match address {
+        IpAddress::Ipv4((a, b, c, d)) => Ipv4Addr::new(a, b, c, d).into(),
+        IpAddress::Ipv6((a, b, c, d, e, f, g, h)) => Ipv6Addr::new(a, b, c, d, e, f, g, h).into(),
+    }
+}
+
+fn socket_address(address: IpSocketAddress) -> SocketAddr {
+    match address {
+        IpSocketAddress::Ipv4(v4) => (ip(IpAddress::Ipv4(v4.address)), v4.port).into(),
+        IpSocketAddress::Ipv6(v6) => (ip(IpAddress::Ipv6(v6.address)), v6.port).into(),
+    }
+}
+
+fn open(dir: &Descriptor, path: &str, open: OpenFlags, flags: DescriptorFlags) -> Descriptor {
+    dir.open_at(PathFlags::empty(), path, open, flags).unwrap()
+}
+
+#[test]
+fn filesystem() {
+    let host = MockHost::new();
+    host.add_file("/root/a/old.txt", b"old");
+    host.preopen("/root");
+    let _guard = host.install();
+    let root = root();
+
+    let file = open(
+        &root,
+        "a/new.txt",
+        OpenFlags::CREATE,
+        DescriptorFlags::READ | DescriptorFlags::WRITE,
+    );
+    assert_eq!(file.write(b"hello", 0).unwrap(), 5);
+    assert_eq!(file.write(b"!", 5).unwrap(), 1);
+    assert_eq!(file.get_type().unwrap(), DescriptorType::RegularFile);
+    assert_eq!(file.stat().unwrap().size, 6);
+    assert_eq!(file.read(3, 2).unwrap(), (b"llo".to_vec(), false));
+    assert_eq!(file.read(10, 3).unwrap(), (b"lo!".to_vec(), true));
+    assert_eq!(host.file("/root/a/new.txt").unwrap(), b"hello!");
+
+    // Paths are resolved relative to the descriptor, and can't escape it.
+    assert_eq!(
+        root.open_at(
+            PathFlags::empty(),
+            "a/missing.txt",
+            OpenFlags::empty(),
+            DescriptorFlags::READ,
+        )
+        .err(),
+        Some(ErrorCode::NoEntry)
+    );
+    assert_eq!(
+        root.open_at(
+            PathFlags::empty(),
+            "a/new.txt",
+            OpenFlags::CREATE | OpenFlags::EXCLUSIVE,
+            DescriptorFlags::WRITE,
+        )
+        .err(),
+        Some(ErrorCode::Exist)
+    );
+    assert!(root
+        .open_at(
+            PathFlags::empty(),
+            "../etc",
+            OpenFlags::DIRECTORY,
+            DescriptorFlags::READ,
+        )
+        .is_err());
+
+    root.create_directory_at("b").unwrap();
+    root.rename_at("a/old.txt", &root, "b/moved.txt").unwrap();
+    assert_eq!(host.file("/root/a/old.txt"), None);
+    assert_eq!(host.file("/root/b/moved.txt").unwrap(), b"old");
+
+    let dir = open(&root, "a", OpenFlags::DIRECTORY, DescriptorFlags::READ);
+    let entries = dir.read_directory().unwrap();
+    let mut names = Vec::new();
+    while let Some(entry) = entries.read_directory_entry().unwrap() {
+        assert_eq!(entry.type_, DescriptorType::RegularFile);
+        names.push(entry.name);
+    }
+    assert_eq!(names, ["new.txt"]);
+
+    root.unlink_file_at("a/new.txt").unwrap();
+    assert_eq!(host.file("/root/a/new.txt"), None);
+    assert_eq!(
+        root.remove_directory_at("b").err(),
+        Some(ErrorCode::NotEmpty)
+    );
+}
+
+#[test]
+fn streams() {
+    let host = MockHost::new();
+    host.add_file("/root/data.txt", b"0123456789");
+    host.preopen("/root");
+    host.push_stdin(b"input");
+    let _guard = host.install();
+
+    // Stdin ends once everything pushed was read.
+    let stdin = wasip2::cli::stdin::get_stdin();
+    assert_eq!(stdin.blocking_read(3).unwrap(), b"inp");
+    assert_eq!(stdin.skip(1).unwrap(), 1);
+    assert_eq!(stdin.blocking_read(10).unwrap(), b"t");
+    assert!(matches!(stdin.blocking_read(1), Err(StreamError::Closed)));
+
+    let file = open(
+        &root(),
+        "data.txt",
+        OpenFlags::empty(),
+        DescriptorFlags::READ | DescriptorFlags::WRITE,
+    );
+    let input = file.read_via_stream(4).unwrap();
+    input.subscribe().block();
+    assert_eq!(input.read(3).unwrap(), b"456");
+
+    // Writing at an offset overwrites, appending extends the file.
+    let output = file.write_via_stream(2).unwrap();
+    assert!(output.check_write().unwrap() > 0);
+    output.write(b"ab").unwrap();
+    output.blocking_flush().unwrap();
+    drop(output);
+    let output = file.append_via_stream().unwrap();
+    output.blocking_write_and_flush(b"cd").unwrap();
+    output.blocking_write_zeroes_and_flush(1).unwrap();
+    drop(output);
+    assert_eq!(host.file("/root/data.txt").unwrap(), b"01ab456789cd\0");
+
+    // The input stream keeps its own offset, and sees what was written.
+    assert_eq!(input.blocking_read(100).unwrap(), b"789cd\0");
+    assert!(matches!(input.read(1), Err(StreamError::Closed)));
+
+    let stdout = wasip2::cli::stdout::get_stdout();
+    let input = file.read_via_stream(0).unwrap();
+    assert_eq!(stdout.blocking_splice(&input, 4).unwrap(), 4);
+    stdout.blocking_flush().unwrap();
+    assert_eq!(host.take_stdout(), b"01ab");
+    assert_eq!(host.take_stdout(), b"");
+
+    let stderr = wasip2::cli::stderr::get_stderr();
+    stderr.blocking_write_and_flush(b"oops").unwrap();
+    assert_eq!(host.take_stderr(), b"oops");
+}
+
+#[test]
+fn clocks() {
+    let host = MockHost::new();
+    host.set_monotonic_clock(1_000);
+    host.set_wall_clock(Duration::new(1_700_000_000, 5));
+    let _guard = host.install();
+
+    // Nothing moves unless told to.
+    assert_eq!(monotonic_clock::now(), 1_000);
+    assert_eq!(monotonic_clock::now(), 1_000);
+    let now = wall_clock::now();
+    assert_eq!((now.seconds, now.nanoseconds), (1_700_000_000, 5));
+
+    host.advance(Duration::from_micros(1));
+    assert_eq!(monotonic_clock::now(), 2_000);
+    assert_eq!(host.monotonic_clock(), 2_000);
+    assert_eq!(wall_clock::now().nanoseconds, 1_005);
+
+    // Blocking on a clock advances both clocks to its deadline.
+    let later = monotonic_clock::subscribe_duration(500);
+    let earlier = monotonic_clock::subscribe_instant(2_100);
+    assert!(!later.ready());
+    assert_eq!(wasip2::io::poll::poll(&[&later, &earlier]), [1]);
+    assert_eq!(monotonic_clock::now(), 2_100);
+    later.block();
+    assert_eq!(monotonic_clock::now(), 2_500);
+    assert!(earlier.ready());
+    assert_eq!(host.wall_clock(), Duration::new(1_700_000_000, 1_505));
+
+    // Instants in the past are ready without moving the clocks.
+    monotonic_clock::subscribe_instant(0).block();
+    assert_eq!(monotonic_clock::now(), 2_500);
+}
+
+#[test]
+fn random() {
+    let values = |seed| {
+        let host = MockHost::new();
+        host.set_random_seed(seed);
+        let _guard = host.install();
+        (
+            random::get_random_bytes(13),
+            random::get_random_u64(),
+            insecure::get_insecure_random_u64(),
+            insecure::get_insecure_random_bytes(3),
+            insecure_seed::insecure_seed(),
+        )
+    };
+    let a = values(1);
+    assert_eq!(a.0.len(), 13);
+    assert_eq!(a.3.len(), 3);
+    assert_eq!(a, values(1));
+    assert_ne!(a, values(2));
+
+    // Each interface has its own generator, so using one doesn't change the
+    // values of another.
+    let host = MockHost::new();
+    host.set_random_seed(1);
+    let _guard = host.install();
+    assert_eq!(insecure::get_insecure_random_u64(), a.2);
+    assert_eq!(insecure_seed::insecure_seed(), a.4);
+    assert_eq!(insecure_seed::insecure_seed(), a.4);
+    assert_eq!(random::get_random_bytes(13), a.0);
+    assert_eq!(random::get_random_u64(), a.1);
+}
+
+#[test]
+fn tcp() {
+    let host = MockHost::new();
+    let _guard = host.install();
+    let network = instance_network();
+
+    let listener = tcp_create_socket::create_tcp_socket(IpAddressFamily::Ipv4).unwrap();
+    listener.start_bind(&network, loopback(8080)).unwrap();
+    listener.finish_bind().unwrap();
+    listener.start_listen().unwrap();
+    listener.finish_listen().unwrap();
+    assert!(listener.is_listening());
+
+    // Connecting to a port nothing listens on is refused.
+    let client = tcp_create_socket::create_tcp_socket(IpAddressFamily::Ipv4).unwrap();
+    client.start_connect(&network, loopback(9)).unwrap();
+    client.subscribe().block();
+    assert_eq!(
+        client.finish_connect().err(),
+        Some(network::ErrorCode::ConnectionRefused)
+    );
+
+    let client = tcp_create_socket::create_tcp_socket(IpAddressFamily::Ipv4).unwrap();
+    client.start_connect(&network, loopback(8080)).unwrap();
+    client.subscribe().block();
+    let (client_in, client_out) = client.finish_connect().unwrap();
+    listener.subscribe().block();
+    let (server, server_in, server_out) = listener.accept().unwrap();
+    let client_address = socket_address(client.local_address().unwrap());
+    assert_eq!(
+        socket_address(server.remote_address().unwrap()),
+        client_address
+    );
+    assert_eq!(
+        socket_address(client.remote_address().unwrap()),
+        "127.0.0.1:8080".parse().unwrap()
+    );
+
+    client_out.blocking_write_and_flush(b"ping").unwrap();
+    assert_eq!(server_in.blocking_read(10).unwrap(), b"ping");
+    server_out.blocking_write_and_flush(b"pong").unwrap();
+    assert_eq!(client_in.blocking_read(10).unwrap(), b"pong");
+
+    // Shutting down sending ends the stream the peer reads.
+    client.shutdown(ShutdownType::Send).unwrap();
+    assert!(matches!(
+        server_in.blocking_read(1),
+        Err(StreamError::Closed)
+    ));
+}
+
+#[test]
+fn udp() {
+    let host = MockHost::new();
+    let _guard = host.install();
+    let network = instance_network();
+
+    let receiver = udp_create_socket::create_udp_socket(IpAddressFamily::Ipv4).unwrap();
+    receiver.start_bind(&network, loopback(5000)).unwrap();
+    receiver.finish_bind().unwrap();
+    let (incoming, _) = receiver.stream(None).unwrap();
+
+    let sender = udp_create_socket::create_udp_socket(IpAddressFamily::Ipv4).unwrap();
+    sender.start_bind(&network, loopback(0)).unwrap();
+    sender.finish_bind().unwrap();
+    let (_, outgoing) = sender.stream(Some(loopback(5000))).unwrap();
+    assert!(outgoing.check_send().unwrap() > 0);
+    let datagram = OutgoingDatagram {
+        data: b"hello".to_vec(),
+        remote_address: None,
+    };
+    assert_eq!(outgoing.send(&[datagram]).unwrap(), 1);
+
+    incoming.subscribe().block();
+    let datagrams = incoming.receive(10).unwrap();
+    assert_eq!(datagrams.len(), 1);
+    assert_eq!(datagrams[0].data, b"hello");
+    assert_eq!(
+        socket_address(datagrams[0].remote_address),
+        socket_address(sender.local_address().unwrap())
+    );
+    assert!(incoming.receive(10).unwrap().is_empty());
+}
+
+#[test]
+fn ip_name_lookup() {
+    let host = MockHost::new();
+    let _guard = host.install();
+    let network = instance_network();
+    let resolve = |name| {
+        let stream = ip_name_lookup::resolve_addresses(&network, name)?;
+        stream.subscribe().block();
+        let mut addresses = Vec::new();
+        while let Some(address) = stream.resolve_next_address()? {
+            addresses.push(ip(address));
+        }
+        Ok::<_, network::ErrorCode>(addresses)
+    };
+
+    assert_eq!(
+        resolve("localhost").unwrap(),
+        [
+            IpAddr::from(Ipv4Addr::LOCALHOST),
+            Ipv6Addr::LOCALHOST.into()
+        ]
+    );
+    assert_eq!(resolve("10.0.0.1").unwrap(), [IpAddr::from([10, 0, 0, 1])]);
+    assert_eq!(
+        resolve("example.com").err(),
+        Some(network::ErrorCode::NameUnresolvable)
+    );
+    assert_eq!(
+        resolve("not a name").err(),
+        Some(network::ErrorCode::InvalidArgument)
+    );
+}
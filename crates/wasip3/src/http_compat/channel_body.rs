@@ -0,0 +1,161 @@
+use super::{body_writer::Error, BodyResult, BodyWriter, IncomingBody, IncomingMessage, RawBody};
+use crate::{
+    http::types::{ErrorCode, Fields, Headers, Trailers},
+    wit_bindgen::{FutureReader, StreamReader},
+};
+use bytes::Bytes;
+use http::HeaderMap;
+use http_body::{Frame, SizeHint};
+use std::prelude::v1::*;
+use std::{
+    pin::Pin,
+    task::{self, Poll},
+};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// Creates a new body which is fed from a [`BodySender`].
+///
+/// The returned [`ChannelBody`] is backed directly by a `wasi:http` body
+/// stream and trailers future, so when it is passed to
+/// [`http_into_wasi_response`](super::http_into_wasi_response) or
+/// [`http_into_wasi_request`](super::http_into_wasi_request) the data sent
+/// through the [`BodySender`] goes straight to the host without being copied
+/// by an intermediate task. It may also be read in the guest like any other
+/// [`http_body::Body`].
+///
+/// ```no_run
+/// use wasip3::http::types::{self, ErrorCode};
+/// use wasip3::http_compat::{channel_body, http_into_wasi_response};
+///
+/// async fn handle(_request: types::Request) -> Result<types::Response, ErrorCode> {
+///     let (mut sender, body) = channel_body();
+///     wasip3::spawn(async move {
+///         for i in 0..10 {
+///             if sender.send_data(format!("chunk {i}\n")).await.is_err() {
+///                 return;
+///             }
+///         }
+///         _ = sender.finish().await;
+///     });
+///     http_into_wasi_response(http::Response::new(body))
+/// }
+/// ```
+pub fn channel_body() -> (BodySender, ChannelBody) {
+    let (writer, contents, trailers) = BodyWriter::new();
+    let body = ChannelBody {
+        inner: IncomingBody::with_content_length(ChannelMessage { contents, trailers }, None),
+    };
+    (BodySender { writer }, body)
+}
+
+/// The sending half of a [`channel_body`].
+///
+/// Writes complete only once the data has been read by the other end, which
+/// provides backpressure to the producer. Dropping the sender without calling
+/// [`Self::finish`], [`Self::send_trailers`] or [`Self::abort`] ends the body
+/// with an [`ErrorCode::InternalError`].
+pub struct BodySender {
+    writer: BodyWriter,
+}
+
+impl BodySender {
+    /// Sends a chunk of data, waiting until it has been read.
+    ///
+    /// On success the number of bytes written is returned. If the receiving
+    /// end was dropped [`Error::StreamReaderClosed`] is returned.
+    pub async fn send_data(&mut self, data: impl Into<Vec<u8>>) -> Result<usize, Error> {
+        self.writer.send_frame(Frame::data(data.into())).await
+    }
+
+    /// Ends the body successfully without trailers.
+    pub async fn finish(self) -> Result<(), Error> {
+        self.finish_with(Ok(None)).await
+    }
+
+    /// Ends the body successfully with the given trailers.
+    pub async fn send_trailers(self, trailers: HeaderMap) -> Result<(), Error> {
+        let trailers = Trailers::try_from(trailers).map_err(Error::InvalidTrailers)?;
+        self.finish_with(Ok(Some(trailers))).await
+    }
+
+    /// Ends the body with an error.
+    ///
+    /// If `error` is an [`ErrorCode`] it is reported as-is, otherwise it is
+    /// reported as an [`ErrorCode::InternalError`] with the error's message.
+    pub async fn abort(self, error: impl Into<BoxError>) -> Result<(), Error> {
+        let error_code = match error.into().downcast::<ErrorCode>() {
+            Ok(error_code) => *error_code,
+            Err(err) => ErrorCode::InternalError(Some(err.to_string())),
+        };
+        self.finish_with(Err(error_code)).await
+    }
+
+    async fn finish_with(self, result: BodyResult) -> Result<(), Error> {
+        let BodyWriter {
+            stream_writer,
+            result_writer,
+            ..
+        } = self.writer;
+        drop(stream_writer);
+        result_writer
+            .write(result)
+            .await
+            .map_err(|err| Error::ResultReaderClosed(err.value))
+    }
+}
+
+/// The receiving half of a [`channel_body`].
+pub struct ChannelBody {
+    inner: IncomingBody<ChannelMessage>,
+}
+
+impl ChannelBody {
+    /// Takes the raw body contents stream and trailers future out of this
+    /// body, if no data has been read from it yet.
+    ///
+    /// See [`IncomingBody::take_raw`].
+    pub fn take_raw(&mut self) -> Option<RawBody> {
+        self.inner.take_raw()
+    }
+}
+
+impl http_body::Body for ChannelBody {
+    type Data = Bytes;
+    type Error = ErrorCode;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        Pin::new(&mut self.inner).poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// The body stream and trailers of a [`channel_body`], which aren't attached
+/// to a `wasi:http` request or response.
+struct ChannelMessage {
+    contents: StreamReader<u8>,
+    trailers: FutureReader<BodyResult>,
+}
+
+impl IncomingMessage for ChannelMessage {
+    fn get_headers(&self) -> Headers {
+        Fields::new()
+    }
+
+    fn consume_body(
+        self,
+        _res: FutureReader<Result<(), ErrorCode>>,
+    ) -> (StreamReader<u8>, FutureReader<BodyResult>) {
+        (self.contents, self.trailers)
+    }
+}
@@ -1,11 +1,13 @@
 use super::{
-    body_writer::BodyWriter, to_internal_error_code, IncomingRequestBody, IncomingResponseBody,
-    Request as HttpRequest, RequestOptionsExtension, Response as HttpResponse,
+    body_writer::BodyWriter, to_internal_error_code, ChannelBody, IncomingRequestBody,
+    IncomingResponseBody, RawBody, Request as HttpRequest, RequestOptionsExtension,
+    Response as HttpResponse, ResponseHeadersExtension,
 };
 use crate::http::types::{
     ErrorCode, Fields, HeaderError, Headers, Method, Request as WasiHttpRequest,
     Response as WasiHttpResponse, Scheme,
 };
+use http::HeaderMap;
 use std::prelude::v1::*;
 use std::{any::Any, convert::TryFrom};
 
@@ -16,40 +18,93 @@ use std::{any::Any, convert::TryFrom};
 /// representations. It supports generic response body types and streams the response
 /// asynchronously into the WASI environment.
 ///
+/// Returns [`ErrorCode::HttpProtocolError`] if the host rejects the status code.
+///
+/// # Pass-through
+///
+/// If the body is an [`IncomingResponseBody`] which hasn't been polled yet,
+/// the original [`WasiHttpResponse`] it was created from is reused rather
+/// than copying the body through guest memory. A changed status code is
+/// applied to the original response, and if the headers were modified a new
+/// response is created around the original body stream and trailers.
+///
+/// A [`ChannelBody`] which hasn't been polled is likewise handed to the new
+/// response directly.
+///
+/// # Extensions
+///
+/// A WASI response has nowhere to store [`http::Extensions`] or the HTTP
+/// version, so they're dropped. The only extension read is the
+/// [`ResponseHeadersExtension`] inserted by [`http_from_wasi_response`],
+/// whose fields are sent instead of [`http::Response::headers`] when both
+/// hold the same headers. The original response itself is kept in its
+/// [`IncomingResponseBody`] rather than in the extensions, so it's passed
+/// through for as long as that body is. Anything else the host should see
+/// has to be encoded in the headers.
+///
 /// # See Also
 ///
 /// - [`http_from_wasi_response`] — converts a WASI response back into a host-side HTTP response.
 /// - [`BodyWriter`] — for streaming body data into WASI.
 /// - [`IncomingResponseBody`] — for handling pending or unstarted response states.
-pub fn http_into_wasi_response<T>(mut resp: HttpResponse<T>) -> Result<WasiHttpResponse, ErrorCode>
+pub fn http_into_wasi_response<T>(resp: HttpResponse<T>) -> Result<WasiHttpResponse, ErrorCode>
 where
     T: http_body::Body + Any,
     T::Data: Into<Vec<u8>>,
     T::Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
 {
-    if let Some(incoming_body) = (&mut resp as &mut dyn Any).downcast_mut::<IncomingResponseBody>()
+    let (mut parts, mut body) = resp.into_parts();
+    let status = parts.status.as_u16();
+
+    let raw_body;
+    if let Some(incoming_body) = (&mut body as &mut dyn Any).downcast_mut::<IncomingResponseBody>()
     {
-        if let Some(response) = incoming_body.take_unstarted() {
-            return Ok(response);
+        // The original response can only be passed on as-is when its body
+        // needn't be limited by `take_raw`.
+        if let Some(original) = incoming_body
+            .unstarted()
+            .filter(|_| incoming_body.max_body_size.is_none())
+        {
+            if same_headers(&original.get_headers(), &parts.headers) {
+                let response = incoming_body.take_unstarted().unwrap();
+                if response.get_status_code() != status {
+                    response
+                        .set_status_code(status)
+                        .map_err(|()| ErrorCode::HttpProtocolError)?;
+                }
+                return Ok(response);
+            }
         }
+        raw_body = incoming_body.take_raw();
+    } else {
+        raw_body = take_channel_body(&mut body);
     }
 
-    let headers = resp
-        .headers()
-        .clone()
-        .try_into()
-        .map_err(to_internal_error_code)?;
-
-    let (body_writer, body_rx, body_result_rx) = BodyWriter::new();
+    let headers = match parts.extensions.remove::<ResponseHeadersExtension>() {
+        Some(original) if same_headers(&original.0, &parts.headers) => original.0,
+        _ => parts.headers.try_into().map_err(to_internal_error_code)?,
+    };
 
-    let (response, _future_result) = WasiHttpResponse::new(headers, Some(body_rx), body_result_rx);
+    let (response, body_writer) = match raw_body {
+        Some(raw_body) => (raw_body.into_response(headers), None),
+        None => {
+            let (body_writer, body_rx, body_result_rx) = BodyWriter::new();
+            let (response, _future_result) =
+                WasiHttpResponse::new(headers, Some(body_rx), body_result_rx);
+            (response, Some(body_writer))
+        }
+    };
 
-    _ = response.set_status_code(resp.status().as_u16());
+    response
+        .set_status_code(status)
+        .map_err(|()| ErrorCode::HttpProtocolError)?;
 
-    wit_bindgen::spawn(async move {
-        let mut body = std::pin::pin!(resp.into_body());
-        _ = body_writer.send_http_body(&mut body).await;
-    });
+    if let Some(body_writer) = body_writer {
+        crate::spawn(async move {
+            let mut body = std::pin::pin!(body);
+            _ = body_writer.send_http_body(&mut body).await;
+        });
+    }
 
     Ok(response)
 }
@@ -61,6 +116,9 @@ where
 /// the fields and body of a response from the WASI HTTP model into the conventional Rust
 /// `http` crate representation.
 ///
+/// The original fields are kept in a [`ResponseHeadersExtension`], so that
+/// [`http_into_wasi_response`] can send them unchanged.
+///
 /// # See Also
 ///
 /// - [`http_into_wasi_response`] — the inverse conversion.
@@ -69,9 +127,14 @@ where
 pub fn http_from_wasi_response(resp: WasiHttpResponse) -> Result<HttpResponse, ErrorCode> {
     let mut builder = http::Response::builder().status(resp.get_status_code());
 
-    for (k, v) in resp.get_headers().copy_all() {
+    // As with request options, `get_headers` returns an immutable child of
+    // `resp`, so store a detached copy.
+    let headers = resp.get_headers();
+    for (k, v) in headers.copy_all() {
         builder = builder.header(k, v);
     }
+    builder = builder.extension(ResponseHeadersExtension(headers.clone()));
+    drop(headers);
 
     let body = IncomingResponseBody::new(resp)?;
     builder.body(body).map_err(to_internal_error_code) // TODO: downcast to more specific http error codes
@@ -83,25 +146,32 @@ pub fn http_from_wasi_response(resp: WasiHttpResponse) -> Result<HttpResponse, E
 /// request model used in WebAssembly components. It serializes headers, method, URI components,
 /// and body streams into their WASI equivalents while preserving request metadata.
 ///
+/// # Pass-through
+///
+/// If the body is an [`IncomingRequestBody`] which hasn't been polled yet,
+/// its raw contents stream and trailers are handed directly to the new WASI
+/// request (see [`IncomingBody::take_raw`](super::IncomingBody::take_raw)), so a proxy which only rewrites
+/// the method, URI or headers doesn't copy the body through guest memory.
+/// The same applies to a [`ChannelBody`].
+///
 /// # See Also
 ///
 /// - [`http_from_wasi_response`] — for converting WASI responses back into standard HTTP responses.
 /// - [`http_into_wasi_response`] — for converting HTTP responses into WASI format.
 /// - [`BodyWriter`] — for streaming request bodies to WASI.
 /// - [`IncomingRequestBody`] — for managing unstarted or in-progress request states.
-pub fn http_into_wasi_request<T>(mut req: HttpRequest<T>) -> Result<WasiHttpRequest, ErrorCode>
+pub fn http_into_wasi_request<T>(req: HttpRequest<T>) -> Result<WasiHttpRequest, ErrorCode>
 where
     T: http_body::Body + Any,
     T::Data: Into<Vec<u8>>,
     T::Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
 {
-    if let Some(incoming_body) = (&mut req as &mut dyn Any).downcast_mut::<IncomingRequestBody>() {
-        if let Some(request) = incoming_body.take_unstarted() {
-            return Ok(request);
-        }
-    }
+    let (mut parts, mut body) = req.into_parts();
 
-    let (mut parts, body) = req.into_parts();
+    let raw_body = match (&mut body as &mut dyn Any).downcast_mut::<IncomingRequestBody>() {
+        Some(incoming_body) => incoming_body.take_raw(),
+        None => take_channel_body(&mut body),
+    };
 
     let options = parts
         .extensions
@@ -136,9 +206,15 @@ where
 
     let headers = parts.headers.try_into().map_err(to_internal_error_code)?;
 
-    let (body_writer, contents_rx, trailers_rx) = BodyWriter::new();
-
-    let (req, _result) = WasiHttpRequest::new(headers, Some(contents_rx), trailers_rx, options);
+    let (req, body_writer) = match raw_body {
+        Some(raw_body) => (raw_body.into_request(headers, options), None),
+        None => {
+            let (body_writer, contents_rx, trailers_rx) = BodyWriter::new();
+            let (req, _result) =
+                WasiHttpRequest::new(headers, Some(contents_rx), trailers_rx, options);
+            (req, Some(body_writer))
+        }
+    };
 
     req.set_method(&parts.method.into())
         .map_err(|()| ErrorCode::HttpRequestMethodInvalid)?;
@@ -153,10 +229,12 @@ where
     req.set_path_with_query(parts.uri.path_and_query().map(|pq| pq.as_str()))
         .map_err(|()| ErrorCode::HttpRequestUriInvalid)?;
 
-    wit_bindgen::spawn(async move {
-        let mut body = std::pin::pin!(body);
-        _ = body_writer.send_http_body(&mut body).await;
-    });
+    if let Some(body_writer) = body_writer {
+        crate::spawn(async move {
+            let mut body = std::pin::pin!(body);
+            _ = body_writer.send_http_body(&mut body).await;
+        });
+    }
 
     Ok(req)
 }
@@ -194,8 +272,10 @@ pub fn http_from_wasi_request(req: WasiHttpRequest) -> Result<HttpRequest, Error
 
     let mut builder = http::Request::builder().method(req.get_method()).uri(uri);
 
-    if let Some(options) = req.get_options().map(RequestOptionsExtension) {
-        builder = builder.extension(options);
+    // The options returned by `get_options` are an immutable child of `req`
+    // which must not outlive it, so store a detached deep copy instead.
+    if let Some(options) = req.get_options() {
+        builder = builder.extension(RequestOptionsExtension(options.clone()));
     }
 
     for (k, v) in req.get_headers().copy_all() {
@@ -207,6 +287,26 @@ pub fn http_from_wasi_request(req: WasiHttpRequest) -> Result<HttpRequest, Error
     builder.body(body).map_err(to_internal_error_code) // TODO: downcast to more specific http error codes
 }
 
+/// Returns whether `fields` hold the same headers as `headers`, compared as
+/// [`HeaderMap`]s are, without converting them to one.
+fn same_headers(fields: &Fields, headers: &HeaderMap) -> bool {
+    let entries = fields.copy_all();
+    entries.len() == headers.len()
+        && headers.keys().all(|name| {
+            let values = entries
+                .iter()
+                .filter(|(k, _)| k.eq_ignore_ascii_case(name.as_str()))
+                .map(|(_, v)| v.as_slice());
+            values.eq(headers.get_all(name).iter().map(|v| v.as_bytes()))
+        })
+}
+
+fn take_channel_body<T: Any>(body: &mut T) -> Option<RawBody> {
+    (body as &mut dyn Any)
+        .downcast_mut::<ChannelBody>()
+        .and_then(ChannelBody::take_raw)
+}
+
 impl TryFrom<Scheme> for http::uri::Scheme {
     type Error = http::uri::InvalidUri;
 
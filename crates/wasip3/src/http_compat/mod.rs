@@ -3,15 +3,22 @@
 pub use body_writer::*;
 mod body_writer;
 
+pub use channel_body::*;
+mod channel_body;
+
 pub use conversions::*;
 mod conversions;
 
+pub use request_options::*;
+mod request_options;
+
 use crate::{
     http::types::{self, ErrorCode},
     wit_bindgen::{self, StreamResult},
     wit_future,
 };
 use bytes::Bytes;
+use futures::Stream;
 use http_body::SizeHint;
 use std::prelude::v1::*;
 use std::{
@@ -19,7 +26,8 @@ use std::{
     task::{self, Poll},
 };
 
-const READ_FRAME_SIZE: usize = 16 * 1024;
+/// The default maximum size of data frames yielded by an [`IncomingBody`].
+pub const DEFAULT_READ_FRAME_SIZE: usize = 16 * 1024;
 
 /// The body type used for incoming HTTP requests.
 ///
@@ -64,6 +72,11 @@ pub type Request<T = IncomingRequestBody> = http::Request<T>;
 pub type Response<T = IncomingResponseBody> = http::Response<T>;
 
 /// A wrapper around [`types::RequestOptions`] that implements [`Clone`]
+///
+/// This is read from [`http::Extensions`] by [`http_into_wasi_request`] and
+/// inserted by [`http_from_wasi_request`], so options survive a round trip
+/// through both conversions. See [`RequestOptionsBuilder`] for constructing
+/// options with typed timeouts.
 pub struct RequestOptionsExtension(pub types::RequestOptions);
 
 impl Clone for RequestOptionsExtension {
@@ -72,6 +85,22 @@ impl Clone for RequestOptionsExtension {
     }
 }
 
+/// A wrapper around the [`types::Headers`] of a WASI response that implements
+/// [`Clone`]
+///
+/// This is inserted by [`http_from_wasi_response`] and read by
+/// [`http_into_wasi_response`], which sends these fields rather than
+/// converting [`http::Response::headers`] as long as both hold the same
+/// headers. This preserves what [`http::HeaderMap`] can't represent, such as
+/// the relative order of fields with different names.
+pub struct ResponseHeadersExtension(pub types::Headers);
+
+impl Clone for ResponseHeadersExtension {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
 /// Internal trait representing a readable inbound HTTP message.
 ///
 /// Implemented by types that expose request or response headers
@@ -86,6 +115,12 @@ pub trait IncomingMessage: Unpin {
         wit_bindgen::StreamReader<u8>,
         wit_bindgen::FutureReader<Result<Option<types::Trailers>, ErrorCode>>,
     );
+
+    /// Returns the error reported when the body exceeds the maximum size set
+    /// with [`IncomingBody::with_max_body_size`].
+    fn body_size_error(size: Option<u64>) -> ErrorCode {
+        ErrorCode::HttpRequestBodySize(size)
+    }
 }
 
 impl IncomingMessage for types::Request {
@@ -118,12 +153,24 @@ impl IncomingMessage for types::Response {
     ) {
         Self::consume_body(self, res)
     }
+
+    fn body_size_error(size: Option<u64>) -> ErrorCode {
+        ErrorCode::HttpResponseBodySize(size)
+    }
 }
 
 /// A stream of Bytes, used when receiving bodies from the network.
+///
+/// Each data frame is the buffer it was read into, handed out without
+/// copying. Once a frame is dropped its buffer is read into again, so a body
+/// consumed frame by frame reuses a single allocation of
+/// [`Self::with_frame_size`] bytes.
 pub struct IncomingBody<T> {
     state: StartedState<T>,
     content_length: Option<u64>,
+    frame_size: usize,
+    max_body_size: Option<u64>,
+    received: u64,
 }
 
 enum StartedState<T> {
@@ -147,10 +194,41 @@ impl<T: IncomingMessage> IncomingBody<T> {
     /// be determined.
     pub fn new(msg: T) -> Result<Self, ErrorCode> {
         let content_length = get_content_length(msg.get_headers())?;
-        Ok(Self {
+        Ok(Self::with_content_length(msg, content_length))
+    }
+
+    fn with_content_length(msg: T, content_length: Option<u64>) -> Self {
+        Self {
             state: StartedState::Unstarted(msg),
             content_length,
-        })
+            frame_size: DEFAULT_READ_FRAME_SIZE,
+            max_body_size: None,
+            received: 0,
+        }
+    }
+
+    /// Sets the maximum size of each data frame read from the body.
+    ///
+    /// Defaults to [`DEFAULT_READ_FRAME_SIZE`]. A size of zero is treated as
+    /// one.
+    pub fn with_frame_size(mut self, size: usize) -> Self {
+        self.frame_size = size.max(1);
+        self
+    }
+
+    /// Sets the maximum total size of the body.
+    ///
+    /// Once more than `limit` bytes have been received, or if the
+    /// `Content-Length` header already announces more than that, reading
+    /// fails with [`ErrorCode::HttpRequestBodySize`] for requests and
+    /// [`ErrorCode::HttpResponseBodySize`] for responses, carrying the
+    /// offending size.
+    ///
+    /// The limit also applies to the body returned by [`Self::take_raw`],
+    /// whose trailers then fail with that error.
+    pub fn with_max_body_size(mut self, limit: u64) -> Self {
+        self.max_body_size = Some(limit);
+        self
     }
 
     /// Takes ownership of the inner message if the body has not yet been started.
@@ -172,6 +250,63 @@ impl<T: IncomingMessage> IncomingBody<T> {
         }
     }
 
+    /// Takes the raw body contents stream and trailers future out of this
+    /// body, if no data has been read from it yet.
+    ///
+    /// This allows proxies to pass a body on to a new [`types::Request`] or
+    /// [`types::Response`] without copying it through guest memory, see
+    /// [`RawBody::into_request`] and [`RawBody::into_response`]. Afterwards
+    /// this body can no longer be read from.
+    ///
+    /// With [`Self::with_max_body_size`], the contents are instead forwarded
+    /// through a spawned task which enforces the limit, and so pass through
+    /// guest memory.
+    ///
+    /// Returns `None` if the body has already been polled for data or taken.
+    pub fn take_raw(&mut self) -> Option<RawBody> {
+        let raw = match &self.state {
+            StartedState::Unstarted(_) => {
+                let msg = self.take_unstarted().unwrap();
+                let (result, reader) = wit_future::new(|| Ok(()));
+                let (contents, trailers) = msg.consume_body(reader);
+                RawBody {
+                    contents,
+                    trailers,
+                    result,
+                }
+            }
+            StartedState::Started {
+                state: IncomingState::Ready { .. },
+                ..
+            } => {
+                let StartedState::Started {
+                    result,
+                    state: IncomingState::Ready { stream, trailers },
+                } = std::mem::replace(&mut self.state, StartedState::Empty)
+                else {
+                    unreachable!();
+                };
+                RawBody {
+                    contents: stream,
+                    trailers,
+                    result,
+                }
+            }
+            _ => return None,
+        };
+        match self.max_body_size {
+            Some(limit) => Some(raw.limit::<T>(limit, self.content_length, self.frame_size)),
+            None => Some(raw),
+        }
+    }
+
+    fn unstarted(&self) -> Option<&T> {
+        match &self.state {
+            StartedState::Unstarted(msg) => Some(msg),
+            _ => None,
+        }
+    }
+
     fn ensure_started(&mut self) -> Result<&mut IncomingState, ErrorCode> {
         if let StartedState::Unstarted(_) = self.state {
             let msg = self.take_unstarted().unwrap();
@@ -186,28 +321,187 @@ impl<T: IncomingMessage> IncomingBody<T> {
             StartedState::Started { state, .. } => Ok(state),
             StartedState::Unstarted(_) => unreachable!(),
             StartedState::Empty => Err(to_internal_error_code(
-                "cannot use IncomingBody after call to take_unstarted",
+                "cannot use IncomingBody after call to take_unstarted or take_raw",
             )),
         }
     }
 }
 
+/// The raw contents stream and trailers future of an [`IncomingBody`].
+///
+/// Returned by [`IncomingBody::take_raw`].
+pub struct RawBody {
+    /// The stream of body contents.
+    pub contents: wit_bindgen::StreamReader<u8>,
+    /// The future resolving to the body's trailers once `contents` is closed.
+    pub trailers: wit_bindgen::FutureReader<Result<Option<types::Trailers>, ErrorCode>>,
+    /// Reports the outcome of handling the body back to the sender of the
+    /// original message.
+    pub result: wit_bindgen::FutureWriter<Result<(), ErrorCode>>,
+}
+
+impl RawBody {
+    /// Creates a new [`types::Request`] with this body's contents and
+    /// trailers.
+    ///
+    /// The outcome of transmitting the new request is forwarded to
+    /// [`Self::result`] from a spawned task.
+    pub fn into_request(
+        self,
+        headers: types::Headers,
+        options: Option<types::RequestOptions>,
+    ) -> types::Request {
+        let (request, transmitted) =
+            types::Request::new(headers, Some(self.contents), self.trailers, options);
+        forward_result(self.result, transmitted);
+        request
+    }
+
+    /// Creates a new [`types::Response`] with this body's contents and
+    /// trailers.
+    ///
+    /// The outcome of transmitting the new response is forwarded to
+    /// [`Self::result`] from a spawned task.
+    pub fn into_response(self, headers: types::Headers) -> types::Response {
+        let (response, transmitted) =
+            types::Response::new(headers, Some(self.contents), self.trailers);
+        forward_result(self.result, transmitted);
+        response
+    }
+
+    /// Forwards the contents through a spawned task which fails the body
+    /// with [`IncomingMessage::body_size_error`] once more than `limit`
+    /// bytes, or a `content_length` of more than that, were received.
+    fn limit<T: IncomingMessage>(
+        self,
+        limit: u64,
+        content_length: Option<u64>,
+        frame_size: usize,
+    ) -> RawBody {
+        let (mut writer, contents, trailers) = BodyWriter::new();
+        let RawBody {
+            contents: source,
+            trailers: source_trailers,
+            result,
+        } = self;
+        crate::spawn(async move {
+            let outcome = match content_length {
+                Some(len) if len > limit => Err(T::body_size_error(Some(len))),
+                _ => {
+                    let forwarded =
+                        forward_limited::<T>(source, &mut writer.stream_writer, limit, frame_size)
+                            .await;
+                    match forwarded {
+                        Ok(()) => source_trailers.await,
+                        Err(e) => Err(e),
+                    }
+                }
+            };
+            drop(writer.stream_writer);
+            _ = writer.result_writer.write(outcome).await;
+        });
+        RawBody {
+            contents,
+            trailers,
+            result,
+        }
+    }
+}
+
+/// Copies `source` into `dest` in chunks of at most `frame_size` bytes,
+/// failing once more than `limit` bytes were read.
+async fn forward_limited<T: IncomingMessage>(
+    mut source: wit_bindgen::StreamReader<u8>,
+    dest: &mut wit_bindgen::StreamWriter<u8>,
+    limit: u64,
+    frame_size: usize,
+) -> Result<(), ErrorCode> {
+    let mut received = 0;
+    let mut buf = Vec::with_capacity(frame_size);
+    loop {
+        let (status, read) = source.read(buf).await;
+        match status {
+            StreamResult::Complete(n) => received += n as u64,
+            StreamResult::Cancelled => unreachable!(),
+            StreamResult::Dropped => return Ok(()),
+        }
+        if received > limit {
+            return Err(T::body_size_error(Some(received)));
+        }
+        buf = dest.write_all(read).await;
+        if !buf.is_empty() {
+            return Err(to_internal_error_code("body reader dropped"));
+        }
+    }
+}
+
+fn forward_result(
+    result: wit_bindgen::FutureWriter<Result<(), ErrorCode>>,
+    transmitted: wit_bindgen::FutureReader<Result<(), ErrorCode>>,
+) {
+    crate::spawn(async move {
+        _ = result.write(transmitted.await).await;
+    });
+}
+
 enum IncomingState {
     Ready {
         stream: wit_bindgen::StreamReader<u8>,
         trailers: wit_bindgen::FutureReader<Result<Option<types::Trailers>, ErrorCode>>,
     },
-    Reading(Pin<Box<dyn std::future::Future<Output = ReadResult> + 'static + Send>>),
+    Reading(Frames),
     Done,
 }
 
+/// The frames of a body as they're read, see [`read_frames`].
+type Frames = Pin<Box<dyn Stream<Item = ReadResult> + Send>>;
+
 enum ReadResult {
     Trailers(Result<Option<types::Trailers>, ErrorCode>),
-    BodyChunk {
-        chunk: Vec<u8>,
-        stream: wit_bindgen::StreamReader<u8>,
-        trailers: wit_bindgen::FutureReader<Result<Option<types::Trailers>, ErrorCode>>,
-    },
+    BodyChunk(Bytes),
+}
+
+/// Reads the contents of a body in frames of at most `frame_size` bytes,
+/// followed by its trailers.
+///
+/// The reads all happen in the one future of the returned stream, which
+/// keeps the last frame to read into its buffer again once the caller has
+/// dropped it.
+fn read_frames(
+    stream: wit_bindgen::StreamReader<u8>,
+    trailers: wit_bindgen::FutureReader<Result<Option<types::Trailers>, ErrorCode>>,
+    frame_size: usize,
+) -> Frames {
+    Box::pin(futures::stream::unfold(
+        Some((stream, trailers, None)),
+        move |state| async move {
+            let (mut stream, trailers, last) = state?;
+            let (result, buf) = stream.read(reclaim(last, frame_size)).await;
+            match result {
+                StreamResult::Complete(_n) => {
+                    let chunk = Bytes::from(buf);
+                    let last = Some(chunk.clone());
+                    Some((ReadResult::BodyChunk(chunk), Some((stream, trailers, last))))
+                }
+                StreamResult::Cancelled => unreachable!(),
+                StreamResult::Dropped => Some((ReadResult::Trailers(trailers.await), None)),
+            }
+        },
+    ))
+}
+
+/// Returns an empty buffer with room for `frame_size` bytes, which is the
+/// buffer of the `last` frame if nothing else refers to it anymore.
+fn reclaim(last: Option<Bytes>, frame_size: usize) -> Vec<u8> {
+    let mut buf = match last.map(Bytes::try_into_mut) {
+        Some(Ok(mut buf)) => {
+            buf.clear();
+            Vec::from(buf)
+        }
+        _ => Vec::new(),
+    };
+    buf.reserve_exact(frame_size);
+    buf
 }
 
 impl<T: IncomingMessage> http_body::Body for IncomingBody<T> {
@@ -218,42 +512,43 @@ impl<T: IncomingMessage> http_body::Body for IncomingBody<T> {
         mut self: Pin<&mut Self>,
         cx: &mut task::Context<'_>,
     ) -> Poll<Option<Result<http_body::Frame<Self::Data>, Self::Error>>> {
-        let state = self.ensure_started()?;
+        if let StartedState::Unstarted(_) = self.state {
+            if let (Some(len), Some(limit)) = (self.content_length, self.max_body_size) {
+                if len > limit {
+                    return Poll::Ready(Some(Err(T::body_size_error(Some(len)))));
+                }
+            }
+        }
+        self.ensure_started()?;
+        let this = &mut *self;
+        let StartedState::Started { state, .. } = &mut this.state else {
+            unreachable!();
+        };
         loop {
             match state {
                 IncomingState::Ready { .. } => {
-                    let IncomingState::Ready {
-                        mut stream,
-                        trailers,
-                    } = std::mem::replace(state, IncomingState::Done)
+                    let IncomingState::Ready { stream, trailers } =
+                        std::mem::replace(state, IncomingState::Done)
                     else {
                         unreachable!();
                     };
-                    *state = IncomingState::Reading(Box::pin(async move {
-                        let (result, chunk) =
-                            stream.read(Vec::with_capacity(READ_FRAME_SIZE)).await;
-                        match result {
-                            StreamResult::Complete(_n) => ReadResult::BodyChunk {
-                                chunk,
-                                stream,
-                                trailers,
-                            },
-                            StreamResult::Cancelled => unreachable!(),
-                            StreamResult::Dropped => ReadResult::Trailers(trailers.await),
-                        }
-                    }));
+                    *state = IncomingState::Reading(read_frames(stream, trailers, this.frame_size));
                 }
-                IncomingState::Reading(future) => {
-                    match std::task::ready!(future.as_mut().poll(cx)) {
-                        ReadResult::BodyChunk {
-                            chunk,
-                            stream,
-                            trailers,
-                        } => {
-                            *state = IncomingState::Ready { stream, trailers };
-                            break Poll::Ready(Some(Ok(http_body::Frame::data(chunk.into()))));
+                IncomingState::Reading(frames) => {
+                    match std::task::ready!(frames.as_mut().poll_next(cx)) {
+                        Some(ReadResult::BodyChunk(chunk)) => {
+                            this.received += chunk.len() as u64;
+                            if let Some(limit) = this.max_body_size {
+                                if this.received > limit {
+                                    *state = IncomingState::Done;
+                                    break Poll::Ready(Some(Err(T::body_size_error(Some(
+                                        this.received,
+                                    )))));
+                                }
+                            }
+                            break Poll::Ready(Some(Ok(http_body::Frame::data(chunk))));
                         }
-                        ReadResult::Trailers(trailers) => {
+                        Some(ReadResult::Trailers(trailers)) => {
                             *state = IncomingState::Done;
                             match trailers {
                                 Ok(Some(fields)) => {
@@ -268,6 +563,7 @@ impl<T: IncomingMessage> http_body::Body for IncomingBody<T> {
                                 }
                             }
                         }
+                        None => *state = IncomingState::Done,
                     }
                 }
                 IncomingState::Done => break Poll::Ready(None),
@@ -0,0 +1,220 @@
+use super::RequestOptionsExtension;
+use crate::http::types::{self, RequestOptionsError};
+use core::fmt;
+use core::time::Duration;
+
+/// Identifies one of the timeouts carried by [`types::RequestOptions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Timeout {
+    /// The timeout for the initial connect to the HTTP server.
+    Connect,
+    /// The timeout for receiving the first byte of the response body.
+    FirstByte,
+    /// The timeout for receiving subsequent chunks of the response body.
+    BetweenBytes,
+}
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Timeout::Connect => "connect",
+            Timeout::FirstByte => "first-byte",
+            Timeout::BetweenBytes => "between-bytes",
+        })
+    }
+}
+
+/// Error returned by [`RequestOptionsBuilder::build`] when the host refuses
+/// to set one of the configured timeouts.
+#[derive(Debug, thiserror::Error)]
+#[error("failed to set {timeout} timeout: {error}")]
+pub struct RequestOptionsBuildError {
+    /// The timeout which could not be set.
+    pub timeout: Timeout,
+    /// The error reported by the host.
+    #[source]
+    pub error: RequestOptionsError,
+}
+
+/// A typed builder for [`types::RequestOptions`].
+///
+/// Timeouts are expressed as [`Duration`]s and converted to the nanosecond
+/// `wasi:clocks` durations used by the host, saturating at `u64::MAX`.
+/// Timeouts which are left unset are not passed to the host at all, so hosts
+/// which don't support a particular timeout only fail if it is requested.
+///
+/// ```no_run
+/// use core::time::Duration;
+/// use wasip3::http_compat::{RequestOptionsBuilder, RequestOptionsExtension};
+///
+/// # fn main() -> Result<(), wasip3::http_compat::RequestOptionsBuildError> {
+/// let options = RequestOptionsBuilder::new()
+///     .connect_timeout(Duration::from_secs(5))
+///     .first_byte_timeout(Duration::from_secs(30))
+///     .build()?;
+///
+/// let request = http::Request::builder()
+///     .uri("https://example.com/")
+///     .extension(RequestOptionsExtension(options))
+///     .body(String::new());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestOptionsBuilder {
+    connect_timeout: Option<Duration>,
+    first_byte_timeout: Option<Duration>,
+    between_bytes_timeout: Option<Duration>,
+}
+
+impl RequestOptionsBuilder {
+    /// Creates a builder with no timeouts configured.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the timeout for the initial connect to the HTTP server.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for receiving the first byte of the response body.
+    pub fn first_byte_timeout(mut self, timeout: Duration) -> Self {
+        self.first_byte_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for receiving subsequent chunks of the response body.
+    pub fn between_bytes_timeout(mut self, timeout: Duration) -> Self {
+        self.between_bytes_timeout = Some(timeout);
+        self
+    }
+
+    /// Creates a new [`types::RequestOptions`] resource with the configured
+    /// timeouts.
+    ///
+    /// Returns a [`RequestOptionsBuildError`] naming the first timeout the
+    /// host refused to set.
+    pub fn build(self) -> Result<types::RequestOptions, RequestOptionsBuildError> {
+        let options = types::RequestOptions::new();
+        if let Some(timeout) = self.connect_timeout {
+            options
+                .set_connect_timeout(Some(to_wasi_duration(timeout)))
+                .map_err(|error| RequestOptionsBuildError {
+                    timeout: Timeout::Connect,
+                    error,
+                })?;
+        }
+        if let Some(timeout) = self.first_byte_timeout {
+            options
+                .set_first_byte_timeout(Some(to_wasi_duration(timeout)))
+                .map_err(|error| RequestOptionsBuildError {
+                    timeout: Timeout::FirstByte,
+                    error,
+                })?;
+        }
+        if let Some(timeout) = self.between_bytes_timeout {
+            options
+                .set_between_bytes_timeout(Some(to_wasi_duration(timeout)))
+                .map_err(|error| RequestOptionsBuildError {
+                    timeout: Timeout::BetweenBytes,
+                    error,
+                })?;
+        }
+        Ok(options)
+    }
+
+    /// Like [`Self::build`], but wraps the result in a
+    /// [`RequestOptionsExtension`] ready to be inserted into
+    /// [`http::Extensions`].
+    pub fn build_extension(self) -> Result<RequestOptionsExtension, RequestOptionsBuildError> {
+        self.build().map(RequestOptionsExtension)
+    }
+}
+
+impl From<&types::RequestOptions> for RequestOptionsBuilder {
+    fn from(options: &types::RequestOptions) -> Self {
+        Self {
+            connect_timeout: options.get_connect_timeout().map(Duration::from_nanos),
+            first_byte_timeout: options.get_first_byte_timeout().map(Duration::from_nanos),
+            between_bytes_timeout: options
+                .get_between_bytes_timeout()
+                .map(Duration::from_nanos),
+        }
+    }
+}
+
+impl RequestOptionsExtension {
+    /// Returns the timeout for the initial connect to the HTTP server.
+    pub fn connect_timeout(&self) -> Option<Duration> {
+        self.0.get_connect_timeout().map(Duration::from_nanos)
+    }
+
+    /// Returns the timeout for receiving the first byte of the response body.
+    pub fn first_byte_timeout(&self) -> Option<Duration> {
+        self.0.get_first_byte_timeout().map(Duration::from_nanos)
+    }
+
+    /// Returns the timeout for receiving subsequent chunks of the response
+    /// body.
+    pub fn between_bytes_timeout(&self) -> Option<Duration> {
+        self.0.get_between_bytes_timeout().map(Duration::from_nanos)
+    }
+}
+
+fn to_wasi_duration(duration: Duration) -> u64 {
+    duration.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::format;
+    use std::string::ToString;
+
+    #[test]
+    fn builder_sets_timeouts() {
+        let builder = RequestOptionsBuilder::new()
+            .connect_timeout(Duration::from_secs(1))
+            .between_bytes_timeout(Duration::from_millis(2));
+        assert_eq!(builder.connect_timeout, Some(Duration::from_secs(1)));
+        assert_eq!(builder.first_byte_timeout, None);
+        assert_eq!(
+            builder.between_bytes_timeout,
+            Some(Duration::from_millis(2))
+        );
+        assert_eq!(
+            RequestOptionsBuilder::new(),
+            RequestOptionsBuilder::default()
+        );
+    }
+
+    #[test]
+    fn durations_saturate() {
+        assert_eq!(to_wasi_duration(Duration::ZERO), 0);
+        assert_eq!(to_wasi_duration(Duration::new(1, 5)), 1_000_000_005);
+        assert_eq!(to_wasi_duration(Duration::from_nanos(u64::MAX)), u64::MAX);
+        assert_eq!(to_wasi_duration(Duration::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn build_errors_name_timeout() {
+        let error = RequestOptionsBuildError {
+            timeout: Timeout::BetweenBytes,
+            error: RequestOptionsError::NotSupported,
+        };
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "failed to set between-bytes timeout: {}",
+                RequestOptionsError::NotSupported
+            )
+        );
+        let source = error.source().unwrap().to_string();
+        assert_eq!(source, RequestOptionsError::NotSupported.to_string());
+        assert_eq!(Timeout::Connect.to_string(), "connect");
+        assert_eq!(Timeout::FirstByte.to_string(), "first-byte");
+    }
+}
@@ -36,13 +36,14 @@ pub mod wasi {
                             * ::core::mem::size_of::<*const u8>()],
                     );
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/environment@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "get-environment"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-environment")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/environment@0.3.0#get-environment")]
                         fn wit_import1(_: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
@@ -102,13 +103,14 @@ pub mod wasi {
                             * ::core::mem::size_of::<*const u8>()],
                     );
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/environment@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "get-arguments"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-arguments")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/environment@0.3.0#get-arguments")]
                         fn wit_import1(_: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
@@ -161,13 +163,14 @@ pub mod wasi {
                             * ::core::mem::size_of::<*const u8>()],
                     );
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/environment@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "get-initial-cwd"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-initial-cwd")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/environment@0.3.0#get-initial-cwd")]
                         fn wit_import1(_: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
@@ -214,13 +217,14 @@ pub mod wasi {
                         Ok(_) => 0i32,
                         Err(_) => 1i32,
                     };
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/exit@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "exit"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "exit")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/exit@0.3.0#exit")]
                         fn wit_import1(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: i32) {
                         unreachable!()
                     }
@@ -239,13 +243,14 @@ pub mod wasi {
             #[allow(async_fn_in_trait)]
             pub fn exit_with_code(status_code: u8) -> () {
                 unsafe {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/exit@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "exit-with-code"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "exit-with-code")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/exit@0.3.0#exit-with-code")]
                         fn wit_import0(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import0(_: i32) {
                         unreachable!()
                     }
@@ -328,13 +333,14 @@ pub mod wasi {
                     struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/stdin@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "read-via-stream"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "read-via-stream")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/stdin@0.3.0#read-via-stream")]
                         fn wit_import1(_: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
@@ -378,13 +384,14 @@ pub mod wasi {
                 data: wit_bindgen::rt::async_support::StreamReader<u8>,
             ) -> wit_bindgen::rt::async_support::FutureReader<Result<(), ErrorCode>> {
                 unsafe {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/stdout@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "write-via-stream"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "write-via-stream")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/stdout@0.3.0#write-via-stream")]
                         fn wit_import0(_: i32) -> i32;
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                         unreachable!()
                     }
@@ -419,13 +426,14 @@ pub mod wasi {
                 data: wit_bindgen::rt::async_support::StreamReader<u8>,
             ) -> wit_bindgen::rt::async_support::FutureReader<Result<(), ErrorCode>> {
                 unsafe {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/stderr@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "write-via-stream"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "write-via-stream")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/stderr@0.3.0#write-via-stream")]
                         fn wit_import0(_: i32) -> i32;
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                         unreachable!()
                     }
@@ -476,13 +484,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for TerminalInput {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/terminal-input@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]terminal-input"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]terminal-input")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/terminal-input@0.3.0#[resource-drop]terminal-input")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -528,13 +537,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for TerminalOutput {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/terminal-output@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]terminal-output"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]terminal-output")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/terminal-output@0.3.0#[resource-drop]terminal-output")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -563,13 +573,14 @@ pub mod wasi {
                     struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/terminal-stdin@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "get-terminal-stdin"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-terminal-stdin")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/terminal-stdin@0.3.0#get-terminal-stdin")]
                         fn wit_import1(_: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
@@ -611,13 +622,14 @@ pub mod wasi {
                     struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/terminal-stdout@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "get-terminal-stdout"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-terminal-stdout")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/terminal-stdout@0.3.0#get-terminal-stdout")]
                         fn wit_import1(_: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
@@ -659,13 +671,14 @@ pub mod wasi {
                     struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:cli/terminal-stderr@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "get-terminal-stderr"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-terminal-stderr")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:cli/terminal-stderr@0.3.0#get-terminal-stderr")]
                         fn wit_import1(_: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
@@ -731,13 +744,14 @@ pub mod wasi {
             #[allow(async_fn_in_trait)]
             pub fn now() -> Mark {
                 unsafe {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:clocks/monotonic-clock@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "now"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "now")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:clocks/monotonic-clock@0.3.0#now")]
                         fn wit_import0() -> i64;
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import0() -> i64 {
                         unreachable!()
                     }
@@ -751,13 +765,14 @@ pub mod wasi {
             #[allow(async_fn_in_trait)]
             pub fn get_resolution() -> Duration {
                 unsafe {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:clocks/monotonic-clock@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "get-resolution"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-resolution")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:clocks/monotonic-clock@0.3.0#get-resolution")]
                         fn wit_import0() -> i64;
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import0() -> i64 {
                         unreachable!()
                     }
@@ -795,15 +810,16 @@ pub mod wasi {
                             _params: Self::ParamsLower,
                             _results: *mut u8,
                         ) -> u32 {
-                            #[cfg(target_arch = "wasm32")]
+                            #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                             #[link(
                                 wasm_import_module = "wasi:clocks/monotonic-clock@0.3.0"
                             )]
                             unsafe extern "C" {
-                                #[link_name = "[async-lower]wait-until"]
+                                #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower]wait-until")]
+                                #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:clocks/monotonic-clock@0.3.0#[async-lower]wait-until")]
                                 fn call(_: i64) -> i32;
                             }
-                            #[cfg(not(target_arch = "wasm32"))]
+                            #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                             unsafe extern "C" fn call(_: i64) -> i32 {
                                 unreachable!()
                             }
@@ -870,15 +886,16 @@ pub mod wasi {
                             _params: Self::ParamsLower,
                             _results: *mut u8,
                         ) -> u32 {
-                            #[cfg(target_arch = "wasm32")]
+                            #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                             #[link(
                                 wasm_import_module = "wasi:clocks/monotonic-clock@0.3.0"
                             )]
                             unsafe extern "C" {
-                                #[link_name = "[async-lower]wait-for"]
+                                #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower]wait-for")]
+                                #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:clocks/monotonic-clock@0.3.0#[async-lower]wait-for")]
                                 fn call(_: i64) -> i32;
                             }
-                            #[cfg(not(target_arch = "wasm32"))]
+                            #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                             unsafe extern "C" fn call(_: i64) -> i32 {
                                 unreachable!()
                             }
@@ -979,13 +996,14 @@ pub mod wasi {
                     struct RetArea([::core::mem::MaybeUninit<u8>; 16]);
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 16]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:clocks/system-clock@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "now"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "now")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:clocks/system-clock@0.3.0#now")]
                         fn wit_import1(_: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
@@ -1005,13 +1023,14 @@ pub mod wasi {
             #[allow(async_fn_in_trait)]
             pub fn get_resolution() -> Duration {
                 unsafe {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:clocks/system-clock@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "get-resolution"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-resolution")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:clocks/system-clock@0.3.0#get-resolution")]
                         fn wit_import0() -> i64;
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import0() -> i64 {
                         unreachable!()
                     }
@@ -1580,13 +1599,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for Descriptor {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]descriptor"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]descriptor")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[resource-drop]descriptor")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -1628,13 +1648,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 8],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.read-via-stream"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.read-via-stream")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[method]descriptor.read-via-stream")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -1681,13 +1702,14 @@ pub mod wasi {
                     Result<(), ErrorCode>,
                 > {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.write-via-stream"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.write-via-stream")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[method]descriptor.write-via-stream")]
                             fn wit_import0(_: i32, _: i32, _: i64) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32, _: i32, _: i64) -> i32 {
                             unreachable!()
                         }
@@ -1724,13 +1746,14 @@ pub mod wasi {
                     Result<(), ErrorCode>,
                 > {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.append-via-stream"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.append-via-stream")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[method]descriptor.append-via-stream")]
                             fn wit_import0(_: i32, _: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32, _: i32) -> i32 {
                             unreachable!()
                         }
@@ -1789,13 +1812,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.advise"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.advise")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.advise")]
                                     fn call(_: i32, _: i64, _: i64, _: i32, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(
                                     _: i32,
                                     _: i64,
@@ -1979,13 +2003,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.sync-data"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.sync-data")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.sync-data")]
                                     fn call(_: i32, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(_: i32, _: *mut u8) -> i32 {
                                     unreachable!()
                                 }
@@ -2153,13 +2178,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.get-flags"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.get-flags")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.get-flags")]
                                     fn call(_: i32, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(_: i32, _: *mut u8) -> i32 {
                                     unreachable!()
                                 }
@@ -2337,13 +2363,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.get-type"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.get-type")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.get-type")]
                                     fn call(_: i32, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(_: i32, _: *mut u8) -> i32 {
                                     unreachable!()
                                 }
@@ -2556,13 +2583,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.set-size"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.set-size")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.set-size")]
                                     fn call(_: i32, _: i64, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(
                                     _: i32,
                                     _: i64,
@@ -2739,13 +2767,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.set-times"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.set-times")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.set-times")]
                                     fn call(_: *mut u8, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(_: *mut u8, _: *mut u8) -> i32 {
                                     unreachable!()
                                 }
@@ -2955,13 +2984,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 8],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]descriptor.read-directory"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]descriptor.read-directory")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[method]descriptor.read-directory")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -3024,13 +3054,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.sync"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.sync")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.sync")]
                                     fn call(_: i32, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(_: i32, _: *mut u8) -> i32 {
                                     unreachable!()
                                 }
@@ -3198,13 +3229,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.create-directory-at"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.create-directory-at")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.create-directory-at")]
                                     fn call(_: i32, _: *mut u8, _: usize, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(
                                     _: i32,
                                     _: *mut u8,
@@ -3396,13 +3428,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.stat"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.stat")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.stat")]
                                     fn call(_: i32, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(_: i32, _: *mut u8) -> i32 {
                                     unreachable!()
                                 }
@@ -3700,10 +3733,11 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.stat-at"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.stat-at")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.stat-at")]
                                     fn call(
                                         _: i32,
                                         _: i32,
@@ -3712,7 +3746,7 @@ pub mod wasi {
                                         _: *mut u8,
                                     ) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(
                                     _: i32,
                                     _: i32,
@@ -4042,13 +4076,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.set-times-at"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.set-times-at")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.set-times-at")]
                                     fn call(_: *mut u8, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(_: *mut u8, _: *mut u8) -> i32 {
                                     unreachable!()
                                 }
@@ -4319,13 +4354,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.link-at"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.link-at")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.link-at")]
                                     fn call(_: *mut u8, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(_: *mut u8, _: *mut u8) -> i32 {
                                     unreachable!()
                                 }
@@ -4591,13 +4627,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.open-at"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.open-at")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.open-at")]
                                     fn call(_: *mut u8, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(_: *mut u8, _: *mut u8) -> i32 {
                                     unreachable!()
                                 }
@@ -4825,13 +4862,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.readlink-at"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.readlink-at")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.readlink-at")]
                                     fn call(_: i32, _: *mut u8, _: usize, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(
                                     _: i32,
                                     _: *mut u8,
@@ -5036,13 +5074,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.remove-directory-at"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.remove-directory-at")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.remove-directory-at")]
                                     fn call(_: i32, _: *mut u8, _: usize, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(
                                     _: i32,
                                     _: *mut u8,
@@ -5238,13 +5277,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.rename-at"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.rename-at")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.rename-at")]
                                     fn call(_: *mut u8, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(_: *mut u8, _: *mut u8) -> i32 {
                                     unreachable!()
                                 }
@@ -5492,13 +5532,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.symlink-at"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.symlink-at")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.symlink-at")]
                                     fn call(_: *mut u8, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(_: *mut u8, _: *mut u8) -> i32 {
                                     unreachable!()
                                 }
@@ -5741,13 +5782,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.unlink-file-at"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.unlink-file-at")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.unlink-file-at")]
                                     fn call(_: i32, _: *mut u8, _: usize, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(
                                     _: i32,
                                     _: *mut u8,
@@ -5933,13 +5975,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.is-same-object"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.is-same-object")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.is-same-object")]
                                     fn call(_: i32, _: i32, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(
                                     _: i32,
                                     _: i32,
@@ -6045,13 +6088,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.metadata-hash"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.metadata-hash")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.metadata-hash")]
                                     fn call(_: i32, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(_: i32, _: *mut u8) -> i32 {
                                     unreachable!()
                                 }
@@ -6226,10 +6270,11 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]descriptor.metadata-hash-at"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]descriptor.metadata-hash-at")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/types@0.3.0#[async-lower][method]descriptor.metadata-hash-at")]
                                     fn call(
                                         _: i32,
                                         _: i32,
@@ -6238,7 +6283,7 @@ pub mod wasi {
                                         _: *mut u8,
                                     ) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(
                                     _: i32,
                                     _: i32,
@@ -6422,13 +6467,14 @@ pub mod wasi {
                             * ::core::mem::size_of::<*const u8>()],
                     );
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:filesystem/preopens@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "get-directories"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-directories")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:filesystem/preopens@0.3.0#get-directories")]
                         fn wit_import1(_: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
@@ -6520,13 +6566,14 @@ pub mod wasi {
                             * ::core::mem::size_of::<*const u8>()],
                     );
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:random/random@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "get-random-bytes"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-random-bytes")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:random/random@0.3.0#get-random-bytes")]
                         fn wit_import1(_: i64, _: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: i64, _: *mut u8) {
                         unreachable!()
                     }
@@ -6550,13 +6597,14 @@ pub mod wasi {
             #[allow(async_fn_in_trait)]
             pub fn get_random_u64() -> u64 {
                 unsafe {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:random/random@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "get-random-u64"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-random-u64")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:random/random@0.3.0#get-random-u64")]
                         fn wit_import0() -> i64;
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import0() -> i64 {
                         unreachable!()
                     }
@@ -6606,13 +6654,14 @@ pub mod wasi {
                             * ::core::mem::size_of::<*const u8>()],
                     );
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:random/insecure@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "get-insecure-random-bytes"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-insecure-random-bytes")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:random/insecure@0.3.0#get-insecure-random-bytes")]
                         fn wit_import1(_: i64, _: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: i64, _: *mut u8) {
                         unreachable!()
                     }
@@ -6636,13 +6685,14 @@ pub mod wasi {
             #[allow(async_fn_in_trait)]
             pub fn get_insecure_random_u64() -> u64 {
                 unsafe {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:random/insecure@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "get-insecure-random-u64"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-insecure-random-u64")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:random/insecure@0.3.0#get-insecure-random-u64")]
                         fn wit_import0() -> i64;
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import0() -> i64 {
                         unreachable!()
                     }
@@ -6685,13 +6735,14 @@ pub mod wasi {
                     struct RetArea([::core::mem::MaybeUninit<u8>; 16]);
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 16]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:random/insecure-seed@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "get-insecure-seed"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "get-insecure-seed")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:random/insecure-seed@0.3.0#get-insecure-seed")]
                         fn wit_import1(_: *mut u8);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
@@ -7028,13 +7079,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for TcpSocket {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]tcp-socket"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]tcp-socket")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[resource-drop]tcp-socket")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -7068,13 +7120,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for UdpSocket {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]udp-socket"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]udp-socket")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[resource-drop]udp-socket")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -7120,13 +7173,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[static]tcp-socket.create"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[static]tcp-socket.create")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[static]tcp-socket.create")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -7315,10 +7369,11 @@ pub mod wasi {
                             }
                         };
                         let ptr5 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.bind"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.bind")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.bind")]
                             fn wit_import6(
                                 _: i32,
                                 _: i32,
@@ -7336,7 +7391,7 @@ pub mod wasi {
                                 _: *mut u8,
                             );
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import6(
                             _: i32,
                             _: i32,
@@ -7511,13 +7566,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]tcp-socket.connect"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]tcp-socket.connect")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[async-lower][method]tcp-socket.connect")]
                                     fn call(_: *mut u8, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(_: *mut u8, _: *mut u8) -> i32 {
                                     unreachable!()
                                 }
@@ -7767,13 +7823,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.listen"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.listen")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.listen")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -7888,13 +7945,14 @@ pub mod wasi {
                     Result<(), ErrorCode>,
                 > {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.send"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.send")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.send")]
                             fn wit_import0(_: i32, _: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32, _: i32) -> i32 {
                             unreachable!()
                         }
@@ -7955,13 +8013,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 8],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.receive"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.receive")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.receive")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -8019,13 +8078,14 @@ pub mod wasi {
                                 + 1 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.get-local-address"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.get-local-address")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.get-local-address")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -8240,13 +8300,14 @@ pub mod wasi {
                                 + 1 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.get-remote-address"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.get-remote-address")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.get-remote-address")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -8442,13 +8503,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn get_is_listening(&self) -> bool {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.get-is-listening"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.get-is-listening")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.get-is-listening")]
                             fn wit_import0(_: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
@@ -8467,13 +8529,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn get_address_family(&self) -> IpAddressFamily {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.get-address-family"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.get-address-family")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.get-address-family")]
                             fn wit_import0(_: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
@@ -8513,13 +8576,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.set-listen-backlog-size"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.set-listen-backlog-size")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.set-listen-backlog-size")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -8621,13 +8685,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.get-keep-alive-enabled"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.get-keep-alive-enabled")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.get-keep-alive-enabled")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -8727,13 +8792,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.set-keep-alive-enabled"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.set-keep-alive-enabled")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.set-keep-alive-enabled")]
                             fn wit_import1(_: i32, _: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -8843,13 +8909,14 @@ pub mod wasi {
                                 + 4 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.get-keep-alive-idle-time"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.get-keep-alive-idle-time")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.get-keep-alive-idle-time")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -8945,13 +9012,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.set-keep-alive-idle-time"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.set-keep-alive-idle-time")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.set-keep-alive-idle-time")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -9053,13 +9121,14 @@ pub mod wasi {
                                 + 4 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.get-keep-alive-interval"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.get-keep-alive-interval")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.get-keep-alive-interval")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -9155,13 +9224,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.set-keep-alive-interval"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.set-keep-alive-interval")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.set-keep-alive-interval")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -9265,13 +9335,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.get-keep-alive-count"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.get-keep-alive-count")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.get-keep-alive-count")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -9368,13 +9439,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.set-keep-alive-count"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.set-keep-alive-count")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.set-keep-alive-count")]
                             fn wit_import1(_: i32, _: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -9472,13 +9544,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.get-hop-limit"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.get-hop-limit")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.get-hop-limit")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -9575,13 +9648,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.set-hop-limit"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.set-hop-limit")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.set-hop-limit")]
                             fn wit_import1(_: i32, _: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -9693,13 +9767,14 @@ pub mod wasi {
                                 + 4 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.get-receive-buffer-size"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.get-receive-buffer-size")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.get-receive-buffer-size")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -9795,13 +9870,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.set-receive-buffer-size"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.set-receive-buffer-size")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.set-receive-buffer-size")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -9892,13 +9968,14 @@ pub mod wasi {
                                 + 4 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.get-send-buffer-size"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.get-send-buffer-size")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.get-send-buffer-size")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -9991,13 +10068,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]tcp-socket.set-send-buffer-size"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]tcp-socket.set-send-buffer-size")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]tcp-socket.set-send-buffer-size")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -10106,13 +10184,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[static]udp-socket.create"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[static]udp-socket.create")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[static]udp-socket.create")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -10287,10 +10366,11 @@ pub mod wasi {
                             }
                         };
                         let ptr5 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.bind"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.bind")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]udp-socket.bind")]
                             fn wit_import6(
                                 _: i32,
                                 _: i32,
@@ -10308,7 +10388,7 @@ pub mod wasi {
                                 _: *mut u8,
                             );
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import6(
                             _: i32,
                             _: i32,
@@ -10525,10 +10605,11 @@ pub mod wasi {
                             }
                         };
                         let ptr5 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.connect"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.connect")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]udp-socket.connect")]
                             fn wit_import6(
                                 _: i32,
                                 _: i32,
@@ -10546,7 +10627,7 @@ pub mod wasi {
                                 _: *mut u8,
                             );
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import6(
                             _: i32,
                             _: i32,
@@ -10683,13 +10764,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.disconnect"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.disconnect")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]udp-socket.disconnect")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -10847,13 +10929,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]udp-socket.send"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]udp-socket.send")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[async-lower][method]udp-socket.send")]
                                     fn call(_: *mut u8, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(_: *mut u8, _: *mut u8) -> i32 {
                                     unreachable!()
                                 }
@@ -11124,13 +11207,14 @@ pub mod wasi {
                                 _params: Self::ParamsLower,
                                 _results: *mut u8,
                             ) -> u32 {
-                                #[cfg(target_arch = "wasm32")]
+                                #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                                 #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                                 unsafe extern "C" {
-                                    #[link_name = "[async-lower][method]udp-socket.receive"]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower][method]udp-socket.receive")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[async-lower][method]udp-socket.receive")]
                                     fn call(_: i32, _: *mut u8) -> i32;
                                 }
-                                #[cfg(not(target_arch = "wasm32"))]
+                                #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                                 unsafe extern "C" fn call(_: i32, _: *mut u8) -> i32 {
                                     unreachable!()
                                 }
@@ -11398,13 +11482,14 @@ pub mod wasi {
                                 + 1 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.get-local-address"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.get-local-address")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]udp-socket.get-local-address")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -11619,13 +11704,14 @@ pub mod wasi {
                                 + 1 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.get-remote-address"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.get-remote-address")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]udp-socket.get-remote-address")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -11823,13 +11909,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn get_address_family(&self) -> IpAddressFamily {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.get-address-family"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.get-address-family")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]udp-socket.get-address-family")]
                             fn wit_import0(_: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
@@ -11861,13 +11948,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.get-unicast-hop-limit"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.get-unicast-hop-limit")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]udp-socket.get-unicast-hop-limit")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -11964,13 +12052,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.set-unicast-hop-limit"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.set-unicast-hop-limit")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]udp-socket.set-unicast-hop-limit")]
                             fn wit_import1(_: i32, _: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -12074,13 +12163,14 @@ pub mod wasi {
                                 + 4 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.get-receive-buffer-size"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.get-receive-buffer-size")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]udp-socket.get-receive-buffer-size")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -12176,13 +12266,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.set-receive-buffer-size"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.set-receive-buffer-size")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]udp-socket.set-receive-buffer-size")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -12273,13 +12364,14 @@ pub mod wasi {
                                 + 4 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.get-send-buffer-size"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.get-send-buffer-size")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]udp-socket.get-send-buffer-size")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -12372,13 +12464,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]udp-socket.set-send-buffer-size"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]udp-socket.set-send-buffer-size")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/types@0.3.0#[method]udp-socket.set-send-buffer-size")]
                             fn wit_import1(_: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
                             unreachable!()
                         }
@@ -12579,15 +12672,16 @@ pub mod wasi {
                             _params: Self::ParamsLower,
                             _results: *mut u8,
                         ) -> u32 {
-                            #[cfg(target_arch = "wasm32")]
+                            #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                             #[link(
                                 wasm_import_module = "wasi:sockets/ip-name-lookup@0.3.0"
                             )]
                             unsafe extern "C" {
-                                #[link_name = "[async-lower]resolve-addresses"]
+                                #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower]resolve-addresses")]
+                                #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:sockets/ip-name-lookup@0.3.0#[async-lower]resolve-addresses")]
                                 fn call(_: *mut u8, _: usize, _: *mut u8) -> i32;
                             }
-                            #[cfg(not(target_arch = "wasm32"))]
+                            #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                             unsafe extern "C" fn call(
                                 _: *mut u8,
                                 _: usize,
@@ -12965,55 +13059,99 @@ pub mod wit_future {
     #[doc(hidden)]
     #[allow(unused_unsafe)]
     pub mod vtable0 {
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn cancel_write(_: u32) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn cancel_read(_: u32) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn drop_writable(_: u32) {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn drop_readable(_: u32) {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn new() -> u64 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn start_read(_: u32, _: *mut u8) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn start_write(_: u32, _: *const u8) -> u32 {
             unreachable!()
         }
-        #[cfg(target_arch = "wasm32")]
+        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
         #[link(wasm_import_module = "wasi:cli/stdin@0.3.0")]
         unsafe extern "C" {
-            #[link_name = "[future-new-1]read-via-stream"]
+            #[cfg_attr(target_arch = "wasm32", link_name = "[future-new-1]read-via-stream")]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:cli/stdin@0.3.0#[future-new-1]read-via-stream"
+            )]
             fn new() -> u64;
-            #[link_name = "[future-cancel-write-1]read-via-stream"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-cancel-write-1]read-via-stream"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:cli/stdin@0.3.0#[future-cancel-write-1]read-via-stream"
+            )]
             fn cancel_write(_: u32) -> u32;
-            #[link_name = "[future-cancel-read-1]read-via-stream"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-cancel-read-1]read-via-stream"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:cli/stdin@0.3.0#[future-cancel-read-1]read-via-stream"
+            )]
             fn cancel_read(_: u32) -> u32;
-            #[link_name = "[future-drop-writable-1]read-via-stream"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-drop-writable-1]read-via-stream"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:cli/stdin@0.3.0#[future-drop-writable-1]read-via-stream"
+            )]
             fn drop_writable(_: u32);
-            #[link_name = "[future-drop-readable-1]read-via-stream"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-drop-readable-1]read-via-stream"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:cli/stdin@0.3.0#[future-drop-readable-1]read-via-stream"
+            )]
             fn drop_readable(_: u32);
-            #[link_name = "[async-lower][future-read-1]read-via-stream"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[async-lower][future-read-1]read-via-stream"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:cli/stdin@0.3.0#[async-lower][future-read-1]read-via-stream"
+            )]
             fn start_read(_: u32, _: *mut u8) -> u32;
-            #[link_name = "[async-lower][future-write-1]read-via-stream"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[async-lower][future-write-1]read-via-stream"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:cli/stdin@0.3.0#[async-lower][future-write-1]read-via-stream"
+            )]
             fn start_write(_: u32, _: *const u8) -> u32;
         }
-        unsafe fn lift(
-            ptr: *mut u8,
-        ) -> Result<(), super::super::wasi::cli::stdin::ErrorCode> {
+        unsafe fn lift(ptr: *mut u8) -> Result<(), super::super::wasi::cli::stdin::ErrorCode> {
             unsafe {
                 let l0 = i32::from(*ptr.add(0).cast::<u8>());
                 match l0 {
@@ -13068,58 +13206,106 @@ pub mod wit_future {
             start_read,
             start_write,
         };
-        impl super::FuturePayload
-        for Result<(), super::super::wasi::cli::stdin::ErrorCode> {
+        impl super::FuturePayload for Result<(), super::super::wasi::cli::stdin::ErrorCode> {
             const VTABLE: &'static wit_bindgen::rt::async_support::FutureVtable<Self> = &VTABLE;
         }
     }
     #[doc(hidden)]
     #[allow(unused_unsafe)]
     pub mod vtable1 {
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn cancel_write(_: u32) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn cancel_read(_: u32) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn drop_writable(_: u32) {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn drop_readable(_: u32) {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn new() -> u64 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn start_read(_: u32, _: *mut u8) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn start_write(_: u32, _: *const u8) -> u32 {
             unreachable!()
         }
-        #[cfg(target_arch = "wasm32")]
+        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
         #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
         unsafe extern "C" {
-            #[link_name = "[future-new-1][method]descriptor.read-via-stream"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-new-1][method]descriptor.read-via-stream"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:filesystem/types@0.3.0#[future-new-1][method]descriptor.read-via-stream"
+            )]
             fn new() -> u64;
-            #[link_name = "[future-cancel-write-1][method]descriptor.read-via-stream"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-cancel-write-1][method]descriptor.read-via-stream"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:filesystem/types@0.3.0#[future-cancel-write-1][method]descriptor.read-via-stream"
+            )]
             fn cancel_write(_: u32) -> u32;
-            #[link_name = "[future-cancel-read-1][method]descriptor.read-via-stream"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-cancel-read-1][method]descriptor.read-via-stream"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:filesystem/types@0.3.0#[future-cancel-read-1][method]descriptor.read-via-stream"
+            )]
             fn cancel_read(_: u32) -> u32;
-            #[link_name = "[future-drop-writable-1][method]descriptor.read-via-stream"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-drop-writable-1][method]descriptor.read-via-stream"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:filesystem/types@0.3.0#[future-drop-writable-1][method]descriptor.read-via-stream"
+            )]
             fn drop_writable(_: u32);
-            #[link_name = "[future-drop-readable-1][method]descriptor.read-via-stream"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-drop-readable-1][method]descriptor.read-via-stream"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:filesystem/types@0.3.0#[future-drop-readable-1][method]descriptor.read-via-stream"
+            )]
             fn drop_readable(_: u32);
-            #[link_name = "[async-lower][future-read-1][method]descriptor.read-via-stream"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[async-lower][future-read-1][method]descriptor.read-via-stream"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:filesystem/types@0.3.0#[async-lower][future-read-1][method]descriptor.read-via-stream"
+            )]
             fn start_read(_: u32, _: *mut u8) -> u32;
-            #[link_name = "[async-lower][future-write-1][method]descriptor.read-via-stream"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[async-lower][future-write-1][method]descriptor.read-via-stream"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:filesystem/types@0.3.0#[async-lower][future-write-1][method]descriptor.read-via-stream"
+            )]
             fn start_write(_: u32, _: *const u8) -> u32;
         }
         unsafe fn lift(
@@ -13179,8 +13365,7 @@ pub mod wit_future {
                                     debug_assert_eq!(n, 36, "invalid enum discriminant");
                                     let e6 = {
                                         let l2 = i32::from(
-                                            *ptr
-                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                            *ptr.add(2 * ::core::mem::size_of::<*const u8>())
                                                 .cast::<u8>(),
                                         );
                                         match l2 {
@@ -13188,17 +13373,22 @@ pub mod wit_future {
                                             1 => {
                                                 let e = {
                                                     let l3 = *ptr
-                                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                                        .add(
+                                                            3 * ::core::mem::size_of::<*const u8>(),
+                                                        )
                                                         .cast::<*mut u8>();
                                                     let l4 = *ptr
-                                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                                        .add(
+                                                            4 * ::core::mem::size_of::<*const u8>(),
+                                                        )
                                                         .cast::<usize>();
                                                     let len5 = l4;
-                                                    let bytes5 = super::super::_rt::Vec::from_raw_parts(
-                                                        l3.cast(),
-                                                        len5,
-                                                        len5,
-                                                    );
+                                                    let bytes5 =
+                                                        super::super::_rt::Vec::from_raw_parts(
+                                                            l3.cast(),
+                                                            len5,
+                                                            len5,
+                                                        );
                                                     super::super::_rt::string_lift(bytes5)
                                                 };
                                                 Some(e)
@@ -13231,208 +13421,167 @@ pub mod wit_future {
                         use super::super::wasi::filesystem::types::ErrorCode as V1;
                         match e {
                             V1::Access => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (0i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (0i32) as u8;
                             }
                             V1::Already => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (1i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (1i32) as u8;
                             }
                             V1::BadDescriptor => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (2i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (2i32) as u8;
                             }
                             V1::Busy => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (3i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (3i32) as u8;
                             }
                             V1::Deadlock => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (4i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (4i32) as u8;
                             }
                             V1::Quota => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (5i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (5i32) as u8;
                             }
                             V1::Exist => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (6i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (6i32) as u8;
                             }
                             V1::FileTooLarge => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (7i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (7i32) as u8;
                             }
                             V1::IllegalByteSequence => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (8i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (8i32) as u8;
                             }
                             V1::InProgress => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (9i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (9i32) as u8;
                             }
                             V1::Interrupted => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (10i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (10i32) as u8;
                             }
                             V1::Invalid => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (11i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (11i32) as u8;
                             }
                             V1::Io => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (12i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (12i32) as u8;
                             }
                             V1::IsDirectory => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (13i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (13i32) as u8;
                             }
                             V1::Loop => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (14i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (14i32) as u8;
                             }
                             V1::TooManyLinks => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (15i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (15i32) as u8;
                             }
                             V1::MessageSize => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (16i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (16i32) as u8;
                             }
                             V1::NameTooLong => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (17i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (17i32) as u8;
                             }
                             V1::NoDevice => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (18i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (18i32) as u8;
                             }
                             V1::NoEntry => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (19i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (19i32) as u8;
                             }
                             V1::NoLock => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (20i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (20i32) as u8;
                             }
                             V1::InsufficientMemory => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (21i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (21i32) as u8;
                             }
                             V1::InsufficientSpace => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (22i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (22i32) as u8;
                             }
                             V1::NotDirectory => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (23i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (23i32) as u8;
                             }
                             V1::NotEmpty => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (24i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (24i32) as u8;
                             }
                             V1::NotRecoverable => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (25i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (25i32) as u8;
                             }
                             V1::Unsupported => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (26i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (26i32) as u8;
                             }
                             V1::NoTty => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (27i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (27i32) as u8;
                             }
                             V1::NoSuchDevice => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (28i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (28i32) as u8;
                             }
                             V1::Overflow => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (29i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (29i32) as u8;
                             }
                             V1::NotPermitted => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (30i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (30i32) as u8;
                             }
                             V1::Pipe => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (31i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (31i32) as u8;
                             }
                             V1::ReadOnly => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (32i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (32i32) as u8;
                             }
                             V1::InvalidSeek => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (33i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (33i32) as u8;
                             }
                             V1::TextFileBusy => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (34i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (34i32) as u8;
                             }
                             V1::CrossDevice => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (35i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (35i32) as u8;
                             }
                             V1::Other(e) => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (36i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (36i32) as u8;
                                 match e {
                                     Some(e) => {
-                                        *ptr
-                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(2 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (1i32) as u8;
                                         let vec0 = (e.into_bytes()).into_boxed_slice();
                                         let ptr0 = vec0.as_ptr().cast::<u8>();
                                         let len0 = vec0.len();
                                         ::core::mem::forget(vec0);
-                                        *ptr
-                                            .add(4 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(4 * ::core::mem::size_of::<*const u8>())
                                             .cast::<usize>() = len0;
-                                        *ptr
-                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<*mut u8>() = ptr0.cast_mut();
                                     }
                                     None => {
-                                        *ptr
-                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(2 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (0i32) as u8;
                                     }
                                 };
@@ -13448,9 +13597,8 @@ pub mod wit_future {
                 match l0 {
                     0 => {}
                     _ => {
-                        let l1 = i32::from(
-                            *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
-                        );
+                        let l1 =
+                            i32::from(*ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>());
                         match l1 {
                             0 => {}
                             1 => {}
@@ -13490,8 +13638,7 @@ pub mod wit_future {
                             35 => {}
                             _ => {
                                 let l2 = i32::from(
-                                    *ptr
-                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                    *ptr.add(2 * ::core::mem::size_of::<*const u8>())
                                         .cast::<u8>(),
                                 );
                                 match l2 {
@@ -13522,70 +13669,124 @@ pub mod wit_future {
             drop_writable,
             drop_readable,
             dealloc_lists,
+            #[cfg(any(target_arch = "wasm32", not(feature = "mock-host")))]
             layout: unsafe { ::core::alloc::Layout::from_size_align_unchecked(20, 4) },
+            #[cfg(all(feature = "mock-host", not(target_arch = "wasm32")))]
+            layout: unsafe {
+                ::core::alloc::Layout::from_size_align_unchecked(
+                    5 * ::core::mem::size_of::<*const u8>(),
+                    ::core::mem::size_of::<*const u8>(),
+                )
+            },
             lift,
             lower,
             new,
             start_read,
             start_write,
         };
-        impl super::FuturePayload
-        for Result<(), super::super::wasi::filesystem::types::ErrorCode> {
+        impl super::FuturePayload for Result<(), super::super::wasi::filesystem::types::ErrorCode> {
             const VTABLE: &'static wit_bindgen::rt::async_support::FutureVtable<Self> = &VTABLE;
         }
     }
     #[doc(hidden)]
     #[allow(unused_unsafe)]
     pub mod vtable2 {
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn cancel_write(_: u32) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn cancel_read(_: u32) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn drop_writable(_: u32) {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn drop_readable(_: u32) {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn new() -> u64 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn start_read(_: u32, _: *mut u8) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn start_write(_: u32, _: *const u8) -> u32 {
             unreachable!()
         }
-        #[cfg(target_arch = "wasm32")]
+        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
         unsafe extern "C" {
-            #[link_name = "[future-new-1][method]tcp-socket.send"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-new-1][method]tcp-socket.send"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:sockets/types@0.3.0#[future-new-1][method]tcp-socket.send"
+            )]
             fn new() -> u64;
-            #[link_name = "[future-cancel-write-1][method]tcp-socket.send"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-cancel-write-1][method]tcp-socket.send"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:sockets/types@0.3.0#[future-cancel-write-1][method]tcp-socket.send"
+            )]
             fn cancel_write(_: u32) -> u32;
-            #[link_name = "[future-cancel-read-1][method]tcp-socket.send"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-cancel-read-1][method]tcp-socket.send"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:sockets/types@0.3.0#[future-cancel-read-1][method]tcp-socket.send"
+            )]
             fn cancel_read(_: u32) -> u32;
-            #[link_name = "[future-drop-writable-1][method]tcp-socket.send"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-drop-writable-1][method]tcp-socket.send"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:sockets/types@0.3.0#[future-drop-writable-1][method]tcp-socket.send"
+            )]
             fn drop_writable(_: u32);
-            #[link_name = "[future-drop-readable-1][method]tcp-socket.send"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-drop-readable-1][method]tcp-socket.send"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:sockets/types@0.3.0#[future-drop-readable-1][method]tcp-socket.send"
+            )]
             fn drop_readable(_: u32);
-            #[link_name = "[async-lower][future-read-1][method]tcp-socket.send"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[async-lower][future-read-1][method]tcp-socket.send"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:sockets/types@0.3.0#[async-lower][future-read-1][method]tcp-socket.send"
+            )]
             fn start_read(_: u32, _: *mut u8) -> u32;
-            #[link_name = "[async-lower][future-write-1][method]tcp-socket.send"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[async-lower][future-write-1][method]tcp-socket.send"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:sockets/types@0.3.0#[async-lower][future-write-1][method]tcp-socket.send"
+            )]
             fn start_write(_: u32, _: *const u8) -> u32;
         }
-        unsafe fn lift(
-            ptr: *mut u8,
-        ) -> Result<(), super::super::wasi::sockets::types::ErrorCode> {
+        unsafe fn lift(ptr: *mut u8) -> Result<(), super::super::wasi::sockets::types::ErrorCode> {
             unsafe {
                 let l0 = i32::from(*ptr.add(0).cast::<u8>());
                 match l0 {
@@ -13618,8 +13819,7 @@ pub mod wit_future {
                                     debug_assert_eq!(n, 14, "invalid enum discriminant");
                                     let e6 = {
                                         let l2 = i32::from(
-                                            *ptr
-                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                            *ptr.add(2 * ::core::mem::size_of::<*const u8>())
                                                 .cast::<u8>(),
                                         );
                                         match l2 {
@@ -13627,17 +13827,22 @@ pub mod wit_future {
                                             1 => {
                                                 let e = {
                                                     let l3 = *ptr
-                                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                                        .add(
+                                                            3 * ::core::mem::size_of::<*const u8>(),
+                                                        )
                                                         .cast::<*mut u8>();
                                                     let l4 = *ptr
-                                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                                        .add(
+                                                            4 * ::core::mem::size_of::<*const u8>(),
+                                                        )
                                                         .cast::<usize>();
                                                     let len5 = l4;
-                                                    let bytes5 = super::super::_rt::Vec::from_raw_parts(
-                                                        l3.cast(),
-                                                        len5,
-                                                        len5,
-                                                    );
+                                                    let bytes5 =
+                                                        super::super::_rt::Vec::from_raw_parts(
+                                                            l3.cast(),
+                                                            len5,
+                                                            len5,
+                                                        );
                                                     super::super::_rt::string_lift(bytes5)
                                                 };
                                                 Some(e)
@@ -13670,98 +13875,79 @@ pub mod wit_future {
                         use super::super::wasi::sockets::types::ErrorCode as V1;
                         match e {
                             V1::AccessDenied => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (0i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (0i32) as u8;
                             }
                             V1::NotSupported => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (1i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (1i32) as u8;
                             }
                             V1::InvalidArgument => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (2i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (2i32) as u8;
                             }
                             V1::OutOfMemory => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (3i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (3i32) as u8;
                             }
                             V1::Timeout => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (4i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (4i32) as u8;
                             }
                             V1::InvalidState => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (5i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (5i32) as u8;
                             }
                             V1::AddressNotBindable => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (6i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (6i32) as u8;
                             }
                             V1::AddressInUse => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (7i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (7i32) as u8;
                             }
                             V1::RemoteUnreachable => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (8i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (8i32) as u8;
                             }
                             V1::ConnectionRefused => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (9i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (9i32) as u8;
                             }
                             V1::ConnectionBroken => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (10i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (10i32) as u8;
                             }
                             V1::ConnectionReset => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (11i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (11i32) as u8;
                             }
                             V1::ConnectionAborted => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (12i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (12i32) as u8;
                             }
                             V1::DatagramTooLarge => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (13i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (13i32) as u8;
                             }
                             V1::Other(e) => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (14i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (14i32) as u8;
                                 match e {
                                     Some(e) => {
-                                        *ptr
-                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(2 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (1i32) as u8;
                                         let vec0 = (e.into_bytes()).into_boxed_slice();
                                         let ptr0 = vec0.as_ptr().cast::<u8>();
                                         let len0 = vec0.len();
                                         ::core::mem::forget(vec0);
-                                        *ptr
-                                            .add(4 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(4 * ::core::mem::size_of::<*const u8>())
                                             .cast::<usize>() = len0;
-                                        *ptr
-                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<*mut u8>() = ptr0.cast_mut();
                                     }
                                     None => {
-                                        *ptr
-                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(2 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (0i32) as u8;
                                     }
                                 };
@@ -13777,9 +13963,8 @@ pub mod wit_future {
                 match l0 {
                     0 => {}
                     _ => {
-                        let l1 = i32::from(
-                            *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
-                        );
+                        let l1 =
+                            i32::from(*ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>());
                         match l1 {
                             0 => {}
                             1 => {}
@@ -13797,8 +13982,7 @@ pub mod wit_future {
                             13 => {}
                             _ => {
                                 let l2 = i32::from(
-                                    *ptr
-                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                    *ptr.add(2 * ::core::mem::size_of::<*const u8>())
                                         .cast::<u8>(),
                                 );
                                 match l2 {
@@ -13829,15 +14013,22 @@ pub mod wit_future {
             drop_writable,
             drop_readable,
             dealloc_lists,
+            #[cfg(any(target_arch = "wasm32", not(feature = "mock-host")))]
             layout: unsafe { ::core::alloc::Layout::from_size_align_unchecked(20, 4) },
+            #[cfg(all(feature = "mock-host", not(target_arch = "wasm32")))]
+            layout: unsafe {
+                ::core::alloc::Layout::from_size_align_unchecked(
+                    5 * ::core::mem::size_of::<*const u8>(),
+                    ::core::mem::size_of::<*const u8>(),
+                )
+            },
             lift,
             lower,
             new,
             start_read,
             start_write,
         };
-        impl super::FuturePayload
-        for Result<(), super::super::wasi::sockets::types::ErrorCode> {
+        impl super::FuturePayload for Result<(), super::super::wasi::sockets::types::ErrorCode> {
             const VTABLE: &'static wit_bindgen::rt::async_support::FutureVtable<Self> = &VTABLE;
         }
     }
@@ -13862,67 +14053,112 @@ pub mod wit_stream {
     #[doc(hidden)]
     #[allow(unused_unsafe)]
     pub mod vtable0 {
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn cancel_write(_: u32) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn cancel_read(_: u32) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn drop_writable(_: u32) {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn drop_readable(_: u32) {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn new() -> u64 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn start_read(_: u32, _: *mut u8, _: usize) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn start_write(_: u32, _: *const u8, _: usize) -> u32 {
             unreachable!()
         }
-        #[cfg(target_arch = "wasm32")]
+        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
         #[link(wasm_import_module = "wasi:cli/stdin@0.3.0")]
         unsafe extern "C" {
-            #[link_name = "[stream-new-0]read-via-stream"]
+            #[cfg_attr(target_arch = "wasm32", link_name = "[stream-new-0]read-via-stream")]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:cli/stdin@0.3.0#[stream-new-0]read-via-stream"
+            )]
             fn new() -> u64;
-            #[link_name = "[stream-cancel-write-0]read-via-stream"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[stream-cancel-write-0]read-via-stream"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:cli/stdin@0.3.0#[stream-cancel-write-0]read-via-stream"
+            )]
             fn cancel_write(_: u32) -> u32;
-            #[link_name = "[stream-cancel-read-0]read-via-stream"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[stream-cancel-read-0]read-via-stream"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:cli/stdin@0.3.0#[stream-cancel-read-0]read-via-stream"
+            )]
             fn cancel_read(_: u32) -> u32;
-            #[link_name = "[stream-drop-writable-0]read-via-stream"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[stream-drop-writable-0]read-via-stream"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:cli/stdin@0.3.0#[stream-drop-writable-0]read-via-stream"
+            )]
             fn drop_writable(_: u32);
-            #[link_name = "[stream-drop-readable-0]read-via-stream"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[stream-drop-readable-0]read-via-stream"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:cli/stdin@0.3.0#[stream-drop-readable-0]read-via-stream"
+            )]
             fn drop_readable(_: u32);
-            #[link_name = "[async-lower][stream-read-0]read-via-stream"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[async-lower][stream-read-0]read-via-stream"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:cli/stdin@0.3.0#[async-lower][stream-read-0]read-via-stream"
+            )]
             fn start_read(_: u32, _: *mut u8, _: usize) -> u32;
-            #[link_name = "[async-lower][stream-write-0]read-via-stream"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[async-lower][stream-write-0]read-via-stream"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:cli/stdin@0.3.0#[async-lower][stream-write-0]read-via-stream"
+            )]
             fn start_write(_: u32, _: *const u8, _: usize) -> u32;
         }
-        pub static VTABLE: wit_bindgen::rt::async_support::StreamVtable<u8> = wit_bindgen::rt::async_support::StreamVtable::<
-            u8,
-        > {
-            cancel_write,
-            cancel_read,
-            drop_writable,
-            drop_readable,
-            dealloc_lists: None,
-            layout: unsafe { ::core::alloc::Layout::from_size_align_unchecked(1, 1) },
-            lift: None,
-            lower: None,
-            new,
-            start_read,
-            start_write,
-        };
+        pub static VTABLE: wit_bindgen::rt::async_support::StreamVtable<u8> =
+            wit_bindgen::rt::async_support::StreamVtable::<u8> {
+                cancel_write,
+                cancel_read,
+                drop_writable,
+                drop_readable,
+                dealloc_lists: None,
+                layout: unsafe { ::core::alloc::Layout::from_size_align_unchecked(1, 1) },
+                lift: None,
+                lower: None,
+                new,
+                start_read,
+                start_write,
+            };
         impl super::StreamPayload for u8 {
             const VTABLE: &'static wit_bindgen::rt::async_support::StreamVtable<Self> = &VTABLE;
         }
@@ -13930,55 +14166,102 @@ pub mod wit_stream {
     #[doc(hidden)]
     #[allow(unused_unsafe)]
     pub mod vtable1 {
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn cancel_write(_: u32) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn cancel_read(_: u32) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn drop_writable(_: u32) {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn drop_readable(_: u32) {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn new() -> u64 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn start_read(_: u32, _: *mut u8, _: usize) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn start_write(_: u32, _: *const u8, _: usize) -> u32 {
             unreachable!()
         }
-        #[cfg(target_arch = "wasm32")]
+        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
         #[link(wasm_import_module = "wasi:filesystem/types@0.3.0")]
         unsafe extern "C" {
-            #[link_name = "[stream-new-0][method]descriptor.read-directory"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[stream-new-0][method]descriptor.read-directory"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:filesystem/types@0.3.0#[stream-new-0][method]descriptor.read-directory"
+            )]
             fn new() -> u64;
-            #[link_name = "[stream-cancel-write-0][method]descriptor.read-directory"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[stream-cancel-write-0][method]descriptor.read-directory"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:filesystem/types@0.3.0#[stream-cancel-write-0][method]descriptor.read-directory"
+            )]
             fn cancel_write(_: u32) -> u32;
-            #[link_name = "[stream-cancel-read-0][method]descriptor.read-directory"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[stream-cancel-read-0][method]descriptor.read-directory"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:filesystem/types@0.3.0#[stream-cancel-read-0][method]descriptor.read-directory"
+            )]
             fn cancel_read(_: u32) -> u32;
-            #[link_name = "[stream-drop-writable-0][method]descriptor.read-directory"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[stream-drop-writable-0][method]descriptor.read-directory"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:filesystem/types@0.3.0#[stream-drop-writable-0][method]descriptor.read-directory"
+            )]
             fn drop_writable(_: u32);
-            #[link_name = "[stream-drop-readable-0][method]descriptor.read-directory"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[stream-drop-readable-0][method]descriptor.read-directory"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:filesystem/types@0.3.0#[stream-drop-readable-0][method]descriptor.read-directory"
+            )]
             fn drop_readable(_: u32);
-            #[link_name = "[async-lower][stream-read-0][method]descriptor.read-directory"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[async-lower][stream-read-0][method]descriptor.read-directory"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:filesystem/types@0.3.0#[async-lower][stream-read-0][method]descriptor.read-directory"
+            )]
             fn start_read(_: u32, _: *mut u8, _: usize) -> u32;
-            #[link_name = "[async-lower][stream-write-0][method]descriptor.read-directory"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[async-lower][stream-write-0][method]descriptor.read-directory"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:filesystem/types@0.3.0#[async-lower][stream-write-0][method]descriptor.read-directory"
+            )]
             fn start_write(_: u32, _: *const u8, _: usize) -> u32;
         }
-        unsafe fn lift(
-            ptr: *mut u8,
-        ) -> super::super::wasi::filesystem::types::DirectoryEntry {
+        unsafe fn lift(ptr: *mut u8) -> super::super::wasi::filesystem::types::DirectoryEntry {
             unsafe {
                 let l0 = i32::from(*ptr.add(0).cast::<u8>());
                 use super::super::wasi::filesystem::types::DescriptorType as V5;
@@ -14029,11 +14312,7 @@ pub mod wit_stream {
                     .add(5 * ::core::mem::size_of::<*const u8>())
                     .cast::<usize>();
                 let len8 = l7;
-                let bytes8 = super::super::_rt::Vec::from_raw_parts(
-                    l6.cast(),
-                    len8,
-                    len8,
-                );
+                let bytes8 = super::super::_rt::Vec::from_raw_parts(l6.cast(), len8, len8);
                 super::super::wasi::filesystem::types::DirectoryEntry {
                     type_: v5,
                     name: super::super::_rt::string_lift(bytes8),
@@ -14076,24 +14355,20 @@ pub mod wit_stream {
                         *ptr.add(0).cast::<u8>() = (7i32) as u8;
                         match e {
                             Some(e) => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (1i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (1i32) as u8;
                                 let vec1 = (e.into_bytes()).into_boxed_slice();
                                 let ptr1 = vec1.as_ptr().cast::<u8>();
                                 let len1 = vec1.len();
                                 ::core::mem::forget(vec1);
-                                *ptr
-                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                *ptr.add(3 * ::core::mem::size_of::<*const u8>())
                                     .cast::<usize>() = len1;
-                                *ptr
-                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                *ptr.add(2 * ::core::mem::size_of::<*const u8>())
                                     .cast::<*mut u8>() = ptr1.cast_mut();
                             }
                             None => {
-                                *ptr
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<u8>() = (0i32) as u8;
+                                *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>() =
+                                    (0i32) as u8;
                             }
                         };
                     }
@@ -14102,9 +14377,10 @@ pub mod wit_stream {
                 let ptr3 = vec3.as_ptr().cast::<u8>();
                 let len3 = vec3.len();
                 ::core::mem::forget(vec3);
-                *ptr.add(5 * ::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
-                *ptr.add(4 * ::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr3
-                    .cast_mut();
+                *ptr.add(5 * ::core::mem::size_of::<*const u8>())
+                    .cast::<usize>() = len3;
+                *ptr.add(4 * ::core::mem::size_of::<*const u8>())
+                    .cast::<*mut u8>() = ptr3.cast_mut();
             }
         }
         unsafe fn dealloc_lists(ptr: *mut u8) {
@@ -14119,9 +14395,8 @@ pub mod wit_stream {
                     5 => {}
                     6 => {}
                     _ => {
-                        let l1 = i32::from(
-                            *ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
-                        );
+                        let l1 =
+                            i32::from(*ptr.add(::core::mem::size_of::<*const u8>()).cast::<u8>());
                         match l1 {
                             0 => {}
                             _ => {
@@ -14155,65 +14430,121 @@ pub mod wit_stream {
             drop_writable,
             drop_readable,
             dealloc_lists: Some(dealloc_lists),
+            #[cfg(any(target_arch = "wasm32", not(feature = "mock-host")))]
             layout: unsafe { ::core::alloc::Layout::from_size_align_unchecked(24, 4) },
+            #[cfg(all(feature = "mock-host", not(target_arch = "wasm32")))]
+            layout: unsafe {
+                ::core::alloc::Layout::from_size_align_unchecked(
+                    6 * ::core::mem::size_of::<*const u8>(),
+                    ::core::mem::size_of::<*const u8>(),
+                )
+            },
             lift: Some(lift),
             lower: Some(lower),
             new,
             start_read,
             start_write,
         };
-        impl super::StreamPayload
-        for super::super::wasi::filesystem::types::DirectoryEntry {
+        impl super::StreamPayload for super::super::wasi::filesystem::types::DirectoryEntry {
             const VTABLE: &'static wit_bindgen::rt::async_support::StreamVtable<Self> = &VTABLE;
         }
     }
     #[doc(hidden)]
     #[allow(unused_unsafe)]
     pub mod vtable2 {
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn cancel_write(_: u32) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn cancel_read(_: u32) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn drop_writable(_: u32) {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn drop_readable(_: u32) {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn new() -> u64 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn start_read(_: u32, _: *mut u8, _: usize) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn start_write(_: u32, _: *const u8, _: usize) -> u32 {
             unreachable!()
         }
-        #[cfg(target_arch = "wasm32")]
+        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
         #[link(wasm_import_module = "wasi:sockets/types@0.3.0")]
         unsafe extern "C" {
-            #[link_name = "[stream-new-0][method]tcp-socket.listen"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[stream-new-0][method]tcp-socket.listen"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:sockets/types@0.3.0#[stream-new-0][method]tcp-socket.listen"
+            )]
             fn new() -> u64;
-            #[link_name = "[stream-cancel-write-0][method]tcp-socket.listen"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[stream-cancel-write-0][method]tcp-socket.listen"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:sockets/types@0.3.0#[stream-cancel-write-0][method]tcp-socket.listen"
+            )]
             fn cancel_write(_: u32) -> u32;
-            #[link_name = "[stream-cancel-read-0][method]tcp-socket.listen"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[stream-cancel-read-0][method]tcp-socket.listen"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:sockets/types@0.3.0#[stream-cancel-read-0][method]tcp-socket.listen"
+            )]
             fn cancel_read(_: u32) -> u32;
-            #[link_name = "[stream-drop-writable-0][method]tcp-socket.listen"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[stream-drop-writable-0][method]tcp-socket.listen"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:sockets/types@0.3.0#[stream-drop-writable-0][method]tcp-socket.listen"
+            )]
             fn drop_writable(_: u32);
-            #[link_name = "[stream-drop-readable-0][method]tcp-socket.listen"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[stream-drop-readable-0][method]tcp-socket.listen"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:sockets/types@0.3.0#[stream-drop-readable-0][method]tcp-socket.listen"
+            )]
             fn drop_readable(_: u32);
-            #[link_name = "[async-lower][stream-read-0][method]tcp-socket.listen"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[async-lower][stream-read-0][method]tcp-socket.listen"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:sockets/types@0.3.0#[async-lower][stream-read-0][method]tcp-socket.listen"
+            )]
             fn start_read(_: u32, _: *mut u8, _: usize) -> u32;
-            #[link_name = "[async-lower][stream-write-0][method]tcp-socket.listen"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[async-lower][stream-write-0][method]tcp-socket.listen"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:sockets/types@0.3.0#[async-lower][stream-write-0][method]tcp-socket.listen"
+            )]
             fn start_write(_: u32, _: *const u8, _: usize) -> u32;
         }
         unsafe fn lift(ptr: *mut u8) -> super::super::wasi::sockets::types::TcpSocket {
@@ -14222,10 +14553,7 @@ pub mod wit_stream {
                 super::super::wasi::sockets::types::TcpSocket::from_handle(l0 as u32)
             }
         }
-        unsafe fn lower(
-            value: super::super::wasi::sockets::types::TcpSocket,
-            ptr: *mut u8,
-        ) {
+        unsafe fn lower(value: super::super::wasi::sockets::types::TcpSocket, ptr: *mut u8) {
             unsafe {
                 *ptr.add(0).cast::<i32>() = (value).take_handle() as i32;
             }
@@ -132,6 +132,14 @@
 //! - [`wasi::cli::command::export!`](crate::cli::command::export)
 //! - [`wasi::http::proxy::export!`](crate::http::proxy::export)
 //!
+//! ## Testing Natively
+//!
+//! Outside of wasm the imports of this crate panic when called. With the
+//! `mock-host` feature they instead call an in-memory host, `MockHost` in the
+//! `mock_host` module, whose executor also runs the async code built on this
+//! crate, so it can be unit tested with a plain `cargo test`. The feature has
+//! no effect on wasm targets.
+//!
 //! [worlds]: https://component-model.bytecodealliance.org/design/worlds.html
 //! [`wasi:cli/command`]: https://github.com/WebAssembly/WASI/tree/main/proposals/cli
 //! [`wasi:http/service`]: https://github.com/WebAssembly/WASI/tree/main/proposals/http
@@ -154,6 +162,9 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(all(feature = "mock-host", not(target_arch = "wasm32")))]
+pub mod mock_host;
+
 // These modules are all auto-generated by `./ci/regenerate.sh`
 #[allow(unused_imports)]
 mod command;
@@ -181,8 +192,18 @@ where
 // Reexport wit-bindgen to downstream users don't have to depend on it.
 pub use wit_bindgen;
 
-#[cfg(feature = "async-spawn")]
+#[cfg(all(
+    feature = "async-spawn",
+    not(all(feature = "mock-host", not(target_arch = "wasm32")))
+))]
 pub use wit_bindgen::spawn;
+// Tasks need to run on the executor of the mock host instead.
+#[cfg(all(
+    feature = "async-spawn",
+    feature = "mock-host",
+    not(target_arch = "wasm32")
+))]
+pub use mock_host::spawn;
 
 // Expand the `cli` and `http` modules with `export!` macros for the
 // command/proxy worlds, but also retain all the contents defined in the
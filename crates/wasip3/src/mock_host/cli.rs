@@ -0,0 +1,139 @@
+//! `wasi:cli`, apart from the `run` export.
+
+use super::streams::{self, Channel, Pull};
+use super::{alloc_list, store, store_bytes, with, PTR};
+use crate::cli::types::ErrorCode;
+use crate::wit_future::FuturePayload;
+use crate::wit_stream::StreamPayload;
+use std::cell::RefCell;
+use std::mem::MaybeUninit;
+use std::process;
+use std::rc::Rc;
+use std::string::String;
+use std::vec::Vec;
+
+#[derive(Default)]
+pub(super) struct Cli {
+    pub(super) args: Vec<String>,
+    pub(super) env: Vec<(String, String)>,
+    pub(super) initial_cwd: Option<String>,
+    pub(super) stdin: Rc<RefCell<Vec<u8>>>,
+    pub(super) stdout: Rc<RefCell<Vec<u8>>>,
+    pub(super) stderr: Rc<RefCell<Vec<u8>>>,
+}
+
+#[unsafe(export_name = symbol!("cli/environment", "get-environment"))]
+unsafe extern "C" fn get_environment(ret: *mut u8) {
+    with(|s| unsafe {
+        let env = &s.cli.env;
+        let list = alloc_list(env.len(), 4 * PTR, PTR);
+        for (i, (key, value)) in env.iter().enumerate() {
+            let elem = list.add(i * 4 * PTR);
+            store_bytes(elem, 0, key.as_bytes());
+            store_bytes(elem, 2 * PTR, value.as_bytes());
+        }
+        store(ret, 0, list);
+        store(ret, PTR, env.len());
+    })
+}
+
+#[unsafe(export_name = symbol!("cli/environment", "get-arguments"))]
+unsafe extern "C" fn get_arguments(ret: *mut u8) {
+    with(|s| unsafe {
+        let args = &s.cli.args;
+        let list = alloc_list(args.len(), 2 * PTR, PTR);
+        for (i, arg) in args.iter().enumerate() {
+            store_bytes(list.add(i * 2 * PTR), 0, arg.as_bytes());
+        }
+        store(ret, 0, list);
+        store(ret, PTR, args.len());
+    })
+}
+
+#[unsafe(export_name = symbol!("cli/environment", "get-initial-cwd"))]
+unsafe extern "C" fn get_initial_cwd(ret: *mut u8) {
+    with(|s| unsafe {
+        match &s.cli.initial_cwd {
+            Some(cwd) => {
+                store(ret, 0, 1u8);
+                store_bytes(ret, PTR, cwd.as_bytes());
+            }
+            None => store(ret, 0, 0u8),
+        }
+    })
+}
+
+#[unsafe(export_name = symbol!("cli/exit", "exit"))]
+unsafe extern "C" fn exit(status: i32) {
+    process::exit(status)
+}
+
+#[unsafe(export_name = symbol!("cli/exit", "exit-with-code"))]
+unsafe extern "C" fn exit_with_code(status_code: i32) {
+    process::exit(status_code & 0xff)
+}
+
+/// Implements `write-via-stream` for `stdout` or `stderr`.
+fn write_via_stream(output: fn(&super::State) -> &Rc<RefCell<Vec<u8>>>, data: i32) -> i32 {
+    with(|s| {
+        let output = output(s).clone();
+        let data = streams::take_readable(s, data);
+        data.borrow_mut()
+            .set_sink(move |bytes| output.borrow_mut().extend_from_slice(bytes));
+        let future = streams::written(&data, Ok::<(), ErrorCode>(()));
+        streams::push_readable(s, future)
+    })
+}
+
+#[unsafe(export_name = symbol!("cli/stdin", "read-via-stream"))]
+unsafe extern "C" fn stdin_read_via_stream(ret: *mut u8) {
+    with(|s| unsafe {
+        // Stdin ends once everything pushed to it was read, rather than
+        // blocking forever.
+        let stdin = s.cli.stdin.clone();
+        let mut data = Channel::stream(<u8 as StreamPayload>::VTABLE);
+        data.set_source(move |_| {
+            let bytes = core::mem::take(&mut *stdin.borrow_mut());
+            if bytes.is_empty() {
+                return Pull::End;
+            }
+            Pull::Data(bytes.into_iter().map(MaybeUninit::new).collect())
+        });
+        let result = Channel::ready(<Result<(), ErrorCode> as FuturePayload>::VTABLE, Ok(()));
+        store(ret, 0, streams::push_readable(s, data));
+        store(ret, 4, streams::push_readable(s, result));
+    })
+}
+
+#[unsafe(export_name = symbol!("cli/stdout", "write-via-stream"))]
+unsafe extern "C" fn stdout_write_via_stream(data: i32) -> i32 {
+    write_via_stream(|s| &s.cli.stdout, data)
+}
+
+#[unsafe(export_name = symbol!("cli/stderr", "write-via-stream"))]
+unsafe extern "C" fn stderr_write_via_stream(data: i32) -> i32 {
+    write_via_stream(|s| &s.cli.stderr, data)
+}
+
+// There are no terminals, so no terminal resources are ever handed out.
+
+#[unsafe(export_name = symbol!("cli/terminal-input", "[resource-drop]terminal-input"))]
+unsafe extern "C" fn drop_terminal_input(_handle: i32) {}
+
+#[unsafe(export_name = symbol!("cli/terminal-output", "[resource-drop]terminal-output"))]
+unsafe extern "C" fn drop_terminal_output(_handle: i32) {}
+
+#[unsafe(export_name = symbol!("cli/terminal-stdin", "get-terminal-stdin"))]
+unsafe extern "C" fn get_terminal_stdin(ret: *mut u8) {
+    unsafe { store(ret, 0, 0u8) }
+}
+
+#[unsafe(export_name = symbol!("cli/terminal-stdout", "get-terminal-stdout"))]
+unsafe extern "C" fn get_terminal_stdout(ret: *mut u8) {
+    unsafe { store(ret, 0, 0u8) }
+}
+
+#[unsafe(export_name = symbol!("cli/terminal-stderr", "get-terminal-stderr"))]
+unsafe extern "C" fn get_terminal_stderr(ret: *mut u8) {
+    unsafe { store(ret, 0, 0u8) }
+}
@@ -0,0 +1,62 @@
+//! `wasi:clocks`, with clocks which only move when told to.
+
+use super::{store, with, RETURNED};
+use std::time::Duration;
+
+#[derive(Default)]
+pub(super) struct Clocks {
+    /// The time of `monotonic-clock`, in nanoseconds.
+    pub(super) monotonic: u64,
+    /// The time of `system-clock`, since the Unix epoch.
+    pub(super) system: Duration,
+}
+
+impl Clocks {
+    pub(super) fn advance(&mut self, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        self.monotonic = self.monotonic.saturating_add(nanos);
+        self.system = self.system.saturating_add(duration);
+    }
+
+    /// Advances the clocks until `monotonic-clock` reaches `instant`.
+    pub(super) fn advance_to(&mut self, instant: u64) {
+        if let Some(nanos) = instant.checked_sub(self.monotonic) {
+            self.advance(Duration::from_nanos(nanos));
+        }
+    }
+}
+
+#[unsafe(export_name = symbol!("clocks/monotonic-clock", "now"))]
+unsafe extern "C" fn monotonic_now() -> i64 {
+    with(|s| s.clocks.monotonic as i64)
+}
+
+#[unsafe(export_name = symbol!("clocks/monotonic-clock", "get-resolution"))]
+unsafe extern "C" fn monotonic_get_resolution() -> i64 {
+    1
+}
+
+#[unsafe(export_name = symbol!("clocks/monotonic-clock", "[async-lower]wait-until"))]
+unsafe extern "C" fn wait_until(when: i64) -> i32 {
+    with(|s| s.clocks.advance_to(when as u64));
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("clocks/monotonic-clock", "[async-lower]wait-for"))]
+unsafe extern "C" fn wait_for(how_long: i64) -> i32 {
+    with(|s| s.clocks.advance(Duration::from_nanos(how_long as u64)));
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("clocks/system-clock", "now"))]
+unsafe extern "C" fn system_now(ret: *mut u8) {
+    with(|s| unsafe {
+        store(ret, 0, s.clocks.system.as_secs() as i64);
+        store(ret, 8, s.clocks.system.subsec_nanos());
+    })
+}
+
+#[unsafe(export_name = symbol!("clocks/system-clock", "get-resolution"))]
+unsafe extern "C" fn system_get_resolution() -> i64 {
+    1
+}
@@ -0,0 +1,121 @@
+//! A single-threaded executor for the tasks of the code under test.
+//!
+//! Imports never block the guest: whenever one would have to wait, it runs
+//! the other tasks in-line with [`Executor::run_one`] until it can complete.
+
+use std::boxed::Box;
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::{pin, Pin};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+/// Whether a task was woken since it was last polled.
+#[derive(Default)]
+struct Flag(AtomicBool);
+
+impl Flag {
+    fn take(&self) -> bool {
+        self.0.swap(false, Ordering::SeqCst)
+    }
+}
+
+impl Wake for Flag {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+struct Task {
+    /// The future of the task, which is taken out while it's polled so that
+    /// it can run other tasks.
+    future: Option<Pin<Box<dyn Future<Output = ()>>>>,
+    woken: Arc<Flag>,
+}
+
+#[derive(Default)]
+pub(super) struct Executor {
+    next: Cell<u64>,
+    tasks: RefCell<BTreeMap<u64, Task>>,
+}
+
+impl Executor {
+    pub(super) fn spawn(&self, future: impl Future<Output = ()> + 'static) {
+        let id = self.next.get();
+        self.next.set(id + 1);
+        let woken = Arc::new(Flag::default());
+        woken.wake_by_ref();
+        let task = Task {
+            future: Some(Box::pin(future)),
+            woken,
+        };
+        self.tasks.borrow_mut().insert(id, task);
+    }
+
+    /// Polls the oldest task which was woken, returning whether there was
+    /// one.
+    pub(super) fn run_one(&self) -> bool {
+        let found = self.tasks.borrow_mut().iter_mut().find_map(|(id, task)| {
+            if task.future.is_some() && task.woken.take() {
+                Some((*id, task.future.take().unwrap(), task.woken.clone()))
+            } else {
+                None
+            }
+        });
+        let Some((id, mut future, woken)) = found else {
+            return false;
+        };
+        let waker = Waker::from(woken);
+        let poll = future.as_mut().poll(&mut Context::from_waker(&waker));
+        let mut tasks = self.tasks.borrow_mut();
+        match poll {
+            Poll::Ready(()) => {
+                tasks.remove(&id);
+            }
+            Poll::Pending => tasks.get_mut(&id).unwrap().future = Some(future),
+        }
+        true
+    }
+
+    /// Drops the tasks which didn't complete, along with the tasks they spawn
+    /// while they're dropped.
+    pub(super) fn clear(&self) {
+        loop {
+            let tasks = core::mem::take(&mut *self.tasks.borrow_mut());
+            if tasks.is_empty() {
+                break;
+            }
+            drop(tasks);
+        }
+    }
+
+    /// Polls `future` until it completes, running the spawned tasks whenever
+    /// it's waiting.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `future` waits while no task can make progress.
+    pub(super) fn block_on<F: Future>(&self, future: F) -> F::Output {
+        let mut future = pin!(future);
+        let woken = Arc::new(Flag::default());
+        let waker = Waker::from(woken.clone());
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+            while !woken.take() {
+                assert!(
+                    self.run_one(),
+                    "deadlock: the future waits on something no task of the mock host can provide"
+                );
+            }
+        }
+    }
+}
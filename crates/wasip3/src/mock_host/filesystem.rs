@@ -0,0 +1,966 @@
+//! `wasi:filesystem`, backed by a tree of directories and files in memory.
+
+use super::streams::{self, Channel, Pull};
+use super::{
+    alloc_list, drop_resource, load, load_string, store, store_bytes, store_result, string, with,
+    Lower, PTR, RETURNED,
+};
+use crate::filesystem::types::{
+    DescriptorFlags, DescriptorType, DirectoryEntry, ErrorCode, OpenFlags,
+};
+use crate::wit_future::FuturePayload;
+use crate::wit_stream::StreamPayload;
+use std::borrow::ToOwned;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::mem::MaybeUninit;
+use std::rc::Rc;
+use std::string::String;
+use std::time::Duration;
+use std::vec::Vec;
+
+pub(super) type NodeRef = Rc<RefCell<Node>>;
+
+pub(super) struct Node {
+    kind: Kind,
+    /// The number of directory entries referring to the node.
+    links: u64,
+    accessed: Duration,
+    modified: Duration,
+}
+
+enum Kind {
+    File(Vec<u8>),
+    Dir(BTreeMap<String, NodeRef>),
+}
+
+impl Node {
+    fn new(kind: Kind, now: Duration) -> NodeRef {
+        Rc::new(RefCell::new(Node {
+            kind,
+            links: 1,
+            accessed: now,
+            modified: now,
+        }))
+    }
+
+    fn is_dir(&self) -> bool {
+        matches!(self.kind, Kind::Dir(_))
+    }
+
+    fn entries(&self) -> Result<&BTreeMap<String, NodeRef>, ErrorCode> {
+        match &self.kind {
+            Kind::Dir(entries) => Ok(entries),
+            Kind::File(_) => Err(ErrorCode::NotDirectory),
+        }
+    }
+
+    fn entries_mut(&mut self) -> Result<&mut BTreeMap<String, NodeRef>, ErrorCode> {
+        match &mut self.kind {
+            Kind::Dir(entries) => Ok(entries),
+            Kind::File(_) => Err(ErrorCode::NotDirectory),
+        }
+    }
+
+    fn data_mut(&mut self) -> Result<&mut Vec<u8>, ErrorCode> {
+        match &mut self.kind {
+            Kind::File(data) => Ok(data),
+            Kind::Dir(_) => Err(ErrorCode::IsDirectory),
+        }
+    }
+
+    fn descriptor_type(&self) -> DescriptorType {
+        match self.kind {
+            Kind::File(_) => DescriptorType::RegularFile,
+            Kind::Dir(_) => DescriptorType::Directory,
+        }
+    }
+
+    fn size(&self) -> u64 {
+        match &self.kind {
+            Kind::File(data) => data.len() as u64,
+            Kind::Dir(_) => 0,
+        }
+    }
+
+    /// Returns up to `len` bytes at `offset` of a file, or `None` for a
+    /// directory.
+    fn read_at(&self, offset: u64, len: usize) -> Option<Vec<u8>> {
+        match &self.kind {
+            Kind::File(data) => {
+                let start = usize::try_from(offset)
+                    .unwrap_or(usize::MAX)
+                    .min(data.len());
+                let end = start.saturating_add(len).min(data.len());
+                Some(data[start..end].to_vec())
+            }
+            Kind::Dir(_) => None,
+        }
+    }
+
+    fn write_at(&mut self, offset: u64, bytes: &[u8], now: Duration) -> Result<(), ErrorCode> {
+        let data = self.data_mut()?;
+        let start = usize::try_from(offset).map_err(|_| ErrorCode::FileTooLarge)?;
+        let end = start
+            .checked_add(bytes.len())
+            .ok_or(ErrorCode::FileTooLarge)?;
+        if data.len() < end {
+            data.resize(end, 0);
+        }
+        data[start..end].copy_from_slice(bytes);
+        self.modified = now;
+        Ok(())
+    }
+
+    fn append(&mut self, bytes: &[u8], now: Duration) -> Result<(), ErrorCode> {
+        self.data_mut()?.extend_from_slice(bytes);
+        self.modified = now;
+        Ok(())
+    }
+
+    fn stat(&self) -> Stat {
+        Stat {
+            ty: self.descriptor_type(),
+            links: self.links,
+            size: self.size(),
+            accessed: self.accessed,
+            modified: self.modified,
+        }
+    }
+}
+
+/// A `descriptor-stat`.
+struct Stat {
+    ty: DescriptorType,
+    links: u64,
+    size: u64,
+    accessed: Duration,
+    modified: Duration,
+}
+
+impl Lower for Stat {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        unsafe {
+            self.ty.lower(ret, offset);
+            store(ret, offset + 4 * PTR, self.links);
+            store(ret, offset + 8 + 4 * PTR, self.size);
+            store_instant(ret, offset + 16 + 4 * PTR, Some(self.accessed));
+            store_instant(ret, offset + 40 + 4 * PTR, Some(self.modified));
+            store_instant(ret, offset + 64 + 4 * PTR, None);
+        }
+    }
+}
+
+impl Lower for DescriptorType {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        let disc = match self {
+            DescriptorType::Directory => 2u8,
+            DescriptorType::RegularFile => 5,
+            _ => unreachable!("the filesystem only has directories and files"),
+        };
+        unsafe { store(ret, offset, disc) }
+    }
+}
+
+/// Stores an `option<instant>` at `offset` of `ret`.
+unsafe fn store_instant(ret: *mut u8, offset: usize, time: Option<Duration>) {
+    unsafe {
+        match time {
+            Some(time) => {
+                store(ret, offset, 1u8);
+                store(ret, offset + 8, time.as_secs() as i64);
+                store(ret, offset + 16, time.subsec_nanos());
+            }
+            None => store(ret, offset, 0u8),
+        }
+    }
+}
+
+/// A `metadata-hash-value`.
+struct Hash(u64, u64);
+
+impl Lower for Hash {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        unsafe {
+            store(ret, offset, self.0);
+            store(ret, offset + 8, self.1);
+        }
+    }
+}
+
+pub(super) struct Fs {
+    root: NodeRef,
+    preopens: Vec<(NodeRef, String)>,
+}
+
+impl Default for Fs {
+    fn default() -> Fs {
+        Fs {
+            root: Node::new(Kind::Dir(BTreeMap::new()), Duration::ZERO),
+            preopens: Vec::new(),
+        }
+    }
+}
+
+/// Splits an absolute path given to [`MockHost`](super::MockHost).
+fn host_components(path: &str) -> impl Iterator<Item = &str> {
+    assert!(path.starts_with('/'), "`{}` isn't an absolute path", path);
+    path.split('/')
+        .filter(|c| !c.is_empty() && *c != ".")
+        .inspect(move |c| {
+            assert!(*c != "..", "`{}` contains `..`", path);
+        })
+}
+
+impl Fs {
+    pub(super) fn add_dir(&mut self, path: &str) -> NodeRef {
+        let mut dir = self.root.clone();
+        for name in host_components(path) {
+            let next = dir
+                .borrow_mut()
+                .entries_mut()
+                .unwrap_or_else(|_| panic!("a parent of `{}` is a file", path))
+                .entry(name.to_owned())
+                .or_insert_with(|| Node::new(Kind::Dir(BTreeMap::new()), Duration::ZERO))
+                .clone();
+            dir = next;
+        }
+        assert!(dir.borrow().is_dir(), "`{}` is a file", path);
+        dir
+    }
+
+    pub(super) fn add_file(&mut self, path: &str, contents: &[u8]) {
+        let (parent, name) = path.rsplit_once('/').expect("paths are absolute");
+        assert!(
+            !name.is_empty() && name != "." && name != "..",
+            "`{}` isn't a file path",
+            path
+        );
+        let parent = self.add_dir(if parent.is_empty() { "/" } else { parent });
+        let mut parent = parent.borrow_mut();
+        let entries = parent.entries_mut().unwrap();
+        match entries.get(name) {
+            Some(node) => match &mut node.borrow_mut().kind {
+                Kind::File(data) => *data = contents.to_vec(),
+                Kind::Dir(_) => panic!("`{}` is a directory", path),
+            },
+            None => {
+                let node = Node::new(Kind::File(contents.to_vec()), Duration::ZERO);
+                entries.insert(name.to_owned(), node);
+            }
+        }
+    }
+
+    pub(super) fn file(&self, path: &str) -> Option<Vec<u8>> {
+        let mut node = self.root.clone();
+        for name in host_components(path) {
+            let next = node.borrow().entries().ok()?.get(name)?.clone();
+            node = next;
+        }
+        let node = node.borrow();
+        match &node.kind {
+            Kind::File(data) => Some(data.clone()),
+            Kind::Dir(_) => None,
+        }
+    }
+
+    pub(super) fn preopen(&mut self, path: &str) {
+        let dir = self.add_dir(path);
+        self.preopens.push((dir, path.to_owned()));
+    }
+}
+
+/// Where a path leads, relative to a directory.
+enum Target {
+    /// A directory which the path names directly, such as `.`.
+    Dir(NodeRef),
+    /// The entry `name` of a directory, which may not exist.
+    Entry(NodeRef, String),
+}
+
+/// Resolves `path` relative to `base`, which it can't leave.
+fn resolve(base: &NodeRef, path: &str) -> Result<Target, ErrorCode> {
+    if path.is_empty() {
+        return Err(ErrorCode::NoEntry);
+    }
+    if path.starts_with('/') {
+        return Err(ErrorCode::NotPermitted);
+    }
+    let components = path
+        .split('/')
+        .filter(|c| !c.is_empty() && *c != ".")
+        .collect::<Vec<_>>();
+    let mut dirs = std::vec![base.clone()];
+    let (last, parents) = match components.split_last() {
+        Some(split) => split,
+        None => return Ok(Target::Dir(base.clone())),
+    };
+    for name in parents {
+        if *name == ".." {
+            if dirs.len() == 1 {
+                return Err(ErrorCode::NotPermitted);
+            }
+            dirs.pop();
+            continue;
+        }
+        let dir = dirs.last().unwrap().borrow().entries()?.get(*name).cloned();
+        let dir = dir.ok_or(ErrorCode::NoEntry)?;
+        if !dir.borrow().is_dir() {
+            return Err(ErrorCode::NotDirectory);
+        }
+        dirs.push(dir);
+    }
+    if *last == ".." {
+        if dirs.len() == 1 {
+            return Err(ErrorCode::NotPermitted);
+        }
+        dirs.pop();
+        return Ok(Target::Dir(dirs.pop().unwrap()));
+    }
+    Ok(Target::Entry(dirs.pop().unwrap(), (*last).to_owned()))
+}
+
+/// Returns the node `path` leads to relative to `base`.
+fn lookup(base: &NodeRef, path: &str) -> Result<NodeRef, ErrorCode> {
+    match resolve(base, path)? {
+        Target::Dir(dir) => Ok(dir),
+        Target::Entry(dir, name) => {
+            let node = dir.borrow().entries()?.get(&name).cloned();
+            node.ok_or(ErrorCode::NoEntry)
+        }
+    }
+}
+
+/// Returns whether `node` is `dir` or one of its ancestors.
+fn contains(node: &NodeRef, dir: &NodeRef) -> bool {
+    if Rc::ptr_eq(node, dir) {
+        return true;
+    }
+    match &node.borrow().kind {
+        Kind::Dir(entries) => entries.values().any(|entry| contains(entry, dir)),
+        Kind::File(_) => false,
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct Descriptor {
+    node: NodeRef,
+    flags: DescriptorFlags,
+}
+
+impl Descriptor {
+    fn has(&self, flag: DescriptorFlags) -> bool {
+        self.flags.bits() & flag.bits() != 0
+    }
+
+    fn readable(&self) -> Result<&NodeRef, ErrorCode> {
+        if !self.has(DescriptorFlags::READ) {
+            return Err(ErrorCode::BadDescriptor);
+        }
+        Ok(&self.node)
+    }
+
+    fn writable(&self) -> Result<&NodeRef, ErrorCode> {
+        if !self.has(DescriptorFlags::WRITE) {
+            return Err(ErrorCode::BadDescriptor);
+        }
+        Ok(&self.node)
+    }
+
+    /// Returns the directory, if its entries may be changed.
+    fn mutable(&self) -> Result<&NodeRef, ErrorCode> {
+        if !self.has(DescriptorFlags::MUTATE_DIRECTORY) {
+            return Err(ErrorCode::ReadOnly);
+        }
+        Ok(&self.node)
+    }
+
+    fn open_at(
+        &self,
+        path: &str,
+        open_flags: OpenFlags,
+        flags: DescriptorFlags,
+        now: Duration,
+    ) -> Result<Descriptor, ErrorCode> {
+        let open = |flag: OpenFlags| open_flags.bits() & flag.bits() != 0;
+        let wants = |flag: DescriptorFlags| flags.bits() & flag.bits() != 0;
+        if wants(DescriptorFlags::WRITE) || wants(DescriptorFlags::MUTATE_DIRECTORY) {
+            self.mutable()?;
+        }
+        let exclusive = open(OpenFlags::CREATE) && open(OpenFlags::EXCLUSIVE);
+        let node = match resolve(&self.node, path)? {
+            Target::Dir(_) if exclusive => return Err(ErrorCode::Exist),
+            Target::Dir(dir) => dir,
+            Target::Entry(dir, name) => {
+                let node = dir.borrow().entries()?.get(&name).cloned();
+                match node {
+                    Some(_) if exclusive => return Err(ErrorCode::Exist),
+                    Some(node) => node,
+                    None if open(OpenFlags::CREATE) => {
+                        if open(OpenFlags::DIRECTORY) {
+                            return Err(ErrorCode::Invalid);
+                        }
+                        self.mutable()?;
+                        let node = Node::new(Kind::File(Vec::new()), now);
+                        dir.borrow_mut().entries_mut()?.insert(name, node.clone());
+                        node
+                    }
+                    None => return Err(ErrorCode::NoEntry),
+                }
+            }
+        };
+        let is_dir = node.borrow().is_dir();
+        if open(OpenFlags::DIRECTORY) && !is_dir {
+            return Err(ErrorCode::NotDirectory);
+        }
+        if is_dir && wants(DescriptorFlags::WRITE) {
+            return Err(ErrorCode::IsDirectory);
+        }
+        if open(OpenFlags::TRUNCATE) {
+            if !wants(DescriptorFlags::WRITE) {
+                return Err(ErrorCode::Invalid);
+            }
+            let mut node = node.borrow_mut();
+            node.data_mut()?.clear();
+            node.modified = now;
+        }
+        let flags = if is_dir {
+            flags
+        } else {
+            // Only directories can be mutable.
+            DescriptorFlags::from_bits_retain(
+                flags.bits() & !DescriptorFlags::MUTATE_DIRECTORY.bits(),
+            )
+        };
+        Ok(Descriptor { node, flags })
+    }
+}
+
+/// Returns the time the `new-timestamp` at `offset` of `params` sets, if
+/// any.
+unsafe fn new_timestamp(params: *const u8, offset: usize, now: Duration) -> Option<Duration> {
+    unsafe {
+        match load::<u8>(params, offset) {
+            0 => None,
+            1 => Some(now),
+            _ => Some(Duration::new(
+                load::<i64>(params, offset + 8) as u64,
+                load::<u32>(params, offset + 16),
+            )),
+        }
+    }
+}
+
+fn set_times(node: &NodeRef, accessed: Option<Duration>, modified: Option<Duration>) {
+    let mut node = node.borrow_mut();
+    if let Some(accessed) = accessed {
+        node.accessed = accessed;
+    }
+    if let Some(modified) = modified {
+        node.modified = modified;
+    }
+}
+
+/// Returns the future of the `result<_, error-code>` of a stream import.
+fn result_future(result: Result<(), ErrorCode>) -> Channel {
+    Channel::ready(<Result<(), ErrorCode> as FuturePayload>::VTABLE, result)
+}
+
+/// Implements `write-via-stream` and `append-via-stream`, writing at
+/// `offset`, or appending if it's `None`.
+fn write_via_stream(handle: i32, data: i32, offset: Option<u64>) -> i32 {
+    with(|s| {
+        let d = s.table.get::<Descriptor>(handle);
+        let node = d.writable().cloned();
+        let data = streams::take_readable(s, data);
+        let node = match node {
+            Ok(node) => node,
+            Err(e) => {
+                streams::drop_reader(&data);
+                return streams::push_readable(s, result_future(Err(e)));
+            }
+        };
+        let status = Rc::new(RefCell::new(Ok(())));
+        let sink_status = status.clone();
+        let mut offset = offset;
+        data.borrow_mut().set_sink(move |bytes| {
+            if bytes.is_empty() || sink_status.borrow().is_err() {
+                return;
+            }
+            let now = with(|s| s.clocks.system);
+            let mut node = node.borrow_mut();
+            let result = match &mut offset {
+                Some(offset) => {
+                    let result = node.write_at(*offset, bytes, now);
+                    *offset += bytes.len() as u64;
+                    result
+                }
+                None => node.append(bytes, now),
+            };
+            *sink_status.borrow_mut() = result;
+        });
+        let future = Channel::lazy(
+            <Result<(), ErrorCode> as FuturePayload>::VTABLE,
+            move |idle| {
+                if idle || data.borrow().writer_dropped() {
+                    Some(status.borrow().clone())
+                } else {
+                    None
+                }
+            },
+        );
+        streams::push_readable(s, future)
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[resource-drop]descriptor"))]
+unsafe extern "C" fn drop_descriptor(handle: i32) {
+    drop_resource::<Descriptor>(handle);
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.read-via-stream"))]
+unsafe extern "C" fn descriptor_read_via_stream(handle: i32, offset: i64, ret: *mut u8) {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        let result = d.readable().and_then(|node| {
+            if node.borrow().is_dir() {
+                return Err(ErrorCode::IsDirectory);
+            }
+            Ok(node.clone())
+        });
+        let vtable = <u8 as StreamPayload>::VTABLE;
+        let (data, result) = match result {
+            Ok(node) => {
+                let mut data = Channel::stream(vtable);
+                let mut offset = offset as u64;
+                data.set_source(move |_| match node.borrow().read_at(offset, 64 * 1024) {
+                    Some(bytes) if !bytes.is_empty() => {
+                        offset += bytes.len() as u64;
+                        Pull::Data(bytes.into_iter().map(MaybeUninit::new).collect())
+                    }
+                    _ => Pull::End,
+                });
+                (data, result_future(Ok(())))
+            }
+            Err(e) => (Channel::bytes(vtable, &[]), result_future(Err(e))),
+        };
+        store(ret, 0, streams::push_readable(s, data));
+        store(ret, 4, streams::push_readable(s, result));
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.write-via-stream"))]
+unsafe extern "C" fn descriptor_write_via_stream(handle: i32, data: i32, offset: i64) -> i32 {
+    write_via_stream(handle, data, Some(offset as u64))
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.append-via-stream"))]
+unsafe extern "C" fn descriptor_append_via_stream(handle: i32, data: i32) -> i32 {
+    write_via_stream(handle, data, None)
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.advise"))]
+unsafe extern "C" fn descriptor_advise(
+    handle: i32,
+    _offset: i64,
+    _len: i64,
+    _advice: i32,
+    ret: *mut u8,
+) -> i32 {
+    with(|s| unsafe {
+        s.table.get::<Descriptor>(handle);
+        store_result(ret, PTR, Ok::<(), ErrorCode>(()));
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.sync-data"))]
+unsafe extern "C" fn descriptor_sync_data(handle: i32, ret: *mut u8) -> i32 {
+    unsafe { descriptor_sync(handle, ret) }
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.get-flags"))]
+unsafe extern "C" fn descriptor_get_flags(handle: i32, ret: *mut u8) -> i32 {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        store_result(ret, PTR, Ok::<u8, ErrorCode>(d.flags.bits()));
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.get-type"))]
+unsafe extern "C" fn descriptor_get_type(handle: i32, ret: *mut u8) -> i32 {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        let ty = d.node.borrow().descriptor_type();
+        store_result(ret, PTR, Ok::<_, ErrorCode>(ty));
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.set-size"))]
+unsafe extern "C" fn descriptor_set_size(handle: i32, size: i64, ret: *mut u8) -> i32 {
+    with(|s| unsafe {
+        let now = s.clocks.system;
+        let d = s.table.get::<Descriptor>(handle);
+        let result = d.writable().and_then(|node| {
+            let mut node = node.borrow_mut();
+            let size = usize::try_from(size as u64).map_err(|_| ErrorCode::FileTooLarge)?;
+            node.data_mut()?.resize(size, 0);
+            node.modified = now;
+            Ok(())
+        });
+        store_result(ret, PTR, result);
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.set-times"))]
+unsafe extern "C" fn descriptor_set_times(params: *const u8, ret: *mut u8) -> i32 {
+    with(|s| unsafe {
+        let now = s.clocks.system;
+        let d = s.table.get::<Descriptor>(load(params, 0));
+        set_times(
+            &d.node,
+            new_timestamp(params, 8, now),
+            new_timestamp(params, 32, now),
+        );
+        store_result(ret, PTR, Ok::<(), ErrorCode>(()));
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[method]descriptor.read-directory"))]
+unsafe extern "C" fn descriptor_read_directory(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        let vtable = <DirectoryEntry as StreamPayload>::VTABLE;
+        let mut entries = Channel::stream(vtable);
+        let result = d.node.borrow().entries().map(|dir| {
+            for (name, node) in dir {
+                let entry = DirectoryEntry {
+                    type_: node.borrow().descriptor_type(),
+                    name: name.clone(),
+                };
+                entries.push(vtable, entry);
+            }
+        });
+        entries.close();
+        store(ret, 0, streams::push_readable(s, entries));
+        store(ret, 4, streams::push_readable(s, result_future(result)));
+    })
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.sync"))]
+unsafe extern "C" fn descriptor_sync(handle: i32, ret: *mut u8) -> i32 {
+    with(|s| unsafe {
+        s.table.get::<Descriptor>(handle);
+        store_result(ret, PTR, Ok::<(), ErrorCode>(()));
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.create-directory-at"))]
+unsafe extern "C" fn descriptor_create_directory_at(
+    handle: i32,
+    ptr: *const u8,
+    len: usize,
+    ret: *mut u8,
+) -> i32 {
+    with(|s| unsafe {
+        let now = s.clocks.system;
+        let d = s.table.get::<Descriptor>(handle);
+        let result = d
+            .mutable()
+            .and_then(|base| match resolve(base, string(ptr, len))? {
+                Target::Dir(_) => Err(ErrorCode::Exist),
+                Target::Entry(dir, name) => {
+                    let mut dir = dir.borrow_mut();
+                    let entries = dir.entries_mut()?;
+                    if entries.contains_key(&name) {
+                        return Err(ErrorCode::Exist);
+                    }
+                    entries.insert(name, Node::new(Kind::Dir(BTreeMap::new()), now));
+                    Ok(())
+                }
+            });
+        store_result(ret, PTR, result);
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.stat"))]
+unsafe extern "C" fn descriptor_stat(handle: i32, ret: *mut u8) -> i32 {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        let stat = d.node.borrow().stat();
+        store_result(ret, 8, Ok::<Stat, ErrorCode>(stat));
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.stat-at"))]
+unsafe extern "C" fn descriptor_stat_at(
+    handle: i32,
+    _path_flags: i32,
+    ptr: *const u8,
+    len: usize,
+    ret: *mut u8,
+) -> i32 {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        let result = lookup(&d.node, string(ptr, len)).map(|node| node.borrow().stat());
+        store_result(ret, 8, result);
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.set-times-at"))]
+unsafe extern "C" fn descriptor_set_times_at(params: *const u8, ret: *mut u8) -> i32 {
+    with(|s| unsafe {
+        let now = s.clocks.system;
+        let d = s.table.get::<Descriptor>(load(params, 0));
+        let result = lookup(&d.node, load_string(params, 8)).map(|node| {
+            set_times(
+                &node,
+                new_timestamp(params, 8 + 2 * PTR, now),
+                new_timestamp(params, 32 + 2 * PTR, now),
+            )
+        });
+        store_result(ret, PTR, result);
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.link-at"))]
+unsafe extern "C" fn descriptor_link_at(params: *const u8, ret: *mut u8) -> i32 {
+    with(|s| unsafe {
+        let old = s.table.get::<Descriptor>(load(params, 0)).clone();
+        let new = s.table.get::<Descriptor>(load(params, 8 + 2 * PTR));
+        let result = new.mutable().and_then(|new_base| {
+            let node = lookup(&old.node, load_string(params, 8))?;
+            if node.borrow().is_dir() {
+                return Err(ErrorCode::NotPermitted);
+            }
+            match resolve(new_base, load_string(params, 8 + 3 * PTR))? {
+                Target::Dir(_) => Err(ErrorCode::Exist),
+                Target::Entry(dir, name) => {
+                    let mut dir = dir.borrow_mut();
+                    let entries = dir.entries_mut()?;
+                    if entries.contains_key(&name) {
+                        return Err(ErrorCode::Exist);
+                    }
+                    node.borrow_mut().links += 1;
+                    entries.insert(name, node);
+                    Ok(())
+                }
+            }
+        });
+        store_result(ret, PTR, result);
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.open-at"))]
+unsafe extern "C" fn descriptor_open_at(params: *const u8, ret: *mut u8) -> i32 {
+    with(|s| unsafe {
+        let now = s.clocks.system;
+        let d = s.table.get::<Descriptor>(load(params, 0));
+        let result = d.open_at(
+            load_string(params, 8),
+            OpenFlags::from_bits_retain(load(params, 8 + 2 * PTR)),
+            DescriptorFlags::from_bits_retain(load(params, 9 + 2 * PTR)),
+            now,
+        );
+        store_result(ret, PTR, result.map(|d| s.table.push(d)));
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.readlink-at"))]
+unsafe extern "C" fn descriptor_readlink_at(
+    handle: i32,
+    ptr: *const u8,
+    len: usize,
+    ret: *mut u8,
+) -> i32 {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        // There are no symbolic links to read.
+        let result = lookup(&d.node, string(ptr, len)).and(Err::<String, _>(ErrorCode::Invalid));
+        store_result(ret, PTR, result);
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.remove-directory-at"))]
+unsafe extern "C" fn descriptor_remove_directory_at(
+    handle: i32,
+    ptr: *const u8,
+    len: usize,
+    ret: *mut u8,
+) -> i32 {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        let result = d
+            .mutable()
+            .and_then(|base| match resolve(base, string(ptr, len))? {
+                Target::Dir(_) => Err(ErrorCode::Invalid),
+                Target::Entry(dir, name) => {
+                    let mut dir = dir.borrow_mut();
+                    let entries = dir.entries_mut()?;
+                    let node = entries.get(&name).ok_or(ErrorCode::NoEntry)?;
+                    if !node.borrow().entries()?.is_empty() {
+                        return Err(ErrorCode::NotEmpty);
+                    }
+                    let node = entries.remove(&name).unwrap();
+                    node.borrow_mut().links -= 1;
+                    Ok(())
+                }
+            });
+        store_result(ret, PTR, result);
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.rename-at"))]
+unsafe extern "C" fn descriptor_rename_at(params: *const u8, ret: *mut u8) -> i32 {
+    with(|s| unsafe {
+        let old = s.table.get::<Descriptor>(load(params, 0)).clone();
+        let new = s.table.get::<Descriptor>(load(params, 3 * PTR));
+        let result = (|| {
+            let (old_dir, old_name) = match resolve(old.mutable()?, load_string(params, PTR))? {
+                Target::Entry(dir, name) => (dir, name),
+                Target::Dir(_) => return Err(ErrorCode::Invalid),
+            };
+            let (new_dir, new_name) = match resolve(new.mutable()?, load_string(params, 4 * PTR))? {
+                Target::Entry(dir, name) => (dir, name),
+                Target::Dir(_) => return Err(ErrorCode::Invalid),
+            };
+            let node = old_dir.borrow().entries()?.get(&old_name).cloned();
+            let node = node.ok_or(ErrorCode::NoEntry)?;
+            if Rc::ptr_eq(&old_dir, &new_dir) && old_name == new_name {
+                return Ok(());
+            }
+            let is_dir = node.borrow().is_dir();
+            if is_dir && contains(&node, &new_dir) {
+                return Err(ErrorCode::Invalid);
+            }
+            let replaced = new_dir.borrow().entries()?.get(&new_name).cloned();
+            if let Some(replaced) = &replaced {
+                let replaced = replaced.borrow();
+                match (is_dir, replaced.entries()) {
+                    (false, Ok(_)) => return Err(ErrorCode::IsDirectory),
+                    (true, Err(_)) => return Err(ErrorCode::NotDirectory),
+                    (true, Ok(entries)) if !entries.is_empty() => return Err(ErrorCode::NotEmpty),
+                    _ => {}
+                }
+            }
+            if let Some(replaced) = replaced {
+                replaced.borrow_mut().links -= 1;
+            }
+            old_dir.borrow_mut().entries_mut()?.remove(&old_name);
+            new_dir.borrow_mut().entries_mut()?.insert(new_name, node);
+            Ok(())
+        })();
+        store_result(ret, PTR, result);
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.symlink-at"))]
+unsafe extern "C" fn descriptor_symlink_at(params: *const u8, ret: *mut u8) -> i32 {
+    with(|s| unsafe {
+        s.table.get::<Descriptor>(load(params, 0));
+        store_result(ret, PTR, Err::<(), ErrorCode>(ErrorCode::Unsupported));
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.unlink-file-at"))]
+unsafe extern "C" fn descriptor_unlink_file_at(
+    handle: i32,
+    ptr: *const u8,
+    len: usize,
+    ret: *mut u8,
+) -> i32 {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        let result = d
+            .mutable()
+            .and_then(|base| match resolve(base, string(ptr, len))? {
+                Target::Dir(_) => Err(ErrorCode::IsDirectory),
+                Target::Entry(dir, name) => {
+                    let mut dir = dir.borrow_mut();
+                    let entries = dir.entries_mut()?;
+                    let node = entries.get(&name).ok_or(ErrorCode::NoEntry)?;
+                    if node.borrow().is_dir() {
+                        return Err(ErrorCode::IsDirectory);
+                    }
+                    let node = entries.remove(&name).unwrap();
+                    node.borrow_mut().links -= 1;
+                    Ok(())
+                }
+            });
+        store_result(ret, PTR, result);
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.is-same-object"))]
+unsafe extern "C" fn descriptor_is_same_object(handle: i32, other: i32, ret: *mut u8) -> i32 {
+    with(|s| unsafe {
+        let node = s.table.get::<Descriptor>(handle).node.clone();
+        let other = &s.table.get::<Descriptor>(other).node;
+        store(ret, 0, Rc::ptr_eq(&node, other) as u8);
+    });
+    RETURNED
+}
+
+fn metadata_hash(node: &NodeRef) -> Hash {
+    let modified = node.borrow().modified;
+    Hash(Rc::as_ptr(node) as usize as u64, modified.as_nanos() as u64)
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.metadata-hash"))]
+unsafe extern "C" fn descriptor_metadata_hash(handle: i32, ret: *mut u8) -> i32 {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        store_result(ret, 8, Ok::<Hash, ErrorCode>(metadata_hash(&d.node)));
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("filesystem/types", "[async-lower][method]descriptor.metadata-hash-at"))]
+unsafe extern "C" fn descriptor_metadata_hash_at(
+    handle: i32,
+    _path_flags: i32,
+    ptr: *const u8,
+    len: usize,
+    ret: *mut u8,
+) -> i32 {
+    with(|s| unsafe {
+        let d = s.table.get::<Descriptor>(handle);
+        let result = lookup(&d.node, string(ptr, len)).map(|node| metadata_hash(&node));
+        store_result(ret, 8, result);
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("filesystem/preopens", "get-directories"))]
+unsafe extern "C" fn get_directories(ret: *mut u8) {
+    with(|s| unsafe {
+        let list = alloc_list(s.fs.preopens.len(), 3 * PTR, PTR);
+        for (i, (dir, path)) in s.fs.preopens.iter().enumerate() {
+            let descriptor = Descriptor {
+                node: dir.clone(),
+                flags: DescriptorFlags::READ | DescriptorFlags::MUTATE_DIRECTORY,
+            };
+            let elem = list.add(i * 3 * PTR);
+            store(elem, 0, s.table.push(descriptor));
+            store_bytes(elem, PTR, path.as_bytes());
+        }
+        store(ret, 0, list);
+        store(ret, PTR, s.fs.preopens.len());
+    })
+}
@@ -0,0 +1,746 @@
+//! `wasi:http`, whose requests are sent to the client set with
+//! [`MockHost::set_http_client`](super::MockHost::set_http_client).
+
+use super::streams::{self, Channel, ChannelRef, Pull, Readable};
+use super::{
+    alloc_list, bytes, drop_resource, load, store, store_bytes, store_result, string, with, Lower,
+    State, PTR, RETURNED,
+};
+use crate::http::types::{self, ErrorCode, HeaderError, Method, RequestOptionsError, Scheme};
+use crate::wit_future::FuturePayload;
+use core::future::Future;
+use core::pin::Pin;
+use std::borrow::ToOwned;
+use std::boxed::Box;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::string::String;
+use std::vec::Vec;
+
+pub(super) type BoxFuture<T> = Pin<Box<dyn Future<Output = T>>>;
+
+/// What handles the requests sent with `wasi:http/client`.
+pub(super) type Client =
+    Rc<dyn Fn(types::Request) -> BoxFuture<Result<types::Response, ErrorCode>>>;
+
+#[derive(Default)]
+pub(super) struct Http {
+    pub(super) client: Option<Client>,
+}
+
+/// Lowers an error whose last case is `other(option<string>)`, given its
+/// discriminant and, for that case, its message.
+unsafe fn lower_error_case(ret: *mut u8, offset: usize, code: u8, other: Option<Option<String>>) {
+    unsafe {
+        store(ret, offset, code);
+        match other {
+            Some(Some(message)) => {
+                store(ret, offset + PTR, 1u8);
+                store_bytes(ret, offset + 2 * PTR, message.as_bytes());
+            }
+            Some(None) => store(ret, offset + PTR, 0u8),
+            None => {}
+        }
+    }
+}
+
+impl Lower for HeaderError {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        let (code, other) = match self {
+            HeaderError::InvalidSyntax => (0, None),
+            HeaderError::Forbidden => (1, None),
+            HeaderError::Immutable => (2, None),
+            HeaderError::SizeExceeded => (3, None),
+            HeaderError::Other(message) => (4, Some(message)),
+        };
+        unsafe { lower_error_case(ret, offset, code, other) }
+    }
+}
+
+impl Lower for RequestOptionsError {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        let (code, other) = match self {
+            RequestOptionsError::NotSupported => (0, None),
+            RequestOptionsError::Immutable => (1, None),
+            RequestOptionsError::Other(message) => (2, Some(message)),
+        };
+        unsafe { lower_error_case(ret, offset, code, other) }
+    }
+}
+
+/// The values of a field, as returned by `get`.
+struct Values(Vec<Vec<u8>>);
+
+impl Lower for Values {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        let list = alloc_list(self.0.len(), 2 * PTR, PTR);
+        unsafe {
+            for (i, value) in self.0.iter().enumerate() {
+                store_bytes(list.add(i * 2 * PTR), 0, value);
+            }
+            store(ret, offset, list);
+            store(ret, offset + PTR, self.0.len());
+        }
+    }
+}
+
+/// Checks that `name` is a valid field name, which is an HTTP token.
+fn check_name(name: &str) -> Result<(), HeaderError> {
+    let token = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c);
+    if name.is_empty() || !name.chars().all(token) {
+        return Err(HeaderError::InvalidSyntax);
+    }
+    Ok(())
+}
+
+/// Checks that `value` is a valid field value.
+fn check_value(value: &[u8]) -> Result<(), HeaderError> {
+    if value.iter().any(|b| matches!(b, b'\r' | b'\n' | b'\0')) {
+        return Err(HeaderError::InvalidSyntax);
+    }
+    Ok(())
+}
+
+#[derive(Clone, Default)]
+pub(super) struct Fields {
+    entries: Vec<(String, Vec<u8>)>,
+    /// Whether the fields belong to a request or response.
+    immutable: bool,
+}
+
+impl Fields {
+    /// Returns a copy of the fields, which can't be modified.
+    fn immutable(&self) -> Fields {
+        Fields {
+            entries: self.entries.clone(),
+            immutable: true,
+        }
+    }
+
+    fn check_mutable(&self) -> Result<(), HeaderError> {
+        match self.immutable {
+            true => Err(HeaderError::Immutable),
+            false => Ok(()),
+        }
+    }
+
+    /// Returns the values of `name`, whose case doesn't matter.
+    fn get(&self, name: &str) -> Vec<Vec<u8>> {
+        self.entries
+            .iter()
+            .filter(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.clone())
+            .collect()
+    }
+
+    fn delete(&mut self, name: &str) -> Result<Vec<Vec<u8>>, HeaderError> {
+        self.check_mutable()?;
+        check_name(name)?;
+        let values = self.get(name);
+        self.entries.retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+        Ok(values)
+    }
+
+    fn append(&mut self, name: &str, value: &[u8]) -> Result<(), HeaderError> {
+        self.check_mutable()?;
+        check_name(name)?;
+        check_value(value)?;
+        self.entries.push((name.to_owned(), value.to_vec()));
+        Ok(())
+    }
+
+    fn set(&mut self, name: &str, values: Vec<&[u8]>) -> Result<(), HeaderError> {
+        self.check_mutable()?;
+        check_name(name)?;
+        values.iter().try_for_each(|value| check_value(value))?;
+        self.entries.retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+        for value in values {
+            self.entries.push((name.to_owned(), value.to_vec()));
+        }
+        Ok(())
+    }
+}
+
+/// The body of a request or response.
+struct Body {
+    contents: Option<ChannelRef>,
+    trailers: ChannelRef,
+    /// The future passed to `consume-body`, which is what the future returned
+    /// along with the request or response resolves to.
+    result: Rc<RefCell<Option<ChannelRef>>>,
+}
+
+impl Body {
+    /// Creates the body of a new request or response, returning it along
+    /// with the future reporting how it was transmitted.
+    fn new(s: &mut State, contents: Option<i32>, trailers: i32) -> (Body, Channel) {
+        let body = Body {
+            contents: contents.map(|contents| streams::take_readable(s, contents)),
+            trailers: streams::take_readable(s, trailers),
+            result: Rc::default(),
+        };
+        let vtable = <Result<(), ErrorCode> as FuturePayload>::VTABLE;
+        let result = body.result.clone();
+        let mut transmitted = Channel::future(vtable);
+        transmitted.set_source(move |idle| {
+            let res = result.borrow().clone();
+            match res.map(|res| Channel::pull(&res, idle)) {
+                Some(Pull::Data(data)) => Pull::Data(data),
+                Some(Pull::Pending) | None if !idle => Pull::Pending,
+                // Bodies which aren't consumed, or whose consumer doesn't
+                // report how it went, count as transmitted.
+                _ => Pull::Data(streams::lower(vtable.layout.size(), |ptr| unsafe {
+                    (vtable.lower)(Ok(()), ptr)
+                })),
+            }
+        });
+        (body, transmitted)
+    }
+
+    /// Implements `consume-body`, returning the handles of the contents and
+    /// trailers.
+    fn consume(self, s: &mut State, res: i32) -> (i32, i32) {
+        *self.result.borrow_mut() = Some(streams::take_readable(s, res));
+        let contents = match self.contents {
+            Some(contents) => s.table.push(Readable(contents)),
+            None => {
+                let vtable = <u8 as crate::service::wit_stream::StreamPayload>::VTABLE;
+                streams::push_readable(s, Channel::bytes(vtable, &[]))
+            }
+        };
+        let trailers = s.table.push(Readable(self.trailers));
+        (contents, trailers)
+    }
+}
+
+unsafe fn load_method(disc: i32, ptr: *const u8, len: usize) -> Method {
+    match disc {
+        0 => Method::Get,
+        1 => Method::Head,
+        2 => Method::Post,
+        3 => Method::Put,
+        4 => Method::Delete,
+        5 => Method::Connect,
+        6 => Method::Options,
+        7 => Method::Trace,
+        8 => Method::Patch,
+        _ => Method::Other(unsafe { string(ptr, len) }.to_owned()),
+    }
+}
+
+unsafe fn load_scheme(disc: i32, ptr: *const u8, len: usize) -> Scheme {
+    match disc {
+        0 => Scheme::Http,
+        1 => Scheme::Https,
+        _ => Scheme::Other(unsafe { string(ptr, len) }.to_owned()),
+    }
+}
+
+/// Stores an `option<string>` at `offset` of `ret`.
+unsafe fn store_option_string(ret: *mut u8, offset: usize, value: &Option<String>) {
+    unsafe {
+        match value {
+            Some(value) => {
+                store(ret, offset, 1u8);
+                store_bytes(ret, offset + PTR, value.as_bytes());
+            }
+            None => store(ret, offset, 0u8),
+        }
+    }
+}
+
+pub(super) struct Request {
+    method: Method,
+    scheme: Option<Scheme>,
+    authority: Option<String>,
+    path_with_query: Option<String>,
+    headers: Fields,
+    options: Option<RequestOptions>,
+    body: Body,
+}
+
+#[derive(Clone, Default)]
+pub(super) struct RequestOptions {
+    connect_timeout: Option<u64>,
+    first_byte_timeout: Option<u64>,
+    between_bytes_timeout: Option<u64>,
+    /// Whether the options belong to a request.
+    immutable: bool,
+}
+
+pub(super) struct Response {
+    status: u16,
+    headers: Fields,
+    body: Body,
+}
+
+#[unsafe(export_name = symbol!("http/types", "[resource-drop]fields"))]
+unsafe extern "C" fn drop_fields(handle: i32) {
+    drop_resource::<Fields>(handle);
+}
+
+#[unsafe(export_name = symbol!("http/types", "[resource-drop]request"))]
+unsafe extern "C" fn drop_request(handle: i32) {
+    drop_resource::<Request>(handle);
+}
+
+#[unsafe(export_name = symbol!("http/types", "[resource-drop]request-options"))]
+unsafe extern "C" fn drop_request_options(handle: i32) {
+    drop_resource::<RequestOptions>(handle);
+}
+
+#[unsafe(export_name = symbol!("http/types", "[resource-drop]response"))]
+unsafe extern "C" fn drop_response(handle: i32) {
+    drop_resource::<Response>(handle);
+}
+
+#[unsafe(export_name = symbol!("http/types", "[constructor]fields"))]
+unsafe extern "C" fn fields_new() -> i32 {
+    with(|s| s.table.push(Fields::default()))
+}
+
+#[unsafe(export_name = symbol!("http/types", "[static]fields.from-list"))]
+unsafe extern "C" fn fields_from_list(ptr: *const u8, len: usize, ret: *mut u8) {
+    with(|s| unsafe {
+        let mut fields = Fields::default();
+        let result = (0..len)
+            .try_for_each(|i| {
+                let elem = ptr.add(i * 4 * PTR);
+                let name = string(load(elem, 0), load(elem, PTR));
+                let value = bytes(load(elem, 2 * PTR), load(elem, 3 * PTR));
+                fields.append(name, value)
+            })
+            .map(|()| s.table.push(fields));
+        store_result(ret, PTR, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]fields.get"))]
+unsafe extern "C" fn fields_get(handle: i32, ptr: *const u8, len: usize, ret: *mut u8) {
+    with(|s| unsafe {
+        let values = s.table.get::<Fields>(handle).get(string(ptr, len));
+        Values(values).lower(ret, 0);
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]fields.has"))]
+unsafe extern "C" fn fields_has(handle: i32, ptr: *const u8, len: usize) -> i32 {
+    with(|s| unsafe {
+        let name = string(ptr, len);
+        !s.table.get::<Fields>(handle).get(name).is_empty() as i32
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]fields.set"))]
+unsafe extern "C" fn fields_set(
+    handle: i32,
+    name_ptr: *const u8,
+    name_len: usize,
+    ptr: *const u8,
+    len: usize,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let values = (0..len)
+            .map(|i| {
+                let elem = ptr.add(i * 2 * PTR);
+                bytes(load(elem, 0), load(elem, PTR))
+            })
+            .collect();
+        let name = string(name_ptr, name_len);
+        let result = s.table.get::<Fields>(handle).set(name, values);
+        store_result(ret, PTR, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]fields.delete"))]
+unsafe extern "C" fn fields_delete(handle: i32, ptr: *const u8, len: usize, ret: *mut u8) {
+    with(|s| unsafe {
+        let result = s.table.get::<Fields>(handle).delete(string(ptr, len));
+        store_result(ret, PTR, result.map(drop));
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]fields.get-and-delete"))]
+unsafe extern "C" fn fields_get_and_delete(handle: i32, ptr: *const u8, len: usize, ret: *mut u8) {
+    with(|s| unsafe {
+        let result = s.table.get::<Fields>(handle).delete(string(ptr, len));
+        store_result(ret, PTR, result.map(Values));
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]fields.append"))]
+unsafe extern "C" fn fields_append(
+    handle: i32,
+    name_ptr: *const u8,
+    name_len: usize,
+    ptr: *const u8,
+    len: usize,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let name = string(name_ptr, name_len);
+        let result = s.table.get::<Fields>(handle).append(name, bytes(ptr, len));
+        store_result(ret, PTR, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]fields.copy-all"))]
+unsafe extern "C" fn fields_copy_all(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let entries = &s.table.get::<Fields>(handle).entries;
+        let list = alloc_list(entries.len(), 4 * PTR, PTR);
+        for (i, (name, value)) in entries.iter().enumerate() {
+            let elem = list.add(i * 4 * PTR);
+            store_bytes(elem, 0, name.as_bytes());
+            store_bytes(elem, 2 * PTR, value);
+        }
+        store(ret, 0, list);
+        store(ret, PTR, entries.len());
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]fields.clone"))]
+unsafe extern "C" fn fields_clone(handle: i32) -> i32 {
+    with(|s| {
+        let fields = Fields {
+            entries: s.table.get::<Fields>(handle).entries.clone(),
+            immutable: false,
+        };
+        s.table.push(fields)
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[static]request.new"))]
+unsafe extern "C" fn request_new(
+    headers: i32,
+    has_contents: i32,
+    contents: i32,
+    trailers: i32,
+    has_options: i32,
+    options: i32,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let headers = s.table.delete::<Fields>(headers).immutable();
+        let options = (has_options != 0).then(|| RequestOptions {
+            immutable: true,
+            ..s.table.delete::<RequestOptions>(options)
+        });
+        let (body, transmitted) = Body::new(s, (has_contents != 0).then_some(contents), trailers);
+        let request = s.table.push(Request {
+            method: Method::Get,
+            scheme: None,
+            authority: None,
+            path_with_query: None,
+            headers,
+            options,
+            body,
+        });
+        store(ret, 0, request);
+        store(ret, 4, streams::push_readable(s, transmitted));
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]request.get-method"))]
+unsafe extern "C" fn request_get_method(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let method = &s.table.get::<Request>(handle).method;
+        let disc = match method {
+            Method::Get => 0u8,
+            Method::Head => 1,
+            Method::Post => 2,
+            Method::Put => 3,
+            Method::Delete => 4,
+            Method::Connect => 5,
+            Method::Options => 6,
+            Method::Trace => 7,
+            Method::Patch => 8,
+            Method::Other(method) => {
+                store_bytes(ret, PTR, method.as_bytes());
+                9
+            }
+        };
+        store(ret, 0, disc);
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]request.set-method"))]
+unsafe extern "C" fn request_set_method(handle: i32, disc: i32, ptr: *const u8, len: usize) -> i32 {
+    with(|s| unsafe {
+        let method = load_method(disc, ptr, len);
+        if let Method::Other(method) = &method {
+            if check_name(method).is_err() {
+                return 1;
+            }
+        }
+        s.table.get::<Request>(handle).method = method;
+        0
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]request.get-path-with-query"))]
+unsafe extern "C" fn request_get_path_with_query(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let path_with_query = &s.table.get::<Request>(handle).path_with_query;
+        store_option_string(ret, 0, path_with_query);
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]request.set-path-with-query"))]
+unsafe extern "C" fn request_set_path_with_query(
+    handle: i32,
+    is_some: i32,
+    ptr: *const u8,
+    len: usize,
+) -> i32 {
+    with(|s| unsafe {
+        let path_with_query = (is_some != 0).then(|| string(ptr, len));
+        let valid = path_with_query
+            .is_none_or(|path| !path.is_empty() && path.chars().all(|c| c.is_ascii_graphic()));
+        if !valid {
+            return 1;
+        }
+        s.table.get::<Request>(handle).path_with_query = path_with_query.map(ToOwned::to_owned);
+        0
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]request.get-scheme"))]
+unsafe extern "C" fn request_get_scheme(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let scheme = &s.table.get::<Request>(handle).scheme;
+        let Some(scheme) = scheme else {
+            store(ret, 0, 0u8);
+            return;
+        };
+        store(ret, 0, 1u8);
+        let disc = match scheme {
+            Scheme::Http => 0u8,
+            Scheme::Https => 1,
+            Scheme::Other(scheme) => {
+                store_bytes(ret, 2 * PTR, scheme.as_bytes());
+                2
+            }
+        };
+        store(ret, PTR, disc);
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]request.set-scheme"))]
+unsafe extern "C" fn request_set_scheme(
+    handle: i32,
+    is_some: i32,
+    disc: i32,
+    ptr: *const u8,
+    len: usize,
+) -> i32 {
+    with(|s| unsafe {
+        let scheme = (is_some != 0).then(|| load_scheme(disc, ptr, len));
+        if let Some(Scheme::Other(scheme)) = &scheme {
+            let valid = |c: char| c.is_ascii_alphanumeric() || "+-.".contains(c);
+            if !scheme.starts_with(|c: char| c.is_ascii_alphabetic()) || !scheme.chars().all(valid)
+            {
+                return 1;
+            }
+        }
+        s.table.get::<Request>(handle).scheme = scheme;
+        0
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]request.get-authority"))]
+unsafe extern "C" fn request_get_authority(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let authority = &s.table.get::<Request>(handle).authority;
+        store_option_string(ret, 0, authority);
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]request.set-authority"))]
+unsafe extern "C" fn request_set_authority(
+    handle: i32,
+    is_some: i32,
+    ptr: *const u8,
+    len: usize,
+) -> i32 {
+    with(|s| unsafe {
+        let authority = (is_some != 0).then(|| string(ptr, len));
+        let valid =
+            authority.is_none_or(|authority| authority.chars().all(|c| c.is_ascii_graphic()));
+        if !valid {
+            return 1;
+        }
+        s.table.get::<Request>(handle).authority = authority.map(ToOwned::to_owned);
+        0
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]request.get-options"))]
+unsafe extern "C" fn request_get_options(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        match s.table.get::<Request>(handle).options.clone() {
+            Some(options) => {
+                store(ret, 0, 1u8);
+                store(ret, 4, s.table.push(options));
+            }
+            None => store(ret, 0, 0u8),
+        }
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]request.get-headers"))]
+unsafe extern "C" fn request_get_headers(handle: i32) -> i32 {
+    with(|s| {
+        let headers = s.table.get::<Request>(handle).headers.immutable();
+        s.table.push(headers)
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[static]request.consume-body"))]
+unsafe extern "C" fn request_consume_body(handle: i32, res: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let request = s.table.delete::<Request>(handle);
+        let (contents, trailers) = request.body.consume(s, res);
+        store(ret, 0, contents);
+        store(ret, 4, trailers);
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[constructor]request-options"))]
+unsafe extern "C" fn request_options_new() -> i32 {
+    with(|s| s.table.push(RequestOptions::default()))
+}
+
+/// Defines the getter and setter imports of a timeout of `request-options`.
+macro_rules! timeout {
+    ($field:ident, $get:literal => $get_fn:ident, $set:literal => $set_fn:ident $(,)?) => {
+        #[unsafe(export_name = symbol!("http/types", $get))]
+        unsafe extern "C" fn $get_fn(handle: i32, ret: *mut u8) {
+            with(|s| unsafe {
+                match s.table.get::<RequestOptions>(handle).$field {
+                    Some(timeout) => {
+                        store(ret, 0, 1u8);
+                        store(ret, 8, timeout);
+                    }
+                    None => store(ret, 0, 0u8),
+                }
+            })
+        }
+
+        #[unsafe(export_name = symbol!("http/types", $set))]
+        unsafe extern "C" fn $set_fn(handle: i32, is_some: i32, value: i64, ret: *mut u8) {
+            with(|s| unsafe {
+                let options = s.table.get::<RequestOptions>(handle);
+                let result = match options.immutable {
+                    true => Err(RequestOptionsError::Immutable),
+                    false => {
+                        options.$field = (is_some != 0).then_some(value as u64);
+                        Ok(())
+                    }
+                };
+                store_result(ret, PTR, result);
+            })
+        }
+    };
+}
+
+timeout!(
+    connect_timeout,
+    "[method]request-options.get-connect-timeout" => request_options_get_connect_timeout,
+    "[method]request-options.set-connect-timeout" => request_options_set_connect_timeout,
+);
+
+timeout!(
+    first_byte_timeout,
+    "[method]request-options.get-first-byte-timeout" => request_options_get_first_byte_timeout,
+    "[method]request-options.set-first-byte-timeout" => request_options_set_first_byte_timeout,
+);
+
+timeout!(
+    between_bytes_timeout,
+    "[method]request-options.get-between-bytes-timeout" => request_options_get_between_bytes_timeout,
+    "[method]request-options.set-between-bytes-timeout" => request_options_set_between_bytes_timeout,
+);
+
+#[unsafe(export_name = symbol!("http/types", "[method]request-options.clone"))]
+unsafe extern "C" fn request_options_clone(handle: i32) -> i32 {
+    with(|s| {
+        let options = RequestOptions {
+            immutable: false,
+            ..s.table.get::<RequestOptions>(handle).clone()
+        };
+        s.table.push(options)
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[static]response.new"))]
+unsafe extern "C" fn response_new(
+    headers: i32,
+    has_contents: i32,
+    contents: i32,
+    trailers: i32,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let headers = s.table.delete::<Fields>(headers).immutable();
+        let (body, transmitted) = Body::new(s, (has_contents != 0).then_some(contents), trailers);
+        let response = s.table.push(Response {
+            status: 200,
+            headers,
+            body,
+        });
+        store(ret, 0, response);
+        store(ret, 4, streams::push_readable(s, transmitted));
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]response.get-status-code"))]
+unsafe extern "C" fn response_get_status_code(handle: i32) -> i32 {
+    with(|s| s.table.get::<Response>(handle).status.into())
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]response.set-status-code"))]
+unsafe extern "C" fn response_set_status_code(handle: i32, status: i32) -> i32 {
+    with(|s| {
+        if !(100..=999).contains(&status) {
+            return 1;
+        }
+        s.table.get::<Response>(handle).status = status as u16;
+        0
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[method]response.get-headers"))]
+unsafe extern "C" fn response_get_headers(handle: i32) -> i32 {
+    with(|s| {
+        let headers = s.table.get::<Response>(handle).headers.immutable();
+        s.table.push(headers)
+    })
+}
+
+#[unsafe(export_name = symbol!("http/types", "[static]response.consume-body"))]
+unsafe extern "C" fn response_consume_body(handle: i32, res: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let response = s.table.delete::<Response>(handle);
+        let (contents, trailers) = response.body.consume(s, res);
+        store(ret, 0, contents);
+        store(ret, 4, trailers);
+    })
+}
+
+#[unsafe(export_name = symbol!("http/client", "[async-lower]send"))]
+unsafe extern "C" fn client_send(request: i32, ret: *mut u8) -> i32 {
+    let (client, executor) = with(|s| (s.http.client.clone(), s.executor.clone()));
+    // The request stays in the table, handed over to the client.
+    let request = unsafe { types::Request::from_handle(request as u32) };
+    // The client runs like any other code under test, which may use the
+    // host, so it's not borrowed meanwhile.
+    let result = match client {
+        Some(client) => executor.block_on(client(request)),
+        None => Err(ErrorCode::ConnectionRefused),
+    };
+    let result = result.map(|response| response.take_handle() as i32);
+    unsafe { store_result(ret, 8, result) };
+    RETURNED
+}
@@ -0,0 +1,616 @@
+//! An in-memory WASI host for running code built on this crate natively.
+//!
+//! With the `mock-host` feature, on targets other than wasm the imports of
+//! this crate link against functions defined in this module instead of being
+//! unimplemented. They act on the [`MockHost`] installed on the current
+//! thread, and async code built on this crate runs on the executor of that
+//! host with [`MockHost::block_on`], so it can be tested with a plain
+//! `cargo test`:
+//!
+//! ```
+//! use wasip3::mock_host::MockHost;
+//! use wasip3::filesystem::types::{DescriptorFlags, OpenFlags, PathFlags};
+//!
+//! let host = MockHost::new();
+//! host.add_file("/data/greeting.txt", b"hello");
+//! host.preopen("/data");
+//!
+//! host.block_on(async {
+//!     let (dir, path) = wasip3::filesystem::preopens::get_directories().remove(0);
+//!     assert_eq!(path, "/data");
+//!     let file = dir
+//!         .open_at(
+//!             PathFlags::empty(),
+//!             "greeting.txt".into(),
+//!             OpenFlags::empty(),
+//!             DescriptorFlags::READ,
+//!         )
+//!         .await
+//!         .unwrap();
+//!     let (contents, result) = file.read_via_stream(0);
+//!     let greeting = contents.collect().await;
+//!     result.await.unwrap();
+//!
+//!     let (mut tx, rx) = wasip3::wit_stream::new();
+//!     let written = wasip3::cli::stdout::write_via_stream(rx);
+//!     tx.write_all(greeting).await;
+//!     drop(tx);
+//!     written.await.unwrap();
+//! });
+//! assert_eq!(host.take_stdout(), b"hello");
+//! ```
+//!
+//! The host provides:
+//!
+//! * A filesystem tree, populated with [`MockHost::add_file`] and
+//!   [`MockHost::add_dir`], whose directories are handed to the guest with
+//!   [`MockHost::preopen`].
+//! * Stdin fed with [`MockHost::push_stdin`], which reports the end of the
+//!   stream once everything pushed was read, and stdout and stderr captured
+//!   for [`MockHost::take_stdout`] and [`MockHost::take_stderr`].
+//! * Arguments, environment variables and an initial working directory.
+//! * Clocks which only move when told to, with [`MockHost::advance`] and
+//!   friends. Waiting on `monotonic-clock` advances both clocks to its
+//!   deadline instead of sleeping.
+//! * `random`, `insecure` and `insecure-seed` implementations which are
+//!   deterministic for a given [`MockHost::set_random_seed`].
+//! * A loopback network, on which TCP and UDP sockets of the host can bind,
+//!   connect and send to each other. `ip-name-lookup` resolves `localhost`
+//!   and IP addresses.
+//! * The resources of `wasi:http/types`, so requests can be built and handed
+//!   to a `wasi:http/handler` implementation, and its responses inspected.
+//!   `wasi:http/client` sends requests to the function set with
+//!   [`MockHost::set_http_client`].
+//!
+//! Tasks spawned with [`spawn`], which is what the crate's `spawn` is with
+//! this feature, run whenever the future passed to [`MockHost::block_on`], or
+//! an import, waits for them. Those which haven't completed when the last
+//! clone of the host is dropped are dropped with the host installed.
+//!
+//! Everything happens on the calling thread, and imports complete before they
+//! return, so reading from a stream or future which no task writes to panics
+//! instead of hanging. Futures which report that a write completed, such as
+//! the one of `write-via-stream`, resolve once the writer is dropped or
+//! nothing else can run. Since the imports are `extern "C"` functions, such
+//! panics, as well as calling an import without an installed host, abort the
+//! process. `wasi:cli/exit` exits the process, as it would in a wasm host.
+//! Terminals aren't provided.
+
+use core::future::Future;
+use std::alloc::{self, Layout};
+use std::boxed::Box;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::string::String;
+use std::time::Duration;
+use std::vec::Vec;
+
+/// Expands to the native symbol of an import, which `ci/regenerate.sh`
+/// qualifies with the interface it belongs to.
+macro_rules! symbol {
+    ($interface:literal, $name:expr) => {
+        concat!("wasi:", $interface, "@0.3.0#", $name)
+    };
+}
+
+mod cli;
+mod clocks;
+mod executor;
+mod filesystem;
+mod http;
+mod random;
+mod sockets;
+mod streams;
+
+std::thread_local! {
+    static CURRENT: RefCell<Option<Rc<RefCell<State>>>> = const { RefCell::new(None) };
+}
+
+/// An in-memory host, see the [module documentation](self).
+///
+/// Clones refer to the same host, so one can be kept to inspect and drive the
+/// host while the code under test runs.
+#[derive(Clone, Default)]
+pub struct MockHost {
+    state: Rc<RefCell<State>>,
+}
+
+impl MockHost {
+    /// Creates a host with an empty filesystem, no arguments, environment
+    /// variables or stdin, both clocks at zero and a random seed of zero.
+    pub fn new() -> MockHost {
+        MockHost::default()
+    }
+
+    /// Installs this host on the current thread, until the returned guard is
+    /// dropped.
+    ///
+    /// Dropping the guard reinstalls the host which was installed before, if
+    /// any, so hosts can be nested.
+    pub fn install(&self) -> MockHostGuard {
+        let prev = CURRENT.with(|c| c.replace(Some(self.state.clone())));
+        MockHostGuard {
+            prev,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Installs this host and runs `future` to completion, along with the
+    /// tasks it spawns while it waits for them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `future` waits on something which none of the spawned tasks
+    /// can provide.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        let _guard = self.install();
+        let executor = self.state.borrow().executor.clone();
+        executor.block_on(future)
+    }
+
+    /// Sets the arguments returned by `wasi:cli/environment`.
+    pub fn set_args<I>(&self, args: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.state.borrow_mut().cli.args = args.into_iter().map(Into::into).collect();
+    }
+
+    /// Adds an environment variable returned by `wasi:cli/environment`.
+    pub fn set_env(&self, key: impl Into<String>, value: impl Into<String>) {
+        let mut state = self.state.borrow_mut();
+        let env = &mut state.cli.env;
+        let key = key.into();
+        env.retain(|(k, _)| *k != key);
+        env.push((key, value.into()));
+    }
+
+    /// Sets the initial working directory returned by `wasi:cli/environment`.
+    pub fn set_initial_cwd(&self, cwd: impl Into<String>) {
+        self.state.borrow_mut().cli.initial_cwd = Some(cwd.into());
+    }
+
+    /// Appends `bytes` to what stdin has left to read.
+    pub fn push_stdin(&self, bytes: &[u8]) {
+        let state = self.state.borrow();
+        state.cli.stdin.borrow_mut().extend_from_slice(bytes);
+    }
+
+    /// Returns what was written to stdout since the last call.
+    pub fn take_stdout(&self) -> Vec<u8> {
+        let state = self.state.borrow();
+        let mut stdout = state.cli.stdout.borrow_mut();
+        core::mem::take(&mut *stdout)
+    }
+
+    /// Returns what was written to stderr since the last call.
+    pub fn take_stderr(&self) -> Vec<u8> {
+        let state = self.state.borrow();
+        let mut stderr = state.cli.stderr.borrow_mut();
+        core::mem::take(&mut *stderr)
+    }
+
+    /// Returns the time of `monotonic-clock`, in nanoseconds.
+    pub fn monotonic_clock(&self) -> u64 {
+        self.state.borrow().clocks.monotonic
+    }
+
+    /// Sets the time of `monotonic-clock`, in nanoseconds.
+    pub fn set_monotonic_clock(&self, instant: u64) {
+        self.state.borrow_mut().clocks.monotonic = instant;
+    }
+
+    /// Returns the time of `system-clock`, since the Unix epoch.
+    pub fn system_clock(&self) -> Duration {
+        self.state.borrow().clocks.system
+    }
+
+    /// Sets the time of `system-clock`, since the Unix epoch.
+    pub fn set_system_clock(&self, since_epoch: Duration) {
+        self.state.borrow_mut().clocks.system = since_epoch;
+    }
+
+    /// Advances both clocks by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        self.state.borrow_mut().clocks.advance(duration);
+    }
+
+    /// Restarts the random number generators from `seed`, which also
+    /// determines the value of `insecure-seed`.
+    pub fn set_random_seed(&self, seed: u64) {
+        self.state.borrow_mut().random = random::Random::new(seed);
+    }
+
+    /// Creates the directory at the absolute `path` and its parents, if they
+    /// don't exist.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `path` or one of its parents is a file.
+    pub fn add_dir(&self, path: &str) {
+        self.state.borrow_mut().fs.add_dir(path);
+    }
+
+    /// Creates or replaces the file at the absolute `path`, creating its
+    /// parents if they don't exist.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `path` is a directory or one of its parents is a file.
+    pub fn add_file(&self, path: &str, contents: &[u8]) {
+        self.state.borrow_mut().fs.add_file(path, contents);
+    }
+
+    /// Returns the contents of the file at the absolute `path`, if there is
+    /// one.
+    pub fn file(&self, path: &str) -> Option<Vec<u8>> {
+        self.state.borrow().fs.file(path)
+    }
+
+    /// Creates the directory at the absolute `path` like [`add_dir`], and
+    /// returns it from `wasi:filesystem/preopens` under that path.
+    ///
+    /// [`add_dir`]: MockHost::add_dir
+    pub fn preopen(&self, path: &str) {
+        self.state.borrow_mut().fs.preopen(path);
+    }
+
+    /// Handles the requests sent with `wasi:http/client` with `client`,
+    /// which is typically the `handle` function of a `wasi:http/handler`
+    /// implementation.
+    ///
+    /// Without a client, sending fails with `connection-refused`.
+    pub fn set_http_client<F, Fut>(&self, client: F)
+    where
+        F: Fn(crate::http::types::Request) -> Fut + 'static,
+        Fut: Future<Output = Result<crate::http::types::Response, crate::http::types::ErrorCode>>
+            + 'static,
+    {
+        let client: http::Client = Rc::new(move |request| Box::pin(client(request)));
+        self.state.borrow_mut().http.client = Some(client);
+    }
+}
+
+impl core::fmt::Debug for MockHost {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MockHost").finish_non_exhaustive()
+    }
+}
+
+impl Drop for MockHost {
+    fn drop(&mut self) {
+        // The tasks which didn't complete may still use the host as they're
+        // dropped, e.g. to close the streams they were writing to.
+        if Rc::strong_count(&self.state) == 1 {
+            let _guard = self.install();
+            let executor = self.state.borrow().executor.clone();
+            executor.clear();
+        }
+    }
+}
+
+/// Uninstalls a host installed with [`MockHost::install`] when dropped.
+#[must_use = "the host is uninstalled when the guard is dropped"]
+pub struct MockHostGuard {
+    prev: Option<Rc<RefCell<State>>>,
+    // The host is installed on the thread which created the guard.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for MockHostGuard {
+    fn drop(&mut self) {
+        let prev = self.prev.take();
+        CURRENT.with(|c| *c.borrow_mut() = prev);
+    }
+}
+
+impl core::fmt::Debug for MockHostGuard {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MockHostGuard").finish_non_exhaustive()
+    }
+}
+
+/// Spawns `future` on the executor of the host installed on the current
+/// thread.
+///
+/// The task runs whenever the future passed to [`MockHost::block_on`], or an
+/// import, waits for something.
+///
+/// # Panics
+///
+/// Panics if no host is installed.
+pub fn spawn(future: impl Future<Output = ()> + 'static) {
+    let executor = with(|s| s.executor.clone());
+    executor.spawn(future);
+}
+
+#[derive(Default)]
+struct State {
+    table: Table,
+    executor: Rc<executor::Executor>,
+    cli: cli::Cli,
+    clocks: clocks::Clocks,
+    random: random::Random,
+    fs: filesystem::Fs,
+    net: sockets::Net,
+    http: http::Http,
+}
+
+/// Calls `f` with the host installed on the current thread.
+fn with<R>(f: impl FnOnce(&mut State) -> R) -> R {
+    let state = CURRENT.with(|c| c.borrow().clone()).expect(
+        "no `MockHost` is installed on this thread, see `wasip3::mock_host::MockHost::install`",
+    );
+    let mut state = state.borrow_mut();
+    f(&mut state)
+}
+
+/// Removes the resource `handle` from the table, for a `[resource-drop]`
+/// import.
+///
+/// Resources may outlive the host they came from, in which case this does
+/// nothing.
+fn drop_resource<T: Entry>(handle: i32) -> Option<T> {
+    let state = CURRENT.with(|c| c.borrow().clone())?;
+    let resource = state.borrow_mut().table.delete::<T>(handle);
+    Some(resource)
+}
+
+/// The resources, and ends of streams and futures, handed out to the guest.
+#[derive(Default)]
+struct Table {
+    next: u32,
+    entries: HashMap<u32, Resource>,
+}
+
+impl Table {
+    fn push(&mut self, resource: impl Into<Resource>) -> i32 {
+        self.next += 1;
+        self.entries.insert(self.next, resource.into());
+        self.next as i32
+    }
+
+    fn get<T: Entry>(&mut self, handle: i32) -> &mut T {
+        self.entries
+            .get_mut(&(handle as u32))
+            .and_then(T::get)
+            .unwrap_or_else(|| panic!("invalid {} handle {}", T::NAME, handle))
+    }
+
+    fn delete<T: Entry>(&mut self, handle: i32) -> T {
+        let resource = self.entries.remove(&(handle as u32));
+        resource
+            .and_then(T::take)
+            .unwrap_or_else(|| panic!("invalid {} handle {}", T::NAME, handle))
+    }
+}
+
+/// A type of resource held in the [`Table`].
+trait Entry: Sized {
+    const NAME: &'static str;
+
+    fn get(resource: &mut Resource) -> Option<&mut Self>;
+
+    fn take(resource: Resource) -> Option<Self>;
+}
+
+macro_rules! resources {
+    ($($variant:ident($ty:ty) = $name:literal,)*) => {
+        enum Resource {
+            $($variant($ty),)*
+        }
+
+        $(
+            impl From<$ty> for Resource {
+                fn from(resource: $ty) -> Resource {
+                    Resource::$variant(resource)
+                }
+            }
+
+            impl Entry for $ty {
+                const NAME: &'static str = $name;
+
+                fn get(resource: &mut Resource) -> Option<&mut Self> {
+                    match resource {
+                        Resource::$variant(r) => Some(r),
+                        _ => None,
+                    }
+                }
+
+                fn take(resource: Resource) -> Option<Self> {
+                    match resource {
+                        Resource::$variant(r) => Some(r),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+resources! {
+    Readable(streams::Readable) = "readable end",
+    Writable(streams::Writable) = "writable end",
+    Descriptor(filesystem::Descriptor) = "descriptor",
+    TcpSocket(sockets::TcpSocket) = "tcp-socket",
+    UdpSocket(sockets::UdpSocket) = "udp-socket",
+    Fields(http::Fields) = "fields",
+    Request(http::Request) = "request",
+    RequestOptions(http::RequestOptions) = "request-options",
+    Response(http::Response) = "response",
+}
+
+/// What an `[async-lower]` import returns once it completed, which all of
+/// them do before returning.
+const RETURNED: i32 = 2;
+
+/// The size of pointers and list lengths in the canonical ABI of the
+/// bindings, which use native pointers outside of wasm.
+const PTR: usize = core::mem::size_of::<*const u8>();
+
+/// Writes `value` at `offset` of the return area `ret`.
+unsafe fn store<T>(ret: *mut u8, offset: usize, value: T) {
+    unsafe { ret.add(offset).cast::<T>().write(value) }
+}
+
+/// Reads a value at `offset` of memory passed by the guest.
+unsafe fn load<T: Copy>(ptr: *const u8, offset: usize) -> T {
+    unsafe { ptr.add(offset).cast::<T>().read() }
+}
+
+/// Returns a list of bytes passed by the guest.
+unsafe fn bytes<'a>(ptr: *const u8, len: usize) -> &'a [u8] {
+    if len == 0 {
+        return &[];
+    }
+    unsafe { core::slice::from_raw_parts(ptr, len) }
+}
+
+/// Returns a string passed by the guest.
+unsafe fn string<'a>(ptr: *const u8, len: usize) -> &'a str {
+    core::str::from_utf8(unsafe { bytes(ptr, len) }).expect("strings are UTF-8")
+}
+
+/// Returns a string stored at `offset` of memory passed by the guest.
+unsafe fn load_string<'a>(ptr: *const u8, offset: usize) -> &'a str {
+    unsafe { string(load(ptr, offset), load(ptr, offset + PTR)) }
+}
+
+/// Allocates a list of `len` elements returned to the guest, which frees it
+/// with the global allocator.
+fn alloc_list(len: usize, size: usize, align: usize) -> *mut u8 {
+    let layout = Layout::from_size_align(len * size, align).unwrap();
+    if layout.size() == 0 {
+        // Empty lists aren't freed, so any aligned pointer does.
+        return align as *mut u8;
+    }
+    let ptr = unsafe { alloc::alloc(layout) };
+    if ptr.is_null() {
+        alloc::handle_alloc_error(layout);
+    }
+    ptr
+}
+
+/// Stores a copy of `bytes` as a list at `offset` of `ret`.
+unsafe fn store_bytes(ret: *mut u8, offset: usize, bytes: &[u8]) {
+    let ptr = alloc_list(bytes.len(), 1, 1);
+    unsafe {
+        ptr.copy_from_nonoverlapping(bytes.as_ptr(), bytes.len());
+        store(ret, offset, ptr);
+        store(ret, offset + PTR, bytes.len());
+    }
+}
+
+/// A value stored in a return area, as the payload of a `result` or
+/// `option`.
+trait Lower {
+    unsafe fn lower(self, ret: *mut u8, offset: usize);
+}
+
+impl Lower for () {
+    unsafe fn lower(self, _ret: *mut u8, _offset: usize) {}
+}
+
+impl Lower for bool {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        unsafe { store(ret, offset, self as u8) }
+    }
+}
+
+impl Lower for u8 {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        unsafe { store(ret, offset, self) }
+    }
+}
+
+impl Lower for u32 {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        unsafe { store(ret, offset, self) }
+    }
+}
+
+impl Lower for i32 {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        unsafe { store(ret, offset, self) }
+    }
+}
+
+impl Lower for u64 {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        unsafe { store(ret, offset, self) }
+    }
+}
+
+impl Lower for Vec<u8> {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        unsafe { store_bytes(ret, offset, &self) }
+    }
+}
+
+impl Lower for String {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        unsafe { store_bytes(ret, offset, self.as_bytes()) }
+    }
+}
+
+/// Lowers an error code, which may carry an `other` message, at `offset` of
+/// `ret`, with the vtable of the `future<result<_, error-code>>` of the
+/// bindings.
+unsafe fn lower_error<E>(error: E, ret: *mut u8, offset: usize)
+where
+    Result<(), E>: crate::wit_future::FuturePayload,
+{
+    let vtable = <Result<(), E> as crate::wit_future::FuturePayload>::VTABLE;
+    // The error is the only payload of that result, so it follows the
+    // discriminant at the alignment of the result and fills the rest of it.
+    let layout = vtable.layout;
+    let payload = layout.align();
+    unsafe {
+        let tmp = alloc::alloc(layout);
+        if tmp.is_null() {
+            alloc::handle_alloc_error(layout);
+        }
+        (vtable.lower)(Err(error), tmp);
+        // The lists of the error now belong to `ret`.
+        ret.add(offset)
+            .copy_from_nonoverlapping(tmp.add(payload), layout.size() - payload);
+        alloc::dealloc(tmp, layout);
+    }
+}
+
+impl Lower for crate::filesystem::types::ErrorCode {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        unsafe { lower_error(self, ret, offset) }
+    }
+}
+
+impl Lower for crate::sockets::types::ErrorCode {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        unsafe { lower_error(self, ret, offset) }
+    }
+}
+
+impl Lower for crate::http::types::ErrorCode {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        unsafe { lower_error(self, ret, offset) }
+    }
+}
+
+/// Stores `result` in `ret`, with the payload at `offset`.
+unsafe fn store_result<T: Lower, E: Lower>(ret: *mut u8, offset: usize, result: Result<T, E>) {
+    unsafe {
+        match result {
+            Ok(value) => {
+                store(ret, 0, 0u8);
+                value.lower(ret, offset);
+            }
+            Err(err) => {
+                store(ret, 0, 1u8);
+                err.lower(ret, offset);
+            }
+        }
+    }
+}
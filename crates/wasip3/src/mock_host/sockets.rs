@@ -0,0 +1,958 @@
+//! `wasi:sockets`, on a loopback network only reaching the sockets of the
+//! same host.
+
+use super::streams::{self, Channel, ChannelRef, Pull};
+use super::{
+    alloc_list, bytes, drop_resource, load, store, store_bytes, store_result, string, with, Lower,
+    PTR, RETURNED,
+};
+use crate::sockets::types::{ErrorCode, IpAddressFamily};
+use crate::wit_future::FuturePayload;
+use crate::wit_stream::StreamPayload;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV6};
+use std::rc::{Rc, Weak};
+use std::vec::Vec;
+
+/// The first port handed out when binding to port 0.
+const EPHEMERAL_PORTS: u16 = 49152;
+
+/// The sockets bound to an address, which is how sockets find each other.
+#[derive(Default)]
+pub(super) struct Net {
+    tcp: HashMap<SocketAddr, Weak<RefCell<Tcp>>>,
+    udp: HashMap<SocketAddr, Weak<RefCell<Udp>>>,
+    /// The number of ephemeral ports handed out so far.
+    ephemeral: u16,
+}
+
+/// Binds `socket` to `addr` in `bound`, picking an ephemeral port if its port
+/// is 0, and returns the address it's bound to.
+fn bind<T>(
+    bound: &mut HashMap<SocketAddr, Weak<RefCell<T>>>,
+    ephemeral: &mut u16,
+    socket: &Rc<RefCell<T>>,
+    mut addr: SocketAddr,
+) -> Result<SocketAddr, ErrorCode> {
+    if !addr.ip().is_loopback() && !addr.ip().is_unspecified() {
+        return Err(ErrorCode::AddressNotBindable);
+    }
+    bound.retain(|_, socket| socket.strong_count() > 0);
+    if addr.port() == 0 {
+        let port = EPHEMERAL_PORTS
+            .checked_add(*ephemeral)
+            .ok_or(ErrorCode::AddressInUse)?;
+        *ephemeral += 1;
+        addr.set_port(port);
+    }
+    let in_use = bound.keys().any(|other| {
+        other.port() == addr.port()
+            && other.is_ipv4() == addr.is_ipv4()
+            && (other.ip() == addr.ip()
+                || other.ip().is_unspecified()
+                || addr.ip().is_unspecified())
+    });
+    if in_use {
+        return Err(ErrorCode::AddressInUse);
+    }
+    bound.insert(addr, Rc::downgrade(socket));
+    Ok(addr)
+}
+
+/// Returns the socket in `bound` which `addr` reaches, if any.
+fn find<T>(
+    bound: &HashMap<SocketAddr, Weak<RefCell<T>>>,
+    addr: SocketAddr,
+) -> Option<Rc<RefCell<T>>> {
+    if !addr.ip().is_loopback() {
+        return None;
+    }
+    bound.iter().find_map(|(other, socket)| {
+        let reaches = other.port() == addr.port()
+            && other.is_ipv4() == addr.is_ipv4()
+            && (other.ip() == addr.ip() || other.ip().is_unspecified());
+        if reaches {
+            socket.upgrade()
+        } else {
+            None
+        }
+    })
+}
+
+fn family(family: i32) -> IpAddressFamily {
+    match family {
+        0 => IpAddressFamily::Ipv4,
+        _ => IpAddressFamily::Ipv6,
+    }
+}
+
+/// Checks that `addr` belongs to the address family of a socket.
+fn check_family(family: IpAddressFamily, addr: SocketAddr) -> Result<(), ErrorCode> {
+    match (family, addr) {
+        (IpAddressFamily::Ipv4, SocketAddr::V4(_)) | (IpAddressFamily::Ipv6, SocketAddr::V6(_)) => {
+            Ok(())
+        }
+        _ => Err(ErrorCode::InvalidArgument),
+    }
+}
+
+/// Checks that `addr` can be connected or sent to.
+fn check_remote(family: IpAddressFamily, addr: SocketAddr) -> Result<(), ErrorCode> {
+    check_family(family, addr)?;
+    if addr.port() == 0 || addr.ip().is_unspecified() {
+        return Err(ErrorCode::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// Reads an `ip-socket-address` passed as flattened parameters.
+fn lift_address(p: [i32; 12]) -> SocketAddr {
+    match p[0] {
+        0 => {
+            let ip = Ipv4Addr::new(p[2] as u8, p[3] as u8, p[4] as u8, p[5] as u8);
+            SocketAddr::from((ip, p[1] as u16))
+        }
+        _ => {
+            let segments = [3, 4, 5, 6, 7, 8, 9, 10].map(|i| p[i] as u16);
+            let ip = Ipv6Addr::from(segments);
+            SocketAddr::V6(SocketAddrV6::new(
+                ip,
+                p[1] as u16,
+                p[2] as u32,
+                p[11] as u32,
+            ))
+        }
+    }
+}
+
+/// Reads an `ip-socket-address` at `offset` of memory passed by the guest.
+unsafe fn load_address(ptr: *const u8, offset: usize) -> SocketAddr {
+    unsafe {
+        match load::<u8>(ptr, offset) {
+            0 => {
+                let octets = [6, 7, 8, 9].map(|i| load::<u8>(ptr, offset + i));
+                SocketAddr::from((octets, load::<u16>(ptr, offset + 4)))
+            }
+            _ => {
+                let segments =
+                    [0, 1, 2, 3, 4, 5, 6, 7].map(|i| load::<u16>(ptr, offset + 12 + 2 * i));
+                SocketAddr::V6(SocketAddrV6::new(
+                    Ipv6Addr::from(segments),
+                    load(ptr, offset + 4),
+                    load(ptr, offset + 8),
+                    load(ptr, offset + 28),
+                ))
+            }
+        }
+    }
+}
+
+impl Lower for SocketAddr {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        unsafe {
+            match self {
+                SocketAddr::V4(addr) => {
+                    store(ret, offset, 0u8);
+                    store(ret, offset + 4, addr.port());
+                    for (i, octet) in addr.ip().octets().into_iter().enumerate() {
+                        store(ret, offset + 6 + i, octet);
+                    }
+                }
+                SocketAddr::V6(addr) => {
+                    store(ret, offset, 1u8);
+                    store(ret, offset + 4, addr.port());
+                    store(ret, offset + 8, addr.flowinfo());
+                    for (i, segment) in addr.ip().segments().into_iter().enumerate() {
+                        store(ret, offset + 12 + 2 * i, segment);
+                    }
+                    store(ret, offset + 28, addr.scope_id());
+                }
+            }
+        }
+    }
+}
+
+/// The loopback address of `family`.
+fn loopback(family: IpAddressFamily) -> IpAddr {
+    match family {
+        IpAddressFamily::Ipv4 => Ipv4Addr::LOCALHOST.into(),
+        IpAddressFamily::Ipv6 => Ipv6Addr::LOCALHOST.into(),
+    }
+}
+
+/// Checks the value passed to the setter of a socket option, which can't be
+/// zero.
+fn nonzero<T: Default + PartialEq>(value: T) -> Result<T, ErrorCode> {
+    if value == T::default() {
+        return Err(ErrorCode::InvalidArgument);
+    }
+    Ok(value)
+}
+
+fn result_future(result: Result<(), ErrorCode>) -> Channel {
+    Channel::ready(<Result<(), ErrorCode> as FuturePayload>::VTABLE, result)
+}
+
+/// One direction of a TCP connection.
+#[derive(Default)]
+struct Wire {
+    /// The stream the sending end passed to `send`.
+    data: Option<ChannelRef>,
+    /// Whether the sending end was dropped.
+    closed: bool,
+}
+
+/// One end of a TCP connection.
+struct Connection {
+    remote: SocketAddr,
+    /// What the other end sends.
+    input: Rc<RefCell<Wire>>,
+    /// What the other end receives.
+    output: Rc<RefCell<Wire>>,
+    receiving: bool,
+}
+
+impl Connection {
+    fn pair(a: SocketAddr, b: SocketAddr) -> (Connection, Connection) {
+        let input = Rc::<RefCell<Wire>>::default();
+        let output = Rc::<RefCell<Wire>>::default();
+        let a_end = Connection {
+            remote: b,
+            input: input.clone(),
+            output: output.clone(),
+            receiving: false,
+        };
+        let b_end = Connection {
+            remote: a,
+            input: output,
+            output: input,
+            receiving: false,
+        };
+        (a_end, b_end)
+    }
+}
+
+enum TcpState {
+    Unbound,
+    Bound,
+    /// Listening, with the connections yet to be accepted.
+    Listening(VecDeque<Connection>),
+    Connected(Connection),
+    Closed,
+}
+
+pub(super) struct Tcp {
+    family: IpAddressFamily,
+    state: TcpState,
+    local: Option<SocketAddr>,
+    backlog: u64,
+    keep_alive: bool,
+    keep_alive_idle_time: u64,
+    keep_alive_interval: u64,
+    keep_alive_count: u32,
+    hop_limit: u8,
+    receive_buffer_size: u64,
+    send_buffer_size: u64,
+}
+
+impl Tcp {
+    fn new(family: IpAddressFamily) -> Tcp {
+        Tcp {
+            family,
+            state: TcpState::Unbound,
+            local: None,
+            backlog: 128,
+            keep_alive: false,
+            keep_alive_idle_time: 7_200_000_000_000,
+            keep_alive_interval: 75_000_000_000,
+            keep_alive_count: 9,
+            hop_limit: 64,
+            receive_buffer_size: 64 * 1024,
+            send_buffer_size: 64 * 1024,
+        }
+    }
+
+    /// Creates the socket of a connection accepted by this one.
+    fn accepted(&self, connection: Connection) -> Tcp {
+        let mut tcp = Tcp::new(self.family);
+        tcp.state = TcpState::Connected(connection);
+        tcp.local = self.local;
+        tcp.keep_alive = self.keep_alive;
+        tcp.keep_alive_idle_time = self.keep_alive_idle_time;
+        tcp.keep_alive_interval = self.keep_alive_interval;
+        tcp.keep_alive_count = self.keep_alive_count;
+        tcp.hop_limit = self.hop_limit;
+        tcp.receive_buffer_size = self.receive_buffer_size;
+        tcp.send_buffer_size = self.send_buffer_size;
+        tcp
+    }
+}
+
+impl Drop for Tcp {
+    fn drop(&mut self) {
+        if let TcpState::Connected(connection) = &self.state {
+            connection.output.borrow_mut().closed = true;
+        }
+    }
+}
+
+pub(super) struct TcpSocket(Rc<RefCell<Tcp>>);
+
+impl TcpSocket {
+    /// Returns the local address, binding to an ephemeral port first if the
+    /// socket isn't bound.
+    fn bind_implicitly(&self, net: &mut Net) -> Result<SocketAddr, ErrorCode> {
+        let mut tcp = self.0.borrow_mut();
+        if let Some(local) = tcp.local {
+            return Ok(local);
+        }
+        let addr = SocketAddr::new(loopback(tcp.family), 0);
+        let local = bind(&mut net.tcp, &mut net.ephemeral, &self.0, addr)?;
+        tcp.local = Some(local);
+        tcp.state = TcpState::Bound;
+        Ok(local)
+    }
+
+    fn bind(&self, net: &mut Net, addr: SocketAddr) -> Result<(), ErrorCode> {
+        let mut tcp = self.0.borrow_mut();
+        if !matches!(tcp.state, TcpState::Unbound) {
+            return Err(ErrorCode::InvalidState);
+        }
+        check_family(tcp.family, addr)?;
+        tcp.local = Some(bind(&mut net.tcp, &mut net.ephemeral, &self.0, addr)?);
+        tcp.state = TcpState::Bound;
+        Ok(())
+    }
+
+    fn connect(&self, net: &mut Net, remote: SocketAddr) -> Result<(), ErrorCode> {
+        {
+            let tcp = self.0.borrow();
+            if !matches!(tcp.state, TcpState::Unbound | TcpState::Bound) {
+                return Err(ErrorCode::InvalidState);
+            }
+            check_remote(tcp.family, remote)?;
+        }
+        let local = self.bind_implicitly(net)?;
+        let listener = find(&net.tcp, remote).filter(|listener| !Rc::ptr_eq(listener, &self.0));
+        let result = match listener {
+            Some(listener) => {
+                let mut listener = listener.borrow_mut();
+                let backlog = listener.backlog;
+                match &mut listener.state {
+                    TcpState::Listening(pending) if (pending.len() as u64) < backlog => {
+                        let (client, server) = Connection::pair(local, remote);
+                        pending.push_back(server);
+                        Ok(client)
+                    }
+                    _ => Err(ErrorCode::ConnectionRefused),
+                }
+            }
+            None => Err(ErrorCode::ConnectionRefused),
+        };
+        let mut tcp = self.0.borrow_mut();
+        match result {
+            Ok(connection) => {
+                tcp.state = TcpState::Connected(connection);
+                Ok(())
+            }
+            Err(e) => {
+                tcp.state = TcpState::Closed;
+                Err(e)
+            }
+        }
+    }
+
+    /// Starts listening, returning the stream of accepted connections.
+    fn listen(&self, net: &mut Net) -> Result<Channel, ErrorCode> {
+        if !matches!(self.0.borrow().state, TcpState::Unbound | TcpState::Bound) {
+            return Err(ErrorCode::InvalidState);
+        }
+        self.bind_implicitly(net)?;
+        self.0.borrow_mut().state = TcpState::Listening(VecDeque::new());
+        let vtable = <crate::sockets::types::TcpSocket as StreamPayload>::VTABLE;
+        let mut accepted = Channel::stream(vtable);
+        let listener = self.0.clone();
+        accepted.set_source(move |_| {
+            let connection = match &mut listener.borrow_mut().state {
+                TcpState::Listening(pending) => pending.pop_front(),
+                _ => None,
+            };
+            let Some(connection) = connection else {
+                return Pull::Pending;
+            };
+            let tcp = listener.borrow().accepted(connection);
+            let socket = with(|s| s.table.push(TcpSocket(Rc::new(RefCell::new(tcp)))));
+            let socket = unsafe { crate::sockets::types::TcpSocket::from_handle(socket as u32) };
+            Pull::Data(streams::lower_value(vtable, socket))
+        });
+        Ok(accepted)
+    }
+}
+
+#[unsafe(export_name = symbol!("sockets/types", "[resource-drop]tcp-socket"))]
+unsafe extern "C" fn drop_tcp_socket(handle: i32) {
+    drop_resource::<TcpSocket>(handle);
+}
+
+#[unsafe(export_name = symbol!("sockets/types", "[static]tcp-socket.create"))]
+unsafe extern "C" fn tcp_socket_create(address_family: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let tcp = Tcp::new(family(address_family));
+        let socket = s.table.push(TcpSocket(Rc::new(RefCell::new(tcp))));
+        store_result(ret, PTR, Ok::<i32, ErrorCode>(socket));
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+#[unsafe(export_name = symbol!("sockets/types", "[method]tcp-socket.bind"))]
+unsafe extern "C" fn tcp_socket_bind(
+    handle: i32,
+    a0: i32,
+    a1: i32,
+    a2: i32,
+    a3: i32,
+    a4: i32,
+    a5: i32,
+    a6: i32,
+    a7: i32,
+    a8: i32,
+    a9: i32,
+    a10: i32,
+    a11: i32,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let addr = lift_address([a0, a1, a2, a3, a4, a5, a6, a7, a8, a9, a10, a11]);
+        let result = s.table.get::<TcpSocket>(handle).bind(&mut s.net, addr);
+        store_result(ret, PTR, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/types", "[async-lower][method]tcp-socket.connect"))]
+unsafe extern "C" fn tcp_socket_connect(params: *const u8, ret: *mut u8) -> i32 {
+    with(|s| unsafe {
+        let handle = load::<i32>(params, 0);
+        let addr = load_address(params, 4);
+        let result = s.table.get::<TcpSocket>(handle).connect(&mut s.net, addr);
+        store_result(ret, PTR, result);
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("sockets/types", "[method]tcp-socket.listen"))]
+unsafe extern "C" fn tcp_socket_listen(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let result = s.table.get::<TcpSocket>(handle).listen(&mut s.net);
+        let result = result.map(|accepted| streams::push_readable(s, accepted));
+        store_result(ret, PTR, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/types", "[method]tcp-socket.send"))]
+unsafe extern "C" fn tcp_socket_send(handle: i32, data: i32) -> i32 {
+    with(|s| {
+        let tcp = s.table.get::<TcpSocket>(handle).0.clone();
+        let data = streams::take_readable(s, data);
+        let tcp = tcp.borrow();
+        let output = match &tcp.state {
+            TcpState::Connected(connection) => Some(&connection.output),
+            _ => None,
+        };
+        let result = match output {
+            Some(output) if output.borrow().data.is_none() => {
+                output.borrow_mut().data = Some(data.clone());
+                streams::written(&data, Ok::<(), ErrorCode>(()))
+            }
+            _ => {
+                streams::drop_reader(&data);
+                result_future(Err(ErrorCode::InvalidState))
+            }
+        };
+        streams::push_readable(s, result)
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/types", "[method]tcp-socket.receive"))]
+unsafe extern "C" fn tcp_socket_receive(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let tcp = s.table.get::<TcpSocket>(handle).0.clone();
+        let mut tcp = tcp.borrow_mut();
+        let vtable = <u8 as StreamPayload>::VTABLE;
+        let (data, result) = match &mut tcp.state {
+            TcpState::Connected(connection) if !connection.receiving => {
+                connection.receiving = true;
+                let input = connection.input.clone();
+                let mut data = Channel::stream(vtable);
+                data.set_source(move |idle| {
+                    let (sent, closed) = {
+                        let input = input.borrow();
+                        (input.data.clone(), input.closed)
+                    };
+                    match sent {
+                        Some(sent) => Channel::pull(&sent, idle),
+                        None if closed => Pull::End,
+                        None => Pull::Pending,
+                    }
+                });
+                (data, result_future(Ok(())))
+            }
+            _ => (
+                Channel::bytes(vtable, &[]),
+                result_future(Err(ErrorCode::InvalidState)),
+            ),
+        };
+        store(ret, 0, streams::push_readable(s, data));
+        store(ret, 4, streams::push_readable(s, result));
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/types", "[method]tcp-socket.get-local-address"))]
+unsafe extern "C" fn tcp_socket_get_local_address(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let tcp = s.table.get::<TcpSocket>(handle).0.borrow();
+        store_result(ret, PTR, tcp.local.ok_or(ErrorCode::InvalidState));
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/types", "[method]tcp-socket.get-remote-address"))]
+unsafe extern "C" fn tcp_socket_get_remote_address(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let tcp = s.table.get::<TcpSocket>(handle).0.borrow();
+        let result = match &tcp.state {
+            TcpState::Connected(connection) => Ok(connection.remote),
+            _ => Err(ErrorCode::InvalidState),
+        };
+        store_result(ret, PTR, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/types", "[method]tcp-socket.get-is-listening"))]
+unsafe extern "C" fn tcp_socket_get_is_listening(handle: i32) -> i32 {
+    with(|s| {
+        let tcp = s.table.get::<TcpSocket>(handle).0.borrow();
+        matches!(tcp.state, TcpState::Listening(_)) as i32
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/types", "[method]tcp-socket.get-address-family"))]
+unsafe extern "C" fn tcp_socket_get_address_family(handle: i32) -> i32 {
+    with(|s| s.table.get::<TcpSocket>(handle).0.borrow().family as i32)
+}
+
+#[unsafe(export_name = symbol!("sockets/types", "[method]tcp-socket.set-listen-backlog-size"))]
+unsafe extern "C" fn tcp_socket_set_listen_backlog_size(handle: i32, value: i64, ret: *mut u8) {
+    with(|s| unsafe {
+        let mut tcp = s.table.get::<TcpSocket>(handle).0.borrow_mut();
+        let result = match tcp.state {
+            TcpState::Connected(_) | TcpState::Closed => Err(ErrorCode::InvalidState),
+            _ => nonzero(value as u64).map(|value| tcp.backlog = value),
+        };
+        store_result(ret, PTR, result);
+    })
+}
+
+/// Defines the getter and setter imports of a socket option.
+macro_rules! socket_option {
+    (
+        $socket:ident . $field:ident : $ty:ty as $abi:ty,
+        $get:literal => $get_fn:ident, $set:literal => $set_fn:ident,
+        $offset:expr, $check:expr $(,)?
+    ) => {
+        #[unsafe(export_name = symbol!("sockets/types", $get))]
+        unsafe extern "C" fn $get_fn(handle: i32, ret: *mut u8) {
+            with(|s| unsafe {
+                let value = s.table.get::<$socket>(handle).0.borrow().$field;
+                store_result(ret, $offset, Ok::<_, ErrorCode>(value));
+            })
+        }
+
+        #[unsafe(export_name = symbol!("sockets/types", $set))]
+        unsafe extern "C" fn $set_fn(handle: i32, value: $abi, ret: *mut u8) {
+            with(|s| unsafe {
+                let mut socket = s.table.get::<$socket>(handle).0.borrow_mut();
+                let check: fn($abi) -> Result<$ty, ErrorCode> = $check;
+                let result = check(value).map(|value| socket.$field = value);
+                store_result(ret, PTR, result);
+            })
+        }
+    };
+}
+
+socket_option!(
+    TcpSocket.keep_alive: bool as i32,
+    "[method]tcp-socket.get-keep-alive-enabled" => tcp_socket_get_keep_alive_enabled,
+    "[method]tcp-socket.set-keep-alive-enabled" => tcp_socket_set_keep_alive_enabled,
+    PTR, |value| Ok(value != 0),
+);
+
+socket_option!(
+    TcpSocket.keep_alive_idle_time: u64 as i64,
+    "[method]tcp-socket.get-keep-alive-idle-time" => tcp_socket_get_keep_alive_idle_time,
+    "[method]tcp-socket.set-keep-alive-idle-time" => tcp_socket_set_keep_alive_idle_time,
+    8, |value| nonzero(value as u64),
+);
+
+socket_option!(
+    TcpSocket.keep_alive_interval: u64 as i64,
+    "[method]tcp-socket.get-keep-alive-interval" => tcp_socket_get_keep_alive_interval,
+    "[method]tcp-socket.set-keep-alive-interval" => tcp_socket_set_keep_alive_interval,
+    8, |value| nonzero(value as u64),
+);
+
+socket_option!(
+    TcpSocket.keep_alive_count: u32 as i32,
+    "[method]tcp-socket.get-keep-alive-count" => tcp_socket_get_keep_alive_count,
+    "[method]tcp-socket.set-keep-alive-count" => tcp_socket_set_keep_alive_count,
+    PTR, |value| nonzero(value as u32),
+);
+
+socket_option!(
+    TcpSocket.hop_limit: u8 as i32,
+    "[method]tcp-socket.get-hop-limit" => tcp_socket_get_hop_limit,
+    "[method]tcp-socket.set-hop-limit" => tcp_socket_set_hop_limit,
+    PTR, |value| nonzero(value as u8),
+);
+
+socket_option!(
+    TcpSocket.receive_buffer_size: u64 as i64,
+    "[method]tcp-socket.get-receive-buffer-size" => tcp_socket_get_receive_buffer_size,
+    "[method]tcp-socket.set-receive-buffer-size" => tcp_socket_set_receive_buffer_size,
+    8, |value| nonzero(value as u64),
+);
+
+socket_option!(
+    TcpSocket.send_buffer_size: u64 as i64,
+    "[method]tcp-socket.get-send-buffer-size" => tcp_socket_get_send_buffer_size,
+    "[method]tcp-socket.set-send-buffer-size" => tcp_socket_set_send_buffer_size,
+    8, |value| nonzero(value as u64),
+);
+
+pub(super) struct Udp {
+    family: IpAddressFamily,
+    local: Option<SocketAddr>,
+    /// The address set by `connect`, the only one datagrams are sent to and
+    /// received from.
+    remote: Option<SocketAddr>,
+    incoming: VecDeque<(Vec<u8>, SocketAddr)>,
+    unicast_hop_limit: u8,
+    receive_buffer_size: u64,
+    send_buffer_size: u64,
+}
+
+impl Udp {
+    /// Takes the next datagram from the address passed to `connect`, if
+    /// any. Datagrams from other addresses are discarded.
+    fn next_datagram(&mut self) -> Option<(Vec<u8>, SocketAddr)> {
+        while let Some((data, from)) = self.incoming.pop_front() {
+            if self.remote.is_none_or(|remote| remote == from) {
+                return Some((data, from));
+            }
+        }
+        None
+    }
+}
+
+pub(super) struct UdpSocket(Rc<RefCell<Udp>>);
+
+impl UdpSocket {
+    /// Returns the local address, binding to an ephemeral port first if the
+    /// socket isn't bound.
+    fn bind_implicitly(&self, net: &mut Net) -> Result<SocketAddr, ErrorCode> {
+        let mut udp = self.0.borrow_mut();
+        if let Some(local) = udp.local {
+            return Ok(local);
+        }
+        let addr = SocketAddr::new(loopback(udp.family), 0);
+        let local = bind(&mut net.udp, &mut net.ephemeral, &self.0, addr)?;
+        udp.local = Some(local);
+        Ok(local)
+    }
+
+    fn send(&self, net: &mut Net, data: &[u8], to: Option<SocketAddr>) -> Result<(), ErrorCode> {
+        let (family, remote) = {
+            let udp = self.0.borrow();
+            (udp.family, udp.remote)
+        };
+        let to = match (to, remote) {
+            (Some(to), Some(remote)) if to != remote => return Err(ErrorCode::InvalidArgument),
+            (Some(to), _) | (None, Some(to)) => to,
+            (None, None) => return Err(ErrorCode::InvalidArgument),
+        };
+        check_remote(family, to)?;
+        let local = self.bind_implicitly(net)?;
+        // Datagrams which reach no socket are lost, as they would be on a
+        // real network.
+        if let Some(socket) = find(&net.udp, to) {
+            let from = match local.ip().is_unspecified() {
+                true => SocketAddr::new(loopback(family), local.port()),
+                false => local,
+            };
+            socket
+                .borrow_mut()
+                .incoming
+                .push_back((data.to_vec(), from));
+        }
+        Ok(())
+    }
+}
+
+/// The result of `receive`.
+struct Datagram(Vec<u8>, SocketAddr);
+
+impl Lower for Datagram {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        unsafe {
+            store_bytes(ret, offset, &self.0);
+            self.1.lower(ret, offset + 2 * PTR);
+        }
+    }
+}
+
+#[unsafe(export_name = symbol!("sockets/types", "[resource-drop]udp-socket"))]
+unsafe extern "C" fn drop_udp_socket(handle: i32) {
+    drop_resource::<UdpSocket>(handle);
+}
+
+#[unsafe(export_name = symbol!("sockets/types", "[static]udp-socket.create"))]
+unsafe extern "C" fn udp_socket_create(address_family: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let udp = Udp {
+            family: family(address_family),
+            local: None,
+            remote: None,
+            incoming: VecDeque::new(),
+            unicast_hop_limit: 64,
+            receive_buffer_size: 64 * 1024,
+            send_buffer_size: 64 * 1024,
+        };
+        let socket = s.table.push(UdpSocket(Rc::new(RefCell::new(udp))));
+        store_result(ret, PTR, Ok::<i32, ErrorCode>(socket));
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+#[unsafe(export_name = symbol!("sockets/types", "[method]udp-socket.bind"))]
+unsafe extern "C" fn udp_socket_bind(
+    handle: i32,
+    a0: i32,
+    a1: i32,
+    a2: i32,
+    a3: i32,
+    a4: i32,
+    a5: i32,
+    a6: i32,
+    a7: i32,
+    a8: i32,
+    a9: i32,
+    a10: i32,
+    a11: i32,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let addr = lift_address([a0, a1, a2, a3, a4, a5, a6, a7, a8, a9, a10, a11]);
+        let socket = s.table.get::<UdpSocket>(handle).0.clone();
+        let mut udp = socket.borrow_mut();
+        let result = match udp.local {
+            Some(_) => Err(ErrorCode::InvalidState),
+            None => check_family(udp.family, addr)
+                .and_then(|()| bind(&mut s.net.udp, &mut s.net.ephemeral, &socket, addr))
+                .map(|local| udp.local = Some(local)),
+        };
+        store_result(ret, PTR, result);
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+#[unsafe(export_name = symbol!("sockets/types", "[method]udp-socket.connect"))]
+unsafe extern "C" fn udp_socket_connect(
+    handle: i32,
+    a0: i32,
+    a1: i32,
+    a2: i32,
+    a3: i32,
+    a4: i32,
+    a5: i32,
+    a6: i32,
+    a7: i32,
+    a8: i32,
+    a9: i32,
+    a10: i32,
+    a11: i32,
+    ret: *mut u8,
+) {
+    with(|s| unsafe {
+        let remote = lift_address([a0, a1, a2, a3, a4, a5, a6, a7, a8, a9, a10, a11]);
+        let socket = s.table.get::<UdpSocket>(handle);
+        let family = socket.0.borrow().family;
+        let result = check_remote(family, remote)
+            .and_then(|()| socket.bind_implicitly(&mut s.net))
+            .map(|_| socket.0.borrow_mut().remote = Some(remote));
+        store_result(ret, PTR, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/types", "[method]udp-socket.disconnect"))]
+unsafe extern "C" fn udp_socket_disconnect(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let mut udp = s.table.get::<UdpSocket>(handle).0.borrow_mut();
+        let result = match udp.remote.take() {
+            Some(_) => Ok(()),
+            None => Err(ErrorCode::InvalidState),
+        };
+        store_result(ret, PTR, result);
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/types", "[async-lower][method]udp-socket.send"))]
+unsafe extern "C" fn udp_socket_send(params: *const u8, ret: *mut u8) -> i32 {
+    with(|s| unsafe {
+        let handle = load::<i32>(params, 0);
+        let data = bytes(load(params, PTR), load(params, 2 * PTR));
+        let to = match load::<u8>(params, 3 * PTR) {
+            0 => None,
+            _ => Some(load_address(params, 3 * PTR + 4)),
+        };
+        let result = s.table.get::<UdpSocket>(handle).send(&mut s.net, data, to);
+        store_result(ret, PTR, result);
+    });
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("sockets/types", "[async-lower][method]udp-socket.receive"))]
+unsafe extern "C" fn udp_socket_receive(handle: i32, ret: *mut u8) -> i32 {
+    let udp = with(|s| s.table.get::<UdpSocket>(handle).0.clone());
+    let result = match udp.borrow().local {
+        Some(_) => Ok(()),
+        None => Err(ErrorCode::InvalidState),
+    };
+    // Wait for a datagram by running the other tasks, one of which may send
+    // one.
+    let result = result.map(|()| loop {
+        if let Some((data, from)) = udp.borrow_mut().next_datagram() {
+            break Datagram(data, from);
+        }
+        let executor = with(|s| s.executor.clone());
+        if !executor.run_one() {
+            panic!("deadlock: receiving on a UDP socket no task of the mock host sends to");
+        }
+    });
+    unsafe { store_result(ret, PTR, result) };
+    RETURNED
+}
+
+#[unsafe(export_name = symbol!("sockets/types", "[method]udp-socket.get-local-address"))]
+unsafe extern "C" fn udp_socket_get_local_address(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let udp = s.table.get::<UdpSocket>(handle).0.borrow();
+        store_result(ret, PTR, udp.local.ok_or(ErrorCode::InvalidState));
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/types", "[method]udp-socket.get-remote-address"))]
+unsafe extern "C" fn udp_socket_get_remote_address(handle: i32, ret: *mut u8) {
+    with(|s| unsafe {
+        let udp = s.table.get::<UdpSocket>(handle).0.borrow();
+        store_result(ret, PTR, udp.remote.ok_or(ErrorCode::InvalidState));
+    })
+}
+
+#[unsafe(export_name = symbol!("sockets/types", "[method]udp-socket.get-address-family"))]
+unsafe extern "C" fn udp_socket_get_address_family(handle: i32) -> i32 {
+    with(|s| s.table.get::<UdpSocket>(handle).0.borrow().family as i32)
+}
+
+socket_option!(
+    UdpSocket.unicast_hop_limit: u8 as i32,
+    "[method]udp-socket.get-unicast-hop-limit" => udp_socket_get_unicast_hop_limit,
+    "[method]udp-socket.set-unicast-hop-limit" => udp_socket_set_unicast_hop_limit,
+    PTR, |value| nonzero(value as u8),
+);
+
+socket_option!(
+    UdpSocket.receive_buffer_size: u64 as i64,
+    "[method]udp-socket.get-receive-buffer-size" => udp_socket_get_receive_buffer_size,
+    "[method]udp-socket.set-receive-buffer-size" => udp_socket_set_receive_buffer_size,
+    8, |value| nonzero(value as u64),
+);
+
+socket_option!(
+    UdpSocket.send_buffer_size: u64 as i64,
+    "[method]udp-socket.get-send-buffer-size" => udp_socket_get_send_buffer_size,
+    "[method]udp-socket.set-send-buffer-size" => udp_socket_set_send_buffer_size,
+    8, |value| nonzero(value as u64),
+);
+
+/// The addresses a name resolved to.
+struct Addresses(Vec<IpAddr>);
+
+impl Lower for Addresses {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        let list = alloc_list(self.0.len(), 18, 2);
+        unsafe {
+            for (i, ip) in self.0.iter().enumerate() {
+                let elem = list.add(i * 18);
+                match ip {
+                    IpAddr::V4(ip) => {
+                        store(elem, 0, 0u8);
+                        for (i, octet) in ip.octets().into_iter().enumerate() {
+                            store(elem, 2 + i, octet);
+                        }
+                    }
+                    IpAddr::V6(ip) => {
+                        store(elem, 0, 1u8);
+                        for (i, segment) in ip.segments().into_iter().enumerate() {
+                            store(elem, 2 + 2 * i, segment);
+                        }
+                    }
+                }
+            }
+            store(ret, offset, list);
+            store(ret, offset + PTR, self.0.len());
+        }
+    }
+}
+
+impl Lower for crate::sockets::ip_name_lookup::ErrorCode {
+    unsafe fn lower(self, ret: *mut u8, offset: usize) {
+        use crate::sockets::ip_name_lookup::ErrorCode::*;
+        let (code, message) = match self {
+            AccessDenied => (0u8, None),
+            InvalidArgument => (1, None),
+            NameUnresolvable => (2, None),
+            TemporaryResolverFailure => (3, None),
+            PermanentResolverFailure => (4, None),
+            Other(message) => (5, Some(message)),
+        };
+        unsafe {
+            store(ret, offset, code);
+            match message {
+                Some(Some(message)) => {
+                    store(ret, offset + PTR, 1u8);
+                    store_bytes(ret, offset + 2 * PTR, message.as_bytes());
+                }
+                Some(None) => store(ret, offset + PTR, 0u8),
+                None => {}
+            }
+        }
+    }
+}
+
+#[unsafe(export_name = symbol!("sockets/ip-name-lookup", "[async-lower]resolve-addresses"))]
+unsafe extern "C" fn resolve_addresses(ptr: *const u8, len: usize, ret: *mut u8) -> i32 {
+    use crate::sockets::ip_name_lookup::ErrorCode;
+
+    let name = unsafe { string(ptr, len) };
+    let valid = |c: char| c.is_ascii_alphanumeric() || "-.:_".contains(c);
+    let result = if name.is_empty() || !name.chars().all(valid) {
+        Err(ErrorCode::InvalidArgument)
+    } else if name.eq_ignore_ascii_case("localhost") {
+        let addresses = [Ipv4Addr::LOCALHOST.into(), Ipv6Addr::LOCALHOST.into()];
+        Ok(Addresses(addresses.into()))
+    } else {
+        let addresses = name.parse::<IpAddr>().map(|ip| Addresses(std::vec![ip]));
+        addresses.map_err(|_| ErrorCode::NameUnresolvable)
+    };
+    unsafe { store_result(ret, PTR, result) };
+    RETURNED
+}
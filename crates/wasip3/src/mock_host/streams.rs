@@ -0,0 +1,472 @@
+//! The `stream` and `future` intrinsics of the bindings, backed by buffers
+//! in memory.
+//!
+//! Writes always complete at once, copying the values into the buffer of the
+//! channel, or handing them to the host if it attached a sink. Reads take
+//! values from that buffer, or from a source attached by the host, and run
+//! the other tasks until some are available, since the guest can't be
+//! suspended.
+
+use super::{drop_resource, with};
+use crate::wit_future::FuturePayload;
+use std::boxed::Box;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::mem::MaybeUninit;
+use std::rc::Rc;
+use std::vec::Vec;
+use wit_bindgen::rt::async_support::{FutureVtable, StreamVtable};
+
+const COMPLETED: u32 = 0;
+const DROPPED: u32 = 1;
+
+/// What a source attached to a channel produced when asked for values.
+pub(super) enum Pull {
+    /// Some values, in their lowered form.
+    Data(Vec<MaybeUninit<u8>>),
+    /// No values yet.
+    Pending,
+    /// No values, and there won't be any more.
+    End,
+}
+
+/// A source of values for the guest to read. It's passed whether no task
+/// can run anymore, in which case waiting for values would deadlock.
+type Source = Box<dyn FnMut(bool) -> Pull>;
+
+/// Where the bytes the guest writes to a channel go instead of its buffer.
+type Sink = Box<dyn FnMut(&[u8])>;
+
+/// A `stream` or `future`, which is one value long.
+pub(super) struct Channel {
+    /// The size of a value in its lowered form.
+    size: usize,
+    future: bool,
+    /// Whether lowered values may own lists, which are moved out of the
+    /// buffer of the writer when its values are copied.
+    owns_lists: bool,
+    buf: VecDeque<MaybeUninit<u8>>,
+    writer_dropped: bool,
+    reader_dropped: bool,
+    sink: Option<Sink>,
+    source: Option<Source>,
+}
+
+pub(super) type ChannelRef = Rc<RefCell<Channel>>;
+
+impl Channel {
+    pub(super) fn stream<T>(vtable: &StreamVtable<T>) -> Channel {
+        Channel::new(vtable.layout.size(), false, vtable.dealloc_lists.is_some())
+    }
+
+    pub(super) fn future<T>(vtable: &FutureVtable<T>) -> Channel {
+        Channel::new(vtable.layout.size(), true, true)
+    }
+
+    fn new(size: usize, future: bool, owns_lists: bool) -> Channel {
+        Channel {
+            size,
+            future,
+            owns_lists,
+            buf: VecDeque::new(),
+            writer_dropped: false,
+            reader_dropped: false,
+            sink: None,
+            source: None,
+        }
+    }
+
+    /// Creates a channel of bytes whose writer was dropped after writing
+    /// `bytes`.
+    pub(super) fn bytes<T>(vtable: &StreamVtable<T>, bytes: &[u8]) -> Channel {
+        let mut channel = Channel::stream(vtable);
+        channel.push_bytes(bytes);
+        channel.writer_dropped = true;
+        channel
+    }
+
+    /// Creates a future resolving to `value`.
+    pub(super) fn ready<T>(vtable: &FutureVtable<T>, value: T) -> Channel {
+        let mut channel = Channel::future(vtable);
+        channel
+            .buf
+            .extend(lower(vtable.layout.size(), |ptr| unsafe {
+                (vtable.lower)(value, ptr)
+            }));
+        channel.writer_dropped = true;
+        channel
+    }
+
+    /// Creates a future resolving to the value `source` produces.
+    pub(super) fn lazy<T: 'static>(
+        vtable: &'static FutureVtable<T>,
+        mut source: impl FnMut(bool) -> Option<T> + 'static,
+    ) -> Channel {
+        let mut channel = Channel::future(vtable);
+        channel.source = Some(Box::new(move |idle| match source(idle) {
+            Some(value) => Pull::Data(lower(vtable.layout.size(), |ptr| unsafe {
+                (vtable.lower)(value, ptr)
+            })),
+            None => Pull::Pending,
+        }));
+        channel
+    }
+
+    pub(super) fn push_bytes(&mut self, bytes: &[u8]) {
+        debug_assert_eq!(self.size, 1);
+        self.buf.extend(bytes.iter().map(|b| MaybeUninit::new(*b)));
+    }
+
+    pub(super) fn push<T>(&mut self, vtable: &StreamVtable<T>, value: T) {
+        self.buf.extend(lower_value(vtable, value));
+    }
+
+    pub(super) fn set_source(&mut self, source: impl FnMut(bool) -> Pull + 'static) {
+        self.source = Some(Box::new(source));
+    }
+
+    /// Hands what the guest writes, and what it wrote so far, to `sink`.
+    pub(super) fn set_sink(&mut self, mut sink: impl FnMut(&[u8]) + 'static) {
+        debug_assert_eq!(self.size, 1);
+        let buf = self.buf.drain(..).map(|b| unsafe { b.assume_init() });
+        sink(&buf.collect::<Vec<_>>());
+        self.sink = Some(Box::new(sink));
+    }
+
+    pub(super) fn close(&mut self) {
+        self.writer_dropped = true;
+    }
+
+    pub(super) fn writer_dropped(&self) -> bool {
+        self.writer_dropped
+    }
+
+    /// Takes the values which were written, or which an attached source
+    /// produces, without waiting for any.
+    pub(super) fn pull(channel: &ChannelRef, idle: bool) -> Pull {
+        let mut c = channel.borrow_mut();
+        if !c.buf.is_empty() {
+            return Pull::Data(c.buf.drain(..).collect());
+        }
+        if let Some(mut source) = c.source.take() {
+            // The source may pull from other channels, and run tasks using
+            // this one.
+            drop(c);
+            let pull = source(idle);
+            c = channel.borrow_mut();
+            match pull {
+                Pull::End => c.writer_dropped = true,
+                pull => {
+                    c.source = Some(source);
+                    return pull;
+                }
+            }
+        }
+        if c.writer_dropped {
+            Pull::End
+        } else {
+            Pull::Pending
+        }
+    }
+}
+
+/// Returns the future of a `write-via-stream`-like import, resolving to `ok`
+/// once the guest dropped the writer of `data`, or once nothing else can run,
+/// as everything it wrote was already taken.
+pub(super) fn written<T: FuturePayload>(data: &ChannelRef, ok: T) -> Channel {
+    let data = data.clone();
+    let mut ok = Some(ok);
+    Channel::lazy(T::VTABLE, move |idle| {
+        if idle || data.borrow().writer_dropped {
+            ok.take()
+        } else {
+            None
+        }
+    })
+}
+
+/// Returns `value` lowered as a value of a stream of `vtable`.
+pub(super) fn lower_value<T>(vtable: &StreamVtable<T>, value: T) -> Vec<MaybeUninit<u8>> {
+    let lower_value = vtable.lower.expect("values with a lift are lowered");
+    lower(vtable.layout.size(), |ptr| unsafe {
+        lower_value(value, ptr)
+    })
+}
+
+/// Returns a value lowered by `lower` into `size` bytes.
+pub(super) fn lower(size: usize, lower: impl FnOnce(*mut u8)) -> Vec<MaybeUninit<u8>> {
+    // Lowered values are aligned to at most 8 bytes.
+    let mut buf = std::vec![0u64; size.div_ceil(8)];
+    lower(buf.as_mut_ptr().cast());
+    let bytes = unsafe { core::slice::from_raw_parts(buf.as_ptr().cast::<u8>(), size) };
+    bytes.iter().map(|b| MaybeUninit::new(*b)).collect()
+}
+
+/// The reading end of a channel in the table.
+pub(super) struct Readable(pub(super) ChannelRef);
+
+/// The writing end of a channel in the table.
+pub(super) struct Writable(ChannelRef);
+
+/// Hands `channel` to the guest, returning the handle of its reading end.
+pub(super) fn push_readable(s: &mut super::State, channel: Channel) -> i32 {
+    s.table.push(Readable(Rc::new(RefCell::new(channel))))
+}
+
+/// Takes the reading end `handle` passed by the guest.
+pub(super) fn take_readable(s: &mut super::State, handle: i32) -> ChannelRef {
+    s.table.delete::<Readable>(handle).0
+}
+
+pub(super) fn new(channel: Channel) -> u64 {
+    with(|s| {
+        let channel = Rc::new(RefCell::new(channel));
+        let reader = s.table.push(Readable(channel.clone())) as u32;
+        let writer = s.table.push(Writable(channel)) as u32;
+        (u64::from(writer) << 32) | u64::from(reader)
+    })
+}
+
+pub(super) unsafe fn start_write(handle: u32, ptr: *const u8, amt: usize) -> u32 {
+    let channel = with(|s| s.table.get::<Writable>(handle as i32).0.clone());
+    let mut c = channel.borrow_mut();
+    if c.reader_dropped {
+        return DROPPED;
+    }
+    let len = amt * c.size;
+    let values = unsafe { core::slice::from_raw_parts(ptr.cast::<MaybeUninit<u8>>(), len) };
+    match &mut c.sink {
+        Some(sink) => sink(unsafe { &*(values as *const [MaybeUninit<u8>] as *const [u8]) }),
+        None => c.buf.extend(values),
+    }
+    if c.owns_lists {
+        // The lists now belong to the buffer, so there are none left for the
+        // writer to free.
+        unsafe { ptr.cast_mut().write_bytes(0, len) };
+    }
+    if c.future {
+        COMPLETED
+    } else {
+        COMPLETED | ((amt as u32) << 4)
+    }
+}
+
+pub(super) unsafe fn start_read(handle: u32, ptr: *mut u8, amt: usize) -> u32 {
+    let channel = with(|s| s.table.get::<Readable>(handle as i32).0.clone());
+    let mut idle = false;
+    loop {
+        {
+            let mut c = channel.borrow_mut();
+            if amt == 0 {
+                return COMPLETED;
+            }
+            if !c.buf.is_empty() {
+                let count = amt.min(c.buf.len() / c.size);
+                let len = count * c.size;
+                for (i, b) in c.buf.drain(..len).enumerate() {
+                    unsafe { ptr.cast::<MaybeUninit<u8>>().add(i).write(b) };
+                }
+                return if c.future {
+                    COMPLETED
+                } else {
+                    COMPLETED | ((count as u32) << 4)
+                };
+            }
+        }
+        match Channel::pull(&channel, idle) {
+            Pull::Data(data) => {
+                channel.borrow_mut().buf.extend(data);
+                continue;
+            }
+            Pull::End if channel.borrow().future => {
+                panic!("a future was dropped without a value being written to it")
+            }
+            Pull::End => return DROPPED,
+            Pull::Pending => {}
+        }
+        let executor = with(|s| s.executor.clone());
+        if executor.run_one() {
+            idle = false;
+        } else if !idle {
+            idle = true;
+        } else {
+            panic!("deadlock: reading from a stream or future no task of the mock host writes to");
+        }
+    }
+}
+
+/// Drops the reading end of `channel`, after which writes to it report
+/// that it was dropped.
+pub(super) fn drop_reader(channel: &ChannelRef) {
+    let mut c = channel.borrow_mut();
+    c.reader_dropped = true;
+    c.buf.clear();
+    c.source = None;
+}
+
+pub(super) fn drop_readable(handle: u32) {
+    if let Some(Readable(channel)) = drop_resource::<Readable>(handle as i32) {
+        drop_reader(&channel);
+    }
+}
+
+pub(super) fn drop_writable(handle: u32) {
+    if let Some(Writable(channel)) = drop_resource::<Writable>(handle as i32) {
+        channel.borrow_mut().writer_dropped = true;
+    }
+}
+
+/// Defines the intrinsics of a `stream` type of the bindings, given its
+/// vtable.
+macro_rules! stream_intrinsics {
+    ($module:ident, $interface:literal, $index:literal, $name:literal, $vtable:expr) => {
+        mod $module {
+            use super::*;
+
+            #[unsafe(export_name = symbol!($interface, concat!("[stream-new-", $index, "]", $name)))]
+            unsafe extern "C" fn stream_new() -> u64 {
+                new(Channel::stream($vtable))
+            }
+
+            #[unsafe(export_name = symbol!($interface, concat!("[stream-cancel-write-", $index, "]", $name)))]
+            unsafe extern "C" fn stream_cancel_write(_handle: u32) -> u32 {
+                unreachable!("writes of the mock host never block")
+            }
+
+            #[unsafe(export_name = symbol!($interface, concat!("[stream-cancel-read-", $index, "]", $name)))]
+            unsafe extern "C" fn stream_cancel_read(_handle: u32) -> u32 {
+                unreachable!("reads of the mock host never block")
+            }
+
+            #[unsafe(export_name = symbol!($interface, concat!("[stream-drop-writable-", $index, "]", $name)))]
+            unsafe extern "C" fn stream_drop_writable(handle: u32) {
+                drop_writable(handle)
+            }
+
+            #[unsafe(export_name = symbol!($interface, concat!("[stream-drop-readable-", $index, "]", $name)))]
+            unsafe extern "C" fn stream_drop_readable(handle: u32) {
+                drop_readable(handle)
+            }
+
+            #[unsafe(export_name = symbol!($interface, concat!("[async-lower][stream-read-", $index, "]", $name)))]
+            unsafe extern "C" fn stream_read(handle: u32, ptr: *mut u8, amt: usize) -> u32 {
+                unsafe { start_read(handle, ptr, amt) }
+            }
+
+            #[unsafe(export_name = symbol!($interface, concat!("[async-lower][stream-write-", $index, "]", $name)))]
+            unsafe extern "C" fn stream_write(handle: u32, ptr: *const u8, amt: usize) -> u32 {
+                unsafe { start_write(handle, ptr, amt) }
+            }
+        }
+    };
+}
+
+/// Defines the intrinsics of a `future` type of the bindings, given its
+/// vtable.
+macro_rules! future_intrinsics {
+    ($module:ident, $interface:literal, $index:literal, $name:literal, $vtable:expr) => {
+        mod $module {
+            use super::*;
+
+            #[unsafe(export_name = symbol!($interface, concat!("[future-new-", $index, "]", $name)))]
+            unsafe extern "C" fn future_new() -> u64 {
+                new(Channel::future($vtable))
+            }
+
+            #[unsafe(export_name = symbol!($interface, concat!("[future-cancel-write-", $index, "]", $name)))]
+            unsafe extern "C" fn future_cancel_write(_handle: u32) -> u32 {
+                unreachable!("writes of the mock host never block")
+            }
+
+            #[unsafe(export_name = symbol!($interface, concat!("[future-cancel-read-", $index, "]", $name)))]
+            unsafe extern "C" fn future_cancel_read(_handle: u32) -> u32 {
+                unreachable!("reads of the mock host never block")
+            }
+
+            #[unsafe(export_name = symbol!($interface, concat!("[future-drop-writable-", $index, "]", $name)))]
+            unsafe extern "C" fn future_drop_writable(handle: u32) {
+                drop_writable(handle)
+            }
+
+            #[unsafe(export_name = symbol!($interface, concat!("[future-drop-readable-", $index, "]", $name)))]
+            unsafe extern "C" fn future_drop_readable(handle: u32) {
+                drop_readable(handle)
+            }
+
+            #[unsafe(export_name = symbol!($interface, concat!("[async-lower][future-read-", $index, "]", $name)))]
+            unsafe extern "C" fn future_read(handle: u32, ptr: *mut u8) -> u32 {
+                unsafe { start_read(handle, ptr, 1) }
+            }
+
+            #[unsafe(export_name = symbol!($interface, concat!("[async-lower][future-write-", $index, "]", $name)))]
+            unsafe extern "C" fn future_write(handle: u32, ptr: *const u8) -> u32 {
+                unsafe { start_write(handle, ptr, 1) }
+            }
+        }
+    };
+}
+
+stream_intrinsics!(
+    stdin_stream,
+    "cli/stdin",
+    "0",
+    "read-via-stream",
+    <u8 as crate::wit_stream::StreamPayload>::VTABLE
+);
+stream_intrinsics!(
+    directory_entry_stream,
+    "filesystem/types",
+    "0",
+    "[method]descriptor.read-directory",
+    <crate::filesystem::types::DirectoryEntry as crate::wit_stream::StreamPayload>::VTABLE
+);
+stream_intrinsics!(
+    tcp_socket_stream,
+    "sockets/types",
+    "0",
+    "[method]tcp-socket.listen",
+    <crate::sockets::types::TcpSocket as crate::wit_stream::StreamPayload>::VTABLE
+);
+// `wasi:http` has a `stream<u8>` of its own.
+stream_intrinsics!(
+    http_body_stream,
+    "http/types",
+    "0",
+    "[static]request.new",
+    <u8 as crate::service::wit_stream::StreamPayload>::VTABLE
+);
+future_intrinsics!(
+    cli_result_future,
+    "cli/stdin",
+    "1",
+    "read-via-stream",
+    <Result<(), crate::cli::types::ErrorCode> as crate::wit_future::FuturePayload>::VTABLE
+);
+future_intrinsics!(
+    filesystem_result_future,
+    "filesystem/types",
+    "1",
+    "[method]descriptor.read-via-stream",
+    <Result<(), crate::filesystem::types::ErrorCode> as crate::wit_future::FuturePayload>::VTABLE
+);
+future_intrinsics!(
+    sockets_result_future,
+    "sockets/types",
+    "1",
+    "[method]tcp-socket.send",
+    <Result<(), crate::sockets::types::ErrorCode> as crate::wit_future::FuturePayload>::VTABLE
+);
+future_intrinsics!(
+    trailers_future,
+    "http/types",
+    "1",
+    "[static]request.new",
+    <Result<Option<crate::http::types::Trailers>, crate::http::types::ErrorCode> as crate::wit_future::FuturePayload>::VTABLE
+);
+future_intrinsics!(
+    http_result_future,
+    "http/types",
+    "2",
+    "[static]request.new",
+    <Result<(), crate::http::types::ErrorCode> as crate::wit_future::FuturePayload>::VTABLE
+);
@@ -16,25 +16,25 @@
 //   * export-macro-name: _export_service
 //   * pub-export-macro
 #[allow(unfulfilled_lint_expectations, unused_imports)]
-use crate::cli::types as __with_name0;
-#[allow(unfulfilled_lint_expectations, unused_imports)]
-use crate::cli::stdout as __with_name1;
-#[allow(unfulfilled_lint_expectations, unused_imports)]
 use crate::cli::stderr as __with_name2;
 #[allow(unfulfilled_lint_expectations, unused_imports)]
 use crate::cli::stdin as __with_name3;
 #[allow(unfulfilled_lint_expectations, unused_imports)]
-use crate::clocks::types as __with_name4;
+use crate::cli::stdout as __with_name1;
+#[allow(unfulfilled_lint_expectations, unused_imports)]
+use crate::cli::types as __with_name0;
 #[allow(unfulfilled_lint_expectations, unused_imports)]
 use crate::clocks::monotonic_clock as __with_name5;
 #[allow(unfulfilled_lint_expectations, unused_imports)]
 use crate::clocks::system_clock as __with_name6;
 #[allow(unfulfilled_lint_expectations, unused_imports)]
-use crate::random::random as __with_name7;
+use crate::clocks::types as __with_name4;
 #[allow(unfulfilled_lint_expectations, unused_imports)]
 use crate::random::insecure as __with_name8;
 #[allow(unfulfilled_lint_expectations, unused_imports)]
 use crate::random::insecure_seed as __with_name9;
+#[allow(unfulfilled_lint_expectations, unused_imports)]
+use crate::random::random as __with_name7;
 #[rustfmt::skip]
 #[allow(dead_code, clippy::all)]
 pub mod wasi {
@@ -525,13 +525,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for Fields {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]fields"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]fields")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[resource-drop]fields")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -569,13 +570,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for Request {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]request"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]request")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[resource-drop]request")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -614,13 +616,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for RequestOptions {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]request-options"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]request-options")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[resource-drop]request-options")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -656,13 +659,14 @@ pub mod wasi {
             unsafe impl _rt::WasmResource for Response {
                 #[inline]
                 unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
+                    #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                     #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                     unsafe extern "C" {
-                        #[link_name = "[resource-drop]response"]
+                        #[cfg_attr(target_arch = "wasm32", link_name = "[resource-drop]response")]
+                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[resource-drop]response")]
                         fn drop(_: i32);
                     }
-                    #[cfg(not(target_arch = "wasm32"))]
+                    #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                     unsafe extern "C" fn drop(_: i32) {
                         unreachable!()
                     }
@@ -679,13 +683,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn new() -> Self {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[constructor]fields"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[constructor]fields")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[constructor]fields")]
                             fn wit_import0() -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0() -> i32 {
                             unreachable!()
                         }
@@ -762,13 +767,14 @@ pub mod wasi {
                             }
                         }
                         let ptr4 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[static]fields.from-list"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[static]fields.from-list")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[static]fields.from-list")]
                             fn wit_import5(_: *mut u8, _: usize, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import5(
                             _: *mut u8,
                             _: usize,
@@ -866,13 +872,14 @@ pub mod wasi {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]fields.get"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]fields.get")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]fields.get")]
                             fn wit_import2(_: i32, _: *mut u8, _: usize, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import2(
                             _: i32,
                             _: *mut u8,
@@ -924,13 +931,14 @@ pub mod wasi {
                         let vec0 = name;
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]fields.has"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]fields.has")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]fields.has")]
                             fn wit_import1(_: i32, _: *mut u8, _: usize) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(
                             _: i32,
                             _: *mut u8,
@@ -1001,10 +1009,11 @@ pub mod wasi {
                             }
                         }
                         let ptr3 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]fields.set"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]fields.set")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]fields.set")]
                             fn wit_import4(
                                 _: i32,
                                 _: *mut u8,
@@ -1014,7 +1023,7 @@ pub mod wasi {
                                 _: *mut u8,
                             );
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import4(
                             _: i32,
                             _: *mut u8,
@@ -1117,13 +1126,14 @@ pub mod wasi {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]fields.delete"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]fields.delete")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]fields.delete")]
                             fn wit_import2(_: i32, _: *mut u8, _: usize, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import2(
                             _: i32,
                             _: *mut u8,
@@ -1222,13 +1232,14 @@ pub mod wasi {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]fields.get-and-delete"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]fields.get-and-delete")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]fields.get-and-delete")]
                             fn wit_import2(_: i32, _: *mut u8, _: usize, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import2(
                             _: i32,
                             _: *mut u8,
@@ -1363,10 +1374,11 @@ pub mod wasi {
                         let ptr1 = vec1.as_ptr().cast::<u8>();
                         let len1 = vec1.len();
                         let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]fields.append"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]fields.append")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]fields.append")]
                             fn wit_import3(
                                 _: i32,
                                 _: *mut u8,
@@ -1376,7 +1388,7 @@ pub mod wasi {
                                 _: *mut u8,
                             );
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import3(
                             _: i32,
                             _: *mut u8,
@@ -1481,13 +1493,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]fields.copy-all"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]fields.copy-all")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]fields.copy-all")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -1547,13 +1560,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn clone(&self) -> Fields {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]fields.clone"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]fields.clone")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]fields.clone")]
                             fn wit_import0(_: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
@@ -1612,10 +1626,11 @@ pub mod wasi {
                             None => (0i32, 0i32),
                         };
                         let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[static]request.new"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[static]request.new")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[static]request.new")]
                             fn wit_import3(
                                 _: i32,
                                 _: i32,
@@ -1626,7 +1641,7 @@ pub mod wasi {
                                 _: *mut u8,
                             );
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import3(
                             _: i32,
                             _: i32,
@@ -1681,13 +1696,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]request.get-method"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]request.get-method")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]request.get-method")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -1752,13 +1768,14 @@ pub mod wasi {
                                 (9i32, ptr0.cast_mut(), len0)
                             }
                         };
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]request.set-method"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]request.set-method")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]request.set-method")]
                             fn wit_import2(_: i32, _: i32, _: *mut u8, _: usize) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import2(
                             _: i32,
                             _: i32,
@@ -1806,13 +1823,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]request.get-path-with-query"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]request.get-path-with-query")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]request.get-path-with-query")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -1864,13 +1882,14 @@ pub mod wasi {
                             }
                             None => (0i32, ::core::ptr::null_mut(), 0usize),
                         };
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]request.set-path-with-query"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]request.set-path-with-query")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]request.set-path-with-query")]
                             fn wit_import2(_: i32, _: i32, _: *mut u8, _: usize) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import2(
                             _: i32,
                             _: i32,
@@ -1918,13 +1937,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]request.get-scheme"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]request.get-scheme")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]request.get-scheme")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -1994,10 +2014,11 @@ pub mod wasi {
                             }
                             None => (0i32, 0i32, ::core::ptr::null_mut(), 0usize),
                         };
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]request.set-scheme"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]request.set-scheme")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]request.set-scheme")]
                             fn wit_import3(
                                 _: i32,
                                 _: i32,
@@ -2006,7 +2027,7 @@ pub mod wasi {
                                 _: usize,
                             ) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import3(
                             _: i32,
                             _: i32,
@@ -2057,13 +2078,14 @@ pub mod wasi {
                                 * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]request.get-authority"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]request.get-authority")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]request.get-authority")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -2113,13 +2135,14 @@ pub mod wasi {
                             }
                             None => (0i32, ::core::ptr::null_mut(), 0usize),
                         };
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]request.set-authority"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]request.set-authority")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]request.set-authority")]
                             fn wit_import2(_: i32, _: i32, _: *mut u8, _: usize) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import2(
                             _: i32,
                             _: i32,
@@ -2167,13 +2190,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 8],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]request.get-options"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]request.get-options")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]request.get-options")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -2203,13 +2227,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn get_headers(&self) -> Headers {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]request.get-headers"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]request.get-headers")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]request.get-headers")]
                             fn wit_import0(_: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
@@ -2251,13 +2276,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 8],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[static]request.consume-body"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[static]request.consume-body")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[static]request.consume-body")]
                             fn wit_import1(_: i32, _: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -2291,13 +2317,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn new() -> Self {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[constructor]request-options"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[constructor]request-options")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[constructor]request-options")]
                             fn wit_import0() -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0() -> i32 {
                             unreachable!()
                         }
@@ -2318,13 +2345,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 16],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]request-options.get-connect-timeout"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]request-options.get-connect-timeout")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]request-options.get-connect-timeout")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -2372,13 +2400,14 @@ pub mod wasi {
                             None => (0i32, 0i64),
                         };
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]request-options.set-connect-timeout"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]request-options.set-connect-timeout")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]request-options.set-connect-timeout")]
                             fn wit_import2(_: i32, _: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import2(
                             _: i32,
                             _: i32,
@@ -2458,13 +2487,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 16],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]request-options.get-first-byte-timeout"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]request-options.get-first-byte-timeout")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]request-options.get-first-byte-timeout")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -2512,13 +2542,14 @@ pub mod wasi {
                             None => (0i32, 0i64),
                         };
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]request-options.set-first-byte-timeout"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]request-options.set-first-byte-timeout")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]request-options.set-first-byte-timeout")]
                             fn wit_import2(_: i32, _: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import2(
                             _: i32,
                             _: i32,
@@ -2599,13 +2630,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 16],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]request-options.get-between-bytes-timeout"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]request-options.get-between-bytes-timeout")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]request-options.get-between-bytes-timeout")]
                             fn wit_import1(_: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -2653,13 +2685,14 @@ pub mod wasi {
                             None => (0i32, 0i64),
                         };
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]request-options.set-between-bytes-timeout"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]request-options.set-between-bytes-timeout")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]request-options.set-between-bytes-timeout")]
                             fn wit_import2(_: i32, _: i32, _: i64, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import2(
                             _: i32,
                             _: i32,
@@ -2734,13 +2767,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn clone(&self) -> RequestOptions {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]request-options.clone"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]request-options.clone")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]request-options.clone")]
                             fn wit_import0(_: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
@@ -2786,13 +2820,14 @@ pub mod wasi {
                             None => (0i32, 0i32),
                         };
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[static]response.new"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[static]response.new")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[static]response.new")]
                             fn wit_import2(_: i32, _: i32, _: i32, _: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import2(
                             _: i32,
                             _: i32,
@@ -2831,13 +2866,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn get_status_code(&self) -> StatusCode {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]response.get-status-code"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]response.get-status-code")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]response.get-status-code")]
                             fn wit_import0(_: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
@@ -2856,13 +2892,14 @@ pub mod wasi {
                     status_code: StatusCode,
                 ) -> Result<(), ()> {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]response.set-status-code"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]response.set-status-code")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]response.set-status-code")]
                             fn wit_import0(_: i32, _: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32, _: i32) -> i32 {
                             unreachable!()
                         }
@@ -2893,13 +2930,14 @@ pub mod wasi {
                 #[allow(async_fn_in_trait)]
                 pub fn get_headers(&self) -> Headers {
                     unsafe {
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]response.get-headers"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[method]response.get-headers")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[method]response.get-headers")]
                             fn wit_import0(_: i32) -> i32;
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
@@ -2941,13 +2979,14 @@ pub mod wasi {
                             [::core::mem::MaybeUninit::uninit(); 8],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                        #[cfg(target_arch = "wasm32")]
+                        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
                         unsafe extern "C" {
-                            #[link_name = "[static]response.consume-body"]
+                            #[cfg_attr(target_arch = "wasm32", link_name = "[static]response.consume-body")]
+                            #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/types@0.3.0#[static]response.consume-body")]
                             fn wit_import1(_: i32, _: i32, _: *mut u8);
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
+                        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                         unsafe extern "C" fn wit_import1(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
@@ -3029,13 +3068,14 @@ pub mod wasi {
                             _params: Self::ParamsLower,
                             _results: *mut u8,
                         ) -> u32 {
-                            #[cfg(target_arch = "wasm32")]
+                            #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
                             #[link(wasm_import_module = "wasi:http/client@0.3.0")]
                             unsafe extern "C" {
-                                #[link_name = "[async-lower]send"]
+                                #[cfg_attr(target_arch = "wasm32", link_name = "[async-lower]send")]
+                                #[cfg_attr(not(target_arch = "wasm32"), link_name = "wasi:http/client@0.3.0#[async-lower]send")]
                                 fn call(_: i32, _: *mut u8) -> i32;
                             }
-                            #[cfg(not(target_arch = "wasm32"))]
+                            #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
                             unsafe extern "C" fn call(_: i32, _: *mut u8) -> i32 {
                                 unreachable!()
                             }
@@ -4625,50 +4665,99 @@ pub mod wit_future {
     #[doc(hidden)]
     #[allow(unused_unsafe)]
     pub mod vtable0 {
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn cancel_write(_: u32) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn cancel_read(_: u32) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn drop_writable(_: u32) {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn drop_readable(_: u32) {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn new() -> u64 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn start_read(_: u32, _: *mut u8) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn start_write(_: u32, _: *const u8) -> u32 {
             unreachable!()
         }
-        #[cfg(target_arch = "wasm32")]
+        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
         unsafe extern "C" {
-            #[link_name = "[future-new-1][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-new-1][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[future-new-1][static]request.new"
+            )]
             fn new() -> u64;
-            #[link_name = "[future-cancel-write-1][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-cancel-write-1][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[future-cancel-write-1][static]request.new"
+            )]
             fn cancel_write(_: u32) -> u32;
-            #[link_name = "[future-cancel-read-1][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-cancel-read-1][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[future-cancel-read-1][static]request.new"
+            )]
             fn cancel_read(_: u32) -> u32;
-            #[link_name = "[future-drop-writable-1][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-drop-writable-1][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[future-drop-writable-1][static]request.new"
+            )]
             fn drop_writable(_: u32);
-            #[link_name = "[future-drop-readable-1][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-drop-readable-1][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[future-drop-readable-1][static]request.new"
+            )]
             fn drop_readable(_: u32);
-            #[link_name = "[async-lower][future-read-1][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[async-lower][future-read-1][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[async-lower][future-read-1][static]request.new"
+            )]
             fn start_read(_: u32, _: *mut u8) -> u32;
-            #[link_name = "[async-lower][future-write-1][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[async-lower][future-write-1][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[async-lower][future-write-1][static]request.new"
+            )]
             fn start_write(_: u32, _: *const u8) -> u32;
         }
         unsafe fn lift(
@@ -4709,8 +4798,7 @@ pub mod wit_future {
                                     let e65 = {
                                         let l4 = i32::from(*ptr.add(16).cast::<u8>());
                                         let l8 = i32::from(
-                                            *ptr
-                                                .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                            *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                                 .cast::<u8>(),
                                         );
                                         super::super::wasi::http::types::DnsErrorPayload {
@@ -4719,17 +4807,28 @@ pub mod wit_future {
                                                 1 => {
                                                     let e = {
                                                         let l5 = *ptr
-                                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                16 + 1 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<*mut u8>();
                                                         let l6 = *ptr
-                                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                16 + 2 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<usize>();
                                                         let len7 = l6;
-                                                        let bytes7 = super::super::_rt::Vec::from_raw_parts(
-                                                            l5.cast(),
-                                                            len7,
-                                                            len7,
-                                                        );
+                                                        let bytes7 =
+                                                            super::super::_rt::Vec::from_raw_parts(
+                                                                l5.cast(),
+                                                                len7,
+                                                                len7,
+                                                            );
                                                         super::super::_rt::string_lift(bytes7)
                                                     };
                                                     Some(e)
@@ -4741,9 +4840,13 @@ pub mod wit_future {
                                                 1 => {
                                                     let e = {
                                                         let l9 = i32::from(
-                                                            *ptr
-                                                                .add(18 + 3 * ::core::mem::size_of::<*const u8>())
-                                                                .cast::<u16>(),
+                                                            *ptr.add(
+                                                                18 + 3 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
+                                                            .cast::<u16>(),
                                                         );
                                                         l9 as u16
                                                     };
@@ -4771,8 +4874,7 @@ pub mod wit_future {
                                     let e65 = {
                                         let l10 = i32::from(*ptr.add(16).cast::<u8>());
                                         let l12 = i32::from(
-                                            *ptr
-                                                .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                            *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                                 .cast::<u8>(),
                                         );
                                         super::super::wasi::http::types::TlsAlertReceivedPayload {
@@ -4780,7 +4882,8 @@ pub mod wit_future {
                                                 0 => None,
                                                 1 => {
                                                     let e = {
-                                                        let l11 = i32::from(*ptr.add(17).cast::<u8>());
+                                                        let l11 =
+                                                            i32::from(*ptr.add(17).cast::<u8>());
                                                         l11 as u8
                                                     };
                                                     Some(e)
@@ -4792,17 +4895,28 @@ pub mod wit_future {
                                                 1 => {
                                                     let e = {
                                                         let l13 = *ptr
-                                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                16 + 2 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<*mut u8>();
                                                         let l14 = *ptr
-                                                            .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                16 + 3 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<usize>();
                                                         let len15 = l14;
-                                                        let bytes15 = super::super::_rt::Vec::from_raw_parts(
-                                                            l13.cast(),
-                                                            len15,
-                                                            len15,
-                                                        );
+                                                        let bytes15 =
+                                                            super::super::_rt::Vec::from_raw_parts(
+                                                                l13.cast(),
+                                                                len15,
+                                                                len15,
+                                                            );
                                                         super::super::_rt::string_lift(bytes15)
                                                     };
                                                     Some(e)
@@ -4860,14 +4974,22 @@ pub mod wit_future {
                                             1 => {
                                                 let e = {
                                                     let l21 = i32::from(
-                                                        *ptr
-                                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
-                                                            .cast::<u8>(),
+                                                        *ptr.add(
+                                                            16 + 1 * ::core::mem::size_of::<
+                                                                *const u8,
+                                                            >(
+                                                            ),
+                                                        )
+                                                        .cast::<u8>(),
                                                     );
                                                     let l25 = i32::from(
-                                                        *ptr
-                                                            .add(16 + 4 * ::core::mem::size_of::<*const u8>())
-                                                            .cast::<u8>(),
+                                                        *ptr.add(
+                                                            16 + 4 * ::core::mem::size_of::<
+                                                                *const u8,
+                                                            >(
+                                                            ),
+                                                        )
+                                                        .cast::<u8>(),
                                                     );
                                                     super::super::wasi::http::types::FieldSizePayload {
                                                         field_name: match l21 {
@@ -4935,8 +5057,7 @@ pub mod wit_future {
                                     let e65 = {
                                         let l29 = i32::from(*ptr.add(16).cast::<u8>());
                                         let l33 = i32::from(
-                                            *ptr
-                                                .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                            *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                                 .cast::<u8>(),
                                         );
                                         super::super::wasi::http::types::FieldSizePayload {
@@ -4945,17 +5066,28 @@ pub mod wit_future {
                                                 1 => {
                                                     let e = {
                                                         let l30 = *ptr
-                                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                16 + 1 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<*mut u8>();
                                                         let l31 = *ptr
-                                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                16 + 2 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<usize>();
                                                         let len32 = l31;
-                                                        let bytes32 = super::super::_rt::Vec::from_raw_parts(
-                                                            l30.cast(),
-                                                            len32,
-                                                            len32,
-                                                        );
+                                                        let bytes32 =
+                                                            super::super::_rt::Vec::from_raw_parts(
+                                                                l30.cast(),
+                                                                len32,
+                                                                len32,
+                                                            );
                                                         super::super::_rt::string_lift(bytes32)
                                                     };
                                                     Some(e)
@@ -4967,7 +5099,12 @@ pub mod wit_future {
                                                 1 => {
                                                     let e = {
                                                         let l34 = *ptr
-                                                            .add(20 + 3 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                20 + 3 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<i32>();
                                                         l34 as u32
                                                     };
@@ -5001,8 +5138,7 @@ pub mod wit_future {
                                     let e65 = {
                                         let l37 = i32::from(*ptr.add(16).cast::<u8>());
                                         let l41 = i32::from(
-                                            *ptr
-                                                .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                            *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                                 .cast::<u8>(),
                                         );
                                         super::super::wasi::http::types::FieldSizePayload {
@@ -5011,17 +5147,28 @@ pub mod wit_future {
                                                 1 => {
                                                     let e = {
                                                         let l38 = *ptr
-                                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                16 + 1 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<*mut u8>();
                                                         let l39 = *ptr
-                                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                16 + 2 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<usize>();
                                                         let len40 = l39;
-                                                        let bytes40 = super::super::_rt::Vec::from_raw_parts(
-                                                            l38.cast(),
-                                                            len40,
-                                                            len40,
-                                                        );
+                                                        let bytes40 =
+                                                            super::super::_rt::Vec::from_raw_parts(
+                                                                l38.cast(),
+                                                                len40,
+                                                                len40,
+                                                            );
                                                         super::super::_rt::string_lift(bytes40)
                                                     };
                                                     Some(e)
@@ -5033,7 +5180,12 @@ pub mod wit_future {
                                                 1 => {
                                                     let e = {
                                                         let l42 = *ptr
-                                                            .add(20 + 3 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                20 + 3 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<i32>();
                                                         l42 as u32
                                                     };
@@ -5083,8 +5235,7 @@ pub mod wit_future {
                                     let e65 = {
                                         let l47 = i32::from(*ptr.add(16).cast::<u8>());
                                         let l51 = i32::from(
-                                            *ptr
-                                                .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                            *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                                 .cast::<u8>(),
                                         );
                                         super::super::wasi::http::types::FieldSizePayload {
@@ -5093,17 +5244,28 @@ pub mod wit_future {
                                                 1 => {
                                                     let e = {
                                                         let l48 = *ptr
-                                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                16 + 1 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<*mut u8>();
                                                         let l49 = *ptr
-                                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                16 + 2 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<usize>();
                                                         let len50 = l49;
-                                                        let bytes50 = super::super::_rt::Vec::from_raw_parts(
-                                                            l48.cast(),
-                                                            len50,
-                                                            len50,
-                                                        );
+                                                        let bytes50 =
+                                                            super::super::_rt::Vec::from_raw_parts(
+                                                                l48.cast(),
+                                                                len50,
+                                                                len50,
+                                                            );
                                                         super::super::_rt::string_lift(bytes50)
                                                     };
                                                     Some(e)
@@ -5115,7 +5277,12 @@ pub mod wit_future {
                                                 1 => {
                                                     let e = {
                                                         let l52 = *ptr
-                                                            .add(20 + 3 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                20 + 3 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<i32>();
                                                         l52 as u32
                                                     };
@@ -5135,17 +5302,28 @@ pub mod wit_future {
                                             1 => {
                                                 let e = {
                                                     let l54 = *ptr
-                                                        .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                                        .add(
+                                                            16 + 1 * ::core::mem::size_of::<
+                                                                *const u8,
+                                                            >(
+                                                            ),
+                                                        )
                                                         .cast::<*mut u8>();
                                                     let l55 = *ptr
-                                                        .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                        .add(
+                                                            16 + 2 * ::core::mem::size_of::<
+                                                                *const u8,
+                                                            >(
+                                                            ),
+                                                        )
                                                         .cast::<usize>();
                                                     let len56 = l55;
-                                                    let bytes56 = super::super::_rt::Vec::from_raw_parts(
-                                                        l54.cast(),
-                                                        len56,
-                                                        len56,
-                                                    );
+                                                    let bytes56 =
+                                                        super::super::_rt::Vec::from_raw_parts(
+                                                            l54.cast(),
+                                                            len56,
+                                                            len56,
+                                                        );
                                                     super::super::_rt::string_lift(bytes56)
                                                 };
                                                 Some(e)
@@ -5163,17 +5341,28 @@ pub mod wit_future {
                                             1 => {
                                                 let e = {
                                                     let l58 = *ptr
-                                                        .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                                        .add(
+                                                            16 + 1 * ::core::mem::size_of::<
+                                                                *const u8,
+                                                            >(
+                                                            ),
+                                                        )
                                                         .cast::<*mut u8>();
                                                     let l59 = *ptr
-                                                        .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                        .add(
+                                                            16 + 2 * ::core::mem::size_of::<
+                                                                *const u8,
+                                                            >(
+                                                            ),
+                                                        )
                                                         .cast::<usize>();
                                                     let len60 = l59;
-                                                    let bytes60 = super::super::_rt::Vec::from_raw_parts(
-                                                        l58.cast(),
-                                                        len60,
-                                                        len60,
-                                                    );
+                                                    let bytes60 =
+                                                        super::super::_rt::Vec::from_raw_parts(
+                                                            l58.cast(),
+                                                            len60,
+                                                            len60,
+                                                        );
                                                     super::super::_rt::string_lift(bytes60)
                                                 };
                                                 Some(e)
@@ -5197,17 +5386,28 @@ pub mod wit_future {
                                             1 => {
                                                 let e = {
                                                     let l62 = *ptr
-                                                        .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                                        .add(
+                                                            16 + 1 * ::core::mem::size_of::<
+                                                                *const u8,
+                                                            >(
+                                                            ),
+                                                        )
                                                         .cast::<*mut u8>();
                                                     let l63 = *ptr
-                                                        .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                        .add(
+                                                            16 + 2 * ::core::mem::size_of::<
+                                                                *const u8,
+                                                            >(
+                                                            ),
+                                                        )
                                                         .cast::<usize>();
                                                     let len64 = l63;
-                                                    let bytes64 = super::super::_rt::Vec::from_raw_parts(
-                                                        l62.cast(),
-                                                        len64,
-                                                        len64,
-                                                    );
+                                                    let bytes64 =
+                                                        super::super::_rt::Vec::from_raw_parts(
+                                                            l62.cast(),
+                                                            len64,
+                                                            len64,
+                                                        );
                                                     super::super::_rt::string_lift(bytes64)
                                                 };
                                                 Some(e)
@@ -5267,11 +5467,9 @@ pub mod wit_future {
                                         let ptr1 = vec1.as_ptr().cast::<u8>();
                                         let len1 = vec1.len();
                                         ::core::mem::forget(vec1);
-                                        *ptr
-                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 2 * ::core::mem::size_of::<*const u8>())
                                             .cast::<usize>() = len1;
-                                        *ptr
-                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                             .cast::<*mut u8>() = ptr1.cast_mut();
                                     }
                                     None => {
@@ -5280,16 +5478,13 @@ pub mod wit_future {
                                 };
                                 match info_code0 {
                                     Some(e) => {
-                                        *ptr
-                                            .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (1i32) as u8;
-                                        *ptr
-                                            .add(18 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(18 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u16>() = (super::super::_rt::as_i32(e)) as u16;
                                     }
                                     None => {
-                                        *ptr
-                                            .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (0i32) as u8;
                                     }
                                 };
@@ -5339,8 +5534,8 @@ pub mod wit_future {
                                 match alert_id2 {
                                     Some(e) => {
                                         *ptr.add(16).cast::<u8>() = (1i32) as u8;
-                                        *ptr.add(17).cast::<u8>() = (super::super::_rt::as_i32(e))
-                                            as u8;
+                                        *ptr.add(17).cast::<u8>() =
+                                            (super::super::_rt::as_i32(e)) as u8;
                                     }
                                     None => {
                                         *ptr.add(16).cast::<u8>() = (0i32) as u8;
@@ -5348,23 +5543,19 @@ pub mod wit_future {
                                 };
                                 match alert_message2 {
                                     Some(e) => {
-                                        *ptr
-                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (1i32) as u8;
                                         let vec3 = (e.into_bytes()).into_boxed_slice();
                                         let ptr3 = vec3.as_ptr().cast::<u8>();
                                         let len3 = vec3.len();
                                         ::core::mem::forget(vec3);
-                                        *ptr
-                                            .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<usize>() = len3;
-                                        *ptr
-                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 2 * ::core::mem::size_of::<*const u8>())
                                             .cast::<*mut u8>() = ptr3.cast_mut();
                                     }
                                     None => {
-                                        *ptr
-                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (0i32) as u8;
                                     }
                                 };
@@ -5419,39 +5610,46 @@ pub mod wit_future {
                                         } = e;
                                         match field_name4 {
                                             Some(e) => {
-                                                *ptr
-                                                    .add(16 + 1 * ::core::mem::size_of::<*const u8>())
-                                                    .cast::<u8>() = (1i32) as u8;
+                                                *ptr.add(
+                                                    16 + 1 * ::core::mem::size_of::<*const u8>(),
+                                                )
+                                                .cast::<u8>() = (1i32) as u8;
                                                 let vec5 = (e.into_bytes()).into_boxed_slice();
                                                 let ptr5 = vec5.as_ptr().cast::<u8>();
                                                 let len5 = vec5.len();
                                                 ::core::mem::forget(vec5);
-                                                *ptr
-                                                    .add(16 + 3 * ::core::mem::size_of::<*const u8>())
-                                                    .cast::<usize>() = len5;
-                                                *ptr
-                                                    .add(16 + 2 * ::core::mem::size_of::<*const u8>())
-                                                    .cast::<*mut u8>() = ptr5.cast_mut();
+                                                *ptr.add(
+                                                    16 + 3 * ::core::mem::size_of::<*const u8>(),
+                                                )
+                                                .cast::<usize>() = len5;
+                                                *ptr.add(
+                                                    16 + 2 * ::core::mem::size_of::<*const u8>(),
+                                                )
+                                                .cast::<*mut u8>() = ptr5.cast_mut();
                                             }
                                             None => {
-                                                *ptr
-                                                    .add(16 + 1 * ::core::mem::size_of::<*const u8>())
-                                                    .cast::<u8>() = (0i32) as u8;
+                                                *ptr.add(
+                                                    16 + 1 * ::core::mem::size_of::<*const u8>(),
+                                                )
+                                                .cast::<u8>() = (0i32) as u8;
                                             }
                                         };
                                         match field_size4 {
                                             Some(e) => {
-                                                *ptr
-                                                    .add(16 + 4 * ::core::mem::size_of::<*const u8>())
-                                                    .cast::<u8>() = (1i32) as u8;
-                                                *ptr
-                                                    .add(20 + 4 * ::core::mem::size_of::<*const u8>())
-                                                    .cast::<i32>() = super::super::_rt::as_i32(e);
+                                                *ptr.add(
+                                                    16 + 4 * ::core::mem::size_of::<*const u8>(),
+                                                )
+                                                .cast::<u8>() = (1i32) as u8;
+                                                *ptr.add(
+                                                    20 + 4 * ::core::mem::size_of::<*const u8>(),
+                                                )
+                                                .cast::<i32>() = super::super::_rt::as_i32(e);
                                             }
                                             None => {
-                                                *ptr
-                                                    .add(16 + 4 * ::core::mem::size_of::<*const u8>())
-                                                    .cast::<u8>() = (0i32) as u8;
+                                                *ptr.add(
+                                                    16 + 4 * ::core::mem::size_of::<*const u8>(),
+                                                )
+                                                .cast::<u8>() = (0i32) as u8;
                                             }
                                         };
                                     }
@@ -5485,11 +5683,9 @@ pub mod wit_future {
                                         let ptr7 = vec7.as_ptr().cast::<u8>();
                                         let len7 = vec7.len();
                                         ::core::mem::forget(vec7);
-                                        *ptr
-                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 2 * ::core::mem::size_of::<*const u8>())
                                             .cast::<usize>() = len7;
-                                        *ptr
-                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                             .cast::<*mut u8>() = ptr7.cast_mut();
                                     }
                                     None => {
@@ -5498,16 +5694,13 @@ pub mod wit_future {
                                 };
                                 match field_size6 {
                                     Some(e) => {
-                                        *ptr
-                                            .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (1i32) as u8;
-                                        *ptr
-                                            .add(20 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(20 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<i32>() = super::super::_rt::as_i32(e);
                                     }
                                     None => {
-                                        *ptr
-                                            .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (0i32) as u8;
                                     }
                                 };
@@ -5540,11 +5733,9 @@ pub mod wit_future {
                                         let ptr9 = vec9.as_ptr().cast::<u8>();
                                         let len9 = vec9.len();
                                         ::core::mem::forget(vec9);
-                                        *ptr
-                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 2 * ::core::mem::size_of::<*const u8>())
                                             .cast::<usize>() = len9;
-                                        *ptr
-                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                             .cast::<*mut u8>() = ptr9.cast_mut();
                                     }
                                     None => {
@@ -5553,16 +5744,13 @@ pub mod wit_future {
                                 };
                                 match field_size8 {
                                     Some(e) => {
-                                        *ptr
-                                            .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (1i32) as u8;
-                                        *ptr
-                                            .add(20 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(20 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<i32>() = super::super::_rt::as_i32(e);
                                     }
                                     None => {
-                                        *ptr
-                                            .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (0i32) as u8;
                                     }
                                 };
@@ -5604,11 +5792,9 @@ pub mod wit_future {
                                         let ptr11 = vec11.as_ptr().cast::<u8>();
                                         let len11 = vec11.len();
                                         ::core::mem::forget(vec11);
-                                        *ptr
-                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 2 * ::core::mem::size_of::<*const u8>())
                                             .cast::<usize>() = len11;
-                                        *ptr
-                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                             .cast::<*mut u8>() = ptr11.cast_mut();
                                     }
                                     None => {
@@ -5617,16 +5803,13 @@ pub mod wit_future {
                                 };
                                 match field_size10 {
                                     Some(e) => {
-                                        *ptr
-                                            .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (1i32) as u8;
-                                        *ptr
-                                            .add(20 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(20 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<i32>() = super::super::_rt::as_i32(e);
                                     }
                                     None => {
-                                        *ptr
-                                            .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (0i32) as u8;
                                     }
                                 };
@@ -5640,11 +5823,9 @@ pub mod wit_future {
                                         let ptr12 = vec12.as_ptr().cast::<u8>();
                                         let len12 = vec12.len();
                                         ::core::mem::forget(vec12);
-                                        *ptr
-                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 2 * ::core::mem::size_of::<*const u8>())
                                             .cast::<usize>() = len12;
-                                        *ptr
-                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                             .cast::<*mut u8>() = ptr12.cast_mut();
                                     }
                                     None => {
@@ -5661,11 +5842,9 @@ pub mod wit_future {
                                         let ptr13 = vec13.as_ptr().cast::<u8>();
                                         let len13 = vec13.len();
                                         ::core::mem::forget(vec13);
-                                        *ptr
-                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 2 * ::core::mem::size_of::<*const u8>())
                                             .cast::<usize>() = len13;
-                                        *ptr
-                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                             .cast::<*mut u8>() = ptr13.cast_mut();
                                     }
                                     None => {
@@ -5697,11 +5876,9 @@ pub mod wit_future {
                                         let ptr14 = vec14.as_ptr().cast::<u8>();
                                         let len14 = vec14.len();
                                         ::core::mem::forget(vec14);
-                                        *ptr
-                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 2 * ::core::mem::size_of::<*const u8>())
                                             .cast::<usize>() = len14;
-                                        *ptr
-                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                             .cast::<*mut u8>() = ptr14.cast_mut();
                                     }
                                     None => {
@@ -5752,8 +5929,7 @@ pub mod wit_future {
                             13 => {}
                             14 => {
                                 let l5 = i32::from(
-                                    *ptr
-                                        .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                    *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                         .cast::<u8>(),
                                 );
                                 match l5 {
@@ -5782,18 +5958,23 @@ pub mod wit_future {
                                     0 => {}
                                     _ => {
                                         let l9 = i32::from(
-                                            *ptr
-                                                .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                            *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                                 .cast::<u8>(),
                                         );
                                         match l9 {
                                             0 => {}
                                             _ => {
                                                 let l10 = *ptr
-                                                    .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                    .add(
+                                                        16 + 2 * ::core::mem::size_of::<*const u8>(
+                                                        ),
+                                                    )
                                                     .cast::<*mut u8>();
                                                 let l11 = *ptr
-                                                    .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                                    .add(
+                                                        16 + 3 * ::core::mem::size_of::<*const u8>(
+                                                        ),
+                                                    )
                                                     .cast::<usize>();
                                                 super::super::_rt::cabi_dealloc(l10, l11, 1);
                                             }
@@ -5922,7 +6103,15 @@ pub mod wit_future {
             drop_writable,
             drop_readable,
             dealloc_lists,
+            #[cfg(any(target_arch = "wasm32", not(feature = "mock-host")))]
             layout: unsafe { ::core::alloc::Layout::from_size_align_unchecked(40, 8) },
+            #[cfg(all(feature = "mock-host", not(target_arch = "wasm32")))]
+            layout: unsafe {
+                ::core::alloc::Layout::from_size_align_unchecked(
+                    24 + 4 * ::core::mem::size_of::<*const u8>(),
+                    8,
+                )
+            },
             lift,
             lower,
             new,
@@ -5930,65 +6119,113 @@ pub mod wit_future {
             start_write,
         };
         impl super::FuturePayload
-        for Result<
-            Option<super::super::wasi::http::types::Trailers>,
-            super::super::wasi::http::types::ErrorCode,
-        > {
+            for Result<
+                Option<super::super::wasi::http::types::Trailers>,
+                super::super::wasi::http::types::ErrorCode,
+            >
+        {
             const VTABLE: &'static wit_bindgen::rt::async_support::FutureVtable<Self> = &VTABLE;
         }
     }
     #[doc(hidden)]
     #[allow(unused_unsafe)]
     pub mod vtable1 {
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn cancel_write(_: u32) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn cancel_read(_: u32) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn drop_writable(_: u32) {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn drop_readable(_: u32) {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn new() -> u64 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn start_read(_: u32, _: *mut u8) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn start_write(_: u32, _: *const u8) -> u32 {
             unreachable!()
         }
-        #[cfg(target_arch = "wasm32")]
+        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
         unsafe extern "C" {
-            #[link_name = "[future-new-2][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-new-2][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[future-new-2][static]request.new"
+            )]
             fn new() -> u64;
-            #[link_name = "[future-cancel-write-2][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-cancel-write-2][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[future-cancel-write-2][static]request.new"
+            )]
             fn cancel_write(_: u32) -> u32;
-            #[link_name = "[future-cancel-read-2][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-cancel-read-2][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[future-cancel-read-2][static]request.new"
+            )]
             fn cancel_read(_: u32) -> u32;
-            #[link_name = "[future-drop-writable-2][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-drop-writable-2][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[future-drop-writable-2][static]request.new"
+            )]
             fn drop_writable(_: u32);
-            #[link_name = "[future-drop-readable-2][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[future-drop-readable-2][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[future-drop-readable-2][static]request.new"
+            )]
             fn drop_readable(_: u32);
-            #[link_name = "[async-lower][future-read-2][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[async-lower][future-read-2][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[async-lower][future-read-2][static]request.new"
+            )]
             fn start_read(_: u32, _: *mut u8) -> u32;
-            #[link_name = "[async-lower][future-write-2][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[async-lower][future-write-2][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[async-lower][future-write-2][static]request.new"
+            )]
             fn start_write(_: u32, _: *const u8) -> u32;
         }
-        unsafe fn lift(
-            ptr: *mut u8,
-        ) -> Result<(), super::super::wasi::http::types::ErrorCode> {
+        unsafe fn lift(ptr: *mut u8) -> Result<(), super::super::wasi::http::types::ErrorCode> {
             unsafe {
                 let l0 = i32::from(*ptr.add(0).cast::<u8>());
                 match l0 {
@@ -6006,8 +6243,7 @@ pub mod wit_future {
                                     let e63 = {
                                         let l2 = i32::from(*ptr.add(16).cast::<u8>());
                                         let l6 = i32::from(
-                                            *ptr
-                                                .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                            *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                                 .cast::<u8>(),
                                         );
                                         super::super::wasi::http::types::DnsErrorPayload {
@@ -6016,17 +6252,28 @@ pub mod wit_future {
                                                 1 => {
                                                     let e = {
                                                         let l3 = *ptr
-                                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                16 + 1 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<*mut u8>();
                                                         let l4 = *ptr
-                                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                16 + 2 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<usize>();
                                                         let len5 = l4;
-                                                        let bytes5 = super::super::_rt::Vec::from_raw_parts(
-                                                            l3.cast(),
-                                                            len5,
-                                                            len5,
-                                                        );
+                                                        let bytes5 =
+                                                            super::super::_rt::Vec::from_raw_parts(
+                                                                l3.cast(),
+                                                                len5,
+                                                                len5,
+                                                            );
                                                         super::super::_rt::string_lift(bytes5)
                                                     };
                                                     Some(e)
@@ -6038,9 +6285,13 @@ pub mod wit_future {
                                                 1 => {
                                                     let e = {
                                                         let l7 = i32::from(
-                                                            *ptr
-                                                                .add(18 + 3 * ::core::mem::size_of::<*const u8>())
-                                                                .cast::<u16>(),
+                                                            *ptr.add(
+                                                                18 + 3 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
+                                                            .cast::<u16>(),
                                                         );
                                                         l7 as u16
                                                     };
@@ -6068,8 +6319,7 @@ pub mod wit_future {
                                     let e63 = {
                                         let l8 = i32::from(*ptr.add(16).cast::<u8>());
                                         let l10 = i32::from(
-                                            *ptr
-                                                .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                            *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                                 .cast::<u8>(),
                                         );
                                         super::super::wasi::http::types::TlsAlertReceivedPayload {
@@ -6077,7 +6327,8 @@ pub mod wit_future {
                                                 0 => None,
                                                 1 => {
                                                     let e = {
-                                                        let l9 = i32::from(*ptr.add(17).cast::<u8>());
+                                                        let l9 =
+                                                            i32::from(*ptr.add(17).cast::<u8>());
                                                         l9 as u8
                                                     };
                                                     Some(e)
@@ -6089,17 +6340,28 @@ pub mod wit_future {
                                                 1 => {
                                                     let e = {
                                                         let l11 = *ptr
-                                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                16 + 2 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<*mut u8>();
                                                         let l12 = *ptr
-                                                            .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                16 + 3 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<usize>();
                                                         let len13 = l12;
-                                                        let bytes13 = super::super::_rt::Vec::from_raw_parts(
-                                                            l11.cast(),
-                                                            len13,
-                                                            len13,
-                                                        );
+                                                        let bytes13 =
+                                                            super::super::_rt::Vec::from_raw_parts(
+                                                                l11.cast(),
+                                                                len13,
+                                                                len13,
+                                                            );
                                                         super::super::_rt::string_lift(bytes13)
                                                     };
                                                     Some(e)
@@ -6157,14 +6419,22 @@ pub mod wit_future {
                                             1 => {
                                                 let e = {
                                                     let l19 = i32::from(
-                                                        *ptr
-                                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
-                                                            .cast::<u8>(),
+                                                        *ptr.add(
+                                                            16 + 1 * ::core::mem::size_of::<
+                                                                *const u8,
+                                                            >(
+                                                            ),
+                                                        )
+                                                        .cast::<u8>(),
                                                     );
                                                     let l23 = i32::from(
-                                                        *ptr
-                                                            .add(16 + 4 * ::core::mem::size_of::<*const u8>())
-                                                            .cast::<u8>(),
+                                                        *ptr.add(
+                                                            16 + 4 * ::core::mem::size_of::<
+                                                                *const u8,
+                                                            >(
+                                                            ),
+                                                        )
+                                                        .cast::<u8>(),
                                                     );
                                                     super::super::wasi::http::types::FieldSizePayload {
                                                         field_name: match l19 {
@@ -6232,8 +6502,7 @@ pub mod wit_future {
                                     let e63 = {
                                         let l27 = i32::from(*ptr.add(16).cast::<u8>());
                                         let l31 = i32::from(
-                                            *ptr
-                                                .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                            *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                                 .cast::<u8>(),
                                         );
                                         super::super::wasi::http::types::FieldSizePayload {
@@ -6242,17 +6511,28 @@ pub mod wit_future {
                                                 1 => {
                                                     let e = {
                                                         let l28 = *ptr
-                                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                16 + 1 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<*mut u8>();
                                                         let l29 = *ptr
-                                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                16 + 2 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<usize>();
                                                         let len30 = l29;
-                                                        let bytes30 = super::super::_rt::Vec::from_raw_parts(
-                                                            l28.cast(),
-                                                            len30,
-                                                            len30,
-                                                        );
+                                                        let bytes30 =
+                                                            super::super::_rt::Vec::from_raw_parts(
+                                                                l28.cast(),
+                                                                len30,
+                                                                len30,
+                                                            );
                                                         super::super::_rt::string_lift(bytes30)
                                                     };
                                                     Some(e)
@@ -6264,7 +6544,12 @@ pub mod wit_future {
                                                 1 => {
                                                     let e = {
                                                         let l32 = *ptr
-                                                            .add(20 + 3 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                20 + 3 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<i32>();
                                                         l32 as u32
                                                     };
@@ -6298,8 +6583,7 @@ pub mod wit_future {
                                     let e63 = {
                                         let l35 = i32::from(*ptr.add(16).cast::<u8>());
                                         let l39 = i32::from(
-                                            *ptr
-                                                .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                            *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                                 .cast::<u8>(),
                                         );
                                         super::super::wasi::http::types::FieldSizePayload {
@@ -6308,17 +6592,28 @@ pub mod wit_future {
                                                 1 => {
                                                     let e = {
                                                         let l36 = *ptr
-                                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                16 + 1 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<*mut u8>();
                                                         let l37 = *ptr
-                                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                16 + 2 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<usize>();
                                                         let len38 = l37;
-                                                        let bytes38 = super::super::_rt::Vec::from_raw_parts(
-                                                            l36.cast(),
-                                                            len38,
-                                                            len38,
-                                                        );
+                                                        let bytes38 =
+                                                            super::super::_rt::Vec::from_raw_parts(
+                                                                l36.cast(),
+                                                                len38,
+                                                                len38,
+                                                            );
                                                         super::super::_rt::string_lift(bytes38)
                                                     };
                                                     Some(e)
@@ -6330,7 +6625,12 @@ pub mod wit_future {
                                                 1 => {
                                                     let e = {
                                                         let l40 = *ptr
-                                                            .add(20 + 3 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                20 + 3 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<i32>();
                                                         l40 as u32
                                                     };
@@ -6380,8 +6680,7 @@ pub mod wit_future {
                                     let e63 = {
                                         let l45 = i32::from(*ptr.add(16).cast::<u8>());
                                         let l49 = i32::from(
-                                            *ptr
-                                                .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                            *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                                 .cast::<u8>(),
                                         );
                                         super::super::wasi::http::types::FieldSizePayload {
@@ -6390,17 +6689,28 @@ pub mod wit_future {
                                                 1 => {
                                                     let e = {
                                                         let l46 = *ptr
-                                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                16 + 1 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<*mut u8>();
                                                         let l47 = *ptr
-                                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                16 + 2 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<usize>();
                                                         let len48 = l47;
-                                                        let bytes48 = super::super::_rt::Vec::from_raw_parts(
-                                                            l46.cast(),
-                                                            len48,
-                                                            len48,
-                                                        );
+                                                        let bytes48 =
+                                                            super::super::_rt::Vec::from_raw_parts(
+                                                                l46.cast(),
+                                                                len48,
+                                                                len48,
+                                                            );
                                                         super::super::_rt::string_lift(bytes48)
                                                     };
                                                     Some(e)
@@ -6412,7 +6722,12 @@ pub mod wit_future {
                                                 1 => {
                                                     let e = {
                                                         let l50 = *ptr
-                                                            .add(20 + 3 * ::core::mem::size_of::<*const u8>())
+                                                            .add(
+                                                                20 + 3 * ::core::mem::size_of::<
+                                                                    *const u8,
+                                                                >(
+                                                                ),
+                                                            )
                                                             .cast::<i32>();
                                                         l50 as u32
                                                     };
@@ -6432,17 +6747,28 @@ pub mod wit_future {
                                             1 => {
                                                 let e = {
                                                     let l52 = *ptr
-                                                        .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                                        .add(
+                                                            16 + 1 * ::core::mem::size_of::<
+                                                                *const u8,
+                                                            >(
+                                                            ),
+                                                        )
                                                         .cast::<*mut u8>();
                                                     let l53 = *ptr
-                                                        .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                        .add(
+                                                            16 + 2 * ::core::mem::size_of::<
+                                                                *const u8,
+                                                            >(
+                                                            ),
+                                                        )
                                                         .cast::<usize>();
                                                     let len54 = l53;
-                                                    let bytes54 = super::super::_rt::Vec::from_raw_parts(
-                                                        l52.cast(),
-                                                        len54,
-                                                        len54,
-                                                    );
+                                                    let bytes54 =
+                                                        super::super::_rt::Vec::from_raw_parts(
+                                                            l52.cast(),
+                                                            len54,
+                                                            len54,
+                                                        );
                                                     super::super::_rt::string_lift(bytes54)
                                                 };
                                                 Some(e)
@@ -6460,17 +6786,28 @@ pub mod wit_future {
                                             1 => {
                                                 let e = {
                                                     let l56 = *ptr
-                                                        .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                                        .add(
+                                                            16 + 1 * ::core::mem::size_of::<
+                                                                *const u8,
+                                                            >(
+                                                            ),
+                                                        )
                                                         .cast::<*mut u8>();
                                                     let l57 = *ptr
-                                                        .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                        .add(
+                                                            16 + 2 * ::core::mem::size_of::<
+                                                                *const u8,
+                                                            >(
+                                                            ),
+                                                        )
                                                         .cast::<usize>();
                                                     let len58 = l57;
-                                                    let bytes58 = super::super::_rt::Vec::from_raw_parts(
-                                                        l56.cast(),
-                                                        len58,
-                                                        len58,
-                                                    );
+                                                    let bytes58 =
+                                                        super::super::_rt::Vec::from_raw_parts(
+                                                            l56.cast(),
+                                                            len58,
+                                                            len58,
+                                                        );
                                                     super::super::_rt::string_lift(bytes58)
                                                 };
                                                 Some(e)
@@ -6494,17 +6831,28 @@ pub mod wit_future {
                                             1 => {
                                                 let e = {
                                                     let l60 = *ptr
-                                                        .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                                        .add(
+                                                            16 + 1 * ::core::mem::size_of::<
+                                                                *const u8,
+                                                            >(
+                                                            ),
+                                                        )
                                                         .cast::<*mut u8>();
                                                     let l61 = *ptr
-                                                        .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                        .add(
+                                                            16 + 2 * ::core::mem::size_of::<
+                                                                *const u8,
+                                                            >(
+                                                            ),
+                                                        )
                                                         .cast::<usize>();
                                                     let len62 = l61;
-                                                    let bytes62 = super::super::_rt::Vec::from_raw_parts(
-                                                        l60.cast(),
-                                                        len62,
-                                                        len62,
-                                                    );
+                                                    let bytes62 =
+                                                        super::super::_rt::Vec::from_raw_parts(
+                                                            l60.cast(),
+                                                            len62,
+                                                            len62,
+                                                        );
                                                     super::super::_rt::string_lift(bytes62)
                                                 };
                                                 Some(e)
@@ -6552,11 +6900,9 @@ pub mod wit_future {
                                         let ptr1 = vec1.as_ptr().cast::<u8>();
                                         let len1 = vec1.len();
                                         ::core::mem::forget(vec1);
-                                        *ptr
-                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 2 * ::core::mem::size_of::<*const u8>())
                                             .cast::<usize>() = len1;
-                                        *ptr
-                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                             .cast::<*mut u8>() = ptr1.cast_mut();
                                     }
                                     None => {
@@ -6565,16 +6911,13 @@ pub mod wit_future {
                                 };
                                 match info_code0 {
                                     Some(e) => {
-                                        *ptr
-                                            .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (1i32) as u8;
-                                        *ptr
-                                            .add(18 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(18 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u16>() = (super::super::_rt::as_i32(e)) as u16;
                                     }
                                     None => {
-                                        *ptr
-                                            .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (0i32) as u8;
                                     }
                                 };
@@ -6624,8 +6967,8 @@ pub mod wit_future {
                                 match alert_id2 {
                                     Some(e) => {
                                         *ptr.add(16).cast::<u8>() = (1i32) as u8;
-                                        *ptr.add(17).cast::<u8>() = (super::super::_rt::as_i32(e))
-                                            as u8;
+                                        *ptr.add(17).cast::<u8>() =
+                                            (super::super::_rt::as_i32(e)) as u8;
                                     }
                                     None => {
                                         *ptr.add(16).cast::<u8>() = (0i32) as u8;
@@ -6633,23 +6976,19 @@ pub mod wit_future {
                                 };
                                 match alert_message2 {
                                     Some(e) => {
-                                        *ptr
-                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (1i32) as u8;
                                         let vec3 = (e.into_bytes()).into_boxed_slice();
                                         let ptr3 = vec3.as_ptr().cast::<u8>();
                                         let len3 = vec3.len();
                                         ::core::mem::forget(vec3);
-                                        *ptr
-                                            .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<usize>() = len3;
-                                        *ptr
-                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 2 * ::core::mem::size_of::<*const u8>())
                                             .cast::<*mut u8>() = ptr3.cast_mut();
                                     }
                                     None => {
-                                        *ptr
-                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (0i32) as u8;
                                     }
                                 };
@@ -6704,39 +7043,46 @@ pub mod wit_future {
                                         } = e;
                                         match field_name4 {
                                             Some(e) => {
-                                                *ptr
-                                                    .add(16 + 1 * ::core::mem::size_of::<*const u8>())
-                                                    .cast::<u8>() = (1i32) as u8;
+                                                *ptr.add(
+                                                    16 + 1 * ::core::mem::size_of::<*const u8>(),
+                                                )
+                                                .cast::<u8>() = (1i32) as u8;
                                                 let vec5 = (e.into_bytes()).into_boxed_slice();
                                                 let ptr5 = vec5.as_ptr().cast::<u8>();
                                                 let len5 = vec5.len();
                                                 ::core::mem::forget(vec5);
-                                                *ptr
-                                                    .add(16 + 3 * ::core::mem::size_of::<*const u8>())
-                                                    .cast::<usize>() = len5;
-                                                *ptr
-                                                    .add(16 + 2 * ::core::mem::size_of::<*const u8>())
-                                                    .cast::<*mut u8>() = ptr5.cast_mut();
+                                                *ptr.add(
+                                                    16 + 3 * ::core::mem::size_of::<*const u8>(),
+                                                )
+                                                .cast::<usize>() = len5;
+                                                *ptr.add(
+                                                    16 + 2 * ::core::mem::size_of::<*const u8>(),
+                                                )
+                                                .cast::<*mut u8>() = ptr5.cast_mut();
                                             }
                                             None => {
-                                                *ptr
-                                                    .add(16 + 1 * ::core::mem::size_of::<*const u8>())
-                                                    .cast::<u8>() = (0i32) as u8;
+                                                *ptr.add(
+                                                    16 + 1 * ::core::mem::size_of::<*const u8>(),
+                                                )
+                                                .cast::<u8>() = (0i32) as u8;
                                             }
                                         };
                                         match field_size4 {
                                             Some(e) => {
-                                                *ptr
-                                                    .add(16 + 4 * ::core::mem::size_of::<*const u8>())
-                                                    .cast::<u8>() = (1i32) as u8;
-                                                *ptr
-                                                    .add(20 + 4 * ::core::mem::size_of::<*const u8>())
-                                                    .cast::<i32>() = super::super::_rt::as_i32(e);
+                                                *ptr.add(
+                                                    16 + 4 * ::core::mem::size_of::<*const u8>(),
+                                                )
+                                                .cast::<u8>() = (1i32) as u8;
+                                                *ptr.add(
+                                                    20 + 4 * ::core::mem::size_of::<*const u8>(),
+                                                )
+                                                .cast::<i32>() = super::super::_rt::as_i32(e);
                                             }
                                             None => {
-                                                *ptr
-                                                    .add(16 + 4 * ::core::mem::size_of::<*const u8>())
-                                                    .cast::<u8>() = (0i32) as u8;
+                                                *ptr.add(
+                                                    16 + 4 * ::core::mem::size_of::<*const u8>(),
+                                                )
+                                                .cast::<u8>() = (0i32) as u8;
                                             }
                                         };
                                     }
@@ -6770,11 +7116,9 @@ pub mod wit_future {
                                         let ptr7 = vec7.as_ptr().cast::<u8>();
                                         let len7 = vec7.len();
                                         ::core::mem::forget(vec7);
-                                        *ptr
-                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 2 * ::core::mem::size_of::<*const u8>())
                                             .cast::<usize>() = len7;
-                                        *ptr
-                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                             .cast::<*mut u8>() = ptr7.cast_mut();
                                     }
                                     None => {
@@ -6783,16 +7127,13 @@ pub mod wit_future {
                                 };
                                 match field_size6 {
                                     Some(e) => {
-                                        *ptr
-                                            .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (1i32) as u8;
-                                        *ptr
-                                            .add(20 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(20 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<i32>() = super::super::_rt::as_i32(e);
                                     }
                                     None => {
-                                        *ptr
-                                            .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (0i32) as u8;
                                     }
                                 };
@@ -6825,11 +7166,9 @@ pub mod wit_future {
                                         let ptr9 = vec9.as_ptr().cast::<u8>();
                                         let len9 = vec9.len();
                                         ::core::mem::forget(vec9);
-                                        *ptr
-                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 2 * ::core::mem::size_of::<*const u8>())
                                             .cast::<usize>() = len9;
-                                        *ptr
-                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                             .cast::<*mut u8>() = ptr9.cast_mut();
                                     }
                                     None => {
@@ -6838,16 +7177,13 @@ pub mod wit_future {
                                 };
                                 match field_size8 {
                                     Some(e) => {
-                                        *ptr
-                                            .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (1i32) as u8;
-                                        *ptr
-                                            .add(20 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(20 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<i32>() = super::super::_rt::as_i32(e);
                                     }
                                     None => {
-                                        *ptr
-                                            .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (0i32) as u8;
                                     }
                                 };
@@ -6889,11 +7225,9 @@ pub mod wit_future {
                                         let ptr11 = vec11.as_ptr().cast::<u8>();
                                         let len11 = vec11.len();
                                         ::core::mem::forget(vec11);
-                                        *ptr
-                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 2 * ::core::mem::size_of::<*const u8>())
                                             .cast::<usize>() = len11;
-                                        *ptr
-                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                             .cast::<*mut u8>() = ptr11.cast_mut();
                                     }
                                     None => {
@@ -6902,16 +7236,13 @@ pub mod wit_future {
                                 };
                                 match field_size10 {
                                     Some(e) => {
-                                        *ptr
-                                            .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (1i32) as u8;
-                                        *ptr
-                                            .add(20 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(20 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<i32>() = super::super::_rt::as_i32(e);
                                     }
                                     None => {
-                                        *ptr
-                                            .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 3 * ::core::mem::size_of::<*const u8>())
                                             .cast::<u8>() = (0i32) as u8;
                                     }
                                 };
@@ -6925,11 +7256,9 @@ pub mod wit_future {
                                         let ptr12 = vec12.as_ptr().cast::<u8>();
                                         let len12 = vec12.len();
                                         ::core::mem::forget(vec12);
-                                        *ptr
-                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 2 * ::core::mem::size_of::<*const u8>())
                                             .cast::<usize>() = len12;
-                                        *ptr
-                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                             .cast::<*mut u8>() = ptr12.cast_mut();
                                     }
                                     None => {
@@ -6946,11 +7275,9 @@ pub mod wit_future {
                                         let ptr13 = vec13.as_ptr().cast::<u8>();
                                         let len13 = vec13.len();
                                         ::core::mem::forget(vec13);
-                                        *ptr
-                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 2 * ::core::mem::size_of::<*const u8>())
                                             .cast::<usize>() = len13;
-                                        *ptr
-                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                             .cast::<*mut u8>() = ptr13.cast_mut();
                                     }
                                     None => {
@@ -6982,11 +7309,9 @@ pub mod wit_future {
                                         let ptr14 = vec14.as_ptr().cast::<u8>();
                                         let len14 = vec14.len();
                                         ::core::mem::forget(vec14);
-                                        *ptr
-                                            .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 2 * ::core::mem::size_of::<*const u8>())
                                             .cast::<usize>() = len14;
-                                        *ptr
-                                            .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                        *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                             .cast::<*mut u8>() = ptr14.cast_mut();
                                     }
                                     None => {
@@ -7037,8 +7362,7 @@ pub mod wit_future {
                             13 => {}
                             14 => {
                                 let l5 = i32::from(
-                                    *ptr
-                                        .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                    *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                         .cast::<u8>(),
                                 );
                                 match l5 {
@@ -7067,18 +7391,23 @@ pub mod wit_future {
                                     0 => {}
                                     _ => {
                                         let l9 = i32::from(
-                                            *ptr
-                                                .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                            *ptr.add(16 + 1 * ::core::mem::size_of::<*const u8>())
                                                 .cast::<u8>(),
                                         );
                                         match l9 {
                                             0 => {}
                                             _ => {
                                                 let l10 = *ptr
-                                                    .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                    .add(
+                                                        16 + 2 * ::core::mem::size_of::<*const u8>(
+                                                        ),
+                                                    )
                                                     .cast::<*mut u8>();
                                                 let l11 = *ptr
-                                                    .add(16 + 3 * ::core::mem::size_of::<*const u8>())
+                                                    .add(
+                                                        16 + 3 * ::core::mem::size_of::<*const u8>(
+                                                        ),
+                                                    )
                                                     .cast::<usize>();
                                                 super::super::_rt::cabi_dealloc(l10, l11, 1);
                                             }
@@ -7201,15 +7530,22 @@ pub mod wit_future {
             drop_writable,
             drop_readable,
             dealloc_lists,
+            #[cfg(any(target_arch = "wasm32", not(feature = "mock-host")))]
             layout: unsafe { ::core::alloc::Layout::from_size_align_unchecked(40, 8) },
+            #[cfg(all(feature = "mock-host", not(target_arch = "wasm32")))]
+            layout: unsafe {
+                ::core::alloc::Layout::from_size_align_unchecked(
+                    24 + 4 * ::core::mem::size_of::<*const u8>(),
+                    8,
+                )
+            },
             lift,
             lower,
             new,
             start_read,
             start_write,
         };
-        impl super::FuturePayload
-        for Result<(), super::super::wasi::http::types::ErrorCode> {
+        impl super::FuturePayload for Result<(), super::super::wasi::http::types::ErrorCode> {
             const VTABLE: &'static wit_bindgen::rt::async_support::FutureVtable<Self> = &VTABLE;
         }
     }
@@ -7234,67 +7570,115 @@ pub mod wit_stream {
     #[doc(hidden)]
     #[allow(unused_unsafe)]
     pub mod vtable0 {
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn cancel_write(_: u32) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn cancel_read(_: u32) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn drop_writable(_: u32) {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn drop_readable(_: u32) {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn new() -> u64 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn start_read(_: u32, _: *mut u8, _: usize) -> u32 {
             unreachable!()
         }
-        #[cfg(not(target_arch = "wasm32"))]
+        #[cfg(not(any(target_arch = "wasm32", feature = "mock-host")))]
         unsafe extern "C" fn start_write(_: u32, _: *const u8, _: usize) -> u32 {
             unreachable!()
         }
-        #[cfg(target_arch = "wasm32")]
+        #[cfg(any(target_arch = "wasm32", feature = "mock-host"))]
         #[link(wasm_import_module = "wasi:http/types@0.3.0")]
         unsafe extern "C" {
-            #[link_name = "[stream-new-0][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[stream-new-0][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[stream-new-0][static]request.new"
+            )]
             fn new() -> u64;
-            #[link_name = "[stream-cancel-write-0][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[stream-cancel-write-0][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[stream-cancel-write-0][static]request.new"
+            )]
             fn cancel_write(_: u32) -> u32;
-            #[link_name = "[stream-cancel-read-0][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[stream-cancel-read-0][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[stream-cancel-read-0][static]request.new"
+            )]
             fn cancel_read(_: u32) -> u32;
-            #[link_name = "[stream-drop-writable-0][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[stream-drop-writable-0][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[stream-drop-writable-0][static]request.new"
+            )]
             fn drop_writable(_: u32);
-            #[link_name = "[stream-drop-readable-0][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[stream-drop-readable-0][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[stream-drop-readable-0][static]request.new"
+            )]
             fn drop_readable(_: u32);
-            #[link_name = "[async-lower][stream-read-0][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[async-lower][stream-read-0][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[async-lower][stream-read-0][static]request.new"
+            )]
             fn start_read(_: u32, _: *mut u8, _: usize) -> u32;
-            #[link_name = "[async-lower][stream-write-0][static]request.new"]
+            #[cfg_attr(
+                target_arch = "wasm32",
+                link_name = "[async-lower][stream-write-0][static]request.new"
+            )]
+            #[cfg_attr(
+                not(target_arch = "wasm32"),
+                link_name = "wasi:http/types@0.3.0#[async-lower][stream-write-0][static]request.new"
+            )]
             fn start_write(_: u32, _: *const u8, _: usize) -> u32;
         }
-        pub static VTABLE: wit_bindgen::rt::async_support::StreamVtable<u8> = wit_bindgen::rt::async_support::StreamVtable::<
-            u8,
-        > {
-            cancel_write,
-            cancel_read,
-            drop_writable,
-            drop_readable,
-            dealloc_lists: None,
-            layout: unsafe { ::core::alloc::Layout::from_size_align_unchecked(1, 1) },
-            lift: None,
-            lower: None,
-            new,
-            start_read,
-            start_write,
-        };
+        pub static VTABLE: wit_bindgen::rt::async_support::StreamVtable<u8> =
+            wit_bindgen::rt::async_support::StreamVtable::<u8> {
+                cancel_write,
+                cancel_read,
+                drop_writable,
+                drop_readable,
+                dealloc_lists: None,
+                layout: unsafe { ::core::alloc::Layout::from_size_align_unchecked(1, 1) },
+                lift: None,
+                lower: None,
+                new,
+                start_read,
+                start_write,
+            };
         impl super::StreamPayload for u8 {
             const VTABLE: &'static wit_bindgen::rt::async_support::StreamVtable<Self> = &VTABLE;
         }
@@ -0,0 +1,213 @@
+use http_body::Body;
+use std::pin::Pin;
+use wasip3::http::types::{ErrorCode, Fields, Request, Response, Trailers};
+use wasip3::http_compat::{
+    channel_body, http_from_wasi_request, http_from_wasi_response, http_into_wasi_request,
+    http_into_wasi_response, ChannelBody,
+};
+use wasip3::mock_host::{spawn, MockHost};
+use wasip3::wit_future;
+
+fn fields(entries: &[(&str, &str)]) -> Vec<(String, Vec<u8>)> {
+    entries
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.as_bytes().to_vec()))
+        .collect()
+}
+
+fn response(entries: &[(&str, &str)]) -> Response {
+    let headers = Fields::from_list(&fields(entries)).unwrap();
+    let (_, trailers) = wit_future::new::<Result<Option<Trailers>, ErrorCode>>(|| Ok(None));
+    Response::new(headers, None, trailers).0
+}
+
+/// Polls `body` to the end, returning its data or the first error.
+async fn collect<B: Body<Error = ErrorCode> + Unpin>(mut body: B) -> Result<Vec<u8>, ErrorCode>
+where
+    B::Data: AsRef<[u8]>,
+{
+    let mut data = Vec::new();
+    while let Some(frame) = futures::future::poll_fn(|cx| Pin::new(&mut body).poll_frame(cx)).await
+    {
+        if let Ok(chunk) = frame?.into_data() {
+            data.extend_from_slice(chunk.as_ref());
+        }
+    }
+    Ok(data)
+}
+
+/// A handler responding with the method, URI and body of the request.
+async fn echo(request: Request) -> Result<Response, ErrorCode> {
+    let (parts, body) = http_from_wasi_request(request)?.into_parts();
+    let body = String::from_utf8(collect(body).await?).unwrap();
+    let resp = http::Response::builder()
+        .status(http::StatusCode::CREATED)
+        .header("x-echo", "yes")
+        .body(format!("{} {} {body}", parts.method, parts.uri))
+        .unwrap();
+    http_into_wasi_response(resp)
+}
+
+#[test]
+fn handler() {
+    let host = MockHost::new();
+    let body = host.block_on(async {
+        let req = http::Request::post("http://example.com/a?b")
+            .body("hi".to_string())
+            .unwrap();
+        let resp = echo(http_into_wasi_request(req).unwrap()).await.unwrap();
+        let resp = http_from_wasi_response(resp).unwrap();
+        assert_eq!(resp.status(), http::StatusCode::CREATED);
+        assert_eq!(resp.headers()["x-echo"], "yes");
+        collect(resp.into_body()).await.unwrap()
+    });
+    assert_eq!(body, b"POST http://example.com/a?b hi");
+}
+
+#[test]
+fn client() {
+    let host = MockHost::new();
+    host.set_http_client(echo);
+    let body = host.block_on(async {
+        let req = http::Request::put("https://example.com/x")
+            .body("yo".to_string())
+            .unwrap();
+        let req = http_into_wasi_request(req).unwrap();
+        let resp = wasip3::http::client::send(req).await.unwrap();
+        collect(http_from_wasi_response(resp).unwrap().into_body())
+            .await
+            .unwrap()
+    });
+    assert_eq!(body, b"PUT https://example.com/x yo");
+
+    // Without a client, requests are refused.
+    MockHost::new().block_on(async {
+        let req = http_into_wasi_request(http::Request::new(String::new())).unwrap();
+        let result = wasip3::http::client::send(req).await;
+        assert!(matches!(result, Err(ErrorCode::ConnectionRefused)));
+    });
+}
+
+#[test]
+fn body_limits() {
+    let host = MockHost::new();
+    host.block_on(async {
+        let req = http_into_wasi_request(http::Request::new("0123456789".to_string())).unwrap();
+        let body = http_from_wasi_request(req).unwrap().into_body();
+        assert_eq!(
+            collect(body.with_frame_size(3)).await.unwrap(),
+            b"0123456789"
+        );
+
+        // The limit applies to bodies passed on without being read too.
+        let req = http_into_wasi_request(http::Request::new("0123456789".to_string())).unwrap();
+        let body = http_from_wasi_request(req).unwrap().into_body();
+        let raw = body.with_max_body_size(4).take_raw().unwrap();
+        let resp = http_from_wasi_response(raw.into_response(Fields::new())).unwrap();
+        assert!(matches!(
+            collect(resp.into_body()).await,
+            Err(ErrorCode::HttpRequestBodySize(Some(10)))
+        ));
+    });
+}
+
+#[test]
+fn response_fields_survive_round_trip() {
+    let host = MockHost::new();
+    let _guard = host.install();
+    let interleaved = [("a", "1"), ("b", "2"), ("a", "3")];
+
+    // Replacing the body means the original response can't be passed on, yet
+    // the order of the fields, which `http::HeaderMap` loses, is kept.
+    let resp = http_from_wasi_response(response(&interleaved)).unwrap();
+    let resp = resp.map(|_| String::from("replaced"));
+    let resp = http_into_wasi_response(resp).unwrap();
+    assert_eq!(resp.get_headers().copy_all(), fields(&interleaved));
+
+    // Edited headers are converted from the `http::HeaderMap` instead.
+    let mut resp = http_from_wasi_response(response(&interleaved)).unwrap();
+    resp.headers_mut().insert("c", "4".parse().unwrap());
+    let resp = resp.map(|_| String::new());
+    let resp = http_into_wasi_response(resp).unwrap();
+    assert_eq!(
+        resp.get_headers().copy_all(),
+        fields(&[("a", "1"), ("a", "3"), ("b", "2"), ("c", "4")])
+    );
+}
+
+/// Polls `body` to the end, returning its data, trailers and error.
+async fn frames(mut body: ChannelBody) -> (Vec<u8>, Option<http::HeaderMap>, Option<ErrorCode>) {
+    let (mut data, mut trailers) = (Vec::new(), None);
+    while let Some(frame) = futures::future::poll_fn(|cx| Pin::new(&mut body).poll_frame(cx)).await
+    {
+        match frame {
+            Ok(frame) => match frame.into_data() {
+                Ok(chunk) => data.extend_from_slice(&chunk),
+                Err(frame) => trailers = frame.into_trailers().ok(),
+            },
+            Err(error) => return (data, trailers, Some(error)),
+        }
+    }
+    assert!(body.is_end_stream());
+    (data, trailers, None)
+}
+
+#[test]
+fn channel_body_yields_data_and_trailers() {
+    let host = MockHost::new();
+    let (data, trailers, error) = host.block_on(async {
+        let (mut sender, body) = channel_body();
+        spawn(async move {
+            assert_eq!(sender.send_data("hello, ").await.unwrap(), 7);
+            assert_eq!(sender.send_data(b"world".to_vec()).await.unwrap(), 5);
+            let mut trailers = http::HeaderMap::new();
+            trailers.insert("x-checksum", "abc".parse().unwrap());
+            sender.send_trailers(trailers).await.unwrap();
+        });
+        frames(body).await
+    });
+    assert_eq!(data, b"hello, world");
+    assert_eq!(trailers.unwrap()["x-checksum"], "abc");
+    assert!(error.is_none());
+
+    let (data, trailers, error) = host.block_on(async {
+        let (sender, body) = channel_body();
+        spawn(async move { sender.finish().await.unwrap() });
+        frames(body).await
+    });
+    assert!(data.is_empty() && trailers.is_none() && error.is_none());
+}
+
+#[test]
+fn channel_body_reports_abort() {
+    let host = MockHost::new();
+    let (data, _, error) = host.block_on(async {
+        let (mut sender, body) = channel_body();
+        spawn(async move {
+            sender.send_data("partial").await.unwrap();
+            sender.abort(ErrorCode::ConnectionTimeout).await.unwrap();
+        });
+        frames(body).await
+    });
+    assert_eq!(data, b"partial");
+    assert!(matches!(error, Some(ErrorCode::ConnectionTimeout)));
+
+    // Other errors are reported as internal errors with their message.
+    let (_, _, error) = host.block_on(async {
+        let (sender, body) = channel_body();
+        spawn(async move { sender.abort("upstream failed").await.unwrap() });
+        frames(body).await
+    });
+    assert!(matches!(
+        error,
+        Some(ErrorCode::InternalError(Some(message))) if message == "upstream failed"
+    ));
+
+    // So is dropping the sender without ending the body.
+    let (_, _, error) = host.block_on(async {
+        let (sender, body) = channel_body();
+        spawn(async move { drop(sender) });
+        frames(body).await
+    });
+    assert!(matches!(error, Some(ErrorCode::InternalError(_))));
+}
@@ -0,0 +1,116 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasip3::cli::{stdin, stdout};
+use wasip3::filesystem::types::{
+    Descriptor, DescriptorFlags, DescriptorType, ErrorCode, OpenFlags, PathFlags,
+};
+use wasip3::http::types::{self, Trailers};
+use wasip3::mock_host::{spawn, MockHost};
+use wasip3::{wit_future, wit_stream};
+
+fn root() -> Descriptor {
+    let (dir, path) = wasip3::filesystem::preopens::get_directories().remove(0);
+    assert_eq!(path, "/root");
+    dir
+}
+
+#[test]
+fn streams() {
+    let host = MockHost::new();
+    host.push_stdin(b"hello");
+    host.add_file("/root/b.txt", b"");
+    host.add_dir("/root/a");
+    host.preopen("/root");
+    host.block_on(async {
+        let (data, result) = stdin::read_via_stream();
+        assert_eq!(data.collect().await, b"hello");
+        result.await.unwrap();
+
+        let (mut tx, rx) = wit_stream::new();
+        let result = stdout::write_via_stream(rx);
+        assert!(tx.write_all(b"hello, ".to_vec()).await.is_empty());
+        assert!(tx.write_all(b"world".to_vec()).await.is_empty());
+        drop(tx);
+        result.await.unwrap();
+
+        // Directory entries contain pointers, so they're laid out in the
+        // stream differently on native targets than on wasm32.
+        let (entries, result) = root().read_directory();
+        let mut entries = entries.collect().await;
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].name, "a");
+        assert!(matches!(entries[0].type_, DescriptorType::Directory));
+        assert_eq!(entries[1].name, "b.txt");
+        assert!(matches!(entries[1].type_, DescriptorType::RegularFile));
+        result.await.unwrap();
+    });
+    assert_eq!(host.take_stdout(), b"hello, world");
+}
+
+#[test]
+fn futures() {
+    let host = MockHost::new();
+    host.preopen("/root");
+    host.block_on(async {
+        // Errors are lowered into the futures returned by imports.
+        let result = root()
+            .open_at(
+                PathFlags::empty(),
+                "missing.txt".to_string(),
+                OpenFlags::empty(),
+                DescriptorFlags::READ,
+            )
+            .await;
+        assert!(matches!(result, Err(ErrorCode::NoEntry)));
+
+        // Values with lists and resources are passed from writer to reader.
+        let (tx, rx) = wit_future::new::<Result<Option<Trailers>, types::ErrorCode>>(|| Ok(None));
+        let trailers = types::Fields::from_list(&[("a".into(), b"1".to_vec())]).unwrap();
+        tx.write(Ok(Some(trailers))).await.unwrap();
+        let trailers = rx.await.unwrap().unwrap();
+        assert_eq!(trailers.get("a"), [b"1".to_vec()]);
+
+        // Dropping the writer resolves the reader with the default.
+        let (tx, rx) = wit_future::new::<Result<(), types::ErrorCode>>(|| {
+            Err(types::ErrorCode::HttpProtocolError)
+        });
+        drop(tx);
+        assert!(matches!(rx.await, Err(types::ErrorCode::HttpProtocolError)));
+    });
+}
+
+#[test]
+fn spawned_tasks() {
+    let host = MockHost::new();
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let result = host.block_on({
+        let log = log.clone();
+        async move {
+            let (mut tx, mut rx) = wit_stream::new::<u8>();
+            spawn({
+                let log = log.clone();
+                async move {
+                    for byte in *b"abc" {
+                        log.borrow_mut().push(format!("write {}", byte as char));
+                        assert!(tx.write_one(byte).await.is_none());
+                    }
+                }
+            });
+            let mut bytes = Vec::new();
+            while let Some(byte) = rx.next().await {
+                log.borrow_mut().push(format!("read {}", byte as char));
+                bytes.push(byte);
+            }
+            bytes
+        }
+    });
+    assert_eq!(result, b"abc");
+    let log = log.borrow();
+    assert_eq!(log.len(), 6);
+    for word in ["a", "b", "c"] {
+        let write = log.iter().position(|l| *l == format!("write {word}"));
+        let read = log.iter().position(|l| *l == format!("read {word}"));
+        assert!(write < read, "{log:?}");
+    }
+}
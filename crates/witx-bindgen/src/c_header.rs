@@ -0,0 +1,336 @@
+//! Generation of a C header declaring a witx document's types and functions,
+//! in the style of wasi-libc's `wasi/api.h`.
+
+use heck::*;
+use witx::*;
+
+const HEADER: &str = "\
+/**
+ * This file is automatically generated, DO NOT EDIT
+ *
+ * To regenerate this file run the `crates/witx-bindgen` command
+ *
+ * The data layout checks below assume wasm32.
+ */
+
+#ifndef __wasi_api_h
+#define __wasi_api_h
+
+#include <stddef.h>
+#include <stdint.h>
+
+_Static_assert(_Alignof(int8_t) == 1, \"non-wasi data layout\");
+_Static_assert(_Alignof(uint8_t) == 1, \"non-wasi data layout\");
+_Static_assert(_Alignof(int16_t) == 2, \"non-wasi data layout\");
+_Static_assert(_Alignof(uint16_t) == 2, \"non-wasi data layout\");
+_Static_assert(_Alignof(int32_t) == 4, \"non-wasi data layout\");
+_Static_assert(_Alignof(uint32_t) == 4, \"non-wasi data layout\");
+_Static_assert(_Alignof(int64_t) == 8, \"non-wasi data layout\");
+_Static_assert(_Alignof(uint64_t) == 8, \"non-wasi data layout\");
+_Static_assert(_Alignof(void*) == 4, \"non-wasi data layout\");
+
+#ifdef __cplusplus
+extern \"C\" {
+#endif
+
+";
+
+const FOOTER: &str = "\
+#ifdef __cplusplus
+}
+#endif
+
+#endif
+";
+
+pub(crate) fn render(doc: &Document) -> String {
+    let mut src = String::new();
+    src.push_str(HEADER);
+    for ty in doc.typenames() {
+        render_typename(&ty, &mut src);
+    }
+    for c in doc.constants() {
+        comment(&c.docs, "", &mut src);
+        src.push_str(&format!(
+            "#define {} ((__wasi_{}_t){})\n\n",
+            macro_name(c.ty.as_str(), c.name.as_str()),
+            c.ty.as_str(),
+            c.value
+        ));
+    }
+    for m in doc.modules() {
+        for f in m.funcs() {
+            render_func(&f, &mut src);
+        }
+    }
+    src.push_str(FOOTER);
+    src
+}
+
+fn render_typename(ty: &NamedType, src: &mut String) {
+    let name = type_name(&ty.name);
+    let ty_ = match &ty.tref {
+        TypeRef::Name(other) => {
+            comment(&ty.docs, "", src);
+            src.push_str(&format!("typedef {} {};\n\n", type_name(&other.name), name));
+            render_layout_assertions(ty, &[], src);
+            return;
+        }
+        TypeRef::Value(ty) => ty,
+    };
+    // Lists are passed as a pointer and a length, so they have no type of
+    // their own.
+    if let Type::List(_) = &**ty_ {
+        return;
+    }
+    comment(&ty.docs, "", src);
+    let mut offsets = Vec::new();
+    match &**ty_ {
+        Type::Builtin(b) => src.push_str(&format!("typedef {} {};\n\n", builtin(*b), name)),
+        Type::Pointer(t) => src.push_str(&format!("typedef {} *{};\n\n", c_type(t), name)),
+        Type::ConstPointer(t) => {
+            src.push_str(&format!("typedef const {} *{};\n\n", c_type(t), name))
+        }
+        Type::Handle(_) => src.push_str(&format!("typedef int {};\n\n", name)),
+        Type::List(_) => unreachable!(),
+        Type::Variant(v) if v.is_enum() => {
+            src.push_str(&format!("typedef {} {};\n\n", int_repr(v.tag_repr), name));
+            for (i, case) in v.cases.iter().enumerate() {
+                comment(&case.docs, "", src);
+                src.push_str(&format!(
+                    "#define {} ({}({}))\n\n",
+                    macro_name(ty.name.as_str(), case.name.as_str()),
+                    int_constant(v.tag_repr),
+                    i
+                ));
+            }
+        }
+        Type::Variant(v) => {
+            let union_name = format!("__wasi_{}_u_t", ty.name.as_str());
+            src.push_str(&format!("typedef union {} {{\n", union_name));
+            for case in v.cases.iter() {
+                if let Some(tref) = &case.tref {
+                    comment(&case.docs, "    ", src);
+                    src.push_str(&format!("    {} {};\n", c_type(tref), case.name.as_str()));
+                }
+            }
+            src.push_str(&format!("}} {};\n", union_name));
+            src.push_str(&format!("typedef struct {} {{\n", name));
+            src.push_str(&format!("    {} tag;\n", int_repr(v.tag_repr)));
+            src.push_str(&format!("    {} u;\n", union_name));
+            src.push_str(&format!("}} {};\n\n", name));
+            offsets.push(("tag".to_string(), 0));
+            offsets.push(("u".to_string(), v.payload_offset()));
+        }
+        Type::Record(r) => match r.bitflags_repr() {
+            Some(repr) => {
+                src.push_str(&format!("typedef {} {};\n\n", int_repr(repr), name));
+                for (i, member) in r.members.iter().enumerate() {
+                    comment(&member.docs, "", src);
+                    src.push_str(&format!(
+                        "#define {} (({})1 << {})\n\n",
+                        macro_name(ty.name.as_str(), member.name.as_str()),
+                        name,
+                        i
+                    ));
+                }
+            }
+            None => {
+                src.push_str(&format!("typedef struct {} {{\n", name));
+                for (i, member) in r.member_layout().iter().enumerate() {
+                    if i > 0 {
+                        src.push('\n');
+                    }
+                    comment(&member.member.docs, "    ", src);
+                    src.push_str(&format!(
+                        "    {} {};\n",
+                        c_type(&member.member.tref),
+                        member.member.name.as_str()
+                    ));
+                    offsets.push((member.member.name.as_str().to_string(), member.offset));
+                }
+                src.push_str(&format!("}} {};\n\n", name));
+            }
+        },
+    }
+    render_layout_assertions(ty, &offsets, src);
+}
+
+fn render_layout_assertions(ty: &NamedType, offsets: &[(String, usize)], src: &mut String) {
+    let name = type_name(&ty.name);
+    let size_align = ty.mem_size_align();
+    src.push_str(&format!(
+        "_Static_assert(sizeof({}) == {}, \"witx calculated size\");\n",
+        name, size_align.size
+    ));
+    src.push_str(&format!(
+        "_Static_assert(_Alignof({}) == {}, \"witx calculated align\");\n",
+        name, size_align.align
+    ));
+    for (field, offset) in offsets {
+        src.push_str(&format!(
+            "_Static_assert(offsetof({}, {}) == {}, \"witx calculated offset\");\n",
+            name, field, offset
+        ));
+    }
+    src.push('\n');
+}
+
+fn render_func(func: &InterfaceFunc, src: &mut String) {
+    comment(&func.docs, "", src);
+
+    let no_docs = String::new();
+    let mut params = Vec::new();
+    for param in func.params.iter() {
+        let name = param.name.as_str();
+        match &**param.tref.type_() {
+            // Strings are NUL-terminated, as in wasi-libc.
+            Type::List(elem) if is_char(elem) => {
+                params.push((&param.docs, format!("const char *{}", name)));
+            }
+            Type::List(elem) => {
+                params.push((&param.docs, format!("const {} *{}", c_type(elem), name)));
+                params.push((&no_docs, format!("size_t {}_len", name)));
+            }
+            _ => params.push((&param.docs, format!("{} {}", c_type(&param.tref), name))),
+        }
+    }
+
+    let mut ret = "void".to_string();
+    let mut must_use = false;
+    if let Some(result) = func.results.first() {
+        match &**result.tref.type_() {
+            Type::Variant(v) if v.as_expected().is_some() => {
+                let (ok, err) = v.as_expected().unwrap();
+                if let Some(err) = err {
+                    ret = c_type(err);
+                    must_use = true;
+                }
+                let mut ok_types = Vec::new();
+                if let Some(ok) = ok {
+                    match &**ok.type_() {
+                        Type::Record(r) if r.is_tuple() => {
+                            ok_types.extend(r.members.iter().map(|m| c_type(&m.tref)))
+                        }
+                        _ => ok_types.push(c_type(ok)),
+                    }
+                }
+                for (i, ty) in ok_types.into_iter().enumerate() {
+                    params.push((&result.docs, format!("{} *retptr{}", ty, i)));
+                }
+            }
+            _ => ret = c_type(&result.tref),
+        }
+    }
+    if func.noreturn {
+        ret = "_Noreturn void".to_string();
+    }
+
+    src.push_str(&format!("{} __wasi_{}(", ret, func.name.as_str()));
+    if params.is_empty() {
+        src.push_str("void");
+    }
+    for (i, (docs, param)) in params.iter().enumerate() {
+        src.push_str(if i == 0 { "\n" } else { ",\n" });
+        comment(docs, "    ", src);
+        src.push_str("    ");
+        src.push_str(param);
+    }
+    if !params.is_empty() {
+        src.push('\n');
+    }
+    src.push(')');
+    if must_use {
+        src.push_str(" __attribute__((__warn_unused_result__))");
+    }
+    src.push_str(";\n\n");
+}
+
+/// Returns the C type used for values of `tref`.
+fn c_type(tref: &TypeRef) -> String {
+    match tref {
+        TypeRef::Name(nt) => type_name(&nt.name),
+        TypeRef::Value(ty) => match &**ty {
+            Type::Builtin(b) => builtin(*b).to_string(),
+            Type::Pointer(t) => format!("{} *", c_type(t)),
+            Type::ConstPointer(t) => format!("const {} *", c_type(t)),
+            _ => unreachable!("anonymous types are only supported as results"),
+        },
+    }
+}
+
+fn type_name(name: &Id) -> String {
+    format!("__wasi_{}_t", name.as_str())
+}
+
+fn macro_name(ty: &str, name: &str) -> String {
+    format!(
+        "__WASI_{}_{}",
+        ty.to_shouty_snake_case(),
+        name.to_shouty_snake_case()
+    )
+}
+
+fn is_char(tref: &TypeRef) -> bool {
+    matches!(&**tref.type_(), Type::Builtin(BuiltinType::Char))
+}
+
+fn builtin(ty: BuiltinType) -> &'static str {
+    match ty {
+        BuiltinType::U8 { lang_c_char: true } => "char",
+        BuiltinType::U8 { lang_c_char: false } => "uint8_t",
+        BuiltinType::U16 => "uint16_t",
+        BuiltinType::U32 {
+            lang_ptr_size: true,
+        } => "size_t",
+        BuiltinType::U32 {
+            lang_ptr_size: false,
+        }
+        | BuiltinType::Char => "uint32_t",
+        BuiltinType::U64 => "uint64_t",
+        BuiltinType::S8 => "int8_t",
+        BuiltinType::S16 => "int16_t",
+        BuiltinType::S32 => "int32_t",
+        BuiltinType::S64 => "int64_t",
+        BuiltinType::F32 => "float",
+        BuiltinType::F64 => "double",
+    }
+}
+
+fn int_repr(repr: IntRepr) -> &'static str {
+    match repr {
+        IntRepr::U8 => "uint8_t",
+        IntRepr::U16 => "uint16_t",
+        IntRepr::U32 => "uint32_t",
+        IntRepr::U64 => "uint64_t",
+    }
+}
+
+fn int_constant(repr: IntRepr) -> &'static str {
+    match repr {
+        IntRepr::U8 => "UINT8_C",
+        IntRepr::U16 => "UINT16_C",
+        IntRepr::U32 => "UINT32_C",
+        IntRepr::U64 => "UINT64_C",
+    }
+}
+
+/// Renders `docs` as a doc comment indented by `indent`.
+fn comment(docs: &str, indent: &str, dst: &mut String) {
+    if docs.trim().is_empty() {
+        return;
+    }
+    dst.push_str(indent);
+    dst.push_str("/**\n");
+    for line in docs.trim_end().lines() {
+        dst.push_str(indent);
+        dst.push_str(" *");
+        if !line.is_empty() {
+            dst.push(' ');
+            dst.push_str(line);
+        }
+        dst.push('\n');
+    }
+    dst.push_str(indent);
+    dst.push_str(" */\n");
+}
@@ -0,0 +1,946 @@
+//! Generation of host-side Rust bindings, for embedders implementing a witx
+//! document for wasm guests.
+//!
+//! Each witx module becomes a trait with a method per function, taking the
+//! guest's linear memory and interface types, and a Rust module of glue
+//! functions which take the core wasm arguments of the import, lift them,
+//! call the trait method and lower its result back into guest memory.
+
+use crate::{field_name, rustdoc, rustdoc_params, to_rust_ident, Render};
+use heck::*;
+use std::mem;
+use witx::*;
+
+/// Types for accessing guest memory which the generated code relies on.
+const PRELUDE: &str = "
+use core::fmt;
+use core::marker::PhantomData;
+
+/// An error accessing guest memory, which should trap the guest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuestError {
+    /// `len` bytes at `offset` are out of the bounds of guest memory.
+    OutOfBounds { offset: u32, len: u32 },
+    /// `value` isn't a valid value of the witx type `ty`.
+    InvalidValue { ty: &'static str, value: u64 },
+    /// A string isn't valid UTF-8.
+    InvalidUtf8,
+}
+
+impl fmt::Display for GuestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuestError::OutOfBounds { offset, len } => {
+                write!(f, \"{} bytes at {:#x} are out of bounds\", len, offset)
+            }
+            GuestError::InvalidValue { ty, value } => {
+                write!(f, \"invalid `{}` value {}\", ty, value)
+            }
+            GuestError::InvalidUtf8 => write!(f, \"invalid UTF-8 string\"),
+        }
+    }
+}
+
+impl std::error::Error for GuestError {}
+
+fn guest_bytes(mem: &[u8], offset: u32, len: u32) -> Result<&[u8], GuestError> {
+    (offset as usize)
+        .checked_add(len as usize)
+        .and_then(|end| mem.get(offset as usize..end))
+        .ok_or(GuestError::OutOfBounds { offset, len })
+}
+
+fn guest_bytes_mut(mem: &mut [u8], offset: u32, len: u32) -> Result<&mut [u8], GuestError> {
+    (offset as usize)
+        .checked_add(len as usize)
+        .and_then(|end| mem.get_mut(offset as usize..end))
+        .ok_or(GuestError::OutOfBounds { offset, len })
+}
+
+/// A value which can be read from and written to guest memory, with the
+/// witx layout.
+pub trait GuestType: Sized {
+    /// The size of the value in guest memory.
+    const SIZE: u32;
+    /// The alignment of the value in guest memory.
+    const ALIGN: u32;
+
+    /// Reads a value at `offset` in `mem`.
+    fn read(mem: &[u8], offset: u32) -> Result<Self, GuestError>;
+
+    /// Writes the value at `offset` in `mem`.
+    fn write(&self, mem: &mut [u8], offset: u32) -> Result<(), GuestError>;
+}
+
+macro_rules! guest_int {
+    ($($ty:ident)*) => {$(
+        impl GuestType for $ty {
+            const SIZE: u32 = core::mem::size_of::<$ty>() as u32;
+            const ALIGN: u32 = core::mem::size_of::<$ty>() as u32;
+
+            fn read(mem: &[u8], offset: u32) -> Result<Self, GuestError> {
+                let bytes = guest_bytes(mem, offset, Self::SIZE)?;
+                Ok($ty::from_le_bytes(bytes.try_into().unwrap()))
+            }
+
+            fn write(&self, mem: &mut [u8], offset: u32) -> Result<(), GuestError> {
+                guest_bytes_mut(mem, offset, Self::SIZE)?.copy_from_slice(&self.to_le_bytes());
+                Ok(())
+            }
+        }
+    )*};
+}
+
+guest_int!(u8 i8 u16 i16 u32 i32 u64 i64 f32 f64);
+
+impl GuestType for char {
+    const SIZE: u32 = 4;
+    const ALIGN: u32 = 4;
+
+    fn read(mem: &[u8], offset: u32) -> Result<Self, GuestError> {
+        let raw = u32::read(mem, offset)?;
+        char::from_u32(raw).ok_or(GuestError::InvalidValue {
+            ty: \"char\",
+            value: raw.into(),
+        })
+    }
+
+    fn write(&self, mem: &mut [u8], offset: u32) -> Result<(), GuestError> {
+        u32::from(*self).write(mem, offset)
+    }
+}
+
+/// A pointer into guest memory to a `T`.
+pub struct GuestPtr<T> {
+    offset: u32,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> GuestPtr<T> {
+    pub const fn new(offset: u32) -> GuestPtr<T> {
+        GuestPtr {
+            offset,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the address the pointer points to.
+    pub const fn offset(self) -> u32 {
+        self.offset
+    }
+}
+
+impl<T: GuestType> GuestPtr<T> {
+    /// Returns a pointer to the `n`th `T` after this one.
+    pub fn add(self, n: u32) -> Result<GuestPtr<T>, GuestError> {
+        n.checked_mul(T::SIZE)
+            .and_then(|n| self.offset.checked_add(n))
+            .map(GuestPtr::new)
+            .ok_or(GuestError::OutOfBounds {
+                offset: self.offset,
+                len: u32::MAX,
+            })
+    }
+
+    pub fn read(self, mem: &[u8]) -> Result<T, GuestError> {
+        T::read(mem, self.offset)
+    }
+
+    pub fn write(self, mem: &mut [u8], value: &T) -> Result<(), GuestError> {
+        value.write(mem, self.offset)
+    }
+}
+
+impl<T> Clone for GuestPtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GuestPtr<T> {}
+
+impl<T> PartialEq for GuestPtr<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.offset == other.offset
+    }
+}
+
+impl<T> Eq for GuestPtr<T> {}
+
+impl<T> fmt::Debug for GuestPtr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, \"GuestPtr({:#x})\", self.offset)
+    }
+}
+
+impl<T> GuestType for GuestPtr<T> {
+    const SIZE: u32 = 4;
+    const ALIGN: u32 = 4;
+
+    fn read(mem: &[u8], offset: u32) -> Result<Self, GuestError> {
+        u32::read(mem, offset).map(GuestPtr::new)
+    }
+
+    fn write(&self, mem: &mut [u8], offset: u32) -> Result<(), GuestError> {
+        self.offset.write(mem, offset)
+    }
+}
+
+/// A list of `len` `T`s in guest memory.
+pub struct GuestList<T> {
+    ptr: GuestPtr<T>,
+    len: u32,
+}
+
+impl<T> GuestList<T> {
+    pub const fn new(ptr: GuestPtr<T>, len: u32) -> GuestList<T> {
+        GuestList { ptr, len }
+    }
+
+    pub const fn ptr(&self) -> GuestPtr<T> {
+        self.ptr
+    }
+
+    pub const fn len(&self) -> u32 {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<T: GuestType> GuestList<T> {
+    /// Reads the `n`th element of the list.
+    pub fn get(&self, mem: &[u8], n: u32) -> Result<T, GuestError> {
+        if n >= self.len {
+            return Err(GuestError::OutOfBounds {
+                offset: self.ptr.offset,
+                len: n.saturating_add(1).saturating_mul(T::SIZE),
+            });
+        }
+        self.ptr.add(n)?.read(mem)
+    }
+
+    /// Reads the whole list.
+    pub fn to_vec(&self, mem: &[u8]) -> Result<Vec<T>, GuestError> {
+        (0..self.len).map(|n| self.get(mem, n)).collect()
+    }
+}
+
+impl GuestList<u8> {
+    /// Returns the bytes of the list.
+    pub fn as_slice<'a>(&self, mem: &'a [u8]) -> Result<&'a [u8], GuestError> {
+        guest_bytes(mem, self.ptr.offset, self.len)
+    }
+
+    /// Returns the bytes of the list, mutably.
+    pub fn as_mut_slice<'a>(&self, mem: &'a mut [u8]) -> Result<&'a mut [u8], GuestError> {
+        guest_bytes_mut(mem, self.ptr.offset, self.len)
+    }
+}
+
+impl<T> Clone for GuestList<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GuestList<T> {}
+
+impl<T> fmt::Debug for GuestList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(\"GuestList\")
+            .field(\"ptr\", &self.ptr)
+            .field(\"len\", &self.len)
+            .finish()
+    }
+}
+
+impl<T> GuestType for GuestList<T> {
+    const SIZE: u32 = 8;
+    const ALIGN: u32 = 4;
+
+    fn read(mem: &[u8], offset: u32) -> Result<Self, GuestError> {
+        Ok(GuestList::new(
+            GuestType::read(mem, offset)?,
+            GuestType::read(mem, offset + 4)?,
+        ))
+    }
+
+    fn write(&self, mem: &mut [u8], offset: u32) -> Result<(), GuestError> {
+        GuestType::write(&self.ptr, mem, offset)?;
+        self.len.write(mem, offset + 4)
+    }
+}
+
+/// A UTF-8 string of `len` bytes in guest memory.
+#[derive(Debug, Clone, Copy)]
+pub struct GuestStr {
+    bytes: GuestList<u8>,
+}
+
+impl GuestStr {
+    pub const fn new(ptr: GuestPtr<u8>, len: u32) -> GuestStr {
+        GuestStr {
+            bytes: GuestList::new(ptr, len),
+        }
+    }
+
+    /// Returns the bytes of the string, which may not be valid UTF-8.
+    pub const fn bytes(&self) -> GuestList<u8> {
+        self.bytes
+    }
+
+    pub fn as_str<'a>(&self, mem: &'a [u8]) -> Result<&'a str, GuestError> {
+        core::str::from_utf8(self.bytes.as_slice(mem)?).map_err(|_| GuestError::InvalidUtf8)
+    }
+}
+
+impl GuestType for GuestStr {
+    const SIZE: u32 = 8;
+    const ALIGN: u32 = 4;
+
+    fn read(mem: &[u8], offset: u32) -> Result<Self, GuestError> {
+        GuestList::read(mem, offset).map(|bytes| GuestStr { bytes })
+    }
+
+    fn write(&self, mem: &mut [u8], offset: u32) -> Result<(), GuestError> {
+        self.bytes.write(mem, offset)
+    }
+}
+";
+
+pub(crate) fn render(doc: &Document) -> String {
+    let mut src = String::new();
+    src.push_str(PRELUDE);
+    for ty in doc.typenames() {
+        render_typename(&ty, &mut src);
+        src.push('\n');
+    }
+    for c in doc.constants() {
+        let ty = c.ty.as_str().to_camel_case();
+        // Enums and bitflags are newtypes, other integers are aliases.
+        let newtype = doc
+            .typename(&c.ty)
+            .is_some_and(|nt| matches!(&**nt.type_(), Type::Variant(_) | Type::Record(_)));
+        let value = if newtype {
+            format!("{}({})", ty, c.value)
+        } else {
+            c.value.to_string()
+        };
+        rustdoc(&c.docs, &mut src);
+        src.push_str(&format!(
+            "pub const {}_{}: {} = {};\n",
+            c.ty.as_str().to_shouty_snake_case(),
+            c.name.as_str().to_shouty_snake_case(),
+            ty,
+            value,
+        ));
+    }
+    for m in doc.modules() {
+        render_trait(&m, &mut src);
+        render_glue(&m, &mut src);
+    }
+    src
+}
+
+fn render_typename(ty: &NamedType, src: &mut String) {
+    let name = ty.name.as_str();
+    rustdoc(&ty.docs, src);
+    let ty_ = match &ty.tref {
+        TypeRef::Name(_) => return render_alias(name, &ty.tref, src),
+        TypeRef::Value(ty_) => ty_,
+    };
+    match &**ty_ {
+        Type::Record(r) => match r.bitflags_repr() {
+            Some(repr) => render_bitflags(name, r, repr, src),
+            None => render_record(name, r, src),
+        },
+        Type::Variant(v) if v.is_enum() => render_enum(name, v, src),
+        Type::Variant(v) => render_variant(name, v, src),
+        Type::Handle(_) => src.push_str(&format!("pub type {} = u32;\n", name.to_camel_case())),
+        Type::List(_) | Type::Pointer(_) | Type::ConstPointer(_) | Type::Builtin(_) => {
+            render_alias(name, &ty.tref, src)
+        }
+    }
+}
+
+fn render_alias(name: &str, dest: &TypeRef, src: &mut String) {
+    src.push_str(&format!("pub type {} = ", name.to_camel_case()));
+    match dest {
+        TypeRef::Name(nt) => src.push_str(&nt.name.as_str().to_camel_case()),
+        TypeRef::Value(_) => host_type(dest, src),
+    }
+    src.push_str(";\n");
+}
+
+fn render_record(name: &str, r: &RecordDatatype, src: &mut String) {
+    let camel_name = name.to_camel_case();
+    src.push_str("#[derive(Copy, Clone, Debug)]\n");
+    src.push_str(&format!("pub struct {} {{\n", camel_name));
+    for member in r.members.iter() {
+        rustdoc(&member.docs, src);
+        src.push_str(&format!("pub {}: ", field_name(&member.name)));
+        host_type(&member.tref, src);
+        src.push_str(",\n");
+    }
+    src.push_str("}\n");
+
+    let mut read = String::new();
+    let mut write = String::new();
+    for member in r.member_layout() {
+        let field = field_name(&member.member.name);
+        read.push_str(&format!(
+            "{}: GuestType::read(mem, offset + {})?,\n",
+            field, member.offset
+        ));
+        write.push_str(&format!(
+            "GuestType::write(&self.{}, mem, offset + {})?;\n",
+            field, member.offset
+        ));
+    }
+    write.push_str("Ok(())");
+    render_guest_type(
+        &camel_name,
+        r.mem_size_align(),
+        &format!("Ok({} {{ {} }})", camel_name, read),
+        &write,
+        src,
+    );
+}
+
+fn render_bitflags(name: &str, r: &RecordDatatype, repr: IntRepr, src: &mut String) {
+    let camel_name = name.to_camel_case();
+    let mut repr_name = String::new();
+    repr.render(&mut repr_name);
+
+    src.push_str("#[repr(transparent)]\n");
+    src.push_str("#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]\n");
+    src.push_str(&format!("pub struct {}({});\n", camel_name, repr_name));
+    // The constants are named as in the guest-side bindings.
+    for (i, member) in r.members.iter().enumerate() {
+        rustdoc(&member.docs, src);
+        src.push_str(&format!(
+            "pub const {}_{}: {} = {}(1 << {});\n",
+            name.to_shouty_snake_case(),
+            member.name.as_str().to_shouty_snake_case(),
+            camel_name,
+            camel_name,
+            i
+        ));
+    }
+    src.push_str(&format!("impl {} {{\n", camel_name));
+    let all = if r.members.len() == 64 {
+        u64::MAX
+    } else {
+        (1u64 << r.members.len()) - 1
+    };
+    src.push_str(&format!(
+        "
+        /// Every flag.
+        pub const ALL: {ty} = {ty}({all});
+
+        pub const fn from_raw(raw: {repr}) -> {ty} {{ {ty}(raw) }}
+
+        pub const fn bits(&self) -> {repr} {{ self.0 }}
+
+        /// Returns whether all flags of `other` are set.
+        pub const fn contains(&self, other: {ty}) -> bool {{
+            self.0 & other.0 == other.0
+        }}
+        }}
+
+        impl core::ops::BitOr for {ty} {{
+            type Output = {ty};
+            fn bitor(self, other: {ty}) -> {ty} {{ {ty}(self.0 | other.0) }}
+        }}
+
+        impl TryFrom<i64> for {ty} {{
+            type Error = GuestError;
+            fn try_from(raw: i64) -> Result<{ty}, GuestError> {{
+                if raw as u64 & !{all} == 0 {{
+                    Ok({ty}(raw as {repr}))
+                }} else {{
+                    Err(GuestError::InvalidValue {{ ty: \"{name}\", value: raw as u64 }})
+                }}
+            }}
+        }}
+        ",
+        ty = camel_name,
+        repr = repr_name,
+        all = all,
+        name = name,
+    ));
+    render_guest_type(
+        &camel_name,
+        r.mem_size_align(),
+        &format!(
+            "{}::try_from({}::read(mem, offset)? as i64)",
+            camel_name, repr_name
+        ),
+        "self.0.write(mem, offset)",
+        src,
+    );
+}
+
+fn render_enum(name: &str, v: &Variant, src: &mut String) {
+    let camel_name = name.to_camel_case();
+    let mut repr_name = String::new();
+    v.tag_repr.render(&mut repr_name);
+
+    src.push_str("#[repr(transparent)]\n");
+    src.push_str("#[derive(Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]\n");
+    src.push_str(&format!("pub struct {}({});\n", camel_name, repr_name));
+    for (i, case) in v.cases.iter().enumerate() {
+        rustdoc(&case.docs, src);
+        src.push_str(&format!(
+            "pub const {}_{}: {} = {}({});\n",
+            name.to_shouty_snake_case(),
+            case.name.as_str().to_shouty_snake_case(),
+            camel_name,
+            camel_name,
+            i
+        ));
+    }
+    src.push_str(&format!("impl {} {{\n", camel_name));
+    src.push_str(&format!(
+        "pub const fn raw(&self) -> {} {{ self.0 }}\n",
+        repr_name
+    ));
+    src.push_str("pub fn name(&self) -> &'static str {\n");
+    src.push_str("match self.0 {");
+    for (i, case) in v.cases.iter().enumerate() {
+        src.push_str(&format!(
+            "{} => \"{}\",",
+            i,
+            case.name.as_str().to_shouty_snake_case()
+        ));
+    }
+    src.push_str("_ => unreachable!(),");
+    src.push_str("}\n");
+    src.push_str("}\n");
+    src.push_str("}\n");
+
+    src.push_str(&format!(
+        "
+        impl fmt::Debug for {ty} {{
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {{
+                f.write_str(self.name())
+            }}
+        }}
+
+        impl TryFrom<i64> for {ty} {{
+            type Error = GuestError;
+            fn try_from(raw: i64) -> Result<{ty}, GuestError> {{
+                if (raw as u64) < {n} {{
+                    Ok({ty}(raw as {repr}))
+                }} else {{
+                    Err(GuestError::InvalidValue {{ ty: \"{name}\", value: raw as u64 }})
+                }}
+            }}
+        }}
+        ",
+        ty = camel_name,
+        repr = repr_name,
+        n = v.cases.len(),
+        name = name,
+    ));
+    render_guest_type(
+        &camel_name,
+        v.mem_size_align(),
+        &format!(
+            "{}::try_from({}::read(mem, offset)? as i64)",
+            camel_name, repr_name
+        ),
+        "self.0.write(mem, offset)",
+        src,
+    );
+}
+
+fn render_variant(name: &str, v: &Variant, src: &mut String) {
+    let camel_name = name.to_camel_case();
+    let mut repr_name = String::new();
+    v.tag_repr.render(&mut repr_name);
+
+    src.push_str("#[derive(Copy, Clone, Debug)]\n");
+    src.push_str(&format!("pub enum {} {{\n", camel_name));
+    for case in v.cases.iter() {
+        rustdoc(&case.docs, src);
+        src.push_str(&case.name.as_str().to_camel_case());
+        if let Some(tref) = &case.tref {
+            src.push('(');
+            host_type(tref, src);
+            src.push(')');
+        }
+        src.push_str(",\n");
+    }
+    src.push_str("}\n");
+
+    let payload_offset = v.payload_offset();
+    let mut read = format!("match {}::read(mem, offset)? {{\n", repr_name);
+    let mut write = "match self {\n".to_string();
+    for (i, case) in v.cases.iter().enumerate() {
+        let case_name = case.name.as_str().to_camel_case();
+        match &case.tref {
+            Some(_) => {
+                read.push_str(&format!(
+                    "{} => Ok({}::{}(GuestType::read(mem, offset + {})?)),\n",
+                    i, camel_name, case_name, payload_offset
+                ));
+                write.push_str(&format!(
+                    "{}::{}(payload) => {{ ({} as {}).write(mem, offset)?; GuestType::write(payload, mem, offset + {}) }}\n",
+                    camel_name, case_name, i, repr_name, payload_offset
+                ));
+            }
+            None => {
+                read.push_str(&format!("{} => Ok({}::{}),\n", i, camel_name, case_name));
+                write.push_str(&format!(
+                    "{}::{} => ({} as {}).write(mem, offset),\n",
+                    camel_name, case_name, i, repr_name
+                ));
+            }
+        }
+    }
+    read.push_str(&format!(
+        "tag => Err(GuestError::InvalidValue {{ ty: \"{}\", value: tag.into() }}),\n",
+        name
+    ));
+    read.push('}');
+    write.push('}');
+    render_guest_type(&camel_name, v.mem_size_align(), &read, &write, src);
+}
+
+fn render_guest_type(name: &str, size_align: SizeAlign, read: &str, write: &str, src: &mut String) {
+    src.push_str(&format!(
+        "
+        impl GuestType for {} {{
+            const SIZE: u32 = {};
+            const ALIGN: u32 = {};
+
+            fn read(mem: &[u8], offset: u32) -> Result<Self, GuestError> {{
+                {}
+            }}
+
+            fn write(&self, mem: &mut [u8], offset: u32) -> Result<(), GuestError> {{
+                {}
+            }}
+        }}
+        ",
+        name, size_align.size, size_align.align, read, write
+    ));
+}
+
+/// Renders the host-side type of values of `tref`, in which guest pointers
+/// and sizes are 32-bit offsets rather than host pointers.
+fn host_type(tref: &TypeRef, src: &mut String) {
+    let ty = match tref {
+        TypeRef::Name(nt) => return src.push_str(&nt.name.as_str().to_camel_case()),
+        TypeRef::Value(ty) => ty,
+    };
+    match &**ty {
+        Type::Builtin(BuiltinType::U32 {
+            lang_ptr_size: true,
+        }) => src.push_str("u32"),
+        Type::Builtin(b) => b.render(src),
+        Type::List(t) if matches!(&**t.type_(), Type::Builtin(BuiltinType::Char)) => {
+            src.push_str("GuestStr")
+        }
+        Type::List(t) => {
+            src.push_str("GuestList<");
+            host_type(t, src);
+            src.push('>');
+        }
+        Type::Pointer(t) | Type::ConstPointer(t) => {
+            src.push_str("GuestPtr<");
+            host_type(t, src);
+            src.push('>');
+        }
+        Type::Variant(v) => match v.as_expected() {
+            Some((ok, err)) => {
+                src.push_str("Result<");
+                match ok {
+                    Some(ty) => host_type(ty, src),
+                    None => src.push_str("()"),
+                }
+                src.push_str(", ");
+                match err {
+                    Some(ty) => host_type(ty, src),
+                    None => src.push_str("()"),
+                }
+                src.push('>');
+            }
+            None => panic!("unsupported anonymous variant"),
+        },
+        Type::Record(r) if r.is_tuple() => {
+            src.push('(');
+            for member in r.members.iter() {
+                host_type(&member.tref, src);
+                src.push_str(", ");
+            }
+            src.push(')');
+        }
+        t => panic!("reference to anonymous {} not possible!", t.kind()),
+    }
+}
+
+fn render_trait(module: &Module, src: &mut String) {
+    rustdoc(&module.docs, src);
+    if !module.docs.trim().is_empty() {
+        src.push_str("///\n");
+    }
+    src.push_str(&format!(
+        "/// The functions of the `{}` module, called by the glue in\n/// [`{}`].\n",
+        module.name.as_str(),
+        module.name.as_str().to_snake_case()
+    ));
+    src.push_str(&format!(
+        "pub trait {} {{\n",
+        module.name.as_str().to_camel_case()
+    ));
+    for func in module.funcs() {
+        rustdoc(&func.docs, src);
+        rustdoc_params(&func.params, "Parameters", src);
+        rustdoc_params(&func.results, "Return", src);
+        src.push_str(&format!(
+            "fn {}(&mut self, mem: &mut [u8], ",
+            to_rust_ident(&func.name.as_str().to_snake_case())
+        ));
+        for param in func.params.iter() {
+            param.name.render(src);
+            src.push_str(": ");
+            host_type(&param.tref, src);
+            src.push_str(", ");
+        }
+        src.push(')');
+        if let Some(result) = func.results.first() {
+            src.push_str(" -> ");
+            host_type(&result.tref, src);
+        }
+        src.push_str(";\n");
+    }
+    src.push_str("}\n\n");
+}
+
+fn render_glue(module: &Module, src: &mut String) {
+    let trait_name = module.name.as_str().to_camel_case();
+    src.push_str(&format!(
+        "/// Glue implementing the `{}` imports with a [`{}`].\n",
+        module.name.as_str(),
+        trait_name
+    ));
+    src.push_str(&format!(
+        "pub mod {} {{\n",
+        module.name.as_str().to_snake_case()
+    ));
+    src.push_str("use super::*;\n\n");
+    for func in module.funcs() {
+        let name = to_rust_ident(&func.name.as_str().to_snake_case()).to_string();
+        let (params, results) = func.wasm_signature();
+        assert!(results.len() <= 1);
+        src.push_str(&format!(
+            "/// Calls [`{}::{}`] with the arguments of the core wasm import.\n",
+            trait_name, name
+        ));
+        src.push_str(&format!(
+            "pub fn {}<H: {} + ?Sized>(host: &mut H, mem: &mut [u8], ",
+            name, trait_name
+        ));
+        for (i, param) in params.iter().enumerate() {
+            src.push_str(&format!("arg{}: ", i));
+            param.render(src);
+            src.push_str(", ");
+        }
+        src.push_str(") -> Result<");
+        match results.first() {
+            Some(result) => result.render(src),
+            None => src.push_str("()"),
+        }
+        src.push_str(", GuestError> {\n");
+        func.call_interface(
+            &module.name,
+            &mut Host {
+                src,
+                block_storage: Vec::new(),
+                blocks: Vec::new(),
+            },
+        );
+        src.push_str("}\n\n");
+    }
+    src.push_str("}\n\n");
+}
+
+/// Generates the body of a glue function.
+struct Host<'a> {
+    src: &'a mut String,
+    block_storage: Vec<String>,
+    blocks: Vec<String>,
+}
+
+impl Bindgen for Host<'_> {
+    type Operand = String;
+
+    fn push_block(&mut self) {
+        let prev = mem::take(self.src);
+        self.block_storage.push(prev);
+    }
+
+    fn finish_block(&mut self, operand: Option<String>) {
+        let to_restore = self.block_storage.pop().unwrap();
+        let src = mem::replace(self.src, to_restore);
+        match operand {
+            None => self.blocks.push(src),
+            Some(s) => self.blocks.push(format!("{} {}", src, s)),
+        }
+    }
+
+    fn allocate_space(&mut self, _: usize, _: &NamedType) {
+        unreachable!("return pointers are only used to call wasm")
+    }
+
+    fn emit(
+        &mut self,
+        inst: &Instruction<'_>,
+        operands: &mut Vec<String>,
+        results: &mut Vec<String>,
+    ) {
+        let mut top_as = |cvt: &str| {
+            let s = operands.pop().unwrap();
+            results.push(format!("{} as {}", s, cvt));
+        };
+
+        match inst {
+            Instruction::GetArg { nth } => results.push(format!("arg{}", nth)),
+
+            // Lifting the core wasm arguments.
+            Instruction::S8FromI32 => top_as("i8"),
+            Instruction::Char8FromI32 | Instruction::U8FromI32 => top_as("u8"),
+            Instruction::S16FromI32 => top_as("i16"),
+            Instruction::U16FromI32 => top_as("u16"),
+            Instruction::U32FromI32
+            | Instruction::UsizeFromI32
+            | Instruction::HandleFromI32 { .. } => top_as("u32"),
+            Instruction::U64FromI64 => top_as("u64"),
+            Instruction::S32FromI32
+            | Instruction::S64FromI64
+            | Instruction::If32FromF32
+            | Instruction::If64FromF64 => results.push(operands.pop().unwrap()),
+            Instruction::CharFromI32 => {
+                let s = operands.pop().unwrap();
+                results.push(format!(
+                    "char::from_u32({s} as u32).ok_or(GuestError::InvalidValue {{ ty: \"char\", value: {s} as u64 }})?",
+                    s = s
+                ));
+            }
+            Instruction::PointerFromI32 { .. } | Instruction::ConstPointerFromI32 { .. } => {
+                results.push(format!("GuestPtr::new({} as u32)", operands[0]));
+            }
+            Instruction::ListFromPointerLength { ty } => {
+                let list = match &**ty.type_() {
+                    Type::Builtin(BuiltinType::Char) => "GuestStr",
+                    _ => "GuestList",
+                };
+                results.push(format!(
+                    "{}::new(GuestPtr::new({} as u32), {} as u32)",
+                    list, operands[0], operands[1]
+                ));
+            }
+            Instruction::EnumLift { ty }
+            | Instruction::BitflagsFromI32 { ty }
+            | Instruction::BitflagsFromI64 { ty } => {
+                results.push(format!(
+                    "{}::try_from({} as i64)?",
+                    ty.name.as_str().to_camel_case(),
+                    operands[0]
+                ));
+            }
+            Instruction::Load { ty } => {
+                results.push(format!(
+                    "<{} as GuestType>::read(mem, {} as u32)?",
+                    ty.name.as_str().to_camel_case(),
+                    operands[0]
+                ));
+            }
+
+            Instruction::CallInterface { func, .. } => {
+                if !func.results.is_empty() {
+                    self.src.push_str("let ret = ");
+                    results.push("ret".to_string());
+                }
+                self.src.push_str(&format!(
+                    "host.{}(mem, {});\n",
+                    to_rust_ident(&func.name.as_str().to_snake_case()),
+                    operands.join(", ")
+                ));
+            }
+
+            // Lowering the result.
+            Instruction::VariantPayload => results.push("e".to_string()),
+            Instruction::TupleLower { amt } => {
+                let names = (0..*amt).map(|i| format!("e{}", i)).collect::<Vec<_>>();
+                self.src
+                    .push_str(&format!("let ({}) = {};\n", names.join(", "), operands[0]));
+                results.extend(names);
+            }
+            Instruction::Store { .. } => {
+                self.src.push_str(&format!(
+                    "GuestType::write(&{}, mem, {} as u32)?;\n",
+                    operands[0], operands[1]
+                ));
+            }
+            Instruction::ResultLower { ok, err } => {
+                let err_block = self.blocks.pop().unwrap();
+                let ok_block = self.blocks.pop().unwrap();
+                let ok_pat = if ok.is_some() { "e" } else { "()" };
+                let err_pat = if err.is_some() { "e" } else { "()" };
+                let err_expr = if err.is_some() {
+                    err_block
+                } else {
+                    "1".to_string()
+                };
+                results.push(format!(
+                    "match {} {{ Ok({}) => {{ {} 0 }} Err({}) => {} }}",
+                    operands[0], ok_pat, ok_block, err_pat, err_expr
+                ));
+            }
+            Instruction::EnumLower { .. } => results.push(format!("{}.raw() as i32", operands[0])),
+            Instruction::I32FromBitflags { .. } => {
+                results.push(format!("{}.bits() as i32", operands[0]))
+            }
+            Instruction::I64FromBitflags { .. } => {
+                results.push(format!("{}.bits() as i64", operands[0]))
+            }
+            Instruction::I32FromPointer | Instruction::I32FromConstPointer => {
+                results.push(format!("{}.offset() as i32", operands[0]))
+            }
+            Instruction::I64FromU64 | Instruction::I64FromS64 => top_as("i64"),
+            Instruction::I32FromHandle { .. }
+            | Instruction::I32FromUsize
+            | Instruction::I32FromChar
+            | Instruction::I32FromU8
+            | Instruction::I32FromS8
+            | Instruction::I32FromChar8
+            | Instruction::I32FromU16
+            | Instruction::I32FromS16
+            | Instruction::I32FromU32
+            | Instruction::I32FromS32 => top_as("i32"),
+            Instruction::F32FromIf32 | Instruction::F64FromIf64 => {
+                results.push(operands.pop().unwrap())
+            }
+
+            Instruction::Return { amt: 0 } => self.src.push_str("Ok(())\n"),
+            Instruction::Return { .. } => {
+                self.src.push_str(&format!("Ok({})\n", operands[0]));
+            }
+
+            Instruction::AddrOf | Instruction::ListPointerLength => {
+                panic!("only errors and out-params can be returned to the guest")
+            }
+            Instruction::CallWasm { .. }
+            | Instruction::ReturnPointerGet { .. }
+            | Instruction::ReuseReturn
+            | Instruction::TupleLift { .. }
+            | Instruction::ResultLift => unreachable!("only used to call wasm"),
+        }
+    }
+}
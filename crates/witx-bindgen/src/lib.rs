@@ -1,20 +1,150 @@
 use heck::*;
-use std::io::{Read, Write};
+use std::fmt;
+use std::io::Write;
 use std::mem;
 use std::path::Path;
 use std::process::{Command, Stdio};
 use witx::*;
 
-pub fn generate<P: AsRef<Path>>(witx_paths: &[P]) -> String {
-    let doc = witx::load(witx_paths).unwrap();
+mod c_header;
+mod host;
+pub mod model;
 
-    let mut raw = String::new();
-    raw.push_str(
-        "\
+/// The notice placed at the top of generated source, kept out of formatting
+/// as `prettyplease` doesn't preserve comments.
+const HEADER: &str = "\
 // This file is automatically generated, DO NOT EDIT
 //
 // To regenerate this file run the `crates/witx-bindgen` command
 
+";
+
+/// Options for [`generate_with`].
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// How to format the generated source.
+    pub formatter: Formatter,
+    /// Where to put the functions of each witx module.
+    pub module_layout: ModuleLayout,
+    /// The name of a trait to generate with a method per function, which
+    /// the wrapper functions call instead of the raw imports when built with
+    /// the `native-host` feature for a non-wasm target.
+    ///
+    /// The generated code gets the implementation to call from
+    /// `crate::host::current()`, which the crate must provide.
+    pub native_host: Option<String>,
+    /// The path of a Rust module with a function per witx function, with the
+    /// same signatures as the generated wrappers, to export to the host under
+    /// the witx names.
+    ///
+    /// The exports are generated in an `exports` module next to the
+    /// wrappers, for guests which implement an interface themselves, such as
+    /// adapters between WASI versions. Strings which aren't UTF-8 and
+    /// invalid `char`s are rejected with the `ilseq` and `inval` errors of
+    /// the function before calling it.
+    pub exports: Option<String>,
+}
+
+/// How [`generate_with`] formats the generated source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Formatter {
+    /// Format in-process with `prettyplease`.
+    #[default]
+    Prettyplease,
+    /// Pipe the source through an external `rustfmt`, which must be on
+    /// `PATH`.
+    Rustfmt,
+}
+
+/// The configuration in which wrappers call [`Options::native_host`] instead
+/// of the raw imports.
+const NATIVE_HOST_CFG: &str = "all(feature = \"native-host\", not(target_family = \"wasm\"))";
+
+/// Where [`generate_with`] puts the functions of each witx module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ModuleLayout {
+    /// Put the wrapper functions at the top level, and the raw imports in a
+    /// submodule named after the witx module.
+    ///
+    /// This only works for documents whose modules don't share any function
+    /// names, such as the WASI snapshots.
+    #[default]
+    Flat,
+    /// Put the wrapper functions in a submodule named after the witx module,
+    /// and the raw imports in a `raw` submodule of that.
+    ///
+    /// This supports documents with several modules, such as
+    /// `wasi-ephemeral`. Types are shared between modules and stay at the top
+    /// level.
+    Submodules,
+}
+
+/// An error generating bindings.
+#[derive(Debug)]
+pub enum Error {
+    /// The witx documents couldn't be loaded.
+    Witx(WitxError),
+    /// The generated source didn't parse as Rust, which is a bug in this
+    /// crate.
+    Parse(syn::Error),
+    /// The generated source couldn't be formatted with `prettyplease`, with
+    /// the given reason, which is a bug in this crate.
+    Prettyplease(String),
+    /// `rustfmt` couldn't be run.
+    Rustfmt(std::io::Error),
+    /// `rustfmt` exited unsuccessfully, with the given stderr.
+    RustfmtFailed(std::process::ExitStatus, String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Witx(e) => write!(f, "failed to load witx: {}", e.report()),
+            Error::Parse(e) => write!(f, "generated source failed to parse: {}", e),
+            Error::Prettyplease(reason) => {
+                write!(f, "failed to format generated source: {}", reason)
+            }
+            Error::Rustfmt(e) => write!(f, "failed to run rustfmt: {}", e),
+            Error::RustfmtFailed(status, stderr) => {
+                write!(f, "rustfmt failed with {}:\n{}", status, stderr)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Witx(e) => Some(e),
+            Error::Parse(e) => Some(e),
+            Error::Rustfmt(e) => Some(e),
+            Error::Prettyplease(_) | Error::RustfmtFailed(..) => None,
+        }
+    }
+}
+
+impl From<WitxError> for Error {
+    fn from(e: WitxError) -> Error {
+        Error::Witx(e)
+    }
+}
+
+/// Generates Rust bindings for the witx documents with the default
+/// [`Options`].
+pub fn generate<P: AsRef<Path>>(witx_paths: &[P]) -> Result<String, Error> {
+    generate_with(witx_paths, &Options::default())
+}
+
+/// Generates Rust bindings for the witx documents.
+pub fn generate_with<P: AsRef<Path>>(witx_paths: &[P], options: &Options) -> Result<String, Error> {
+    let doc = witx::load(witx_paths)?;
+
+    let mut raw = String::new();
+    if options.native_host.is_some() {
+        raw.push_str(&format!("#[cfg(not({}))]\n", NATIVE_HOST_CFG));
+    }
+    raw.push_str(
+        "\
 use core::mem::MaybeUninit;
 use core::fmt;
 ",
@@ -23,10 +153,18 @@ use core::fmt;
         ty.render(&mut raw);
         raw.push_str("\n");
     }
-    for m in doc.modules() {
-        m.render(&mut raw);
+    let modules = doc.modules().collect::<Vec<_>>();
+    for m in modules.iter() {
+        render_module(m, options, &mut raw);
         raw.push_str("\n");
     }
+    if let (Some(path), ModuleLayout::Flat) = (&options.exports, options.module_layout) {
+        let modules = modules.iter().map(|m| &**m).collect::<Vec<_>>();
+        render_exports(&modules, path, &mut raw);
+    }
+    if let Some(name) = &options.native_host {
+        render_host_trait(&doc, name, options.module_layout, &mut raw);
+    }
     for c in doc.constants() {
         rustdoc(&c.docs, &mut raw);
         raw.push_str(&format!(
@@ -38,27 +176,114 @@ use core::fmt;
         ));
     }
 
+    format(&raw, options.formatter)
+}
+
+/// Generates a C header declaring the types and functions of the witx
+/// documents, in the style of wasi-libc's `wasi/api.h`.
+pub fn generate_c_header<P: AsRef<Path>>(witx_paths: &[P]) -> Result<String, Error> {
+    let doc = witx::load(witx_paths)?;
+    Ok(c_header::render(&doc))
+}
+
+/// Generates host-side Rust bindings for the witx documents, for embedders
+/// implementing them.
+///
+/// Each witx module becomes a trait with a method per function, along with a
+/// module of glue functions which take the core wasm arguments of an import
+/// and the guest's memory, and call the trait method. The generated source
+/// only depends on `std`.
+pub fn generate_host<P: AsRef<Path>>(
+    witx_paths: &[P],
+    formatter: Formatter,
+) -> Result<String, Error> {
+    let doc = witx::load(witx_paths)?;
+    format(&host::render(&doc), formatter)
+}
+
+fn format(raw: &str, formatter: Formatter) -> Result<String, Error> {
+    let formatted = match formatter {
+        Formatter::Prettyplease => prettyplease(raw)?,
+        Formatter::Rustfmt => rustfmt(raw)?,
+    };
+    Ok(format!("{}{}", HEADER, formatted))
+}
+
+/// Formats `src` with `prettyplease`.
+///
+/// `prettyplease` prints macro bodies as flat token streams, so the bodies of
+/// `bitflags!` invocations, which are indented by hand, are swapped out for
+/// placeholders and restored afterwards.
+fn prettyplease(src: &str) -> Result<String, Error> {
+    const MACRO: &str = "bitflags::bitflags! {\n";
+    let mut stripped = String::new();
+    let mut bodies = Vec::new();
+    let mut rest = src;
+    while let Some(start) = rest.find(MACRO) {
+        let body_start = start + MACRO.len();
+        // The closing brace of the invocation is the first one in column 0.
+        let len = rest[body_start..].find("\n}\n").ok_or_else(|| {
+            Error::Prettyplease("unterminated `bitflags!` invocation".to_string())
+        })?;
+        let end = body_start + len + 1;
+        stripped.push_str(&rest[..body_start]);
+        stripped.push_str(&format!("__witx_bindgen_macro_body_{}\n", bodies.len()));
+        bodies.push(&rest[body_start..end]);
+        rest = &rest[end..];
+    }
+    stripped.push_str(rest);
+
+    let file = syn::parse_file(&stripped).map_err(Error::Parse)?;
+    let mut ret = prettyplease::unparse(&file);
+    for (i, body) in bodies.iter().enumerate() {
+        let placeholder = format!("    __witx_bindgen_macro_body_{}\n", i);
+        if !ret.contains(&placeholder) {
+            return Err(Error::Prettyplease(format!(
+                "`bitflags!` invocation {} was printed unexpectedly",
+                i
+            )));
+        }
+        ret = ret.replacen(&placeholder, body, 1);
+    }
+    Ok(ret)
+}
+
+fn rustfmt(src: &str) -> Result<String, Error> {
     let mut rustfmt = Command::new("rustfmt")
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
         .spawn()
-        .unwrap();
-    rustfmt
-        .stdin
-        .take()
-        .unwrap()
-        .write_all(raw.as_bytes())
-        .unwrap();
-    let mut ret = String::new();
-    rustfmt
-        .stdout
-        .take()
-        .unwrap()
-        .read_to_string(&mut ret)
-        .unwrap();
-    let status = rustfmt.wait().unwrap();
-    assert!(status.success());
-    return ret;
+        .map_err(Error::Rustfmt)?;
+    // Write from another thread so that a full stdout pipe can't deadlock
+    // against a full stdin pipe.
+    let mut stdin = rustfmt.stdin.take().unwrap();
+    let src = src.to_string();
+    let writer = std::thread::spawn(move || stdin.write_all(src.as_bytes()));
+    let output = rustfmt.wait_with_output().map_err(Error::Rustfmt)?;
+    writer.join().unwrap().map_err(Error::Rustfmt)?;
+    if !output.status.success() {
+        return Err(Error::RustfmtFailed(
+            output.status,
+            String::from_utf8_lossy(&output.stderr).into_owned(),
+        ));
+    }
+    String::from_utf8(output.stdout)
+        .map_err(|e| Error::Rustfmt(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
+}
+
+/// Returns a description of every type, module and constant in the witx
+/// documents, including layouts and core wasm signatures.
+pub fn generate_model<P: AsRef<Path>>(witx_paths: &[P]) -> Result<model::Api, Error> {
+    let doc = witx::load(witx_paths)?;
+    Ok(model::Api::new(&doc))
+}
+
+/// Returns the output of [`generate_model`] as pretty-printed JSON.
+pub fn generate_json<P: AsRef<Path>>(witx_paths: &[P]) -> Result<String, Error> {
+    let mut ret = serde_json::to_string_pretty(&generate_model(witx_paths)?).unwrap();
+    ret.push('\n');
+    Ok(ret)
 }
 
 trait Render {
@@ -85,20 +310,7 @@ impl Render for NamedType {
 
 fn render_record(src: &mut String, name: &str, s: &RecordDatatype) {
     if let Some(repr) = s.bitflags_repr() {
-        src.push_str(&format!("pub type {} = ", name.to_camel_case()));
-        repr.render(src);
-        src.push(';');
-        for (i, member) in s.members.iter().enumerate() {
-            rustdoc(&member.docs, src);
-            src.push_str(&format!(
-                "pub const {}_{}: {} = 1 << {};\n",
-                name.to_shouty_snake_case(),
-                member.name.as_str().to_shouty_snake_case(),
-                name.to_camel_case(),
-                i,
-            ));
-        }
-        return;
+        return render_bitflags(src, name, s, repr);
     }
     src.push_str("#[repr(C)]\n");
     if record_contains_union(s) {
@@ -116,7 +328,83 @@ fn render_record(src: &mut String, name: &str, s: &RecordDatatype) {
         member.tref.render(src);
         src.push_str(",\n");
     }
-    src.push_str("}");
+    src.push_str("}\n");
+
+    let fields = s
+        .member_layout()
+        .iter()
+        .map(|m| (field_name(&m.member.name), m.offset))
+        .collect::<Vec<_>>();
+    let pointer_sized = s.members.iter().any(|m| is_pointer_sized(&m.tref));
+    render_layout_assertions(
+        src,
+        &name.to_camel_case(),
+        s.mem_size_align(),
+        &fields,
+        pointer_sized,
+    );
+}
+
+// Bitflags are rendered as a plain integer alias by default, or as a
+// `bitflags!` newtype when the generated crate's `bitflags` feature is
+// enabled. The loose `NAME_FLAG` constants are emitted in both cases so code
+// which only uses those constants and `|` works either way.
+fn render_bitflags(src: &mut String, name: &str, s: &RecordDatatype, repr: IntRepr) {
+    let camel_name = name.to_camel_case();
+
+    src.push_str("#[cfg(not(feature = \"bitflags\"))]\n");
+    src.push_str(&format!("pub type {} = ", camel_name));
+    repr.render(src);
+    src.push_str(";\n");
+
+    // Formatters leave macro invocations alone, so indent the body by hand.
+    let mut flags = String::new();
+    for (i, member) in s.members.iter().enumerate() {
+        rustdoc(&member.docs, &mut flags);
+        flags.push_str(&format!(
+            "const {} = 1 << {};\n",
+            member.name.as_str().to_shouty_snake_case(),
+            i,
+        ));
+    }
+    src.push_str("#[cfg(feature = \"bitflags\")]\n");
+    src.push_str("bitflags::bitflags! {\n");
+    src.push_str("    #[repr(transparent)]\n");
+    src.push_str(
+        "    #[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]\n",
+    );
+    src.push_str(&format!("    pub struct {}: ", camel_name));
+    repr.render(src);
+    src.push_str(" {\n");
+    for line in flags.lines() {
+        src.push_str("        ");
+        src.push_str(line);
+        src.push('\n');
+    }
+    src.push_str("    }\n");
+    src.push_str("}\n");
+
+    for (i, member) in s.members.iter().enumerate() {
+        let flag = member.name.as_str().to_shouty_snake_case();
+        rustdoc(&member.docs, src);
+        src.push_str("#[cfg(not(feature = \"bitflags\"))]\n");
+        src.push_str(&format!(
+            "pub const {}_{}: {} = 1 << {};\n",
+            name.to_shouty_snake_case(),
+            flag,
+            camel_name,
+            i,
+        ));
+        rustdoc(&member.docs, src);
+        src.push_str("#[cfg(feature = \"bitflags\")]\n");
+        src.push_str(&format!(
+            "pub const {}_{}: {ty} = {ty}::{};\n",
+            name.to_shouty_snake_case(),
+            flag,
+            flag,
+            ty = camel_name,
+        ));
+    }
 }
 
 fn render_variant(src: &mut String, name: &str, v: &Variant) {
@@ -145,6 +433,106 @@ fn render_variant(src: &mut String, name: &str, v: &Variant) {
     src.push_str(",\n");
     src.push_str(&format!("pub u: {}U,\n", name.to_camel_case()));
     src.push_str("}\n");
+
+    let payloads = v
+        .cases
+        .iter()
+        .filter_map(|c| c.tref.as_ref().map(|t| (c, t)));
+    let mut union_size_align = SizeAlign { size: 0, align: 1 };
+    let mut union_fields = Vec::new();
+    for (case, tref) in payloads.clone() {
+        let size_align = tref.mem_size_align();
+        union_size_align.size = union_size_align.size.max(size_align.size);
+        union_size_align.align = union_size_align.align.max(size_align.align);
+        union_fields.push((field_name(&case.name), 0));
+    }
+    union_size_align.size = align_to(union_size_align.size, union_size_align.align);
+    let pointer_sized = payloads.clone().any(|(_, tref)| is_pointer_sized(tref));
+    render_layout_assertions(
+        src,
+        &format!("{}U", name.to_camel_case()),
+        union_size_align,
+        &union_fields,
+        pointer_sized,
+    );
+    render_layout_assertions(
+        src,
+        &name.to_camel_case(),
+        v.mem_size_align(),
+        &[
+            ("tag".to_string(), 0),
+            ("u".to_string(), v.payload_offset()),
+        ],
+        pointer_sized,
+    );
+}
+
+/// Renders compile-time checks that the Rust type `name` has the layout witx
+/// expects, where `fields` are the names and offsets of its fields.
+///
+/// witx layouts are for wasm32, so if `pointer_sized` is set, because the type
+/// contains pointers or `usize`s, the checks only apply to 32-bit targets.
+fn render_layout_assertions(
+    src: &mut String,
+    name: &str,
+    size_align: SizeAlign,
+    fields: &[(String, usize)],
+    pointer_sized: bool,
+) {
+    if pointer_sized {
+        src.push_str("#[cfg(target_pointer_width = \"32\")]\n");
+    }
+    src.push_str("const _: () = {\n");
+    src.push_str(&format!(
+        "assert!(core::mem::size_of::<{}>() == {});\n",
+        name, size_align.size
+    ));
+    src.push_str(&format!(
+        "assert!(core::mem::align_of::<{}>() == {});\n",
+        name, size_align.align
+    ));
+    for (field, offset) in fields {
+        src.push_str(&format!(
+            "assert!(core::mem::offset_of!({}, {}) == {});\n",
+            name, field, offset
+        ));
+    }
+    src.push_str("};\n");
+}
+
+/// Returns whether the layout of `tref` differs between 32 and 64-bit targets.
+fn is_pointer_sized(tref: &TypeRef) -> bool {
+    // `size` is rendered as `usize`, see `render_alias`.
+    if let TypeRef::Name(nt) = tref {
+        if nt.name.as_str() == "size" {
+            return true;
+        }
+    }
+    match &**tref.type_() {
+        Type::Builtin(BuiltinType::U32 {
+            lang_ptr_size: true,
+        })
+        | Type::Pointer(_)
+        | Type::ConstPointer(_)
+        | Type::List(_) => true,
+        Type::Record(r) => r.members.iter().any(|m| is_pointer_sized(&m.tref)),
+        Type::Variant(v) => v
+            .cases
+            .iter()
+            .filter_map(|c| c.tref.as_ref())
+            .any(is_pointer_sized),
+        Type::Builtin(_) | Type::Handle(_) => false,
+    }
+}
+
+fn field_name(id: &Id) -> String {
+    let mut name = String::new();
+    id.render(&mut name);
+    name
+}
+
+fn align_to(offset: usize, align: usize) -> usize {
+    offset.div_ceil(align) * align
 }
 
 fn render_enum_like_variant(src: &mut String, name: &str, s: &Variant) {
@@ -348,36 +736,70 @@ impl Render for BuiltinType {
     }
 }
 
-impl Render for Module {
-    fn render(&self, src: &mut String) {
-        // wrapper functions
-        for f in self.funcs() {
-            render_highlevel(&f, &self.name, src);
-            src.push_str("\n\n");
+fn render_module(module: &Module, options: &Options, src: &mut String) {
+    let layout = options.module_layout;
+    let rust_name = module.name.as_str().to_snake_case();
+    let raw_name = match layout {
+        ModuleLayout::Flat => rust_name.as_str(),
+        ModuleLayout::Submodules => {
+            rustdoc(&module.docs, src);
+            src.push_str("pub mod ");
+            src.push_str(&rust_name);
+            src.push_str("{\n");
+            src.push_str("use super::*;\n");
+            "raw"
         }
+    };
 
-        // raw module
-        let rust_name = self.name.as_str().to_snake_case();
-        src.push_str("pub mod ");
-        src.push_str(&rust_name);
-        src.push_str("{\n");
-        src.push_str("#[link(wasm_import_module =\"");
-        src.push_str(self.name.as_str());
-        src.push_str("\")]\n");
-        src.push_str("extern \"C\" {\n");
-        for f in self.funcs() {
-            f.render(src);
-            src.push_str("\n");
-        }
-        src.push_str("}");
-        src.push_str("}");
+    // wrapper functions
+    for f in module.funcs() {
+        let host_method = options
+            .native_host
+            .as_ref()
+            .map(|_| host_method_name(&f, module, layout));
+        render_highlevel(&f, &module.name, raw_name, host_method.as_deref(), src);
+        src.push_str("\n\n");
+    }
+
+    // In the flat layout the exports of all modules share one module, see
+    // `generate_with`.
+    if let (Some(path), ModuleLayout::Submodules) = (&options.exports, layout) {
+        render_exports(&[module], path, src);
+    }
+
+    // raw module
+    if options.native_host.is_some() {
+        src.push_str(&format!("#[cfg(not({}))]\n", NATIVE_HOST_CFG));
+    }
+    src.push_str("pub mod ");
+    src.push_str(raw_name);
+    src.push_str("{\n");
+    src.push_str("#[link(wasm_import_module =\"");
+    src.push_str(module.name.as_str());
+    src.push_str("\")]\n");
+    src.push_str("extern \"C\" {\n");
+    for f in module.funcs() {
+        f.render(src);
+        src.push_str("\n");
+    }
+    src.push_str("}");
+    src.push_str("}");
+
+    if layout == ModuleLayout::Submodules {
+        src.push('}');
     }
 }
 
-fn render_highlevel(func: &InterfaceFunc, module: &Id, src: &mut String) {
-    let mut rust_name = String::new();
-    func.name.render(&mut rust_name);
-    let rust_name = rust_name.to_snake_case();
+/// Renders the safe-ish wrapper of `func`, which calls the raw import through
+/// the Rust module `raw_module`, or `host_method` of the native host trait in
+/// the native host configuration.
+fn render_highlevel(
+    func: &InterfaceFunc,
+    module: &Id,
+    raw_module: &str,
+    host_method: Option<&str>,
+    src: &mut String,
+) {
     rustdoc(&func.docs, src);
     rustdoc_params(&func.params, "Parameters", src);
     rustdoc_params(&func.results, "Return", src);
@@ -388,17 +810,47 @@ fn render_highlevel(func: &InterfaceFunc, module: &Id, src: &mut String) {
     // descriptors, which are effectively forgeable and danglable raw pointers
     // into the file descriptor address space.
     src.push_str("pub unsafe fn ");
+    src.push_str(to_rust_ident(&func.name.as_str().to_snake_case()));
+    src.push_str("(");
+    render_highlevel_signature(func, src);
+    src.push_str("{");
 
-    // TODO workout how to handle wasi-ephemeral which introduces multiple
-    // WASI modules into the picture. For now, feature-gate it, and if we're
-    // compiling ephmeral bindings, prefix wrapper syscall with module name.
-    if cfg!(feature = "multi-module") {
-        src.push_str(&[module.as_str().to_snake_case().as_str(), &rust_name].join("_"));
-    } else {
-        src.push_str(to_rust_ident(&rust_name));
+    if let Some(method) = host_method {
+        src.push_str(&format!("#[cfg({})]\n", NATIVE_HOST_CFG));
+        src.push_str("{\n");
+        src.push_str(&format!("crate::host::current().{}(", method));
+        for param in func.params.iter() {
+            param.name.render(src);
+            src.push_str(", ");
+        }
+        src.push_str(")\n");
+        src.push_str("}\n");
+        src.push_str(&format!("#[cfg(not({}))]\n", NATIVE_HOST_CFG));
+        src.push_str("{\n");
     }
 
-    src.push_str("(");
+    func.call_wasm(
+        module,
+        &mut Rust {
+            src,
+            params: Some(&func.params),
+            callee: raw_module,
+            invalid: Invalid::default(),
+            tmp: 0,
+            block_storage: Vec::new(),
+            blocks: Vec::new(),
+        },
+    );
+
+    if host_method.is_some() {
+        src.push_str("}\n");
+    }
+    src.push_str("}");
+}
+
+/// Renders the parameters following an already rendered `(`, and the return
+/// type, of the wrapper of `func`.
+fn render_highlevel_signature(func: &InterfaceFunc, src: &mut String) {
     for param in func.params.iter() {
         param.name.render(src);
         src.push_str(": ");
@@ -407,6 +859,10 @@ fn render_highlevel(func: &InterfaceFunc, module: &Id, src: &mut String) {
     }
     src.push_str(")");
 
+    if func.noreturn {
+        src.push_str(" -> !");
+        return;
+    }
     match func.results.len() {
         0 => {}
         1 => {
@@ -422,28 +878,212 @@ fn render_highlevel(func: &InterfaceFunc, module: &Id, src: &mut String) {
             src.push_str(")");
         }
     }
-    src.push_str("{");
+}
 
-    func.call_wasm(
-        module,
+/// Renders the trait which the wrappers call in the native host
+/// configuration, with a method per function which fails with `nosys` by
+/// default.
+fn render_host_trait(doc: &Document, name: &str, layout: ModuleLayout, src: &mut String) {
+    src.push_str(&format!("#[cfg({})]\n", NATIVE_HOST_CFG));
+    src.push_str("/// The functions called by the wrappers in this crate when built with the\n");
+    src.push_str("/// `native-host` feature for a non-wasm target.\n");
+    src.push_str("///\n");
+    src.push_str("/// Methods which aren't implemented fail with `nosys`, except the ones of\n");
+    src.push_str("/// functions which don't return, which exit the process.\n");
+    src.push_str("#[allow(clippy::too_many_arguments, clippy::missing_safety_doc)]\n");
+    src.push_str(&format!("pub trait {} {{\n", name));
+    for module in doc.modules() {
+        for func in module.funcs() {
+            let wrapper = match layout {
+                ModuleLayout::Flat => func.name.as_str().to_snake_case(),
+                ModuleLayout::Submodules => format!(
+                    "{}::{}",
+                    module.name.as_str().to_snake_case(),
+                    func.name.as_str().to_snake_case()
+                ),
+            };
+            let method = host_method_name(&func, &module, layout);
+            src.push_str(&format!("/// Implements [`{}`].\n", wrapper));
+            src.push_str(&format!("unsafe fn {}(&self, ", method));
+            render_highlevel_signature(&func, src);
+            let body = if func.noreturn && func.params.len() == 1 {
+                let mut code = String::new();
+                func.params[0].name.render(&mut code);
+                format!("std::process::exit({} as i32)", code)
+            } else if let Some(nosys) = error_case(&func, "nosys") {
+                format!("Err({})", nosys)
+            } else {
+                // Without a way to fail, the method has to be implemented.
+                src.push_str(";\n");
+                continue;
+            };
+            src.push_str("{\n");
+            if !func.noreturn && !func.params.is_empty() {
+                src.push_str("let _ = (");
+                for param in func.params.iter() {
+                    param.name.render(src);
+                    src.push_str(", ");
+                }
+                src.push_str(");\n");
+            }
+            src.push_str(&body);
+            src.push_str("\n}\n");
+        }
+    }
+    src.push_str("}\n");
+}
+
+/// Returns the constant of the `case` case of the error enum of `func`, if
+/// it returns a `Result` whose error enum has one.
+fn error_case(func: &InterfaceFunc, case: &str) -> Option<String> {
+    if func.results.len() != 1 {
+        return None;
+    }
+    let err = match &**func.results[0].tref.type_() {
+        Type::Variant(v) => v.as_expected()?.1?,
+        _ => return None,
+    };
+    let name = match err {
+        TypeRef::Name(n) => n,
+        TypeRef::Value(_) => return None,
+    };
+    match &**name.type_() {
+        Type::Variant(v)
+            if v.cases.iter().all(|c| c.tref.is_none())
+                && v.cases.iter().any(|c| c.name.as_str() == case) =>
+        {
+            Some(format!(
+                "{}_{}",
+                name.name.as_str().to_shouty_snake_case(),
+                case.to_shouty_snake_case()
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the name of the native host trait method for `func`, prefixed
+/// with the module name if modules may share function names.
+fn host_method_name(func: &InterfaceFunc, module: &Module, layout: ModuleLayout) -> String {
+    let name = func.name.as_str().to_snake_case();
+    match layout {
+        ModuleLayout::Flat => to_rust_ident(&name).to_string(),
+        ModuleLayout::Submodules => format!("{}_{}", module.name.as_str().to_snake_case(), name),
+    }
+}
+
+/// Renders an `exports` module for `modules`, whose functions lift their core
+/// wasm arguments and call the function of the same name in the Rust module
+/// `path`.
+///
+/// Strings which aren't UTF-8 and invalid `char`s are rejected with the
+/// `ilseq` and `inval` errors of the function.
+fn render_exports(modules: &[&Module], path: &str, src: &mut String) {
+    let names = modules
+        .iter()
+        .map(|m| format!("`{}`", m.name.as_str()))
+        .collect::<Vec<_>>();
+    src.push_str(&format!(
+        "/// Exports of the {} functions implemented by `{}`.\n",
+        names.join(", "),
+        path
+    ));
+    src.push_str("pub mod exports {\n");
+    src.push_str("use super::*;\n");
+    for module in modules {
+        for func in module.funcs() {
+            render_export(module, &func, path, src);
+        }
+    }
+    src.push_str("}\n\n");
+}
+
+fn render_export(module: &Module, func: &InterfaceFunc, path: &str, src: &mut String) {
+    let (params, results) = func.wasm_signature();
+    assert!(results.len() <= 1);
+    src.push_str(&format!("#[export_name = \"{}\"]\n", func.name.as_str()));
+    src.push_str("pub unsafe extern \"C\" fn ");
+    src.push_str(to_rust_ident(&func.name.as_str().to_snake_case()));
+    src.push('(');
+    for (i, param) in params.iter().enumerate() {
+        src.push_str(&format!("arg{}: ", i));
+        param.render(src);
+        src.push_str(", ");
+    }
+    src.push(')');
+    if func.noreturn {
+        src.push_str(" -> !");
+    } else if let Some(result) = results.first() {
+        src.push_str(" -> ");
+        result.render(src);
+    }
+    src.push_str("{\n");
+    let invalid = Invalid {
+        string: error_case(func, "ilseq"),
+        char: error_case(func, "inval"),
+    };
+    func.call_interface(
+        &module.name,
         &mut Rust {
             src,
-            params: &func.params,
+            params: None,
+            callee: path,
+            invalid,
+            tmp: 0,
             block_storage: Vec::new(),
             blocks: Vec::new(),
         },
     );
+    src.push_str("}\n\n");
+}
 
-    src.push_str("}");
+/// The errors an export returns for invalid arguments, if it can.
+#[derive(Default)]
+struct Invalid {
+    /// Returned for strings which aren't UTF-8.
+    string: Option<String>,
+    /// Returned for integers which aren't a `char`.
+    char: Option<String>,
 }
 
 struct Rust<'a> {
     src: &'a mut String,
-    params: &'a [InterfaceFuncParam],
+    /// The parameters of the wrapper calling wasm, or `None` in export glue,
+    /// whose arguments are the core wasm `argN`s.
+    params: Option<&'a [InterfaceFuncParam]>,
+    /// The Rust module whose functions `CallWasm` or `CallInterface` call.
+    callee: &'a str,
+    /// The errors export glue returns for invalid arguments.
+    invalid: Invalid,
+    /// The number of temporaries declared so far.
+    tmp: usize,
     block_storage: Vec<String>,
     blocks: Vec<String>,
 }
 
+impl Rust<'_> {
+    /// Binds `tmp` to the payload of the `success` case of `value`, or
+    /// returns `invalid` from the export, panicking with `msg` if the
+    /// function has no such error.
+    fn lift_checked(
+        &mut self,
+        tmp: &str,
+        value: &str,
+        success: &str,
+        invalid: Option<String>,
+        msg: &str,
+    ) {
+        let fail = match invalid {
+            Some(err) => format!("return {}.0 as i32", err),
+            None => format!("panic!(\"{}\")", msg),
+        };
+        self.src.push_str(&format!(
+            "let {} = match {} {{ {}(v) => v, _ => {} }};\n",
+            tmp, value, success, fail
+        ));
+    }
+}
+
 impl Bindgen for Rust<'_> {
     type Operand = String;
 
@@ -456,10 +1096,9 @@ impl Bindgen for Rust<'_> {
         let to_restore = self.block_storage.pop().unwrap();
         let src = mem::replace(self.src, to_restore);
         match operand {
-            None => {
-                assert!(src.is_empty());
-                self.blocks.push("()".to_string());
-            }
+            // Blocks without a result only contain statements, such as the
+            // stores of an export's out-params.
+            None => self.blocks.push(src),
             Some(s) => {
                 if src.is_empty() {
                     self.blocks.push(s);
@@ -491,15 +1130,31 @@ impl Bindgen for Rust<'_> {
         };
 
         match inst {
-            Instruction::GetArg { nth } => {
-                let mut s = String::new();
-                self.params[*nth].name.render(&mut s);
-                results.push(s);
-            }
+            Instruction::GetArg { nth } => match self.params {
+                Some(params) => {
+                    let mut s = String::new();
+                    params[*nth].name.render(&mut s);
+                    results.push(s);
+                }
+                None => results.push(format!("arg{}", nth)),
+            },
             Instruction::AddrOf => {
                 results.push(format!("&{} as *const _ as i32", operands[0]));
             }
-            Instruction::I64FromBitflags { .. } | Instruction::I64FromU64 => top_as("i64"),
+            // With the `bitflags` feature the flags are a newtype, so unwrap
+            // the argument to its raw bits before the cast below.
+            Instruction::I32FromBitflags { .. } | Instruction::I64FromBitflags { .. } => {
+                let flags = operands.pop().unwrap();
+                self.src.push_str("#[cfg(feature = \"bitflags\")]\n");
+                self.src
+                    .push_str(&format!("let {0} = {0}.bits();\n", flags));
+                let ty = match inst {
+                    Instruction::I64FromBitflags { .. } => "i64",
+                    _ => "i32",
+                };
+                results.push(format!("{} as {}", flags, ty));
+            }
+            Instruction::I64FromU64 => top_as("i64"),
             Instruction::I32FromPointer
             | Instruction::I32FromConstPointer
             | Instruction::I32FromHandle { .. }
@@ -510,8 +1165,7 @@ impl Bindgen for Rust<'_> {
             | Instruction::I32FromChar8
             | Instruction::I32FromU16
             | Instruction::I32FromS16
-            | Instruction::I32FromU32
-            | Instruction::I32FromBitflags { .. } => top_as("i32"),
+            | Instruction::I32FromU32 => top_as("i32"),
 
             Instruction::EnumLower { .. } => {
                 results.push(format!("{}.0 as i32", operands[0]));
@@ -534,16 +1188,37 @@ impl Bindgen for Rust<'_> {
             Instruction::Char8FromI32 | Instruction::U8FromI32 => top_as("u8"),
             Instruction::S16FromI32 => top_as("i16"),
             Instruction::U16FromI32 => top_as("u16"),
-            Instruction::S32FromI32 => {}
-            Instruction::U32FromI32 => top_as("u32"),
-            Instruction::S64FromI64 => {}
+            Instruction::S32FromI32 => results.push(operands.pop().unwrap()),
+            // Let the cast be inferred, as `size` is a `u32` in witx but
+            // rendered as `usize`, see `render_alias`.
+            Instruction::U32FromI32 => top_as("_"),
+            Instruction::S64FromI64 => results.push(operands.pop().unwrap()),
             Instruction::U64FromI64 => top_as("u64"),
             Instruction::UsizeFromI32 => top_as("usize"),
             Instruction::HandleFromI32 { .. } => top_as("u32"),
             Instruction::PointerFromI32 { .. } => top_as("*mut _"),
             Instruction::ConstPointerFromI32 { .. } => top_as("*const _"),
-            Instruction::BitflagsFromI32 { .. } => unimplemented!(),
-            Instruction::BitflagsFromI64 { .. } => unimplemented!(),
+            // The inverse of `I32FromBitflags` above.
+            Instruction::BitflagsFromI32 { ty } | Instruction::BitflagsFromI64 { ty } => {
+                let repr = match &**ty.type_() {
+                    Type::Record(r) => r.bitflags_repr().unwrap(),
+                    _ => unreachable!(),
+                };
+                let mut bits = format!("{} as ", operands[0]);
+                repr.render(&mut bits);
+                let tmp = format!("flags{}", self.tmp);
+                self.tmp += 1;
+                self.src.push_str("#[cfg(not(feature = \"bitflags\"))]\n");
+                self.src.push_str(&format!("let {} = {};\n", tmp, bits));
+                self.src.push_str("#[cfg(feature = \"bitflags\")]\n");
+                self.src.push_str(&format!(
+                    "let {} = {}::from_bits_retain({});\n",
+                    tmp,
+                    ty.name.as_str().to_camel_case(),
+                    bits
+                ));
+                results.push(tmp);
+            }
 
             Instruction::ReturnPointerGet { n } => {
                 results.push(format!("rp{}.as_mut_ptr() as i32", n));
@@ -567,7 +1242,10 @@ impl Bindgen for Rust<'_> {
 
             Instruction::ResultLift => {
                 let err = self.blocks.pop().unwrap();
-                let ok = self.blocks.pop().unwrap();
+                let mut ok = self.blocks.pop().unwrap();
+                if ok.is_empty() {
+                    ok.push_str("()");
+                }
                 let mut result = format!("match {} {{", operands[0]);
                 result.push_str("0 => Ok(");
                 result.push_str(&ok);
@@ -592,10 +1270,91 @@ impl Bindgen for Rust<'_> {
                 results.push(result);
             }
 
-            Instruction::CharFromI32 => unimplemented!(),
+            Instruction::CharFromI32 => {
+                let tmp = format!("char{}", self.tmp);
+                self.tmp += 1;
+                let value = format!("core::char::from_u32({} as u32)", operands[0]);
+                let invalid = self.invalid.char.clone();
+                self.lift_checked(&tmp, &value, "Some", invalid, "invalid `char`");
+                results.push(tmp);
+            }
+
+            Instruction::ListFromPointerLength { ty } => {
+                let slice = format!(
+                    "core::slice::from_raw_parts({} as *const {}, {} as usize)",
+                    operands[0],
+                    match &**ty.type_() {
+                        Type::Builtin(BuiltinType::Char) => "u8".to_string(),
+                        _ => {
+                            let mut elem = String::new();
+                            ty.render(&mut elem);
+                            elem
+                        }
+                    },
+                    operands[1]
+                );
+                match &**ty.type_() {
+                    Type::Builtin(BuiltinType::Char) => {
+                        let tmp = format!("str{}", self.tmp);
+                        self.tmp += 1;
+                        let value = format!("core::str::from_utf8({})", slice);
+                        let invalid = self.invalid.string.clone();
+                        self.lift_checked(&tmp, &value, "Ok", invalid, "invalid UTF-8");
+                        results.push(tmp);
+                    }
+                    _ => results.push(slice),
+                }
+            }
+
+            Instruction::CallInterface { func, .. } => {
+                if !func.results.is_empty() {
+                    self.src.push_str("let ret = ");
+                    results.push("ret".to_string());
+                }
+                self.src.push_str(&format!(
+                    "{}::{}({});\n",
+                    self.callee,
+                    to_rust_ident(&func.name.as_str().to_snake_case()),
+                    operands.join(", ")
+                ));
+            }
+
+            Instruction::VariantPayload => results.push("e".to_string()),
+
+            Instruction::TupleLower { amt } => {
+                let names = (0..*amt).map(|i| format!("e{}", i)).collect::<Vec<_>>();
+                self.src
+                    .push_str(&format!("let ({}) = {};\n", names.join(", "), operands[0]));
+                results.extend(names);
+            }
+
+            Instruction::Store { ty } => {
+                self.src.push_str(&format!(
+                    "core::ptr::write({} as *mut {}, {});\n",
+                    operands[1],
+                    ty.name.as_str().to_camel_case(),
+                    operands[0]
+                ));
+            }
+
+            Instruction::ResultLower { ok, err } => {
+                let err_block = self.blocks.pop().unwrap();
+                let ok_block = self.blocks.pop().unwrap();
+                let mut result = format!("match {} {{", operands[0]);
+                match ok {
+                    Some(_) => result.push_str(&format!("Ok(e) => {{ {} 0 }},", ok_block)),
+                    None => result.push_str("Ok(()) => 0,"),
+                }
+                match err {
+                    Some(_) => result.push_str(&format!("Err(e) => {},", err_block)),
+                    None => result.push_str("Err(()) => 1,"),
+                }
+                result.push('}');
+                results.push(result);
+            }
 
             Instruction::CallWasm {
-                module,
+                module: _,
                 name,
                 params: _,
                 results: func_results,
@@ -605,7 +1364,7 @@ impl Bindgen for Rust<'_> {
                     self.src.push_str("let ret = ");
                     results.push("ret".to_string());
                 }
-                self.src.push_str(&module.to_snake_case());
+                self.src.push_str(self.callee);
                 self.src.push_str("::");
                 self.src.push_str(to_rust_ident(&name.to_snake_case()));
                 self.src.push_str("(");
@@ -622,13 +1381,6 @@ impl Bindgen for Rust<'_> {
                 self.src.push_str(&operands.join(", "));
                 self.src.push_str(")");
             }
-
-            Instruction::Store { .. }
-            | Instruction::ListFromPointerLength { .. }
-            | Instruction::CallInterface { .. }
-            | Instruction::ResultLower { .. }
-            | Instruction::TupleLower { .. }
-            | Instruction::VariantPayload => unimplemented!(),
         }
     }
 }
@@ -1,10 +1,165 @@
 use std::env;
+use std::ffi::OsString;
+use std::fs;
 use std::path::PathBuf;
+use std::process;
+use witx_bindgen::{Formatter, ModuleLayout, Options};
+
+enum Backend {
+    Guest,
+    CHeader,
+    Host,
+}
+
+impl Backend {
+    /// The subcommand selecting this backend, if it isn't the default.
+    fn subcommand(&self) -> Option<&'static str> {
+        match self {
+            Backend::Guest => None,
+            Backend::CHeader => Some("c-header"),
+            Backend::Host => Some("host"),
+        }
+    }
+}
 
 fn main() {
-    let witx_paths = match env::args_os().nth(1) {
-        Some(path) => vec![PathBuf::from(path)],
-        None => witx::phases::snapshot().unwrap(),
+    let mut backend = Backend::Guest;
+    let mut json = false;
+    let mut options = Options::default();
+    let mut submodules = false;
+    let mut phase = None;
+    let mut output = None;
+    let mut check = false;
+    let mut witx_paths = Vec::new();
+    let mut args = env::args_os().skip(1).peekable();
+    if let Some(subcommand) = args.peek() {
+        if subcommand == "c-header" {
+            backend = Backend::CHeader;
+            args.next();
+        } else if subcommand == "host" {
+            backend = Backend::Host;
+            args.next();
+        }
+    }
+    while let Some(arg) = args.next() {
+        if arg == "--json" {
+            json = true;
+        } else if arg == "--rustfmt" {
+            options.formatter = Formatter::Rustfmt;
+        } else if arg == "--submodules" {
+            submodules = true;
+        } else if arg == "--native-host" {
+            options.native_host = Some(string_value(&mut args, "--native-host", "a trait name"));
+        } else if arg == "--exports" {
+            options.exports = Some(string_value(&mut args, "--exports", "a module path"));
+        } else if arg == "--phase" {
+            phase = Some(string_value(&mut args, "--phase", "a phase name"));
+        } else if arg == "-o" || arg == "--output" {
+            output = Some(PathBuf::from(value(&mut args, "--output", "a path")));
+        } else if arg == "--check" {
+            check = true;
+        } else if arg.to_str().is_some_and(|arg| arg.starts_with('-')) {
+            fail(&format!("unknown option `{}`", arg.to_string_lossy()));
+        } else {
+            witx_paths.push(PathBuf::from(arg));
+        }
+    }
+
+    // These only apply to the Rust code of the guest bindings.
+    let rust_only = [
+        (options.native_host.is_some(), "--native-host"),
+        (options.exports.is_some(), "--exports"),
+        (submodules, "--submodules"),
+    ];
+    // The other backends don't generate Rust code or JSON either.
+    let conflict = match backend.subcommand() {
+        Some(subcommand) => Some((subcommand, json)),
+        None if json => Some(("--json", false)),
+        None => None,
+    };
+    if let Some((conflict, with_json)) = conflict {
+        for &(set, flag) in [(with_json, "--json")].iter().chain(rust_only.iter()) {
+            if set {
+                fail(&format!("`{}` can't be combined with `{}`", flag, conflict));
+            }
+        }
+    }
+    if phase.is_some() && !witx_paths.is_empty() {
+        fail("`--phase` can't be combined with witx paths");
+    }
+    if check && output.is_none() {
+        fail("`--check` requires `--output`");
+    }
+    if witx_paths.is_empty() {
+        let paths = match phase.as_deref().unwrap_or("snapshot") {
+            "old" => witx::phases::old::snapshot_0(),
+            "snapshot" => witx::phases::snapshot(),
+            // The ephemeral phase has a witx module per proposal, which need
+            // their own Rust modules.
+            "ephemeral" => {
+                submodules = true;
+                witx::phases::ephemeral()
+            }
+            other => fail(&format!(
+                "unknown phase `{}`, expected `old`, `snapshot` or `ephemeral`",
+                other
+            )),
+        };
+        witx_paths = paths.unwrap_or_else(|e| fail(&e.to_string()));
+    }
+    if submodules {
+        options.module_layout = ModuleLayout::Submodules;
+    }
+
+    let result = match backend {
+        Backend::CHeader => witx_bindgen::generate_c_header(&witx_paths),
+        Backend::Host => witx_bindgen::generate_host(&witx_paths, options.formatter),
+        Backend::Guest if json => witx_bindgen::generate_json(&witx_paths),
+        Backend::Guest => witx_bindgen::generate_with(&witx_paths, &options),
     };
-    print!("{}", witx_bindgen::generate(&witx_paths));
+    let src = result.unwrap_or_else(|e| fail(&e.to_string()));
+
+    let output = match output {
+        Some(output) => output,
+        None => return print!("{}", src),
+    };
+    if !check {
+        if let Err(e) = fs::write(&output, &src) {
+            fail(&format!("failed to write {}: {}", output.display(), e));
+        }
+        return;
+    }
+    let actual = fs::read_to_string(&output)
+        .unwrap_or_else(|e| fail(&format!("failed to read {}: {}", output.display(), e)));
+    if actual != src {
+        let path = output.display().to_string();
+        print!(
+            "{}",
+            similar::TextDiff::from_lines(&actual, &src)
+                .unified_diff()
+                .header(&path, &path)
+        );
+        fail(&format!(
+            "{} is out of date, rerun without `--check` to regenerate it",
+            path
+        ));
+    }
+}
+
+/// Returns the value following `flag`.
+fn value(args: &mut impl Iterator<Item = OsString>, flag: &str, what: &str) -> OsString {
+    args.next()
+        .unwrap_or_else(|| fail(&format!("`{}` requires {}", flag, what)))
+}
+
+/// Returns the value following `flag`, which must be UTF-8.
+fn string_value(args: &mut impl Iterator<Item = OsString>, flag: &str, what: &str) -> String {
+    value(args, flag, what)
+        .into_string()
+        .unwrap_or_else(|_| fail(&format!("`{}` requires {}", flag, what)))
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("error: {}", msg);
+    process::exit(1);
 }
@@ -0,0 +1,305 @@
+//! A serializable description of a witx document.
+//!
+//! This is produced by [`generate_model`](crate::generate_model) and is meant
+//! for tooling which wants to inspect or diff an API without parsing witx
+//! itself. All names are the witx spellings, not the Rust ones.
+
+use serde::Serialize;
+use witx::{Document, Layout};
+
+/// Every definition in a witx document.
+#[derive(Debug, Clone, Serialize)]
+pub struct Api {
+    pub types: Vec<TypeDef>,
+    pub modules: Vec<ModuleDef>,
+    pub constants: Vec<ConstantDef>,
+}
+
+/// A named type.
+#[derive(Debug, Clone, Serialize)]
+pub struct TypeDef {
+    pub name: String,
+    pub docs: String,
+    pub size: usize,
+    pub align: usize,
+    #[serde(rename = "type")]
+    pub ty: Ty,
+}
+
+/// A type, either a reference to a named type or an inline definition.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Ty {
+    Named {
+        name: String,
+    },
+    Builtin {
+        name: &'static str,
+    },
+    List {
+        element: Box<Ty>,
+    },
+    Pointer {
+        pointee: Box<Ty>,
+    },
+    ConstPointer {
+        pointee: Box<Ty>,
+    },
+    Record {
+        members: Vec<MemberDef>,
+    },
+    Bitflags {
+        repr: &'static str,
+        flags: Vec<FlagDef>,
+    },
+    Variant {
+        tag_repr: &'static str,
+        payload_offset: usize,
+        cases: Vec<CaseDef>,
+    },
+    Handle,
+}
+
+/// A member of a record, with its offset from the start of the record.
+#[derive(Debug, Clone, Serialize)]
+pub struct MemberDef {
+    pub name: String,
+    pub docs: String,
+    pub offset: usize,
+    #[serde(rename = "type")]
+    pub ty: Ty,
+}
+
+/// A flag of a bitflags record, which is bit `bit` of the representation.
+#[derive(Debug, Clone, Serialize)]
+pub struct FlagDef {
+    pub name: String,
+    pub docs: String,
+    pub bit: usize,
+}
+
+/// A case of a variant, with its optional payload.
+#[derive(Debug, Clone, Serialize)]
+pub struct CaseDef {
+    pub name: String,
+    pub docs: String,
+    #[serde(rename = "type")]
+    pub ty: Option<Ty>,
+}
+
+/// A module of imported functions.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleDef {
+    pub name: String,
+    pub docs: String,
+    pub functions: Vec<FunctionDef>,
+}
+
+/// A function, with both its interface and core wasm signatures.
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionDef {
+    pub name: String,
+    pub docs: String,
+    pub params: Vec<ParamDef>,
+    pub results: Vec<ParamDef>,
+    pub noreturn: bool,
+    pub wasm_signature: WasmSignature,
+}
+
+/// A parameter or result of a function.
+#[derive(Debug, Clone, Serialize)]
+pub struct ParamDef {
+    pub name: String,
+    pub docs: String,
+    #[serde(rename = "type")]
+    pub ty: Ty,
+}
+
+/// The core wasm types a function is imported with.
+#[derive(Debug, Clone, Serialize)]
+pub struct WasmSignature {
+    pub params: Vec<&'static str>,
+    pub results: Vec<&'static str>,
+}
+
+/// A constant value of a named type.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConstantDef {
+    pub name: String,
+    pub docs: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub value: u64,
+}
+
+impl Api {
+    pub(crate) fn new(doc: &Document) -> Api {
+        Api {
+            types: doc
+                .typenames()
+                .map(|nt| {
+                    let size_align = nt.mem_size_align();
+                    TypeDef {
+                        name: nt.name.as_str().to_string(),
+                        docs: nt.docs.clone(),
+                        size: size_align.size,
+                        align: size_align.align,
+                        ty: Ty::new(&nt.tref),
+                    }
+                })
+                .collect(),
+            modules: doc
+                .modules()
+                .map(|m| ModuleDef {
+                    name: m.name.as_str().to_string(),
+                    docs: m.docs.clone(),
+                    functions: m.funcs().map(|f| FunctionDef::new(&f)).collect(),
+                })
+                .collect(),
+            constants: doc
+                .constants()
+                .map(|c| ConstantDef {
+                    name: c.name.as_str().to_string(),
+                    docs: c.docs.clone(),
+                    ty: c.ty.as_str().to_string(),
+                    value: c.value,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Ty {
+    /// Describes `tref`, which refers to named types by name.
+    fn new(tref: &witx::TypeRef) -> Ty {
+        match tref {
+            witx::TypeRef::Name(nt) => Ty::Named {
+                name: nt.name.as_str().to_string(),
+            },
+            witx::TypeRef::Value(ty) => Ty::value(ty),
+        }
+    }
+
+    fn value(ty: &witx::Type) -> Ty {
+        match ty {
+            witx::Type::Record(r) => match r.bitflags_repr() {
+                Some(repr) => Ty::Bitflags {
+                    repr: int_repr(repr),
+                    flags: r
+                        .members
+                        .iter()
+                        .enumerate()
+                        .map(|(bit, m)| FlagDef {
+                            name: m.name.as_str().to_string(),
+                            docs: m.docs.clone(),
+                            bit,
+                        })
+                        .collect(),
+                },
+                None => Ty::Record {
+                    members: r
+                        .member_layout()
+                        .into_iter()
+                        .map(|l| MemberDef {
+                            name: l.member.name.as_str().to_string(),
+                            docs: l.member.docs.clone(),
+                            offset: l.offset,
+                            ty: Ty::new(&l.member.tref),
+                        })
+                        .collect(),
+                },
+            },
+            witx::Type::Variant(v) => Ty::Variant {
+                tag_repr: int_repr(v.tag_repr),
+                payload_offset: v.payload_offset(),
+                cases: v
+                    .cases
+                    .iter()
+                    .map(|c| CaseDef {
+                        name: c.name.as_str().to_string(),
+                        docs: c.docs.clone(),
+                        ty: c.tref.as_ref().map(Ty::new),
+                    })
+                    .collect(),
+            },
+            witx::Type::Handle(_) => Ty::Handle,
+            witx::Type::List(t) => Ty::List {
+                element: Box::new(Ty::new(t)),
+            },
+            witx::Type::Pointer(t) => Ty::Pointer {
+                pointee: Box::new(Ty::new(t)),
+            },
+            witx::Type::ConstPointer(t) => Ty::ConstPointer {
+                pointee: Box::new(Ty::new(t)),
+            },
+            witx::Type::Builtin(b) => Ty::Builtin { name: builtin(*b) },
+        }
+    }
+}
+
+impl FunctionDef {
+    fn new(func: &witx::InterfaceFunc) -> FunctionDef {
+        let params = |params: &[witx::InterfaceFuncParam]| {
+            params
+                .iter()
+                .map(|p| ParamDef {
+                    name: p.name.as_str().to_string(),
+                    docs: p.docs.clone(),
+                    ty: Ty::new(&p.tref),
+                })
+                .collect()
+        };
+        let (wasm_params, wasm_results) = func.wasm_signature();
+        FunctionDef {
+            name: func.name.as_str().to_string(),
+            docs: func.docs.clone(),
+            params: params(&func.params),
+            results: params(&func.results),
+            noreturn: func.noreturn,
+            wasm_signature: WasmSignature {
+                params: wasm_params.into_iter().map(wasm_type).collect(),
+                results: wasm_results.into_iter().map(wasm_type).collect(),
+            },
+        }
+    }
+}
+
+fn int_repr(repr: witx::IntRepr) -> &'static str {
+    match repr {
+        witx::IntRepr::U8 => "u8",
+        witx::IntRepr::U16 => "u16",
+        witx::IntRepr::U32 => "u32",
+        witx::IntRepr::U64 => "u64",
+    }
+}
+
+fn builtin(ty: witx::BuiltinType) -> &'static str {
+    match ty {
+        witx::BuiltinType::Char => "char",
+        witx::BuiltinType::U8 { lang_c_char: true } => "char8",
+        witx::BuiltinType::U8 { lang_c_char: false } => "u8",
+        witx::BuiltinType::U16 => "u16",
+        witx::BuiltinType::U32 {
+            lang_ptr_size: true,
+        } => "usize",
+        witx::BuiltinType::U32 {
+            lang_ptr_size: false,
+        } => "u32",
+        witx::BuiltinType::U64 => "u64",
+        witx::BuiltinType::S8 => "s8",
+        witx::BuiltinType::S16 => "s16",
+        witx::BuiltinType::S32 => "s32",
+        witx::BuiltinType::S64 => "s64",
+        witx::BuiltinType::F32 => "f32",
+        witx::BuiltinType::F64 => "f64",
+    }
+}
+
+fn wasm_type(ty: witx::WasmType) -> &'static str {
+    match ty {
+        witx::WasmType::I32 => "i32",
+        witx::WasmType::I64 => "i64",
+        witx::WasmType::F32 => "f32",
+        witx::WasmType::F64 => "f64",
+    }
+}
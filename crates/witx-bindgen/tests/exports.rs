@@ -0,0 +1,16 @@
+#[test]
+fn exports_validate_arguments() {
+    let paths = ["tests/exports/greet.witx", "tests/exports/spell.witx"];
+    let options = witx_bindgen::Options {
+        exports: Some("crate::impls".to_string()),
+        ..Default::default()
+    };
+    let src = witx_bindgen::generate_with(&paths, &options).unwrap();
+    // The flat layout puts the exports of every module in the same module.
+    assert_eq!(src.matches("pub mod exports").count(), 1, "{}", src);
+    assert!(src.contains("core::str::from_utf8("), "{}", src);
+    assert!(!src.contains("from_utf8_unchecked"), "{}", src);
+    assert!(src.contains("return ERRNO_ILSEQ.0 as i32"), "{}", src);
+    assert!(src.contains("return ERRNO_INVAL.0 as i32"), "{}", src);
+    assert!(!src.contains(".unwrap()"), "{}", src);
+}
@@ -2,7 +2,11 @@
 fn assert_same_as_src() {
     let actual = include_str!("../../wasip1/src/lib_generated.rs");
     let witx_path = "../wasip1/wasi_snapshot_preview1.witx";
-    let expected = witx_bindgen::generate(&[witx_path]);
+    let options = witx_bindgen::Options {
+        native_host: Some("Preview1Host".to_string()),
+        ..Default::default()
+    };
+    let expected = witx_bindgen::generate_with(&[witx_path], &options).unwrap();
     if actual == expected {
         return;
     }
@@ -12,7 +16,9 @@ fn assert_same_as_src() {
 the generated `raw.rs` does not match the actual source `raw.rs`, it's
 recommended to run this command from the root of the repository:
 
-    cargo run -p witx-bindgen crates/witx-bindgen/{} > src/lib_generated.rs
+    cargo run -p witx-bindgen -- --native-host Preview1Host -o crates/wasip1/src/lib_generated.rs crates/witx-bindgen/{}
+
+or add `--check` to that command to see the differences.
 
 ",
         witx_path